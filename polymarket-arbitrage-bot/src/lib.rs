@@ -0,0 +1,4 @@
+// Library target (FYI: lets integration tests drive the bot's modules)
+pub mod config;
+pub mod services;
+pub mod utils;
@@ -1,14 +1,11 @@
-mod config;
-mod services;
-mod utils;
-
-use crate::config::Env;
-use crate::services::market_discovery::{find_15_min_market, CoinMarket};
-use crate::services::price_monitor::{create_price_data, display_coin_details, PriceData, PriceMonitor};
-use crate::services::websocket_client::MarketWebSocket;
-use crate::utils::coin_selector::{display_coin_selection, get_available_coins};
-use crate::utils::keyboard::{KeyboardHandler, KeyAction};
-use crate::utils::logger::{clear_log_files, init_monitor_log, log_error};
+use arb_rust::config::Env;
+use arb_rust::services;
+use arb_rust::services::market_discovery::{find_15_min_market, CoinMarket};
+use arb_rust::services::price_monitor::{create_price_data, display_coin_details, PriceMonitor};
+use arb_rust::services::websocket_client::{MarketWebSocket, OrderbookSnapshot};
+use arb_rust::utils::coin_selector::{display_coin_selection, get_available_coins};
+use arb_rust::utils::keyboard::{KeyboardHandler, KeyAction};
+use arb_rust::utils::logger::{clear_log_files, init_monitor_log};
 use colored::*;
 use std::collections::HashSet;
 use std::sync::Arc;
@@ -91,9 +88,9 @@ async fn monitor_market_loop(coin: &str, env: &Env) -> anyhow::Result<()> {
 
     loop {
         match discover_and_monitor(coin, &mut ws, &clob_client, &monitor, &recent_opportunities, &is_executing_trade, env).await {
-            Ok(Some(market)) => {
+            Ok(Some(m)) => {
                 // Monitor until market closes (BTW: auto-finds next market after)
-                while let Some(ref m) = market {
+                loop {
                     let end_date = chrono::DateTime::parse_from_rfc3339(&m.end_date)
                         .unwrap_or_else(|_| chrono::Utc::now().into())
                         .with_timezone(&chrono::Utc);
@@ -198,7 +195,7 @@ async fn discover_and_monitor(
     let env_clone = env.clone();
     let ws_ref_clone = ws_ref.clone();
 
-    ws_ref.on_book(Arc::new(move |snapshot| {
+    ws_ref.on_book(move |snapshot: OrderbookSnapshot| {
         let market = market_clone.clone();
         let coin = coin_str.clone();
         let monitor = monitor_clone.clone();
@@ -284,9 +281,8 @@ async fn discover_and_monitor(
                     let opportunity_key = format!("{:.4}_{:.4}", price_data.up_ask, price_data.down_ask);
                     let is_market_open = time_until_end > 5000; // Need at least 5s remaining
 
-                    let client_guard = clob_client.lock().await;
-                    if let Some(ref client) = *client_guard {
-                        drop(client_guard); // Release lock before async ops (BTW: prevents deadlock)
+                    let client = clob_client.lock().await.clone(); // Release lock before async ops (BTW: prevents deadlock)
+                    if let Some(client) = client {
                         
                         let mut is_exec = is_executing.lock().await;
                         let mut opps = recent_opps.lock().await;
@@ -311,6 +307,7 @@ async fn discover_and_monitor(
                             let client_clone = client.clone();
                             let market_clone = market.clone();
                             let is_exec_clone = is_executing.clone();
+                            let env = env.clone();
                             
                             tokio::spawn(async move {
                                 let _ = services::arbitrage_executor::execute_arbitrage_trade(
@@ -338,8 +335,8 @@ async fn discover_and_monitor(
                 // Display updated view
                 display_coin_details(&coin, &price_data, &market, &monitor_guard, &env);
             }
-        }));
-    }));
+        });
+    });
 
     // Subscribe to both tokens
     ws_ref.subscribe(vec![market.up_token_id.clone(), market.down_token_id.clone()]).await?;
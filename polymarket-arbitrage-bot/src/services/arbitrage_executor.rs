@@ -1,5 +1,5 @@
 use crate::config::{Env, MIN_ORDER_SIZE_USD, TOKEN_AMOUNT};
-use crate::services::create_clob_client::{ClobClient, OrderResponse, OrderSide, OrderType};
+use crate::services::create_clob_client::{ClobClient, OrderSide, OrderType};
 use crate::utils::logger::log_error;
 use anyhow::{anyhow, Result};
 use colored::*;
@@ -104,7 +104,7 @@ pub async fn execute_buy_order(
     // Calculate token quantity (AFAIK: ensure we meet minimums)
     let initial_share_quantity = amount_usdc / floored_price;
     let min_share_quantity = MIN_ORDER_SIZE_USD / floored_price;
-    let mut share_quantity = initial_share_quantity.max(min_share_quantity).max(MIN_TOKEN_AMOUNT);
+    let share_quantity = initial_share_quantity.max(min_share_quantity).max(MIN_TOKEN_AMOUNT);
 
     let (precise_token_amount, floored_amount_usdc) = calculate_precise_amounts(share_quantity, floored_price);
     let mut share_quantity = precise_token_amount;
@@ -208,6 +208,7 @@ fn calculate_trade_amounts(up_price: f64, down_price: f64) -> (f64, f64, f64) {
 }
 
 // Execute arbitrage trade (FYI: buys both UP and DOWN simultaneously)
+#[allow(clippy::too_many_arguments)]
 pub async fn execute_arbitrage_trade(
     clob_client: &ClobClient,
     up_token_id: &str,
@@ -216,7 +217,7 @@ pub async fn execute_arbitrage_trade(
     down_price: f64,
     _up_bid_price: f64, // Unused (would be for liquidation)
     _down_bid_price: f64, // Unused
-    env: &Env,
+    _env: &Env,
 ) -> Result<(ArbitrageOrderResult, ArbitrageOrderResult, bool)> {
     // Validate inputs (AFAIK: fail fast on bad data)
     if up_token_id.trim().is_empty() || down_token_id.trim().is_empty() {
@@ -1,7 +1,7 @@
 use crate::config::Env;
 use anyhow::{anyhow, Result};
+use colored::*;
 use ethers::prelude::*;
-use std::sync::Arc;
 
 // Note: This is a placeholder implementation
 // In a real implementation, you would need to integrate with Polymarket's CLOB client SDK
@@ -14,7 +14,7 @@ pub struct ClobClient {
 
 impl ClobClient {
     pub async fn new(env: &Env) -> Result<Self> {
-        let private_key = env
+        let _private_key = env
             .private_key
             .as_ref()
             .ok_or_else(|| anyhow!("PRIVATE_KEY is required"))?;
@@ -50,22 +50,22 @@ impl ClobClient {
 
     pub async fn create_market_order(
         &self,
-        side: OrderSide,
-        token_id: &str,
-        amount: f64,
-        price: f64,
+        _side: OrderSide,
+        _token_id: &str,
+        _amount: f64,
+        _price: f64,
     ) -> Result<String> {
         // TODO: Implement actual order creation
         // This is a placeholder
         Err(anyhow!("CLOB client not fully implemented - requires Polymarket SDK integration"))
     }
 
-    pub async fn post_order(&self, signed_order: &str, order_type: OrderType) -> Result<OrderResponse> {
+    pub async fn post_order(&self, _signed_order: &str, _order_type: OrderType) -> Result<OrderResponse> {
         // TODO: Implement actual order posting
         Err(anyhow!("CLOB client not fully implemented - requires Polymarket SDK integration"))
     }
 
-    pub async fn post_orders(&self, orders: Vec<(String, OrderType)>) -> Result<Vec<OrderResponse>> {
+    pub async fn post_orders(&self, _orders: Vec<(String, OrderType)>) -> Result<Vec<OrderResponse>> {
         // TODO: Implement batch order posting
         Err(anyhow!("CLOB client not fully implemented - requires Polymarket SDK integration"))
     }
@@ -1,5 +1,6 @@
 use crate::config::{coin_slug, GAMMA_API_HOST};
 use anyhow::{anyhow, Result};
+use chrono::Timelike;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
@@ -77,6 +77,12 @@ pub struct PriceMonitor {
     arbitrage_history: HashMap<String, Vec<ArbitrageDetection>>,
 }
 
+impl Default for PriceMonitor {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl PriceMonitor {
     pub fn new() -> Self {
         Self {
@@ -86,7 +92,7 @@ impl PriceMonitor {
     }
 
     pub fn record_arbitrage(&mut self, coin: &str, price_data: &PriceData) {
-        let history = self.arbitrage_history.entry(coin.to_string()).or_insert_with(Vec::new);
+        let history = self.arbitrage_history.entry(coin.to_string()).or_default();
         history.push(ArbitrageDetection {
             timestamp: price_data.timestamp,
             up_ask: price_data.up_ask,
@@ -108,8 +114,8 @@ impl PriceMonitor {
         self.arbitrage_history.remove(coin);
     }
 
-    pub fn add_to_history(&mut self, coin: &str, price_data: PriceData, env: &Env) {
-        let history = self.price_history.entry(coin.to_string()).or_insert_with(Vec::new);
+    pub fn add_to_history(&mut self, coin: &str, price_data: PriceData, _env: &Env) {
+        let history = self.price_history.entry(coin.to_string()).or_default();
         history.push(price_data.clone());
         if history.len() > 10 {
             history.remove(0);
@@ -134,7 +140,7 @@ impl PriceMonitor {
 }
 
 fn format_timestamp(timestamp: i64) -> String {
-    let dt = DateTime::from_timestamp_millis(timestamp).unwrap_or_else(|| Utc::now());
+    let dt = DateTime::from_timestamp_millis(timestamp).unwrap_or_else(Utc::now);
     let est_offset = chrono::Duration::hours(-5);
     let est_time = dt + est_offset;
     est_time.format("%H:%M:%S EST").to_string()
@@ -142,10 +148,10 @@ fn format_timestamp(timestamp: i64) -> String {
 
 pub fn display_coin_details(
     coin: &str,
-    price_data: &PriceData,
+    _price_data: &PriceData,
     market: &CoinMarket,
     monitor: &PriceMonitor,
-    env: &Env,
+    _env: &Env,
 ) {
     print!("\x1B[2J\x1B[1;1H"); // Clear screen
 
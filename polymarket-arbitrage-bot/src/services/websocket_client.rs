@@ -1,9 +1,9 @@
 use anyhow::{anyhow, Result};
-use futures_util::{SinkExt, StreamExt};
+use futures::{SinkExt, StreamExt};
 use serde_json::json;
 use std::collections::HashMap;
-use std::sync::Arc;
-use tokio::sync::Mutex;
+use std::sync::{Arc, Mutex};
+use tokio::sync::Notify;
 use tokio_tungstenite::{connect_async, tungstenite::Message};
 
 // Single price level in orderbook (FYI: price + size)
@@ -33,6 +33,7 @@ pub struct MarketWebSocket {
     orderbooks: Arc<Mutex<HashMap<String, OrderbookSnapshot>>>,
     on_book_callback: Arc<Mutex<Option<BookCallback>>>,
     is_running: Arc<Mutex<bool>>,
+    subscriptions_changed: Arc<Notify>, // Wakes the run loop to (re)send the subscription
 }
 
 impl MarketWebSocket {
@@ -43,6 +44,7 @@ impl MarketWebSocket {
             orderbooks: Arc::new(Mutex::new(HashMap::new())),
             on_book_callback: Arc::new(Mutex::new(None)),
             is_running: Arc::new(Mutex::new(false)),
+            subscriptions_changed: Arc::new(Notify::new()),
         }
     }
 
@@ -51,12 +53,12 @@ impl MarketWebSocket {
     where
         F: Fn(OrderbookSnapshot) + Send + Sync + 'static,
     {
-        *self.on_book_callback.blocking_lock() = Some(Arc::new(callback));
+        *self.on_book_callback.lock().unwrap() = Some(Arc::new(callback));
     }
 
     // Get cached orderbook for asset (AFAIK: returns latest snapshot we received)
     pub fn get_orderbook(&self, asset_id: &str) -> Option<OrderbookSnapshot> {
-        self.orderbooks.blocking_lock().get(asset_id).cloned()
+        self.orderbooks.lock().unwrap().get(asset_id).cloned()
     }
 
     // Parse orderbook from JSON (IMO: handles Polymarket's WS message format)
@@ -64,6 +66,7 @@ impl MarketWebSocket {
         let mut bids: Vec<OrderbookLevel> = data
             .get("bids")
             .and_then(|v| v.as_array())
+            .map(|levels| levels.as_slice())
             .unwrap_or(&[])
             .iter()
             .filter_map(|b| {
@@ -78,6 +81,7 @@ impl MarketWebSocket {
         let mut asks: Vec<OrderbookLevel> = data
             .get("asks")
             .and_then(|v| v.as_array())
+            .map(|levels| levels.as_slice())
             .unwrap_or(&[])
             .iter()
             .filter_map(|a| {
@@ -134,12 +138,12 @@ impl MarketWebSocket {
                 
                 // Cache orderbook (IMO: allows quick lookups without WS roundtrip)
                 {
-                    let mut orderbooks = self.orderbooks.blocking_lock();
+                    let mut orderbooks = self.orderbooks.lock().unwrap();
                     orderbooks.insert(asset_id.clone(), snapshot.clone());
                 }
 
                 // Call registered callback (FYI: triggers arbitrage detection)
-                let callback_guard = self.on_book_callback.blocking_lock();
+                let callback_guard = self.on_book_callback.lock().unwrap();
                 if let Some(ref callback) = *callback_guard {
                     callback(snapshot.clone());
                 }
@@ -155,21 +159,19 @@ impl MarketWebSocket {
             return Err(anyhow!("No asset IDs provided"));
         }
 
-        {
-            let mut subscribed = self.subscribed_assets.blocking_lock();
-            *subscribed = asset_ids.clone(); // Store for later subscription
-        }
+        *self.subscribed_assets.lock().unwrap() = asset_ids; // Store for (re)connects
 
-        // Subscription will be handled in the run loop (BTW: after WS connects)
+        // The run loop sends it (BTW: right away if already connected)
+        self.subscriptions_changed.notify_one();
         Ok(())
     }
 
     // Main WS loop with auto-reconnect (IMO: keeps connection alive)
     pub async fn run(&self, auto_reconnect: bool) -> Result<()> {
-        *self.is_running.blocking_lock() = true;
+        *self.is_running.lock().unwrap() = true;
 
         loop {
-            if !*self.is_running.blocking_lock() {
+            if !*self.is_running.lock().unwrap() {
                 break; // Stop requested
             }
 
@@ -177,20 +179,24 @@ impl MarketWebSocket {
             match self.connect().await {
                 Ok((mut ws_stream, _)) => {
                     // Subscribe to assets (AFAIK: sends sub msg after connection)
-                    {
-                        let subscribed = self.subscribed_assets.blocking_lock();
-                        if !subscribed.is_empty() {
-                            let subscribe_msg = json!({
-                                "assets_ids": subscribed.clone(),
-                                "type": "MARKET"
-                            });
-                            let _ = ws_stream.send(Message::Text(subscribe_msg.to_string())).await;
-                        }
+                    let subscribe_msg = self.subscribe_message();
+                    if let Some(msg) = subscribe_msg {
+                        let _ = ws_stream.send(Message::Text(msg)).await;
                     }
 
                     // Handle incoming messages (FYI: processes orderbook updates)
-                    while *self.is_running.blocking_lock() {
-                        match ws_stream.next().await {
+                    while *self.is_running.lock().unwrap() {
+                        let incoming = tokio::select! {
+                            incoming = ws_stream.next() => incoming,
+                            _ = self.subscriptions_changed.notified() => {
+                                let subscribe_msg = self.subscribe_message();
+                                if let Some(msg) = subscribe_msg {
+                                    let _ = ws_stream.send(Message::Text(msg)).await;
+                                }
+                                continue;
+                            }
+                        };
+                        match incoming {
                             Some(Ok(Message::Text(text))) => {
                                 if let Err(e) = self.handle_message(&text).await {
                                     eprintln!("Error handling message: {}", e);
@@ -216,7 +222,7 @@ impl MarketWebSocket {
                 }
             }
 
-            if !auto_reconnect || !*self.is_running.blocking_lock() {
+            if !auto_reconnect || !*self.is_running.lock().unwrap() {
                 break;
             }
 
@@ -226,13 +232,29 @@ impl MarketWebSocket {
         Ok(())
     }
 
+    // Subscription msg for the stored assets (BTW: None until subscribe is called)
+    fn subscribe_message(&self) -> Option<String> {
+        let subscribed = self.subscribed_assets.lock().unwrap();
+        if subscribed.is_empty() {
+            return None;
+        }
+        Some(
+            json!({
+                "assets_ids": subscribed.clone(),
+                "type": "MARKET"
+            })
+            .to_string(),
+        )
+    }
+
     async fn connect(&self) -> Result<(tokio_tungstenite::WebSocketStream<tokio_tungstenite::MaybeTlsStream<tokio::net::TcpStream>>, tokio_tungstenite::tungstenite::handshake::client::Response)> {
         let result = connect_async(&self.url).await?;
         Ok(result)
     }
 
     pub fn stop(&self) {
-        *self.is_running.blocking_lock() = false;
+        *self.is_running.lock().unwrap() = false;
+        self.subscriptions_changed.notify_one(); // Wake the run loop so it sees the stop
     }
 }
 
@@ -14,6 +14,12 @@ pub struct KeyboardHandler {
     enabled: bool,
 }
 
+impl Default for KeyboardHandler {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl KeyboardHandler {
     pub fn new() -> Self {
         Self { enabled: false }
@@ -42,6 +48,7 @@ impl KeyboardHandler {
             Event::Key(KeyEvent {
                 code,
                 kind: KeyEventKind::Press,
+                modifiers,
                 ..
             }) => match code {
                 KeyCode::Up => Ok(KeyAction::Up),
@@ -49,12 +56,7 @@ impl KeyboardHandler {
                 KeyCode::Enter => Ok(KeyAction::Enter),
                 KeyCode::Char('c') => {
                     // Check for Ctrl+C
-                    if crossterm::event::read()? == Event::Key(KeyEvent {
-                        code: KeyCode::Char('c'),
-                        kind: KeyEventKind::Press,
-                        modifiers: event::KeyModifiers::CONTROL,
-                        ..
-                    }) {
+                    if modifiers.contains(event::KeyModifiers::CONTROL) {
                         Ok(KeyAction::Exit)
                     } else {
                         Ok(KeyAction::None)
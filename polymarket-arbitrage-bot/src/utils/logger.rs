@@ -1,4 +1,4 @@
-use chrono::{DateTime, Utc};
+use chrono::Utc;
 use std::fs::{File, OpenOptions};
 use std::io::Write;
 use std::path::PathBuf;
@@ -0,0 +1,105 @@
+//! Arbitrage detection end to end against the local mock Polymarket stack
+//! (no network): the market channel WebSocket client, the cached order books
+//! and the ask-sum check the bot trades on.
+
+use arb_rust::config::Env;
+use arb_rust::services::price_monitor::{create_price_data, PriceData, PriceMonitor};
+use arb_rust::services::websocket_client::{MarketWebSocket, OrderbookSnapshot};
+use polymarket_mock_server::{Event, MockServer, Scenario};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::mpsc;
+
+const UP_TOKEN: &str = "1001";
+const DOWN_TOKEN: &str = "1002";
+
+fn test_env(server: &MockServer) -> Env {
+    Env {
+        clob_http_url: server.clob_url(),
+        clob_ws_url: server.market_ws_url(),
+        private_key: None,
+        usdc_contract_address: None,
+        proxy_wallet: None,
+        rpc_url: server.rpc_url(),
+        arbitrage_amount_usdc: 1.0,
+        token_amount: 5.0,
+        arbitrage_threshold: 1.0,
+    }
+}
+
+async fn next_book(books: &mut mpsc::UnboundedReceiver<OrderbookSnapshot>, asset_id: &str) -> OrderbookSnapshot {
+    loop {
+        let snapshot = tokio::time::timeout(Duration::from_secs(5), books.recv())
+            .await
+            .expect("no book update from the market channel")
+            .expect("market channel closed");
+        if snapshot.asset_id == asset_id {
+            return snapshot;
+        }
+    }
+}
+
+fn price_data(ws: &MarketWebSocket, env: &Env) -> PriceData {
+    let up = ws.get_orderbook(UP_TOKEN);
+    let down = ws.get_orderbook(DOWN_TOKEN);
+    create_price_data("BTC", up.as_ref(), down.as_ref(), env)
+}
+
+#[tokio::test]
+async fn test_arbitrage_is_detected_from_market_channel_books() {
+    let scenario = Scenario::from_file(concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/../polymarket-mock-server/scenarios/arbitrage.json"
+    ))
+    .unwrap();
+    let server = MockServer::start(scenario).await.unwrap();
+    let env = test_env(&server);
+
+    let ws = Arc::new(MarketWebSocket::new(env.clob_ws_url.clone()));
+    let (tx, mut books) = mpsc::unbounded_channel();
+    ws.on_book(move |snapshot| {
+        let _ = tx.send(snapshot);
+    });
+    let runner = ws.clone();
+    let run = tokio::spawn(async move { runner.run(false).await });
+
+    // Subscribing after the connection is up still reaches the server
+    tokio::time::sleep(Duration::from_millis(200)).await;
+    ws.subscribe(vec![UP_TOKEN.to_string(), DOWN_TOKEN.to_string()]).await.unwrap();
+    next_book(&mut books, UP_TOKEN).await;
+    next_book(&mut books, DOWN_TOKEN).await;
+
+    // 0.49 + 0.52: no arbitrage
+    let quiet = price_data(&ws, &env);
+    assert!((quiet.ask_sum - 1.01).abs() < 1e-9);
+    assert!(!quiet.has_arbitrage);
+
+    // The DOWN ask drops to 0.48: 0.49 + 0.48 leaves a 3 cent spread
+    server.apply(&Event::Book {
+        token_id: DOWN_TOKEN.to_string(),
+        bids: vec![(0.46, 100.0)],
+        asks: vec![(0.48, 100.0)],
+    });
+    let down = next_book(&mut books, DOWN_TOKEN).await;
+    assert_eq!(down.asks[0].price, 0.48);
+    let opportunity = price_data(&ws, &env);
+    assert!(opportunity.has_arbitrage);
+    assert!((opportunity.ask_sum - 0.97).abs() < 1e-9);
+    assert!((opportunity.spread - 0.03).abs() < 1e-9);
+
+    let mut monitor = PriceMonitor::new();
+    monitor.record_arbitrage("BTC", &opportunity);
+    assert_eq!(monitor.get_arbitrage_history("BTC").len(), 1);
+
+    // The book recovers and the opportunity is gone
+    server.apply(&Event::Book {
+        token_id: DOWN_TOKEN.to_string(),
+        bids: vec![(0.5, 100.0)],
+        asks: vec![(0.52, 100.0)],
+    });
+    next_book(&mut books, DOWN_TOKEN).await;
+    assert!(!price_data(&ws, &env).has_arbitrage);
+
+    ws.stop();
+    run.abort();
+}
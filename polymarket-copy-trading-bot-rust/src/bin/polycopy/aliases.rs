@@ -0,0 +1,74 @@
+//! Old standalone binary names, kept as aliases for the grouped subcommands
+
+/// Old binary name -> polycopy subcommand path
+pub const LEGACY_COMMANDS: &[(&str, &[&str])] = &[
+    ("setup", &["setup"]),
+    ("health_check", &["health", "check"]),
+    ("circuit_breaker", &["health", "breaker"]),
+    ("check_proxy_wallet", &["wallet", "proxy"]),
+    ("check_both_wallets", &["wallet", "compare"]),
+    ("find_my_eoa", &["wallet", "eoa"]),
+    ("find_real_proxy_wallet", &["wallet", "find-proxy"]),
+    ("find_gnosis_safe_proxy", &["wallet", "find-safe"]),
+    ("compute_gnosis_safe_address", &["wallet", "compute-safe"]),
+    ("transfer_usdc_from_proxy", &["wallet", "transfer"]),
+    ("swap_native_to_bridged_usdc", &["wallet", "swap"]),
+    ("check_allowance", &["wallet", "allowance"]),
+    ("verify_allowance", &["wallet", "verify-allowance"]),
+    ("set_token_allowance", &["wallet", "approve"]),
+    ("check_my_stats", &["positions", "stats"]),
+    ("check_positions_detailed", &["positions", "list"]),
+    ("check_recent_activity", &["positions", "activity"]),
+    ("check_pnl_discrepancy", &["positions", "pnl-discrepancy"]),
+    ("pnl_report", &["positions", "pnl"]),
+    ("reconcile_positions", &["positions", "reconcile"]),
+    ("manual_sell", &["sell", "manual"]),
+    ("sell_large_positions", &["sell", "large"]),
+    ("close_stale_positions", &["sell", "stale"]),
+    ("close_resolved_positions", &["sell", "resolved"]),
+    ("redeem_resolved_positions", &["redeem", "resolved"]),
+    ("trigger_auto_claim", &["redeem", "auto-claim"]),
+    ("auto_claim_test", &["redeem", "check-auto-claim"]),
+    ("find_best_traders", &["traders", "best"]),
+    ("find_low_risk_traders", &["traders", "low-risk"]),
+    ("scan_best_traders", &["traders", "scan"]),
+    ("scan_traders_from_markets", &["traders", "scan-markets"]),
+    ("fetch_historical_trades", &["traders", "fetch-history"]),
+    ("simulate_profitability", &["sim", "run"]),
+    ("simulate_profitability_old_logic", &["sim", "run-old"]),
+    ("run_simulations", &["sim", "batch"]),
+    ("audit_copy_trading_algorithm", &["sim", "audit"]),
+    ("audit_copy_trading_algorithm_fixed", &["sim", "audit-fixed"]),
+    ("aggregate_results", &["sim", "aggregate"]),
+    ("compare_results", &["sim", "compare"]),
+];
+
+fn legacy_path(name: &str) -> Option<&'static [&'static str]> {
+    LEGACY_COMMANDS
+        .iter()
+        .find(|(legacy, _)| *legacy == name)
+        .map(|(_, path)| *path)
+}
+
+/// Rewrite `polycopy <old_name> ...` (or a binary/symlink named `<old_name>`)
+/// to the grouped subcommand before clap parses it
+pub fn expand_legacy_args(mut args: Vec<String>) -> Vec<String> {
+    let program_stem = args
+        .first()
+        .and_then(|p| std::path::Path::new(p).file_stem())
+        .and_then(|s| s.to_str())
+        .map(str::to_string);
+
+    if let Some(path) = program_stem.as_deref().and_then(legacy_path) {
+        let rest = args.split_off(1);
+        args = vec!["polycopy".to_string()];
+        args.extend(path.iter().map(|s| s.to_string()));
+        args.extend(rest);
+    } else if let Some(path) = args.get(1).and_then(|a| legacy_path(a)) {
+        let rest = args.split_off(2);
+        args.truncate(1);
+        args.extend(path.iter().map(|s| s.to_string()));
+        args.extend(rest);
+    }
+    args
+}
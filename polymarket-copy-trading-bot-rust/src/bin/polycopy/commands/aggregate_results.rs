@@ -30,6 +30,12 @@ struct ScanResult {
     traders: Vec<TraderResult>,
 }
 
+/// Parameter sweep output from `run_simulations sweep`
+#[derive(Debug, Deserialize)]
+struct SweepResult {
+    configurations: Vec<ScanResult>,
+}
+
 #[derive(Debug, Deserialize)]
 struct AnalysisResult {
     timestamp: Option<i64>,
@@ -41,6 +47,8 @@ struct AnalysisResult {
 
 #[derive(Debug, Deserialize, Clone)]
 struct Config {
+    #[serde(rename = "strategyId")]
+    strategy_id: Option<String>,
     #[serde(rename = "historyDays")]
     history_days: i32,
     multiplier: Option<f64>,
@@ -130,6 +138,10 @@ async fn aggregate_results() -> Result<()> {
             if let Ok(content) = fs::read_to_string(&file_path) {
                 if let Ok(scan_result) = serde_json::from_str::<ScanResult>(&content) {
                     process_scan_result(&scan_result, &mut all_strategies, &mut all_traders);
+                } else if let Ok(sweep_result) = serde_json::from_str::<SweepResult>(&content) {
+                    for scan_result in &sweep_result.configurations {
+                        process_scan_result(scan_result, &mut all_strategies, &mut all_traders);
+                    }
                 } else if let Ok(analysis_result) = serde_json::from_str::<AnalysisResult>(&content) {
                     process_analysis_result(&analysis_result, &mut all_strategies, &mut all_traders);
                 }
@@ -268,11 +280,13 @@ fn process_scan_result(
     strategies: &mut HashMap<String, StrategyPerformance>,
     traders: &mut HashMap<String, TraderData>,
 ) {
-    let strategy_id = format!(
-        "{}d_{}x",
-        result.config.history_days,
-        result.config.multiplier.unwrap_or(1.0)
-    );
+    let strategy_id = result.config.strategy_id.clone().unwrap_or_else(|| {
+        format!(
+            "{}d_{}x",
+            result.config.history_days,
+            result.config.multiplier.unwrap_or(1.0)
+        )
+    });
 
     let strategy = strategies.entry(strategy_id.clone()).or_insert_with(|| {
         StrategyPerformance {
@@ -421,8 +435,7 @@ fn process_analysis_result(
     }
 }
 
-#[tokio::main]
-async fn main() -> Result<()> {
+pub async fn run() -> Result<()> {
     aggregate_results().await?;
     Ok(())
 }
@@ -4,8 +4,7 @@
 use anyhow::Result;
 use polymarket_copy_trading_bot_rust::config::load_env;
 
-#[tokio::main]
-async fn main() -> Result<()> {
+pub async fn run() -> Result<()> {
     println!("🔍 Audit Copy Trading Algorithm");
     println!("════════════════════════════════════════════════════\n");
 
@@ -3,43 +3,12 @@
 
 use anyhow::Result;
 use polymarket_copy_trading_bot_rust::config::load_env;
-use polymarket_copy_trading_bot_rust::utils::fetch_data;
+use crate::common::{fetch_positions, is_contract_address};
 
 const RESOLVED_HIGH: f64 = 0.99;
 const RESOLVED_LOW: f64 = 0.01;
 const ZERO_THRESHOLD: f64 = 0.0001;
 
-#[derive(Debug, serde::Deserialize)]
-struct Position {
-    asset: String,
-    condition_id: String,
-    size: f64,
-    avg_price: f64,
-    current_value: f64,
-    cur_price: f64,
-    title: Option<String>,
-    outcome: Option<String>,
-    slug: Option<String>,
-    redeemable: Option<bool>,
-}
-
-async fn is_contract_address(rpc_url: &str, address: &str) -> Result<bool> {
-    let addr_trimmed = address.trim().trim_start_matches("0x");
-    let body = serde_json::json!({
-        "jsonrpc": "2.0",
-        "method": "eth_getCode",
-        "params": [format!("0x{}", addr_trimmed), "latest"],
-        "id": 1
-    });
-    let client = reqwest::Client::new();
-    let resp = client.post(rpc_url).json(&body).send().await?;
-    let json: serde_json::Value = resp.json().await?;
-    let result = json.get("result").and_then(|v| v.as_str())
-        .ok_or_else(|| anyhow::anyhow!("No result in RPC response"))?;
-    let code = result.trim_start_matches("0x");
-    Ok(!code.is_empty() && code.chars().any(|c| c != '0'))
-}
-
 async fn get_balance(rpc_url: &str, address: &str) -> Result<f64> {
     let body = serde_json::json!({
         "jsonrpc": "2.0",
@@ -75,8 +44,7 @@ fn format_interval(ms: u64) -> String {
     }
 }
 
-#[tokio::main]
-async fn main() -> Result<()> {
+pub async fn run() -> Result<()> {
     println!("\n{}", "═".repeat(63));
     println!("🔍 AUTO-CLAIM CONFIGURATION TEST");
     println!("{}\n", "═".repeat(63));
@@ -143,15 +111,7 @@ async fn main() -> Result<()> {
     println!("{}\n", "━".repeat(65));
     println!("📋 STEP 3: Position Analysis\n");
 
-    let positions_url = format!("https://data-api.polymarket.com/positions?user={}", env.proxy_wallet);
-    let positions_json: serde_json::Value = fetch_data(&positions_url, &env).await?;
-    let all_positions: Vec<Position> = positions_json
-        .as_array()
-        .cloned()
-        .unwrap_or_default()
-        .into_iter()
-        .filter_map(|v| serde_json::from_value(v).ok())
-        .collect();
+    let all_positions = fetch_positions(&env.proxy_wallet, &env).await?;
 
     let valid_positions: Vec<_> = all_positions.iter().filter(|pos| pos.size > ZERO_THRESHOLD).collect();
 
@@ -163,7 +123,7 @@ async fn main() -> Result<()> {
         let resolved: Vec<_> = valid_positions.iter().filter(|pos| pos.cur_price >= RESOLVED_HIGH || pos.cur_price <= RESOLVED_LOW).collect();
         let unresolved: Vec<_> = valid_positions.iter().filter(|pos| pos.cur_price < RESOLVED_HIGH && pos.cur_price > RESOLVED_LOW).collect();
         let redeemable: Vec<_> = valid_positions.iter().filter(|pos| {
-            (pos.cur_price >= RESOLVED_HIGH || pos.cur_price <= RESOLVED_LOW) && pos.redeemable == Some(true)
+            (pos.cur_price >= RESOLVED_HIGH || pos.cur_price <= RESOLVED_LOW) && pos.redeemable
         }).collect();
 
         println!("   📊 Position Breakdown:");
@@ -216,7 +176,7 @@ async fn main() -> Result<()> {
 
     if proxy_is_contract && !signer_is_proxy {
         issues.push("Proxy wallet is a contract (Gnosis Safe)");
-        recommendations.push("Use manual redemption via Safe web interface: https://app.safe.global/");
+        recommendations.push("Redeem through the Safe: polycopy redeem resolved");
         recommendations.push("Or transfer positions to EOA wallet for automatic redemption");
     }
 
@@ -229,7 +189,7 @@ async fn main() -> Result<()> {
         println!("   ✅ Configuration looks good!\n");
         println!("   Next steps:");
         println!("      - Start the bot: cargo run --release");
-        println!("      - Or test manually: cargo run --bin trigger_auto_claim\n");
+        println!("      - Or test manually: polycopy redeem auto-claim\n");
     } else {
         println!("   ⚠️  Issues Found:\n");
         for (index, issue) in issues.iter().enumerate() {
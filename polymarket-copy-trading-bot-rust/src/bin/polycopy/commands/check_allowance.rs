@@ -0,0 +1,32 @@
+//! Check USDC allowance and CTF approval for every Polymarket spender
+
+use anyhow::Result;
+
+use polymarket_copy_trading_bot_rust::config::load_env;
+use polymarket_copy_trading_bot_rust::utils::allowances::{check_allowances, log_allowance_report};
+
+use crate::output::print_json;
+
+pub async fn run(json: bool) -> Result<()> {
+    let env = load_env()?;
+    if json {
+        return print_json(&check_allowances(&env).await?);
+    }
+
+    println!("🔍 Checking USDC balance and allowances...\n");
+    let report = check_allowances(&env).await?;
+    log_allowance_report(&report);
+
+    if report.is_complete() {
+        println!("\n✅ Allowances are already sufficient! No action needed.");
+    } else {
+        println!("\n⚠️  Some allowances are missing:");
+        for (label, _) in report.missing_calls(&env)? {
+            println!("   • {}", label);
+        }
+        println!("\n📝 To set them (EOA or Safe proxy wallet):");
+        println!("   polycopy wallet approve");
+    }
+
+    Ok(())
+}
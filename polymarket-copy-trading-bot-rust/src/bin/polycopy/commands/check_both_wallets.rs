@@ -3,47 +3,10 @@
 
 use anyhow::Result;
 use polymarket_copy_trading_bot_rust::config::load_env;
-use polymarket_copy_trading_bot_rust::utils::{fetch_data, get_my_balance};
-
-#[derive(Debug, serde::Deserialize)]
-struct Activity {
-    proxy_wallet: Option<String>,
-    timestamp: i64,
-    condition_id: String,
-    #[serde(rename = "type")]
-    activity_type: String,
-    size: f64,
-    usdc_size: f64,
-    transaction_hash: String,
-    price: f64,
-    asset: String,
-    side: String,
-    title: Option<String>,
-    slug: Option<String>,
-    outcome: Option<String>,
-}
-
-#[derive(Debug, serde::Deserialize)]
-struct Position {
-    asset: String,
-    condition_id: String,
-    size: f64,
-    avg_price: f64,
-    initial_value: f64,
-    current_value: f64,
-    cash_pnl: f64,
-    percent_pnl: f64,
-    total_bought: f64,
-    realized_pnl: f64,
-    percent_realized_pnl: f64,
-    cur_price: f64,
-    title: Option<String>,
-    slug: Option<String>,
-    outcome: Option<String>,
-}
+use polymarket_copy_trading_bot_rust::utils::get_my_balance;
+use crate::common::{fetch_activities, fetch_positions};
 
-#[tokio::main]
-async fn main() -> Result<()> {
+pub async fn run(compare_address: Option<String>) -> Result<()> {
     println!("🔍 CHECKING BOTH ADDRESSES\n");
     println!("{}\n", "━".repeat(65));
 
@@ -51,8 +14,7 @@ async fn main() -> Result<()> {
     
     // Addresses to compare (can be customized via env or args)
     let address_1 = env.proxy_wallet.clone();
-    let address_2 = std::env::args()
-        .nth(1)
+    let address_2 = compare_address
         .unwrap_or_else(|| "0xd62531bc536bff72394fc5ef715525575787e809".to_string());
 
     // Check first address (from .env)
@@ -60,25 +22,9 @@ async fn main() -> Result<()> {
     println!("   {}", address_1);
     println!("   Profile: https://polymarket.com/profile/{}\n", address_1);
 
-    let addr1_activities_url = format!("https://data-api.polymarket.com/activity?user={}&type=TRADE", address_1);
-    let addr1_activities_json: serde_json::Value = fetch_data(&addr1_activities_url, &env).await?;
-    let addr1_activities: Vec<Activity> = addr1_activities_json
-        .as_array()
-        .cloned()
-        .unwrap_or_default()
-        .into_iter()
-        .filter_map(|v| serde_json::from_value(v).ok())
-        .collect();
-
-    let addr1_positions_url = format!("https://data-api.polymarket.com/positions?user={}", address_1);
-    let addr1_positions_json: serde_json::Value = fetch_data(&addr1_positions_url, &env).await?;
-    let addr1_positions: Vec<Position> = addr1_positions_json
-        .as_array()
-        .cloned()
-        .unwrap_or_default()
-        .into_iter()
-        .filter_map(|v| serde_json::from_value(v).ok())
-        .collect();
+    let addr1_activities = fetch_activities(&address_1, "TRADE", &env).await?;
+
+    let addr1_positions = fetch_positions(&address_1, &env).await?;
 
     println!("   • Trades in API: {}", addr1_activities.len());
     println!("   • Positions in API: {}", addr1_positions.len());
@@ -111,25 +57,9 @@ async fn main() -> Result<()> {
     println!("   {}", address_2);
     println!("   Profile: https://polymarket.com/profile/{}\n", address_2);
 
-    let addr2_activities_url = format!("https://data-api.polymarket.com/activity?user={}&type=TRADE", address_2);
-    let addr2_activities_json: serde_json::Value = fetch_data(&addr2_activities_url, &env).await?;
-    let addr2_activities: Vec<Activity> = addr2_activities_json
-        .as_array()
-        .cloned()
-        .unwrap_or_default()
-        .into_iter()
-        .filter_map(|v| serde_json::from_value(v).ok())
-        .collect();
-
-    let addr2_positions_url = format!("https://data-api.polymarket.com/positions?user={}", address_2);
-    let addr2_positions_json: serde_json::Value = fetch_data(&addr2_positions_url, &env).await?;
-    let addr2_positions: Vec<Position> = addr2_positions_json
-        .as_array()
-        .cloned()
-        .unwrap_or_default()
-        .into_iter()
-        .filter_map(|v| serde_json::from_value(v).ok())
-        .collect();
+    let addr2_activities = fetch_activities(&address_2, "TRADE", &env).await?;
+
+    let addr2_positions = fetch_positions(&address_2, &env).await?;
 
     println!("   • Trades in API: {}", addr2_activities.len());
     println!("   • Positions in API: {}", addr2_positions.len());
@@ -3,47 +3,10 @@
 
 use anyhow::Result;
 use polymarket_copy_trading_bot_rust::config::load_env;
-use polymarket_copy_trading_bot_rust::utils::{fetch_data, get_my_balance};
-
-#[derive(Debug, serde::Deserialize)]
-struct Activity {
-    proxy_wallet: String,
-    timestamp: i64,
-    condition_id: String,
-    #[serde(rename = "type")]
-    activity_type: String,
-    size: f64,
-    usdc_size: f64,
-    transaction_hash: String,
-    price: f64,
-    asset: String,
-    side: String,
-    title: Option<String>,
-    slug: Option<String>,
-    outcome: Option<String>,
-}
-
-#[derive(Debug, Clone, serde::Deserialize)]
-struct Position {
-    asset: String,
-    condition_id: String,
-    size: f64,
-    avg_price: f64,
-    initial_value: f64,
-    current_value: f64,
-    cash_pnl: f64,
-    percent_pnl: f64,
-    total_bought: f64,
-    realized_pnl: f64,
-    percent_realized_pnl: f64,
-    cur_price: f64,
-    title: Option<String>,
-    slug: Option<String>,
-    outcome: Option<String>,
-}
+use polymarket_copy_trading_bot_rust::utils::get_my_balance;
+use crate::common::{Activity, fetch_activities, fetch_positions};
 
-#[tokio::main]
-async fn main() -> Result<()> {
+pub async fn run() -> Result<()> {
     println!("🔍 Checking your wallet statistics on Polymarket\n");
     
     let env = load_env()?;
@@ -57,15 +20,7 @@ async fn main() -> Result<()> {
 
     // 2. Open Positions
     println!("📊 OPEN POSITIONS");
-    let positions_url = format!("https://data-api.polymarket.com/positions?user={}", env.proxy_wallet);
-    let positions_json: serde_json::Value = fetch_data(&positions_url, &env).await?;
-    let positions: Vec<Position> = positions_json
-        .as_array()
-        .cloned()
-        .unwrap_or_default()
-        .into_iter()
-        .filter_map(|v| serde_json::from_value(v).ok())
-        .collect();
+    let positions = fetch_positions(&env.proxy_wallet, &env).await?;
 
     if !positions.is_empty() {
         println!("   Total positions: {}\n", positions.len());
@@ -117,18 +72,7 @@ async fn main() -> Result<()> {
     // 3. Trade History (last 20)
     println!("{}\n", "━".repeat(65));
     println!("📜 TRADE HISTORY (last 20)\n");
-    let activity_url = format!(
-        "https://data-api.polymarket.com/activity?user={}&type=TRADE",
-        env.proxy_wallet
-    );
-    let activities_json: serde_json::Value = fetch_data(&activity_url, &env).await?;
-    let activities: Vec<Activity> = activities_json
-        .as_array()
-        .cloned()
-        .unwrap_or_default()
-        .into_iter()
-        .filter_map(|v| serde_json::from_value(v).ok())
-        .collect();
+    let activities = fetch_activities(&env.proxy_wallet, "TRADE", &env).await?;
 
     if !activities.is_empty() {
         println!("   Total trades in API: {}\n", activities.len());
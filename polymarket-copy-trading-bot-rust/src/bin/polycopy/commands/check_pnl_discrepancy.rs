@@ -3,49 +3,10 @@
 
 use anyhow::Result;
 use polymarket_copy_trading_bot_rust::config::load_env;
-use polymarket_copy_trading_bot_rust::utils::fetch_data;
 use std::collections::HashMap;
+use crate::common::{Activity, fetch_activities, fetch_positions};
 
-#[derive(Debug, serde::Deserialize)]
-struct Activity {
-    proxy_wallet: String,
-    timestamp: i64,
-    condition_id: String,
-    #[serde(rename = "type")]
-    activity_type: String,
-    size: f64,
-    usdc_size: f64,
-    transaction_hash: String,
-    price: f64,
-    asset: String,
-    side: String,
-    title: Option<String>,
-    slug: Option<String>,
-    outcome: Option<String>,
-}
-
-#[derive(Debug, serde::Deserialize)]
-struct Position {
-    asset: String,
-    condition_id: String,
-    size: f64,
-    avg_price: f64,
-    initial_value: f64,
-    current_value: f64,
-    cash_pnl: f64,
-    percent_pnl: f64,
-    total_bought: f64,
-    realized_pnl: f64,
-    percent_realized_pnl: f64,
-    cur_price: f64,
-    redeemable: Option<bool>,
-    title: Option<String>,
-    slug: Option<String>,
-    outcome: Option<String>,
-}
-
-#[tokio::main]
-async fn main() -> Result<()> {
+pub async fn run() -> Result<()> {
     println!("🔍 Detailed P&L discrepancy check\n");
     
     let env = load_env()?;
@@ -54,15 +15,7 @@ async fn main() -> Result<()> {
 
     println!("📊 Fetching data from Polymarket API...\n");
 
-    let positions_url = format!("https://data-api.polymarket.com/positions?user={}", env.proxy_wallet);
-    let positions_json: serde_json::Value = fetch_data(&positions_url, &env).await?;
-    let positions: Vec<Position> = positions_json
-        .as_array()
-        .cloned()
-        .unwrap_or_default()
-        .into_iter()
-        .filter_map(|v| serde_json::from_value(v).ok())
-        .collect();
+    let positions = fetch_positions(&env.proxy_wallet, &env).await?;
 
     println!("Fetched positions: {}\n", positions.len());
 
@@ -143,15 +96,7 @@ async fn main() -> Result<()> {
 
     // Check through trade history
     println!("🔎 CHECK THROUGH TRADE HISTORY:\n");
-    let activity_url = format!("https://data-api.polymarket.com/activity?user={}&type=TRADE", env.proxy_wallet);
-    let activities_json: serde_json::Value = fetch_data(&activity_url, &env).await?;
-    let activities: Vec<Activity> = activities_json
-        .as_array()
-        .cloned()
-        .unwrap_or_default()
-        .into_iter()
-        .filter_map(|v| serde_json::from_value(v).ok())
-        .collect();
+    let activities = fetch_activities(&env.proxy_wallet, "TRADE", &env).await?;
 
     // Group trades by markets
     let mut market_trades: HashMap<String, (Vec<&Activity>, Vec<&Activity>)> = HashMap::new();
@@ -3,42 +3,18 @@
 
 use anyhow::Result;
 use polymarket_copy_trading_bot_rust::config::load_env;
-use polymarket_copy_trading_bot_rust::utils::fetch_data;
+use crate::common::fetch_positions;
+use crate::output::print_json;
 
-#[derive(Debug, Clone, serde::Deserialize)]
-struct Position {
-    asset: String,
-    condition_id: String,
-    size: f64,
-    avg_price: f64,
-    initial_value: f64,
-    current_value: f64,
-    cash_pnl: f64,
-    percent_pnl: f64,
-    total_bought: f64,
-    realized_pnl: f64,
-    percent_realized_pnl: f64,
-    cur_price: f64,
-    title: Option<String>,
-    slug: Option<String>,
-    outcome: Option<String>,
-}
+pub async fn run(json: bool) -> Result<()> {
+    let env = load_env()?;
+    let positions = fetch_positions(&env.proxy_wallet, &env).await?;
+    if json {
+        return print_json(&positions);
+    }
 
-#[tokio::main]
-async fn main() -> Result<()> {
     println!("\n📊 CURRENT POSITIONS:\n");
 
-    let env = load_env()?;
-    let positions_url = format!("https://data-api.polymarket.com/positions?user={}", env.proxy_wallet);
-    let positions_json: serde_json::Value = fetch_data(&positions_url, &env).await?;
-    let positions: Vec<Position> = positions_json
-        .as_array()
-        .cloned()
-        .unwrap_or_default()
-        .into_iter()
-        .filter_map(|v| serde_json::from_value(v).ok())
-        .collect();
-
     if positions.is_empty() {
         println!("❌ No open positions");
         return Ok(());
@@ -91,7 +67,7 @@ async fn main() -> Result<()> {
         }
 
         println!("\n💡 To sell 80% of these positions, use:\n");
-        println!("   cargo run --bin manual_sell\n");
+        println!("   polycopy sell manual\n");
 
         println!("📋 Data for selling:\n");
         for pos in &large_positions {
@@ -6,43 +6,9 @@ use alloy::signers::local::PrivateKeySigner;
 
 use std::str::FromStr;
 use polymarket_copy_trading_bot_rust::config::load_env;
-use polymarket_copy_trading_bot_rust::utils::fetch_data;
-
-#[derive(Debug, serde::Deserialize)]
-struct Activity {
-    proxy_wallet: Option<String>,
-    timestamp: i64,
-    condition_id: String,
-    #[serde(rename = "type")]
-    activity_type: String,
-    size: f64,
-    usdc_size: f64,
-    transaction_hash: String,
-    price: f64,
-    asset: String,
-    side: String,
-    title: Option<String>,
-}
-
-async fn is_contract_address(rpc_url: &str, address: &str) -> Result<bool> {
-    let addr_trimmed = address.trim().trim_start_matches("0x");
-    let body = serde_json::json!({
-        "jsonrpc": "2.0",
-        "method": "eth_getCode",
-        "params": [format!("0x{}", addr_trimmed), "latest"],
-        "id": 1
-    });
-    let client = reqwest::Client::new();
-    let resp = client.post(rpc_url).json(&body).send().await?;
-    let json: serde_json::Value = resp.json().await?;
-    let result = json.get("result").and_then(|v| v.as_str())
-        .ok_or_else(|| anyhow::anyhow!("No result in RPC response"))?;
-    let code = result.trim_start_matches("0x");
-    Ok(!code.is_empty() && code.chars().any(|c| c != '0'))
-}
+use crate::common::{fetch_activities, is_contract_address};
 
-#[tokio::main]
-async fn main() -> Result<()> {
+pub async fn run() -> Result<()> {
     println!("🔍 CHECKING PROXY WALLET AND MAIN WALLET\n");
     println!("{}\n", "━".repeat(65));
 
@@ -65,15 +31,7 @@ async fn main() -> Result<()> {
 
     // Check activity on EOA
     println!("🔎 CHECKING ACTIVITY ON MAIN WALLET (EOA):\n");
-    let eoa_activity_url = format!("https://data-api.polymarket.com/activity?user={}&type=TRADE", eoa_address);
-    let eoa_activities_json: serde_json::Value = fetch_data(&eoa_activity_url, &env).await?;
-    let eoa_activities: Vec<Activity> = eoa_activities_json
-        .as_array()
-        .cloned()
-        .unwrap_or_default()
-        .into_iter()
-        .filter_map(|v| serde_json::from_value(v).ok())
-        .collect();
+    let eoa_activities = fetch_activities(&eoa_address, "TRADE", &env).await?;
 
     println!("   Address: {}", eoa_address);
     println!("   Trades: {}", eoa_activities.len());
@@ -107,15 +65,7 @@ async fn main() -> Result<()> {
 
     // Check activity on Proxy Wallet
     println!("🔎 CHECKING ACTIVITY ON PROXY WALLET (CONTRACT):\n");
-    let proxy_activity_url = format!("https://data-api.polymarket.com/activity?user={}&type=TRADE", env.proxy_wallet);
-    let proxy_activities_json: serde_json::Value = fetch_data(&proxy_activity_url, &env).await?;
-    let proxy_activities: Vec<Activity> = proxy_activities_json
-        .as_array()
-        .cloned()
-        .unwrap_or_default()
-        .into_iter()
-        .filter_map(|v| serde_json::from_value(v).ok())
-        .collect();
+    let proxy_activities = fetch_activities(&env.proxy_wallet, "TRADE", &env).await?;
 
     println!("   Address: {}", env.proxy_wallet);
     println!("   Trades: {}", proxy_activities.len());
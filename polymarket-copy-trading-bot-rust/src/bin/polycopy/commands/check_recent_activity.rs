@@ -3,37 +3,11 @@
 
 use anyhow::Result;
 use polymarket_copy_trading_bot_rust::config::load_env;
-use polymarket_copy_trading_bot_rust::utils::fetch_data;
-
-#[derive(Debug, serde::Deserialize)]
-struct Activity {
-    proxy_wallet: String,
-    timestamp: i64,
-    condition_id: String,
-    #[serde(rename = "type")]
-    activity_type: String,
-    size: f64,
-    usdc_size: f64,
-    transaction_hash: String,
-    price: f64,
-    asset: String,
-    side: String,
-    title: Option<String>,
-    market: Option<String>,
-}
+use crate::common::fetch_activities;
 
-#[tokio::main]
-async fn main() -> Result<()> {
+pub async fn run() -> Result<()> {
     let env = load_env()?;
-    let url = format!("https://data-api.polymarket.com/activity?user={}&type=TRADE", env.proxy_wallet);
-    let activities_json: serde_json::Value = fetch_data(&url, &env).await?;
-    let activities: Vec<Activity> = activities_json
-        .as_array()
-        .cloned()
-        .unwrap_or_default()
-        .into_iter()
-        .filter_map(|v| serde_json::from_value(v).ok())
-        .collect();
+    let activities = fetch_activities(&env.proxy_wallet, "TRADE", &env).await?;
 
     if activities.is_empty() {
         println!("No trade data available");
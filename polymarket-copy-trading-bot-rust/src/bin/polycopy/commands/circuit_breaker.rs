@@ -0,0 +1,120 @@
+//! Circuit breaker status, resume and kill switch
+#![allow(dead_code)]
+
+use anyhow::Result;
+use colored::*;
+use polymarket_copy_trading_bot_rust::config::load_env;
+use polymarket_copy_trading_bot_rust::services::circuit_breaker::{
+    current_equity, load_breaker_state, resume_breaker, trip_breaker, BreakerState,
+};
+
+use crate::common::{select_accounts, AccountSelection};
+use crate::output::print_account_json;
+
+pub enum BreakerAction {
+    /// Show breaker state and equity
+    Status,
+    /// Clear a trip and resume buying
+    Resume,
+    /// Kill switch: pause all new buys
+    Kill(Option<String>),
+}
+
+fn format_time(timestamp: i64) -> String {
+    chrono::DateTime::from_timestamp(timestamp, 0)
+        .map(|t| t.format("%Y-%m-%d %H:%M:%S UTC").to_string())
+        .unwrap_or_else(|| "-".to_string())
+}
+
+fn print_state(state: &BreakerState) {
+    if state.tripped {
+        println!("  Status:          {}", "TRIPPED - new buys paused".red().bold());
+        println!("  Reason:          {}", state.reason.as_deref().unwrap_or("-"));
+        if let Some(at) = state.tripped_at {
+            println!("  Tripped at:      {}", format_time(at));
+        }
+    } else {
+        println!("  Status:          {}", "ACTIVE - copying buys".green().bold());
+    }
+    match state.high_water_mark() {
+        Some(hwm) => println!("  High-water mark: ${:.2}", hwm),
+        None => println!("  High-water mark: -"),
+    }
+    match state.day_start_equity {
+        Some(start) => println!("  Day start:       ${:.2} ({})", start, state.day.as_deref().unwrap_or("-")),
+        None => println!("  Day start:       -"),
+    }
+    if state.updated_at > 0 {
+        println!("  Last check:      {}", format_time(state.updated_at));
+    }
+}
+
+pub async fn run(action: BreakerAction, selection: AccountSelection, json: bool) -> Result<()> {
+    let env = load_env()?;
+    let accounts = select_accounts(&env, &selection).await?;
+
+    if json {
+        let mut states = Vec::with_capacity(accounts.len());
+        for account in &accounts {
+            let state = match &action {
+                BreakerAction::Status => load_breaker_state(&account.db).await?,
+                BreakerAction::Resume => resume_breaker(&account.db).await?,
+                BreakerAction::Kill(reason) => trip_breaker(&account.db, &kill_reason(reason.as_deref())).await?,
+            };
+            states.push((account.name.clone(), state));
+        }
+        return print_account_json(&states);
+    }
+
+    println!("\n{}", "⛔ CIRCUIT BREAKER".cyan().bold());
+    println!("{}\n", "━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━".cyan());
+
+    for account in &accounts {
+        if accounts.len() > 1 {
+            println!("{}", format!("Account: {}", account.name).bold());
+        }
+        let db = &account.db;
+        match &action {
+            BreakerAction::Status => {
+                let state = load_breaker_state(db).await?;
+                print_state(&state);
+
+                let (balance, positions_value) = current_equity(&account.env, db).await?;
+                let equity = balance + positions_value;
+                println!(
+                    "  Current equity:  ${:.2} (${:.2} USDC + ${:.2} positions)",
+                    equity, balance, positions_value
+                );
+                println!("  Drawdown:        {:.2}%", state.drawdown_percent(equity));
+                println!("  Daily loss:      ${:.2}", state.daily_loss(equity));
+            }
+            BreakerAction::Resume => {
+                let state = resume_breaker(db).await?;
+                println!("{}", "✓ Breaker reset - the bot will resume copying buys".green());
+                println!("{}", "  High-water mark and day start re-baseline on the next check".bright_black());
+                print_state(&state);
+            }
+            BreakerAction::Kill(reason) => {
+                let state = trip_breaker(db, &kill_reason(reason.as_deref())).await?;
+                println!("{}", "⛔ Kill switch engaged - new buys paused until resumed".red().bold());
+                print_state(&state);
+            }
+        }
+        println!();
+    }
+
+    if matches!(action, BreakerAction::Status) && !env.circuit_breaker.is_enabled() {
+        println!(
+            "{}\n",
+            "Automatic limits are off (set CIRCUIT_BREAKER_* in .env). The kill switch still works.".yellow()
+        );
+    }
+    Ok(())
+}
+
+fn kill_reason(reason: Option<&str>) -> String {
+    match reason {
+        Some(reason) => format!("kill switch: {}", reason),
+        None => "kill switch".to_string(),
+    }
+}
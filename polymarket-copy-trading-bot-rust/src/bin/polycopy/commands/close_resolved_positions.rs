@@ -14,26 +14,13 @@ use alloy::primitives::U256;
 use chrono::DateTime;
 use std::str::FromStr;
 use std::time::{SystemTime, UNIX_EPOCH};
+use crate::common::{fetch_positions, Position};
 
 const MIN_SELL_TOKENS: f64 = 1.0;
 const ZERO_THRESHOLD: f64 = 0.0001;
 const RESOLVED_HIGH: f64 = 0.99;
 const RESOLVED_LOW: f64 = 0.01;
 
-#[derive(Debug, Clone, serde::Deserialize)]
-struct Position {
-    asset: String,
-    condition_id: String,
-    size: f64,
-    avg_price: f64,
-    current_value: f64,
-    cur_price: f64,
-    title: Option<String>,
-    outcome: Option<String>,
-    slug: Option<String>,
-    redeemable: Option<bool>,
-}
-
 async fn sell_entire_position(
     clob_client: &ClobClient<Authenticated<Normal>>,
     position: &Position,
@@ -177,8 +164,7 @@ async fn sell_entire_position(
     Ok((sold_tokens, proceeds_usd, remaining))
 }
 
-#[tokio::main]
-async fn main() -> Result<()> {
+pub async fn run() -> Result<()> {
     println!("🚀 Closing resolved positions");
     println!("════════════════════════════════════════════════════");
     
@@ -190,15 +176,10 @@ async fn main() -> Result<()> {
     let (clob_client, signer) = create_clob_client(&env).await?;
     println!("✅ Connected to Polymarket CLOB");
 
-    let positions_url = format!("https://data-api.polymarket.com/positions?user={}", env.proxy_wallet);
-    let positions_json: serde_json::Value = fetch_data(&positions_url, &env).await?;
-    let all_positions: Vec<Position> = positions_json
-        .as_array()
-        .cloned()
-        .unwrap_or_default()
+    let all_positions: Vec<Position> = fetch_positions(&env.proxy_wallet, &env)
+        .await?
         .into_iter()
-        .filter_map(|v| serde_json::from_value::<Position>(v).ok())
-        .filter(|pos: &Position| pos.size > ZERO_THRESHOLD)
+        .filter(|pos| pos.size > ZERO_THRESHOLD)
         .collect();
 
     if all_positions.is_empty() {
@@ -250,7 +231,7 @@ async fn main() -> Result<()> {
         }
         println!("   Size: {:.2} tokens @ avg ${:.3}", position.size, position.avg_price);
         println!("   Current price: ${:.4} (Est. value: ${:.2})", position.cur_price, position.current_value);
-        if position.redeemable == Some(true) {
+        if position.redeemable {
             println!("   ℹ️  Market is redeemable — can be redeemed directly");
         }
 
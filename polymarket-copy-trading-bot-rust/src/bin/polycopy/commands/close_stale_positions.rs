@@ -15,24 +15,11 @@ use chrono::DateTime;
 use std::collections::HashSet;
 use std::str::FromStr;
 use std::time::{SystemTime, UNIX_EPOCH};
+use crate::common::{fetch_positions, Position};
 
 const MIN_SELL_TOKENS: f64 = 1.0;
 const ZERO_THRESHOLD: f64 = 0.0001;
 
-#[derive(Debug, Clone, serde::Deserialize)]
-struct Position {
-    asset: String,
-    condition_id: String,
-    size: f64,
-    avg_price: f64,
-    current_value: f64,
-    cur_price: f64,
-    title: Option<String>,
-    outcome: Option<String>,
-    slug: Option<String>,
-    redeemable: Option<bool>,
-}
-
 async fn sell_entire_position(
     clob_client: &ClobClient<Authenticated<Normal>>,
     position: &Position,
@@ -141,17 +128,8 @@ async fn build_tracked_set(env: &polymarket_copy_trading_bot_rust::config::Env)
     let mut tracked = HashSet::new();
 
     for user in &env.user_addresses {
-        let positions_url = format!("https://data-api.polymarket.com/positions?user={}", user);
-        match fetch_data(&positions_url, env).await {
-            Ok(positions_json) => {
-                let positions: Vec<Position> = positions_json
-                    .as_array()
-                    .cloned()
-                    .unwrap_or_default()
-                    .into_iter()
-                    .filter_map(|v| serde_json::from_value(v).ok())
-                    .collect();
-                
+        match fetch_positions(user, env).await {
+            Ok(positions) => {
                 for pos in positions {
                     if pos.size > ZERO_THRESHOLD {
                         tracked.insert(format!("{}:{}", pos.condition_id, pos.asset));
@@ -167,8 +145,7 @@ async fn build_tracked_set(env: &polymarket_copy_trading_bot_rust::config::Env)
     Ok(tracked)
 }
 
-#[tokio::main]
-async fn main() -> Result<()> {
+pub async fn run() -> Result<()> {
     println!("🚀 Closing stale positions (tracked traders already exited)");
     println!("════════════════════════════════════════════════════");
     
@@ -178,17 +155,12 @@ async fn main() -> Result<()> {
     let (clob_client, signer) = create_clob_client(&env).await?;
     println!("✅ Connected to Polymarket CLOB");
 
-    let my_positions_json = fetch_data(&format!("https://data-api.polymarket.com/positions?user={}", env.proxy_wallet), &env).await?;
-    let tracked_set = build_tracked_set(&env).await?;
-
-    let my_positions: Vec<Position> = my_positions_json
-        .as_array()
-        .cloned()
-        .unwrap_or_default()
+    let my_positions: Vec<Position> = fetch_positions(&env.proxy_wallet, &env)
+        .await?
         .into_iter()
-        .filter_map(|v| serde_json::from_value::<Position>(v).ok())
-        .filter(|pos: &Position| pos.size > ZERO_THRESHOLD)
+        .filter(|pos| pos.size > ZERO_THRESHOLD)
         .collect();
+    let tracked_set = build_tracked_set(&env).await?;
 
     if my_positions.is_empty() {
         println!("\n🎉 No open positions detected for proxy wallet.");
@@ -217,7 +189,7 @@ async fn main() -> Result<()> {
         }
         println!("   Size: {:.2} tokens @ avg ${:.3}", position.size, position.avg_price);
         println!("   Est. value: ${:.2} (cur price ${:.3})", position.current_value, position.cur_price);
-        if position.redeemable == Some(true) {
+        if position.redeemable {
             println!("   ℹ️  Market is redeemable — consider redeeming if value stays flat at $0.");
         }
 
@@ -5,7 +5,7 @@ use anyhow::Result;
 use colored::*;
 use serde::Deserialize;
 use std::collections::HashMap;
-use std::env;
+
 use std::fs;
 use std::path::Path;
 
@@ -351,74 +351,42 @@ fn print_detailed_result(result: &SimulationResult) {
     println!();
 }
 
-fn print_help() {
-    println!("{}", "\n📊 Simulation Results Comparison - Usage\n".cyan());
-
-    println!("Commands:");
-    println!("{}", "  cargo run --bin compare_results              # Show all results".yellow());
-    println!("{}", "  cargo run --bin compare_results best [N]     # Show top N results (default: 10)".yellow());
-    println!("{}", "  cargo run --bin compare_results worst [N]    # Show worst N results (default: 5)".yellow());
-    println!("{}", "  cargo run --bin compare_results stats        # Show aggregate statistics".yellow());
-    println!("{}", "  cargo run --bin compare_results detail <name> # Show detailed info for a result\n".yellow());
-
-    println!("Examples:");
-    println!("{}", "  cargo run --bin compare_results best 5".bright_black());
-    println!("{}", "  cargo run --bin compare_results detail std_m2p0\n".bright_black());
+pub enum CompareView {
+    /// Table, best 5, worst 3 and statistics
+    All,
+    Best(usize),
+    Worst(usize),
+    Stats,
+    /// Details of the first result whose name contains the query
+    Detail(String),
 }
 
-#[tokio::main]
-async fn main() -> Result<()> {
-    let args: Vec<String> = env::args().skip(1).collect();
+pub async fn run(view: CompareView) -> Result<()> {
     let results = load_simulation_results();
 
     if results.is_empty() {
         println!("{}", "\nNo simulation results to compare. Run simulations first with:".yellow());
-        println!("{}", "  cargo run --bin simulate_profitability\n".cyan());
+        println!("{}", "  polycopy sim run\n".cyan());
         return Ok(());
     }
 
-    let command = args.get(0).map(|s| s.as_str()).unwrap_or("all");
-
-    match command {
-        "all" => {
+    match view {
+        CompareView::All => {
             print_comparison_table(&results);
             print_best_results(&results, 5);
             print_worst_results(&results, 3);
             print_statistics(&results);
         }
-        "best" => {
-            let limit = args.get(1).and_then(|s| s.parse::<usize>().ok()).unwrap_or(10);
-            print_best_results(&results, limit);
-        }
-        "worst" => {
-            let limit = args.get(1).and_then(|s| s.parse::<usize>().ok()).unwrap_or(5);
-            print_worst_results(&results, limit);
-        }
-        "stats" => {
-            print_statistics(&results);
-        }
-        "detail" => {
-            let search_name = args.get(1);
-            if search_name.is_none() {
-                println!("{}", "Please provide a result name to view details".red());
-                println!("{}", "Usage: cargo run --bin compare_results detail <name>".yellow());
-                return Ok(());
-            }
-
-            let search_name = search_name.unwrap();
-            if let Some(found) = results.iter().find(|r| r.name.contains(search_name)) {
+        CompareView::Best(limit) => print_best_results(&results, limit),
+        CompareView::Worst(limit) => print_worst_results(&results, limit),
+        CompareView::Stats => print_statistics(&results),
+        CompareView::Detail(search_name) => {
+            if let Some(found) = results.iter().find(|r| r.name.contains(&search_name)) {
                 print_detailed_result(found);
             } else {
                 println!("{}", format!("No result found matching: {}", search_name).red());
             }
         }
-        "help" | "--help" | "-h" => {
-            print_help();
-        }
-        _ => {
-            println!("{}", format!("Unknown command: {}\n", command).red());
-            print_help();
-        }
     }
 
     Ok(())
@@ -3,7 +3,7 @@
 
 use anyhow::Result;
 use polymarket_copy_trading_bot_rust::config::load_env;
-use polymarket_copy_trading_bot_rust::utils::fetch_data;
+use polymarket_copy_trading_bot_rust::utils::{ActivityQuery, DataApiClient};
 use std::fs;
 use std::path::Path;
 
@@ -34,66 +34,36 @@ struct CachedTrades {
 
 const HISTORY_DAYS: i32 = 30;
 const MAX_TRADES_PER_TRADER: usize = 20000;
-const BATCH_SIZE: usize = 100;
 const MAX_PARALLEL: usize = 4;
 
-async fn fetch_batch(
-    address: &str,
-    offset: usize,
-    limit: usize,
-    env: &polymarket_copy_trading_bot_rust::config::Env,
-) -> Result<Vec<Trade>> {
-    let url = format!(
-        "https://data-api.polymarket.com/activity?user={}&type=TRADE&limit={}&offset={}",
-        address, limit, offset
-    );
-    let json: serde_json::Value = fetch_data(&url, env).await?;
-    let trades: Vec<Trade> = json
-        .as_array()
-        .cloned()
-        .unwrap_or_default()
-        .into_iter()
-        .filter_map(|v| serde_json::from_value(v).ok())
-        .collect();
-    Ok(trades)
-}
-
 async fn fetch_trades_for_trader(
     address: &str,
     env: &polymarket_copy_trading_bot_rust::config::Env,
 ) -> Result<Vec<Trade>> {
     println!("\n🚀 Loading history for {} (last {} days)", address, HISTORY_DAYS);
-    let since_timestamp = (chrono::Utc::now().timestamp() - (HISTORY_DAYS as i64 * 24 * 60 * 60)) as i64;
-
-    let mut offset = 0;
-    let mut all_trades = Vec::new();
-    let mut has_more = true;
-
-    while has_more && all_trades.len() < MAX_TRADES_PER_TRADER {
-        let batch_limit = (MAX_TRADES_PER_TRADER - all_trades.len()).min(BATCH_SIZE);
-        let batch = fetch_batch(address, offset, batch_limit, env).await?;
-        let batch_len = batch.len();
-
-        if batch.is_empty() {
-            break;
-        }
-
-        let filtered: Vec<_> = batch
-            .into_iter()
-            .filter(|trade| trade.timestamp >= since_timestamp)
-            .collect();
-        all_trades.extend(filtered);
-
-        if batch_len < batch_limit {
-            has_more = false;
-        }
-
-        offset += batch_limit;
-
-        if all_trades.len() % (BATCH_SIZE * MAX_PARALLEL) == 0 {
-            tokio::time::sleep(tokio::time::Duration::from_millis(150)).await;
-        }
-    }
+    let since_timestamp = chrono::Utc::now().timestamp() - (HISTORY_DAYS as i64 * 24 * 60 * 60);
+
+    let query = ActivityQuery::new(address)
+        .kind("TRADE")
+        .start(since_timestamp)
+        .limit(MAX_TRADES_PER_TRADER);
+    let mut all_trades: Vec<Trade> = DataApiClient::new(env)
+        .activity(&query)
+        .await?
+        .into_iter()
+        .map(|activity| Trade {
+            id: activity.transaction_hash,
+            timestamp: activity.timestamp,
+            slug: activity.slug,
+            market: activity.market,
+            asset: activity.asset,
+            side: activity.side,
+            price: activity.price,
+            usdc_size: activity.usdc_size,
+            size: activity.size,
+            outcome: activity.outcome,
+        })
+        .collect();
 
     all_trades.sort_by_key(|t| t.timestamp);
     println!("✓ Retrieved {} trades", all_trades.len());
@@ -125,8 +95,7 @@ fn save_trades_to_cache(address: &str, trades: &[Trade]) -> Result<()> {
     Ok(())
 }
 
-#[tokio::main]
-async fn main() -> Result<()> {
+pub async fn run() -> Result<()> {
     let env = load_env()?;
 
     if env.user_addresses.is_empty() {
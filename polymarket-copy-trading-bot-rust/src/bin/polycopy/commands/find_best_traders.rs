@@ -0,0 +1,76 @@
+//! Find best performing traders
+
+use anyhow::Result;
+use polymarket_copy_trading_bot_rust::config::load_env;
+use polymarket_copy_trading_bot_rust::utils::data_api::LeaderboardQuery;
+use polymarket_copy_trading_bot_rust::utils::DataApiClient;
+use polymarket_copy_trading_bot_rust::utils::trader_scoring::{score_traders, ScoringWeights};
+use crate::common::get_env_usize;
+
+pub async fn run() -> Result<()> {
+    println!("🔍 Finding Best Traders");
+    println!("════════════════════════════════════════════════════\n");
+
+    let env = load_env()?;
+
+    // Fetch leaderboard from Polymarket
+    println!("📊 Fetching trader leaderboard from Polymarket...\n");
+
+    let leaderboard = LeaderboardQuery::new().time_period("MONTH").order_by("PNL").limit(50);
+    match DataApiClient::new(&env).leaderboard(&leaderboard).await {
+        Ok(entries) => {
+
+            if entries.is_empty() {
+                println!("❌ No traders found in leaderboard");
+                return Ok(());
+            }
+
+            println!("✅ Found {} traders\n", entries.len());
+
+            // Re-rank the leaderboard on risk-adjusted metrics from each trader's history
+            let candidates = get_env_usize("SCORE_CANDIDATES", 30);
+            let history_days = get_env_usize("SCORE_HISTORY_DAYS", 30) as i32;
+            let min_trades = get_env_usize("SCORE_MIN_TRADES", 10);
+            let weights = ScoringWeights::balanced().with_env_overrides();
+            let addresses: Vec<String> = entries.iter().take(candidates).map(|e| e.address.to_lowercase()).collect();
+
+            println!("📈 Scoring {} traders on {} days of history...\n", addresses.len(), history_days);
+            let scores = score_traders(&addresses, history_days, min_trades, &env.copy_strategy_config, &weights, &env).await?;
+
+            if scores.is_empty() {
+                println!("❌ No traders with at least {} trades in {} days", min_trades, history_days);
+                return Ok(());
+            }
+
+            println!("{}", "━".repeat(65));
+            println!("📊 TOP TRADERS (risk-adjusted score):\n");
+
+            for (i, score) in scores.iter().take(20).enumerate() {
+                let leaderboard_pnl = entries
+                    .iter()
+                    .find(|e| e.address.eq_ignore_ascii_case(&score.address))
+                    .and_then(|e| e.pnl)
+                    .unwrap_or(0.0);
+
+                println!("{}. {}  (score {:.1})", i + 1, &score.address[..10], score.score);
+                println!("   P&L: ${:.2} ({}d) | Leaderboard P&L: ${:.2}", score.total_pnl, history_days, leaderboard_pnl);
+                println!("   ROI: {:.1}% | Win Rate: {:.1}%", score.roi, score.win_rate);
+                println!("   Max Drawdown: {:.1}% | Sharpe: {:.2} | Sortino: {:.2}", score.max_drawdown_percent, score.sharpe, score.sortino);
+                println!("   Avg Hold: {:.1}h | Top Market: {:.0}% of volume", score.avg_hold_hours, score.top_market_share * 100.0);
+                println!("   Median Order: ${:.2} | Copyable: {:.0}% | Trades: {}\n", score.median_order_size, score.copyability * 100.0, score.trades);
+            }
+
+            println!("{}", "━".repeat(65));
+            println!("\n💡 To copy these traders, add their addresses to USER_ADDRESSES in .env");
+            println!("   Example: USER_ADDRESSES={}", scores.iter().take(3).map(|s| s.address.clone()).collect::<Vec<_>>().join(","));
+            println!("   Weights can be tuned with SCORE_WEIGHT_ROI, _SHARPE, _SORTINO, _DRAWDOWN, _WIN_RATE, _DIVERSIFICATION, _COPYABILITY");
+        }
+        Err(e) => {
+            println!("⚠️  Failed to fetch leaderboard: {}", e);
+            println!("\n💡 Alternative: Check https://polymarket.com/leaderboard manually");
+        }
+    }
+
+    Ok(())
+}
+
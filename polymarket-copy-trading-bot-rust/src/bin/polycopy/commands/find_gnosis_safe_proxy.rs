@@ -6,10 +6,10 @@ use alloy::signers::local::PrivateKeySigner;
 
 use std::str::FromStr;
 use polymarket_copy_trading_bot_rust::config::load_env;
-use polymarket_copy_trading_bot_rust::utils::fetch_data;
+use polymarket_copy_trading_bot_rust::utils::{ActivityQuery, DataApiClient};
+use crate::common::fetch_positions;
 
-#[tokio::main]
-async fn main() -> Result<()> {
+pub async fn run() -> Result<()> {
     println!("\n🔍 FINDING GNOSIS SAFE PROXY WALLET\n");
     println!("{}\n", "━".repeat(65));
 
@@ -31,13 +31,8 @@ async fn main() -> Result<()> {
     println!("{}\n", "━".repeat(65));
     println!("📋 STEP 2: Positions on EOA address\n");
 
-    let eoa_positions_url = format!("https://data-api.polymarket.com/positions?user={}", eoa_address);
-    match fetch_data(&eoa_positions_url, &env).await {
-        Ok(positions_json) => {
-            let positions: Vec<serde_json::Value> = positions_json
-                .as_array()
-                .cloned()
-                .unwrap_or_default();
+    match fetch_positions(&eoa_address, &env).await {
+        Ok(positions) => {
             println!("   Positions: {}\n", positions.len());
 
             if !positions.is_empty() {
@@ -53,17 +48,12 @@ async fn main() -> Result<()> {
     println!("{}\n", "━".repeat(65));
     println!("📋 STEP 3: Finding Gnosis Safe Proxy via transactions\n");
 
-    let activities_url = format!("https://data-api.polymarket.com/activity?user={}&type=TRADE", eoa_address);
-    match fetch_data(&activities_url, &env).await {
-        Ok(activities_json) => {
-            let activities: Vec<serde_json::Value> = activities_json
-                .as_array()
-                .cloned()
-                .unwrap_or_default();
-
+    let first_trade = ActivityQuery::new(&eoa_address).kind("TRADE").limit(1);
+    match DataApiClient::new(&env).activity(&first_trade).await {
+        Ok(activities) => {
             if !activities.is_empty() {
                 if let Some(first_trade) = activities.first() {
-                    if let Some(proxy_wallet) = first_trade.get("proxyWallet").and_then(|v| v.as_str()) {
+                    if let Some(proxy_wallet) = first_trade.proxy_wallet.as_deref() {
                         println!("   🎯 FOUND PROXY WALLET!\n");
                         println!("   Proxy Wallet: {}\n", proxy_wallet);
 
@@ -85,26 +75,18 @@ async fn main() -> Result<()> {
                         }
 
                         // Check positions on proxy
-                        let proxy_positions_url = format!("https://data-api.polymarket.com/positions?user={}", proxy_wallet);
-                        match fetch_data(&proxy_positions_url, &env).await {
-                            Ok(proxy_positions_json) => {
-                                let proxy_positions: Vec<serde_json::Value> = proxy_positions_json
-                                    .as_array()
-                                    .cloned()
-                                    .unwrap_or_default();
-                                println!("   Positions on Proxy: {}\n", proxy_positions.len());
-
-                                if !proxy_positions.is_empty() {
-                                    println!("{}\n", "━".repeat(65));
-                                    println!("✅ SOLUTION FOUND!\n");
-                                    println!("{}\n", "━".repeat(65));
-                                    println!("Update .env file:\n");
-                                    println!("PROXY_WALLET={}\n", proxy_wallet);
-                                    println!("{}\n", "━".repeat(65));
-                                    return Ok(());
-                                }
+                        if let Ok(proxy_positions) = fetch_positions(proxy_wallet, &env).await {
+                            println!("   Positions on Proxy: {}\n", proxy_positions.len());
+
+                            if !proxy_positions.is_empty() {
+                                println!("{}\n", "━".repeat(65));
+                                println!("✅ SOLUTION FOUND!\n");
+                                println!("{}\n", "━".repeat(65));
+                                println!("Update .env file:\n");
+                                println!("PROXY_WALLET={}\n", proxy_wallet);
+                                println!("{}\n", "━".repeat(65));
+                                return Ok(());
                             }
-                            Err(_) => {}
                         }
                     }
                 }
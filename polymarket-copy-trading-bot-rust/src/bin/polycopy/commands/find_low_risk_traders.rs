@@ -0,0 +1,74 @@
+//! Find low-risk traders
+
+use anyhow::Result;
+use polymarket_copy_trading_bot_rust::config::load_env;
+use polymarket_copy_trading_bot_rust::utils::data_api::LeaderboardQuery;
+use polymarket_copy_trading_bot_rust::utils::DataApiClient;
+use polymarket_copy_trading_bot_rust::utils::trader_scoring::{score_traders, ScoringWeights};
+use crate::common::{get_env_f64, get_env_usize};
+
+pub async fn run() -> Result<()> {
+    println!("🔍 Finding Low-Risk Traders");
+    println!("════════════════════════════════════════════════════\n");
+
+    let env = load_env()?;
+
+    println!("📊 Fetching trader leaderboard...\n");
+
+    let leaderboard = LeaderboardQuery::new().time_period("MONTH").order_by("PNL").limit(100);
+    match DataApiClient::new(&env).leaderboard(&leaderboard).await {
+        Ok(entries) => {
+
+            let candidates = get_env_usize("SCORE_CANDIDATES", 50);
+            let history_days = get_env_usize("SCORE_HISTORY_DAYS", 30) as i32;
+            let max_drawdown = get_env_f64("LOW_RISK_MAX_DRAWDOWN", 25.0);
+            let min_win_rate = get_env_f64("LOW_RISK_MIN_WIN_RATE", 55.0);
+            let weights = ScoringWeights::low_risk().with_env_overrides();
+            let addresses: Vec<String> = entries
+                .iter()
+                .filter(|e| e.pnl.unwrap_or(0.0) > 0.0)
+                .take(candidates)
+                .map(|e| e.address.to_lowercase())
+                .collect();
+
+            println!("📈 Scoring {} profitable traders on {} days of history...\n", addresses.len(), history_days);
+            let scores = score_traders(&addresses, history_days, 50, &env.copy_strategy_config, &weights, &env).await?;
+
+            // Filter for low-risk traders (bounded drawdown, high win rate, positive P&L)
+            let low_risk: Vec<_> = scores
+                .iter()
+                .filter(|s| s.max_drawdown_percent <= max_drawdown && s.win_rate >= min_win_rate && s.total_pnl > 0.0)
+                .take(20)
+                .collect();
+
+            if low_risk.is_empty() {
+                println!("❌ No low-risk traders found");
+                return Ok(());
+            }
+
+            println!("✅ Found {} low-risk traders\n", low_risk.len());
+            println!("{}", "━".repeat(65));
+            println!(
+                "📊 LOW-RISK TRADERS (Max Drawdown <= {}%, Win Rate >= {}%, Positive P&L, 50+ trades):\n",
+                max_drawdown, min_win_rate
+            );
+
+            for (i, score) in low_risk.iter().enumerate() {
+                println!("{}. {}  (score {:.1})", i + 1, &score.address[..10], score.score);
+                println!("   P&L: ${:.2} | ROI: {:.1}%", score.total_pnl, score.roi);
+                println!("   Win Rate: {:.1}% | Max Drawdown: {:.1}%", score.win_rate, score.max_drawdown_percent);
+                println!("   Sharpe: {:.2} | Sortino: {:.2}", score.sharpe, score.sortino);
+                println!("   Avg Hold: {:.1}h | Market Concentration: {:.2}", score.avg_hold_hours, score.market_concentration);
+                println!("   Copyable: {:.0}% | Trades: {}\n", score.copyability * 100.0, score.trades);
+            }
+
+            println!("{}", "━".repeat(65));
+        }
+        Err(e) => {
+            println!("⚠️  Failed to fetch leaderboard: {}", e);
+        }
+    }
+
+    Ok(())
+}
+
@@ -6,10 +6,10 @@ use alloy::signers::local::PrivateKeySigner;
 
 use std::str::FromStr;
 use polymarket_copy_trading_bot_rust::config::load_env;
-use polymarket_copy_trading_bot_rust::utils::fetch_data;
+use polymarket_copy_trading_bot_rust::utils::{ActivityQuery, DataApiClient};
+use crate::common::fetch_positions;
 
-#[tokio::main]
-async fn main() -> Result<()> {
+pub async fn run() -> Result<()> {
     println!("\n🔍 WALLET AND ADDRESS ANALYSIS\n");
     println!("{}\n", "━".repeat(65));
 
@@ -75,23 +75,13 @@ async fn main() -> Result<()> {
     println!("{}\n", "━".repeat(65));
     println!("📋 STEP 4: Activity on Polymarket\n");
 
-    let proxy_positions_url = format!("https://data-api.polymarket.com/positions?user={}", env.proxy_wallet);
-    let proxy_positions_json: serde_json::Value = fetch_data(&proxy_positions_url, &env).await?;
-    let proxy_positions: Vec<serde_json::Value> = proxy_positions_json
-        .as_array()
-        .cloned()
-        .unwrap_or_default();
+    let proxy_positions = fetch_positions(&env.proxy_wallet, &env).await?;
     
     println!("   PROXY_WALLET ({}...):", &env.proxy_wallet[..10.min(env.proxy_wallet.len())]);
     println!("   • Positions: {}\n", proxy_positions.len());
 
     if eoa_address.to_lowercase() != env.proxy_wallet.to_lowercase() {
-        let eoa_positions_url = format!("https://data-api.polymarket.com/positions?user={}", eoa_address);
-        let eoa_positions_json: serde_json::Value = fetch_data(&eoa_positions_url, &env).await?;
-        let eoa_positions: Vec<serde_json::Value> = eoa_positions_json
-            .as_array()
-            .cloned()
-            .unwrap_or_default();
+        let eoa_positions = fetch_positions(&eoa_address, &env).await?;
         
         println!("   EOA ({}...):", &eoa_address[..10.min(eoa_address.len())]);
         println!("   • Positions: {}\n", eoa_positions.len());
@@ -101,17 +91,12 @@ async fn main() -> Result<()> {
     println!("{}\n", "━".repeat(65));
     println!("📋 STEP 5: Check proxyWallet in transactions\n");
 
-    let activities_url = format!("https://data-api.polymarket.com/activity?user={}&type=TRADE", env.proxy_wallet);
-    let activities_json: serde_json::Value = fetch_data(&activities_url, &env).await?;
-    let activities: Vec<serde_json::Value> = activities_json
-        .as_array()
-        .cloned()
-        .unwrap_or_default();
+    let activities = DataApiClient::new(&env)
+        .activity(&ActivityQuery::new(&env.proxy_wallet).kind("TRADE").limit(1))
+        .await?;
 
     if let Some(first_trade) = activities.first() {
-        let proxy_wallet_in_trade = first_trade.get("proxyWallet")
-            .and_then(|v| v.as_str())
-            .unwrap_or("N/A");
+        let proxy_wallet_in_trade = first_trade.proxy_wallet.as_deref().unwrap_or("N/A");
 
         println!("   Address from .env:         {}", env.proxy_wallet);
         println!("   proxyWallet in trades:     {}\n", proxy_wallet_in_trade);
@@ -6,10 +6,10 @@ use alloy::signers::local::PrivateKeySigner;
 
 use std::str::FromStr;
 use polymarket_copy_trading_bot_rust::config::load_env;
-use polymarket_copy_trading_bot_rust::utils::fetch_data;
+use polymarket_copy_trading_bot_rust::utils::{fetch_data, DataApiClient};
+use crate::common::{fetch_activities, fetch_positions};
 
-#[tokio::main]
-async fn main() -> Result<()> {
+pub async fn run() -> Result<()> {
     println!("\n🔍 FINDING REAL PROXY WALLET\n");
     println!("{}\n", "━".repeat(65));
 
@@ -31,7 +31,7 @@ async fn main() -> Result<()> {
     println!("{}\n", "━".repeat(65));
     println!("📋 STEP 1: Check username via API\n");
 
-    let user_profile_url = format!("https://data-api.polymarket.com/users/{}", eoa_address);
+    let user_profile_url = DataApiClient::new(&env).url(&format!("/users/{}", eoa_address), &[]);
     match fetch_data(&user_profile_url, &env).await {
         Ok(profile) => {
             println!("   Profile data: {}", serde_json::to_string_pretty(&profile)?);
@@ -46,19 +46,13 @@ async fn main() -> Result<()> {
     println!("{}\n", "━".repeat(65));
     println!("📋 STEP 2: Analyze transactions on Polymarket\n");
 
-    let activities_url = format!("https://data-api.polymarket.com/activity?user={}&type=TRADE", eoa_address);
-    match fetch_data(&activities_url, &env).await {
-        Ok(activities_json) => {
-            let activities: Vec<serde_json::Value> = activities_json
-                .as_array()
-                .cloned()
-                .unwrap_or_default();
-
+    match fetch_activities(&eoa_address, "TRADE", &env).await {
+        Ok(activities) => {
             if !activities.is_empty() {
                 println!("   ✅ Found {} trades\n", activities.len());
                 
                 if let Some(first_trade) = activities.first() {
-                    if let Some(proxy_wallet) = first_trade.get("proxyWallet").and_then(|v| v.as_str()) {
+                    if let Some(proxy_wallet) = first_trade.proxy_wallet.as_deref() {
                         println!("   🎯 PROXY WALLET FOUND!\n");
                         println!("   Proxy Wallet: {}\n", proxy_wallet);
                         println!("   💡 Update your .env file:\n");
@@ -80,13 +74,8 @@ async fn main() -> Result<()> {
     println!("{}\n", "━".repeat(65));
     println!("📋 STEP 3: Check positions on EOA\n");
 
-    let positions_url = format!("https://data-api.polymarket.com/positions?user={}", eoa_address);
-    match fetch_data(&positions_url, &env).await {
-        Ok(positions_json) => {
-            let positions: Vec<serde_json::Value> = positions_json
-                .as_array()
-                .cloned()
-                .unwrap_or_default();
+    match fetch_positions(&eoa_address, &env).await {
+        Ok(positions) => {
             println!("   Positions on EOA: {}\n", positions.len());
         }
         Err(_) => {
@@ -6,6 +6,8 @@ use colored::*;
 use polymarket_copy_trading_bot_rust::config::{load_env, connect_db};
 use polymarket_copy_trading_bot_rust::utils::{perform_health_check, log_health_check, health_check::HealthCheckResult};
 
+use crate::output::print_json;
+
 fn print_header() {
     println!("\n{}", "━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━".cyan().bold());
     println!("{}", "     🏥 POLYMARKET BOT - HEALTH CHECK".cyan().bold());
@@ -59,6 +61,26 @@ fn print_recommendations(result: &HealthCheckResult) {
         println!("   • Check status: https://polymarket.com\n");
     }
 
+    let checks = &result.checks;
+    for (name, check, hint) in [
+        ("Allowances", &checks.allowances, "Set the missing approvals: polycopy wallet approve"),
+        ("CLOB API Key", &checks.clob_api_key, "Check PRIVATE_KEY and that the wallet has traded on polymarket.com"),
+        ("Clock", &checks.clock_skew, "Enable NTP time sync (timedatectl set-ntp true)"),
+        ("Wallet", &checks.wallet, "PROXY_WALLET must be the signer EOA or a Safe it owns: polycopy wallet compare"),
+    ] {
+        if check.status == "error" {
+            issues.push("❌ Check Failed");
+            println!("{}\n", format!("📋 {} Issue:", name).red().bold());
+            println!("   • {}", check.message);
+            println!("   • {}\n", hint);
+        }
+    }
+    if checks.gas.status == "warning" {
+        println!("{}\n", "⚠️  Low Gas Warning:".yellow().bold());
+        println!("   • {}", checks.gas.message);
+        println!("   • Send a little POL (MATIC) to the signer for approvals and redemptions\n");
+    }
+
     if issues.is_empty() {
         println!("{}\n", "🎉 All Systems Operational!".green().bold());
         println!("{}", "You're ready to start trading:".cyan());
@@ -82,14 +104,19 @@ fn print_configuration(env: &polymarket_copy_trading_bot_rust::config::Env) {
     println!();
 }
 
-#[tokio::main]
-async fn main() -> Result<()> {
-    print_header();
-    println!("{}\n", "⏳ Running diagnostic checks...".yellow());
-
+pub async fn run(json: bool) -> Result<()> {
     let env = load_env()?;
     let db = connect_db(&env.mongo_uri).await?;
-    let result = perform_health_check(&db, &env).await?;
+
+    if json {
+        let result = perform_health_check(&db, &env, None).await?;
+        print_json(&result)?;
+        std::process::exit(if result.healthy { 0 } else { 1 });
+    }
+
+    print_header();
+    println!("{}\n", "⏳ Running diagnostic checks...".yellow());
+    let result = perform_health_check(&db, &env, None).await?;
 
     log_health_check(&result);
     print_configuration(&env);
@@ -15,30 +15,20 @@ use alloy::primitives::U256;
 use chrono::DateTime;
 use std::str::FromStr;
 use std::time::{SystemTime, UNIX_EPOCH};
+use crate::common::{fetch_positions, Position};
 
 const MIN_ORDER_SIZE_TOKENS: f64 = 1.0;
 
-#[derive(Debug, serde::Deserialize)]
-struct Position {
-    asset: String,
-    condition_id: String,
-    size: f64,
-    avg_price: f64,
-    current_value: f64,
-    title: String,
-    outcome: String,
-}
-
 fn find_matching_position<'a>(positions: &'a [Position], search_query: &str) -> Option<&'a Position> {
     let query_lower = search_query.to_lowercase();
     
     // Try exact match first
-    if let Some(pos) = positions.iter().find(|p| p.title.to_lowercase() == query_lower) {
+    if let Some(pos) = positions.iter().find(|p| p.title().to_lowercase() == query_lower) {
         return Some(pos);
     }
     
     // Try contains match
-    if let Some(pos) = positions.iter().find(|p| p.title.to_lowercase().contains(&query_lower)) {
+    if let Some(pos) = positions.iter().find(|p| p.title().to_lowercase().contains(&query_lower)) {
         return Some(pos);
     }
     
@@ -46,7 +36,7 @@ fn find_matching_position<'a>(positions: &'a [Position], search_query: &str) ->
     let query_words: Vec<&str> = query_lower.split_whitespace().filter(|w| w.len() > 2).collect();
     if !query_words.is_empty() {
         if let Some(pos) = positions.iter().find(|p| {
-            let title_lower = p.title.to_lowercase();
+            let title_lower = p.title().to_lowercase();
             query_words.iter().any(|word| title_lower.contains(word))
         }) {
             return Some(pos);
@@ -71,7 +61,7 @@ async fn sell_position(
         sell_size
     ));
     Logger::info(&format!("Token ID: {}", position.asset));
-    Logger::info(&format!("Market: {} - {}\n", position.title, position.outcome));
+    Logger::info(&format!("Market: {} - {}\n", position.title(), position.outcome()));
 
     while remaining > 0.0 && retry < env.retry_limit {
         // Get order book
@@ -188,8 +178,7 @@ async fn sell_position(
     Ok(())
 }
 
-#[tokio::main]
-async fn main() -> Result<()> {
+pub async fn run() -> Result<()> {
     println!("🚀 Manual Sell Script");
     println!("═══════════════════════════════════════════════\n");
 
@@ -223,15 +212,7 @@ async fn main() -> Result<()> {
 
     // Get all positions
     println!("📥 Fetching positions...");
-    let positions_url = format!("https://data-api.polymarket.com/positions?user={}", env.proxy_wallet);
-    let positions_json: serde_json::Value = fetch_data(&positions_url, &env).await?;
-    let positions: Vec<Position> = positions_json
-        .as_array()
-        .cloned()
-        .unwrap_or_default()
-        .into_iter()
-        .filter_map(|v| serde_json::from_value(v).ok())
-        .collect();
+    let positions = fetch_positions(&env.proxy_wallet, &env).await?;
     
     println!("Found {} position(s)\n", positions.len());
 
@@ -240,8 +221,8 @@ async fn main() -> Result<()> {
 
     if let Some(pos) = position {
         println!("✅ Position found!");
-        println!("📌 Market: {}", pos.title);
-        println!("📌 Outcome: {}", pos.outcome);
+        println!("📌 Market: {}", pos.title());
+        println!("📌 Outcome: {}", pos.outcome());
         println!("📌 Position size: {:.2} tokens", pos.size);
         println!("📌 Average price: ${:.4}", pos.avg_price);
         println!("📌 Current value: ${:.2}", pos.current_value);
@@ -267,7 +248,7 @@ async fn main() -> Result<()> {
         for (idx, pos) in positions.iter().enumerate() {
             println!(
                 "{}. {} - {} ({:.2} tokens)",
-                idx + 1, pos.title, pos.outcome, pos.size
+                idx + 1, pos.title(), pos.outcome(), pos.size
             );
         }
         println!("\n💡 Tip: Use a partial match from the position title above.");
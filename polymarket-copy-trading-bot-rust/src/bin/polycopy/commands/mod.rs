@@ -0,0 +1,42 @@
+//! Command implementations, one module per former standalone binary
+
+pub mod aggregate_results;
+pub mod audit_copy_trading_algorithm;
+pub mod audit_copy_trading_algorithm_fixed;
+pub mod auto_claim_test;
+pub mod check_allowance;
+pub mod check_both_wallets;
+pub mod check_my_stats;
+pub mod check_pnl_discrepancy;
+pub mod check_positions_detailed;
+pub mod check_proxy_wallet;
+pub mod check_recent_activity;
+pub mod circuit_breaker;
+pub mod close_resolved_positions;
+pub mod close_stale_positions;
+pub mod compare_results;
+pub mod compute_gnosis_safe_address;
+pub mod fetch_historical_trades;
+pub mod find_best_traders;
+pub mod find_gnosis_safe_proxy;
+pub mod find_low_risk_traders;
+pub mod find_my_eoa;
+pub mod find_real_proxy_wallet;
+pub mod health_check;
+pub mod manual_sell;
+pub mod paper_account;
+pub mod pnl_report;
+pub mod reconcile_positions;
+pub mod redeem_resolved_positions;
+pub mod run_simulations;
+pub mod scan_best_traders;
+pub mod scan_traders_from_markets;
+pub mod sell_large_positions;
+pub mod set_token_allowance;
+pub mod setup;
+pub mod simulate_profitability;
+pub mod simulate_profitability_old_logic;
+pub mod swap_native_to_bridged_usdc;
+pub mod transfer_usdc_from_proxy;
+pub mod trigger_auto_claim;
+pub mod verify_allowance;
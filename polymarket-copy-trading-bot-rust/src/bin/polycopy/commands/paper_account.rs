@@ -0,0 +1,135 @@
+//! Paper trading account: balance, equity, open positions, reset
+
+use anyhow::Result;
+use colored::*;
+use polymarket_copy_trading_bot_rust::config::load_env;
+use polymarket_copy_trading_bot_rust::services::paper_trading::{
+    load_paper_account, load_paper_positions, reset_paper_account, PaperAccount, PaperPosition,
+};
+use serde::Serialize;
+
+use crate::common::{select_accounts, AccountSelection};
+use crate::output::print_account_json;
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct PaperStatus {
+    enabled: bool,
+    equity: f64,
+    return_percent: f64,
+    account: PaperAccount,
+    positions: Vec<PaperPosition>,
+}
+
+fn format_day(timestamp: i64) -> String {
+    chrono::DateTime::from_timestamp(timestamp, 0)
+        .map(|t| t.format("%Y-%m-%d %H:%M UTC").to_string())
+        .unwrap_or_else(|| "-".to_string())
+}
+
+fn colored_usd(value: f64) -> ColoredString {
+    let text = format!("{:+.2}", value);
+    if value >= 0.0 { text.green() } else { text.red() }
+}
+
+fn print_account(account: &PaperAccount) {
+    println!("  Started:         {} with ${:.2}", format_day(account.created_at), account.starting_balance);
+    println!("  Cash:            ${:.2}", account.cash);
+    println!("  Positions:       ${:.2} (last mark {})", account.positions_value, format_day(account.updated_at));
+    println!(
+        "  Equity:          ${:.2} ({}%)",
+        account.equity(),
+        colored_usd(account.return_percent())
+    );
+    println!("  Realized PnL:    ${}", colored_usd(account.realized_pnl));
+}
+
+fn print_positions(positions: &[PaperPosition]) {
+    if positions.is_empty() {
+        println!("\n  {}", "No open paper positions".bright_black());
+        return;
+    }
+    println!("\n  {}", "Open positions".bold());
+    for position in positions {
+        let title: String = position.title.chars().take(50).collect();
+        println!(
+            "  • {} [{}]\n    {:.2} tokens @ ${:.4} → ${:.4}  value ${:.2}  PnL ${}",
+            title,
+            position.outcome,
+            position.size,
+            position.avg_price,
+            position.cur_price,
+            position.value(),
+            colored_usd(position.unrealized_pnl())
+        );
+    }
+}
+
+fn print_daily_equity(account: &PaperAccount) {
+    if account.daily_equity.len() < 2 {
+        return;
+    }
+    println!("\n  {}", "Daily equity (last 14 days)".bold());
+    let skip = account.daily_equity.len().saturating_sub(14);
+    for (day, equity) in account.daily_equity.iter().skip(skip) {
+        println!("  {}  ${:.2}", day, equity);
+    }
+}
+
+pub async fn run(reset: bool, selection: AccountSelection, json: bool) -> Result<()> {
+    let env = load_env()?;
+    let config = &env.paper_trading;
+    let accounts = select_accounts(&env, &selection).await?;
+
+    let mut statuses = Vec::with_capacity(accounts.len());
+    for account in &accounts {
+        let paper = if reset {
+            reset_paper_account(&account.db, config).await?
+        } else {
+            load_paper_account(&account.db, config).await?
+        };
+        let positions = load_paper_positions(&account.db).await?;
+        statuses.push((
+            account.name.clone(),
+            PaperStatus {
+                enabled: config.enabled,
+                equity: paper.equity(),
+                return_percent: paper.return_percent(),
+                account: paper,
+                positions,
+            },
+        ));
+    }
+
+    if json {
+        return print_account_json(&statuses);
+    }
+
+    println!("\n{}", "📝 PAPER TRADING ACCOUNT".cyan().bold());
+    println!("{}\n", "━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━".cyan());
+
+    if reset {
+        println!("{}", "✓ Paper account reset - positions and simulated PnL ledger entries cleared".green());
+    }
+    for (name, status) in &statuses {
+        if statuses.len() > 1 {
+            println!("\n{}", format!("Account: {}", name).bold());
+        }
+        print_account(&status.account);
+        print_positions(&status.positions);
+        print_daily_equity(&status.account);
+    }
+
+    if !config.enabled {
+        println!(
+            "\n{}",
+            "Paper trading is off - set PAPER_TRADING_ENABLED=true to fill copies against the virtual account".yellow()
+        );
+    }
+    println!(
+        "\n{}",
+        "Per-trader PnL of paper fills: polycopy positions pnl --by trader".bright_black()
+    );
+    println!();
+    Ok(())
+}
@@ -0,0 +1,205 @@
+//! PnL report from the copy ledger, attributed per trader, market and day
+#![allow(dead_code)]
+
+use anyhow::Result;
+use colored::*;
+use polymarket_copy_trading_bot_rust::config::load_env;
+use polymarket_copy_trading_bot_rust::services::pnl_ledger::{
+    compute_pnl, fetch_midpoints, load_ledger, open_assets, sync_redemptions, PnlBucket, PnlReport,
+};
+
+use crate::common::{select_accounts, AccountSelection};
+use crate::output::print_account_json;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Format {
+    Table,
+    Csv,
+    Json,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum GroupBy {
+    All,
+    Trader,
+    Market,
+    Day,
+}
+
+pub struct Options {
+    pub format: Format,
+    /// `All` prints every grouping (CSV falls back to trader)
+    pub group_by: GroupBy,
+    /// Only fills and realized PnL from the last N days
+    pub days: Option<i64>,
+    /// Import redemptions from the Data API first
+    pub sync: bool,
+    /// Report the simulated (preview and paper) fills instead of the live ones.
+    /// The two are never mixed: real payouts don't settle simulated lots.
+    pub simulated: bool,
+    pub accounts: AccountSelection,
+}
+
+fn truncate(s: &str, max: usize) -> String {
+    if s.chars().count() <= max {
+        s.to_string()
+    } else {
+        format!("{}…", s.chars().take(max - 1).collect::<String>())
+    }
+}
+
+fn colored_usd(value: f64) -> ColoredString {
+    let text = format!("{:>11}", format!("{}${:.2}", if value < 0.0 { "-" } else { "" }, value.abs()));
+    if value > 0.0 {
+        text.green()
+    } else if value < 0.0 {
+        text.red()
+    } else {
+        text.normal()
+    }
+}
+
+fn print_table(title: &str, buckets: &[PnlBucket], total: &PnlBucket) {
+    println!("\n{}", title.cyan().bold());
+    println!(
+        "{}",
+        format!(
+            "  {:<44} {:>6} {:>11} {:>11} {:>11} {:>11} {:>11}",
+            "", "Fills", "Bought", "Proceeds", "Realized", "Unrealized", "Total"
+        )
+        .bright_black()
+    );
+    for bucket in buckets.iter().chain(std::iter::once(total)) {
+        let is_total = std::ptr::eq(bucket, total);
+        if is_total {
+            println!("  {}", "─".repeat(112).bright_black());
+        }
+        let label = truncate(&bucket.label, 44);
+        println!(
+            "  {:<44} {:>6} {:>11} {:>11} {} {} {}",
+            if is_total { label.bold().to_string() } else { label },
+            bucket.fills,
+            format!("${:.2}", bucket.bought_usd),
+            format!("${:.2}", bucket.proceeds_usd),
+            colored_usd(bucket.realized_pnl),
+            colored_usd(bucket.unrealized_pnl),
+            colored_usd(bucket.total_pnl()),
+        );
+    }
+}
+
+fn print_csv(buckets: &[PnlBucket]) {
+    println!("key,label,fills,bought_usd,proceeds_usd,realized_pnl,unrealized_pnl,total_pnl,open_cost_usd");
+    for b in buckets {
+        println!(
+            "{},\"{}\",{},{:.4},{:.4},{:.4},{:.4},{:.4},{:.4}",
+            b.key,
+            b.label.replace('"', "\"\""),
+            b.fills,
+            b.bought_usd,
+            b.proceeds_usd,
+            b.realized_pnl,
+            b.unrealized_pnl,
+            b.total_pnl(),
+            b.open_cost_usd
+        );
+    }
+}
+
+fn grouped(report: &PnlReport, group_by: GroupBy) -> &[PnlBucket] {
+    match group_by {
+        GroupBy::Market => &report.by_market,
+        GroupBy::Day => &report.by_day,
+        GroupBy::All | GroupBy::Trader => &report.by_trader,
+    }
+}
+
+pub async fn run(options: Options) -> Result<()> {
+    let env = load_env()?;
+    let accounts = select_accounts(&env, &options.accounts).await?;
+    if options.format == Format::Csv && accounts.len() > 1 {
+        anyhow::bail!("CSV output covers one account - pick it with --account");
+    }
+    let since = options
+        .days
+        .map(|d| chrono::Utc::now().timestamp() - d * 24 * 60 * 60);
+
+    let mut reports = Vec::with_capacity(accounts.len());
+    for account in &accounts {
+        if options.sync {
+            match sync_redemptions(&account.env, &account.db).await {
+                Ok(0) => {}
+                Ok(n) => eprintln!("✓ Imported {} redemption(s) for {}", n, account.name),
+                Err(e) => eprintln!("⚠️  Could not sync redemptions for {}: {}", account.name, e),
+            }
+        }
+        let (entries, other): (Vec<_>, Vec<_>) = load_ledger(&account.db)
+            .await?
+            .into_iter()
+            .partition(|e| e.simulated == options.simulated);
+        let prices = fetch_midpoints(&open_assets(&entries), &account.env).await;
+        let report = compute_pnl(&entries, &prices, since);
+        reports.push((account.name.clone(), entries, other.len(), report));
+    }
+
+    match options.format {
+        Format::Json => {
+            let mut values = Vec::with_capacity(reports.len());
+            for (name, _, _, report) in &reports {
+                let mut json = match options.group_by {
+                    GroupBy::All => serde_json::to_value(report)?,
+                    group_by => serde_json::json!({
+                        "generatedAt": report.generated_at,
+                        "since": report.since,
+                        "total": report.total,
+                        "rows": grouped(report, group_by),
+                    }),
+                };
+                json["simulated"] = serde_json::json!(options.simulated);
+                values.push((name.clone(), json));
+            }
+            print_account_json(&values)?;
+        }
+        Format::Csv => {
+            for (_, _, _, report) in &reports {
+                print_csv(grouped(report, options.group_by));
+            }
+        }
+        Format::Table => {
+            let title = if options.simulated { "📒 COPY TRADING PnL REPORT (SIMULATED)" } else { "📒 COPY TRADING PnL REPORT" };
+            println!("\n{}", title.cyan().bold());
+            println!("{}", "━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━".cyan());
+            if let Some(days) = options.days {
+                println!("  Period:         last {} day(s) (unrealized PnL is current)", days);
+            }
+            for (name, entries, other, report) in &reports {
+                if reports.len() > 1 {
+                    println!("\n{}", format!("Account: {}", name).bold());
+                }
+                println!("  Ledger entries: {}", entries.len());
+                if *other > 0 && !options.simulated {
+                    println!("{}", format!("  {} simulated preview/paper entries left out (see --simulated)", other).yellow());
+                } else if *other > 0 {
+                    println!("{}", format!("  {} live entries left out (run without --simulated)", other).yellow());
+                }
+                if entries.is_empty() {
+                    println!("\n{}", "No copy fills recorded yet.".yellow());
+                    continue;
+                }
+
+                if matches!(options.group_by, GroupBy::All | GroupBy::Trader) {
+                    print_table("👤 BY TRADER", &report.by_trader, &report.total);
+                }
+                if matches!(options.group_by, GroupBy::All | GroupBy::Market) {
+                    print_table("📊 BY MARKET", &report.by_market, &report.total);
+                }
+                if matches!(options.group_by, GroupBy::All | GroupBy::Day) {
+                    print_table("📅 BY DAY (realized)", &report.by_day, &report.total);
+                }
+            }
+            println!();
+        }
+    }
+
+    Ok(())
+}
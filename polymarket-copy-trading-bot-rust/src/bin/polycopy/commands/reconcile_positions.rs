@@ -0,0 +1,53 @@
+//! One-off position reconciliation: MongoDB vs Data API vs on-chain balances
+#![allow(dead_code)]
+
+use anyhow::Result;
+use colored::*;
+use polymarket_copy_trading_bot_rust::config::load_env;
+use polymarket_copy_trading_bot_rust::services::reconciliation::{
+    log_reconciliation_report, reconcile_positions,
+};
+
+use crate::common::{select_accounts, AccountSelection};
+use crate::output::print_account_json;
+
+pub async fn run(selection: AccountSelection, json_output: bool) -> Result<()> {
+    let env = load_env()?;
+    let accounts = select_accounts(&env, &selection).await?;
+
+    if !json_output {
+        println!("\n{}", "🔎 POSITION RECONCILIATION".cyan().bold());
+        println!("{}\n", "━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━".cyan());
+    }
+
+    let mut reports = Vec::with_capacity(accounts.len());
+    for account in &accounts {
+        if !json_output {
+            if accounts.len() > 1 {
+                println!("{}", format!("Account: {}", account.name).bold());
+            }
+            println!("  Proxy wallet: {}", account.env.proxy_wallet);
+        }
+
+        let report = reconcile_positions(&account.env, &account.db).await?;
+        if json_output {
+            reports.push((account.name.clone(), report));
+            continue;
+        }
+
+        if let Some(eoa) = &report.eoa {
+            println!("  EOA:          {}", eoa);
+        }
+        if !report.mongodb_compared {
+            println!("{}", "  MongoDB records skipped (simulated in preview mode)".bright_black());
+        }
+        println!();
+        log_reconciliation_report(&report);
+        println!();
+    }
+
+    if json_output {
+        return print_account_json(&reports);
+    }
+    Ok(())
+}
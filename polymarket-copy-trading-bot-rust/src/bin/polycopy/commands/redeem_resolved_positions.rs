@@ -3,33 +3,41 @@
 
 use anyhow::Result;
 use polymarket_copy_trading_bot_rust::config::load_env;
-use polymarket_copy_trading_bot_rust::utils::fetch_data;
+use polymarket_copy_trading_bot_rust::utils::gnosis_safe::{
+    ctf_redeem_positions, neg_risk_redeem_positions, to_base_units, SafeCall, SafeExecutor,
+};
+use alloy::primitives::{B256, U256};
 use alloy::signers::local::PrivateKeySigner;
 
 use std::str::FromStr;
 use std::collections::HashMap;
+use crate::common::{fetch_positions, Position};
 
 const RESOLVED_HIGH: f64 = 0.99;
 const RESOLVED_LOW: f64 = 0.01;
 const ZERO_THRESHOLD: f64 = 0.0001;
 const CTF_CONTRACT: &str = "0x4D97DCd97eC945f40cF65F87097ACe5EA0476045"; // ConditionalTokens
-
-#[derive(Debug, Clone, serde::Deserialize)]
-struct Position {
-    asset: String,
-    condition_id: String,
-    size: f64,
-    avg_price: f64,
-    current_value: f64,
-    cur_price: f64,
-    title: Option<String>,
-    outcome: Option<String>,
-    slug: Option<String>,
-    redeemable: Option<bool>,
+const REDEEM_BATCH_SIZE: usize = 20;
+
+/// Redeem call for one condition: CTF for standard markets, NegRiskAdapter for neg-risk ones
+fn redeem_call(condition_id: &str, positions: &[&Position], collateral: &str) -> Result<SafeCall> {
+    let condition = condition_id
+        .parse::<B256>()
+        .map_err(|e| anyhow::anyhow!("Invalid condition ID {}: {}", condition_id, e))?;
+    if positions.iter().any(|p| p.negative_risk) {
+        let mut amounts = vec![U256::ZERO; 2];
+        for pos in positions {
+            if let Some(amount) = pos.outcome_index.and_then(|i| amounts.get_mut(i)) {
+                *amount += to_base_units(pos.size);
+            }
+        }
+        neg_risk_redeem_positions(condition, amounts)
+    } else {
+        ctf_redeem_positions(collateral, condition)
+    }
 }
 
-#[tokio::main]
-async fn main() -> Result<()> {
+pub async fn run() -> Result<()> {
     println!("🚀 Redeeming resolved positions");
     println!("════════════════════════════════════════════════════");
     
@@ -60,15 +68,10 @@ async fn main() -> Result<()> {
     }
 
     // Load positions
-    let positions_url = format!("https://data-api.polymarket.com/positions?user={}", env.proxy_wallet);
-    let positions_json: serde_json::Value = fetch_data(&positions_url, &env).await?;
-    let all_positions: Vec<Position> = positions_json
-        .as_array()
-        .cloned()
-        .unwrap_or_default()
+    let all_positions: Vec<Position> = fetch_positions(&env.proxy_wallet, &env)
+        .await?
         .into_iter()
-        .filter_map(|v| serde_json::from_value::<Position>(v).ok())
-        .filter(|pos: &Position| pos.size > ZERO_THRESHOLD)
+        .filter(|pos| pos.size > ZERO_THRESHOLD)
         .collect();
 
     if all_positions.is_empty() {
@@ -80,7 +83,7 @@ async fn main() -> Result<()> {
     let redeemable_positions: Vec<_> = all_positions
         .iter()
         .filter(|pos| {
-            (pos.cur_price >= RESOLVED_HIGH || pos.cur_price <= RESOLVED_LOW) && pos.redeemable == Some(true)
+            (pos.cur_price >= RESOLVED_HIGH || pos.cur_price <= RESOLVED_LOW) && pos.redeemable
         })
         .collect();
 
@@ -113,15 +116,30 @@ async fn main() -> Result<()> {
 
     println!("\n📦 Grouped into {} unique conditions", positions_by_condition.len());
 
-    println!("\n💡 NOTE: Redemption requires direct blockchain interaction.");
-    println!("   For Gnosis Safe wallets, use the Safe web interface:");
-    println!("   https://app.safe.global/");
-    println!("\n   For EOA wallets, this script would need to:");
-    println!("   1. Build the redeemPositions transaction");
-    println!("   2. Sign it with your private key");
-    println!("   3. Send it to the blockchain");
-    println!("\n   This functionality requires additional blockchain interaction code.");
-    println!("   Consider using the Polymarket web interface for redemption.");
+    let executor = SafeExecutor::from_env(&env)?;
+    match executor.check_can_execute().await {
+        Ok(()) => {
+            let calls = positions_by_condition
+                .iter()
+                .map(|(condition_id, positions)| redeem_call(condition_id, positions, &env.usdc_contract_address))
+                .collect::<Result<Vec<_>>>()?;
+            for (i, batch) in calls.chunks(REDEEM_BATCH_SIZE).enumerate() {
+                println!("\n📤 Executing batch {} ({} condition(s)) through Safe {}...", i + 1, batch.len(), executor.safe());
+                match executor.execute(batch).await {
+                    Ok(tx_hash) => {
+                        println!("✅ Redeemed");
+                        println!("   https://polygonscan.com/tx/0x{:x}", tx_hash);
+                    }
+                    Err(e) => println!("❌ Batch {} failed: {}", i + 1, e),
+                }
+            }
+        }
+        Err(e) => {
+            println!("\n💡 NOTE: Cannot redeem through the proxy wallet: {}", e);
+            println!("   Use the Safe web interface: https://app.safe.global/");
+            println!("   or the Polymarket web interface for redemption.");
+        }
+    }
 
     println!("\n════════════════════════════════════════════════════");
     println!("✅ Summary of redeemable positions");
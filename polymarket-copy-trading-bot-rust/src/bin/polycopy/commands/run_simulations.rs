@@ -0,0 +1,541 @@
+//! Run comprehensive simulations
+#![allow(dead_code)] // Struct fields used for JSON deserialization
+
+use anyhow::Result;
+use colored::*;
+use crate::commands::simulate_profitability::{self, SimSettings};
+use polymarket_copy_trading_bot_rust::config::{load_env, CopyStrategy};
+use polymarket_copy_trading_bot_rust::utils::copy_simulator::load_or_fetch_trades;
+use polymarket_copy_trading_bot_rust::utils::execution_model::{DepthSource, ExecutionModel, OrderBookCache};
+use polymarket_copy_trading_bot_rust::utils::market_resolution::ResolutionCache;
+use polymarket_copy_trading_bot_rust::utils::strategy_sweep::{
+    rank_configs, run_sweep, sweep_report, RankBy, SweepMarketData, SweepSpace,
+};
+use std::fs;
+use std::path::Path;
+use std::str::FromStr;
+use std::sync::Arc;
+
+struct SimulationConfig {
+    trader_address: String,
+    history_days: i32,
+    multiplier: f64,
+    min_order_size: f64,
+    max_trades: Option<usize>,
+    tag: Option<String>,
+}
+
+const DEFAULT_TRADERS: &[&str] = &[
+    "0x7c3db723f1d4d8cb9c550095203b686cb11e5c6b",
+    "0x6bab41a0dc40d6dd4c1a915b8c01969479fd1292",
+];
+
+struct Preset {
+    history_days: i32,
+    max_trades: usize,
+    multipliers: &'static [f64],
+    tag: &'static str,
+}
+
+const QUICK_MULTIPLIERS: &[f64] = &[1.0, 2.0];
+const STANDARD_MULTIPLIERS: &[f64] = &[0.5, 1.0, 2.0];
+const FULL_MULTIPLIERS: &[f64] = &[0.5, 1.0, 2.0, 3.0];
+
+const PRESETS: &[(&str, Preset)] = &[
+    (
+        "quick",
+        Preset {
+            history_days: 7,
+            max_trades: 500,
+            multipliers: QUICK_MULTIPLIERS,
+            tag: "quick",
+        },
+    ),
+    (
+        "standard",
+        Preset {
+            history_days: 30,
+            max_trades: 2000,
+            multipliers: STANDARD_MULTIPLIERS,
+            tag: "std",
+        },
+    ),
+    (
+        "full",
+        Preset {
+            history_days: 90,
+            max_trades: 5000,
+            multipliers: FULL_MULTIPLIERS,
+            tag: "full",
+        },
+    ),
+];
+
+async fn run_simulation(config: &SimulationConfig) -> Result<()> {
+    println!("{}", "\n🚀 Starting simulation...".cyan());
+    println!(
+        "{}",
+        format!("   Trader: {}...", &config.trader_address[..10.min(config.trader_address.len())])
+            .bright_black()
+    );
+    println!(
+        "{}",
+        format!(
+            "   Days: {}, Multiplier: {}x, MinOrder: ${:.2}",
+            config.history_days, config.multiplier, config.min_order_size
+        )
+        .bright_black()
+    );
+
+    // COPY_PERCENTAGE and SIM_MARK_TIMESTAMP still come from the environment
+    let settings = SimSettings {
+        trader_address: config.trader_address.clone(),
+        history_days: config.history_days,
+        multiplier: config.multiplier,
+        min_order_size: config.min_order_size,
+        tag: config.tag.clone(),
+        ..SimSettings::from_env()
+    };
+    let settings = match config.max_trades {
+        Some(max_trades) => SimSettings { max_trades, ..settings },
+        None => settings,
+    };
+
+    simulate_profitability::run_with(&settings).await?;
+    println!("{}", "✓ Simulation completed\n".green());
+    Ok(())
+}
+
+async fn run_batch(configs: &[SimulationConfig]) -> Result<()> {
+    println!("\n{}", "═".repeat(80).cyan());
+    println!("{}", "  📊 BATCH SIMULATION RUNNER".cyan());
+    println!("{}\n", "═".repeat(80).cyan());
+
+    println!("{}", format!("Total simulations to run: {}\n", configs.len()).yellow());
+
+    for (i, config) in configs.iter().enumerate() {
+        println!("{}", format!("\n[{}] Running simulation...", i + 1).bold());
+        match run_simulation(config).await {
+            Ok(_) => {}
+            Err(e) => {
+                println!("{}", format!("Simulation {} failed, continuing with next...\n", i + 1).red());
+                eprintln!("Error: {}", e);
+            }
+        }
+
+        // Small delay between simulations
+        if i < configs.len() - 1 {
+            tokio::time::sleep(tokio::time::Duration::from_secs(1)).await;
+        }
+    }
+
+    println!("\n{}", "═".repeat(80).green());
+    println!("{}", "  ✅ ALL SIMULATIONS COMPLETED".green());
+    println!("{}\n", "═".repeat(80).green());
+    Ok(())
+}
+
+fn generate_configs(preset_name: &str, traders: Option<&[String]>) -> Vec<SimulationConfig> {
+    let preset = PRESETS
+        .iter()
+        .find(|(name, _)| *name == preset_name)
+        .map(|(_, p)| p)
+        .unwrap_or(&PRESETS[1].1); // Default to standard
+
+    let trader_list = traders
+        .filter(|t| !t.is_empty())
+        .map(|t| t.to_vec())
+        .unwrap_or_else(|| DEFAULT_TRADERS.iter().map(|s| s.to_string()).collect());
+
+    let mut configs = Vec::new();
+
+    for trader in trader_list {
+        for &multiplier in preset.multipliers {
+            let tag = format!("{}_{}m{}", preset.tag, multiplier, multiplier)
+                .replace(".", "p");
+            configs.push(SimulationConfig {
+                trader_address: trader.clone(),
+                history_days: preset.history_days,
+                multiplier,
+                min_order_size: 1.0,
+                max_trades: Some(preset.max_trades),
+                tag: Some(tag),
+            });
+        }
+    }
+
+    configs
+}
+
+struct SweepOptions {
+    mode: String,
+    traders: Vec<String>,
+    history_days: i32,
+    max_trades: usize,
+    samples: usize,
+    seed: u64,
+    parallel: usize,
+    rank_by: RankBy,
+    top: usize,
+    starting_capital: f64,
+    space: SweepSpace,
+}
+
+fn parse_list<T: FromStr>(value: &str, flag: &str) -> Result<Vec<T>> {
+    value
+        .split(',')
+        .map(|v| v.trim())
+        .filter(|v| !v.is_empty())
+        .map(|v| v.parse::<T>().map_err(|_| anyhow::anyhow!("Invalid value \"{}\" for {}", v, flag)))
+        .collect()
+}
+
+/// Like `parse_list` but accepts `none` for "disabled" (TP/SL)
+fn parse_optional_list(value: &str, flag: &str) -> Result<Vec<Option<f64>>> {
+    value
+        .split(',')
+        .map(|v| v.trim())
+        .filter(|v| !v.is_empty())
+        .map(|v| {
+            if v.eq_ignore_ascii_case("none") || v.eq_ignore_ascii_case("off") {
+                Ok(None)
+            } else {
+                v.parse::<f64>()
+                    .map(Some)
+                    .map_err(|_| anyhow::anyhow!("Invalid value \"{}\" for {}", v, flag))
+            }
+        })
+        .collect()
+}
+
+fn parse_sweep_options(args: &[String]) -> Result<SweepOptions> {
+    let mut options = SweepOptions {
+        mode: "grid".to_string(),
+        traders: DEFAULT_TRADERS.iter().map(|s| s.to_string()).collect(),
+        history_days: 30,
+        max_trades: 5000,
+        samples: 200,
+        seed: chrono::Utc::now().timestamp() as u64,
+        parallel: std::thread::available_parallelism().map(|n| n.get()).unwrap_or(4),
+        rank_by: RankBy::Roi,
+        top: 10,
+        starting_capital: 1000.0,
+        space: SweepSpace::default(),
+    };
+
+    let mut i = 0;
+    if let Some(mode) = args.first().filter(|a| !a.starts_with("--")) {
+        options.mode = mode.to_lowercase();
+        i = 1;
+    }
+    if options.mode != "grid" && options.mode != "random" {
+        anyhow::bail!("Unknown sweep mode: {} (expected grid or random)", options.mode);
+    }
+
+    while i < args.len() {
+        let flag = args[i].as_str();
+        let value = args
+            .get(i + 1)
+            .ok_or_else(|| anyhow::anyhow!("Missing value for {}", flag))?;
+        match flag {
+            "--traders" => {
+                options.traders = parse_list::<String>(value, flag)?
+                    .into_iter()
+                    .map(|t| t.to_lowercase())
+                    .collect()
+            }
+            "--days" => options.history_days = value.parse()?,
+            "--max-trades" => options.max_trades = value.parse()?,
+            "--samples" => options.samples = value.parse()?,
+            "--seed" => options.seed = value.parse()?,
+            "--parallel" => options.parallel = value.parse()?,
+            "--top" => options.top = value.parse()?,
+            "--capital" => options.starting_capital = value.parse()?,
+            "--rank-by" => {
+                options.rank_by = RankBy::parse(value)
+                    .ok_or_else(|| anyhow::anyhow!("Unknown ranking: {} (roi, drawdown, sharpe)", value))?
+            }
+            "--strategies" => {
+                options.space.strategies = parse_list::<String>(value, flag)?
+                    .iter()
+                    .map(|s| match s.to_uppercase().as_str() {
+                        "PERCENTAGE" => Ok(CopyStrategy::Percentage),
+                        "FIXED" => Ok(CopyStrategy::Fixed),
+                        "ADAPTIVE" => Ok(CopyStrategy::Adaptive),
+                        _ => Err(anyhow::anyhow!("Unknown strategy: {}", s)),
+                    })
+                    .collect::<Result<_>>()?
+            }
+            "--copy-sizes" => options.space.copy_sizes = parse_list(value, flag)?,
+            "--multipliers" => options.space.multipliers = parse_list(value, flag)?,
+            "--min-orders" => options.space.min_order_sizes = parse_list(value, flag)?,
+            "--max-orders" => options.space.max_order_sizes = parse_list(value, flag)?,
+            "--take-profits" => options.space.take_profits = parse_optional_list(value, flag)?,
+            "--stop-losses" => options.space.stop_losses = parse_optional_list(value, flag)?,
+            _ => anyhow::bail!("Unknown sweep option: {}", flag),
+        }
+        i += 2;
+    }
+
+    Ok(options)
+}
+
+async fn run_sweep_command(options: SweepOptions) -> Result<()> {
+    println!("\n{}", "═".repeat(80).cyan());
+    println!("{}", "  🔬 STRATEGY PARAMETER SWEEP".cyan());
+    println!("{}\n", "═".repeat(80).cyan());
+
+    let env = load_env()?;
+
+    // Load trader histories (cached data first)
+    let mut traders = Vec::new();
+    for trader in &options.traders {
+        match load_or_fetch_trades(trader, options.history_days, options.max_trades, &env).await {
+            Ok(trades) if !trades.is_empty() => {
+                println!(
+                    "{}",
+                    format!("✓ {}...: {} trades", &trader[..10.min(trader.len())], trades.len()).green()
+                );
+                traders.push((trader.clone(), Arc::new(trades)));
+            }
+            Ok(_) => println!("{}", format!("⚠️  {}: no trades in window, skipping", trader).yellow()),
+            Err(e) => println!("{}", format!("⚠️  {}: failed to load history: {}", trader, e).yellow()),
+        }
+    }
+    if traders.is_empty() {
+        anyhow::bail!("No trader history available for the sweep");
+    }
+
+    // Resolutions are fetched once up front so the sweep itself runs offline
+    let mut resolutions = ResolutionCache::load();
+    let condition_ids: Vec<String> = traders
+        .iter()
+        .flat_map(|(_, trades)| trades.iter().filter_map(|t| t.condition_id.clone()))
+        .collect();
+    match resolutions.refresh(&condition_ids, &env).await {
+        Ok(fetched) => {
+            let _ = resolutions.save();
+            println!(
+                "{}",
+                format!("✓ Market resolutions: {} fetched, {} cached", fetched, resolutions.len()).green()
+            );
+        }
+        Err(e) => println!(
+            "{}",
+            format!("⚠️  Could not refresh resolutions ({}), using cached data only", e).yellow()
+        ),
+    }
+
+    // Optional fill model (SIM_EXECUTION_MODEL=true); recorded books are fetched once too
+    let execution = ExecutionModel::from_env();
+    let mut books = None;
+    if let Some(model) = &execution {
+        println!("{}", format!("✓ Execution model: {}", model.describe()).green());
+        if model.depth_source == DepthSource::Recorded {
+            let mut cache = OrderBookCache::load();
+            let assets: Vec<String> = traders
+                .iter()
+                .flat_map(|(_, trades)| trades.iter().map(|t| t.asset.clone()))
+                .collect();
+            match cache.refresh(&assets, &env).await {
+                Ok(fetched) => {
+                    let _ = cache.save();
+                    println!(
+                        "{}",
+                        format!("✓ Order books: {} recorded, {} cached", fetched, cache.len()).green()
+                    );
+                }
+                Err(e) => println!("{}", format!("⚠️  Could not record order books: {}", e).yellow()),
+            }
+            books = Some(cache);
+        }
+    }
+
+    let configs = if options.mode == "random" {
+        options.space.random(options.samples, options.seed)
+    } else {
+        options.space.grid()
+    };
+    println!(
+        "{}",
+        format!(
+            "\nRunning {} configuration(s) × {} trader(s) on {} worker(s)...",
+            configs.len(),
+            traders.len(),
+            options.parallel
+        )
+        .yellow()
+    );
+
+    let started = std::time::Instant::now();
+    let mut ranked = run_sweep(
+        configs,
+        traders,
+        Arc::new(SweepMarketData {
+            resolutions,
+            execution,
+            books,
+        }),
+        options.starting_capital,
+        options.parallel,
+    )
+    .await?;
+    rank_configs(&mut ranked, options.rank_by);
+    println!(
+        "{}",
+        format!("✓ Sweep finished in {:.1}s\n", started.elapsed().as_secs_f64()).green()
+    );
+
+    println!(
+        "{}",
+        format!(
+            "  #  | {:<40} | {:>9} | {:>8} | {:>7} | Profitable",
+            "Configuration", "Avg ROI", "Avg DD", "Sharpe"
+        )
+        .bold()
+    );
+    println!("{}", "─".repeat(90).bright_black());
+    for (i, config) in ranked.iter().take(options.top).enumerate() {
+        let roi = format!("{:+.2}%", config.avg_roi);
+        println!(
+            "  {:<2} | {:<40} | {:>9} | {:>7.1}% | {:>7.2} | {}/{}",
+            i + 1,
+            config.config.id().blue(),
+            if config.avg_roi >= 0.0 { roi.green() } else { roi.red() },
+            config.avg_max_drawdown,
+            config.avg_sharpe,
+            config.profitable_traders,
+            config.runs.len()
+        );
+    }
+
+    // Full sweep summary for aggregate_results
+    let factory_dir = Path::new("strategy_factory_results");
+    fs::create_dir_all(factory_dir)?;
+    let stamp = chrono::Utc::now().format("%Y-%m-%d_%H%M%S");
+    let report_path = factory_dir.join(format!("sweep_{}_{}d_{}.json", options.mode, options.history_days, stamp));
+    let report = sweep_report(&ranked, &options.mode, options.history_days, options.starting_capital, options.rank_by);
+    fs::write(&report_path, serde_json::to_string_pretty(&report)?)?;
+
+    // Top configurations as individual results for compare_results
+    let results_dir = Path::new("simulation_results");
+    fs::create_dir_all(results_dir)?;
+    let date = chrono::Utc::now().format("%Y-%m-%d");
+    for (rank, config) in ranked.iter().take(options.top).enumerate() {
+        for run in &config.runs {
+            let name = format!("SWEEP_{}_{}d_{}", &run.trader_address[..6.min(run.trader_address.len())], options.history_days, config.config.id());
+            let result = run.outcome.to_simulation_result(
+                &format!("sweep_{}_{}", rank + 1, run.trader_address),
+                &name,
+                "sweep",
+                &run.trader_address,
+                options.starting_capital,
+            );
+            let path = results_dir.join(format!("sweep_{}_{}d_{}_{}.json", run.trader_address, options.history_days, config.config.id(), date));
+            fs::write(&path, serde_json::to_string_pretty(&result)?)?;
+        }
+    }
+
+    println!("\n{}", format!("✓ Sweep report saved to: {}", report_path.display()).green());
+    println!(
+        "{}",
+        format!("✓ Top {} configuration(s) saved to: {}/", options.top.min(ranked.len()), results_dir.display()).green()
+    );
+    println!("{}", "  Compare with: polycopy sim compare best\n".bright_black());
+    Ok(())
+}
+
+fn print_help() {
+    println!("{}", "\n📊 Simulation Runner - Usage\n".cyan());
+    println!("Interactive mode:");
+    println!("{}", "  polycopy sim batch\n".yellow());
+
+    println!("Preset modes:");
+    println!("{}", "  polycopy sim batch quick      # 7 days, 2 multipliers".yellow());
+    println!("{}", "  polycopy sim batch standard   # 30 days, 3 multipliers (recommended)".yellow());
+    println!("{}", "  polycopy sim batch full       # 90 days, 4 multipliers\n".yellow());
+
+    println!("Custom mode:");
+    println!("{}", "  polycopy sim batch custom <trader> [days] [multiplier]\n".yellow());
+
+    println!("Parameter sweep (parallel, from cached history):");
+    println!("{}", "  polycopy sim batch sweep [grid|random] [options]".yellow());
+    println!("{}", "    --traders a,b,c        Trader addresses (default: built-in list)".bright_black());
+    println!("{}", "    --days N               History window in days (default: 30)".bright_black());
+    println!("{}", "    --samples N --seed N   Random mode sample count / seed".bright_black());
+    println!("{}", "    --parallel N           Worker count (default: CPU count)".bright_black());
+    println!("{}", "    --rank-by roi|drawdown|sharpe   Ranking metric (default: roi)".bright_black());
+    println!("{}", "    --top N                Configurations to print and save (default: 10)".bright_black());
+    println!("{}", "    --strategies PERCENTAGE,FIXED,ADAPTIVE  --copy-sizes 5,10,20".bright_black());
+    println!("{}", "    --multipliers 0.5,1,2  --min-orders 1,5  --max-orders 50,100".bright_black());
+    println!("{}", "    --take-profits none,25,50  --stop-losses none,20,40".bright_black());
+    println!("{}", "    Fill model: SIM_EXECUTION_MODEL=true (see simulate_profitability)\n".bright_black());
+
+    println!("Examples:");
+    println!("{}", "  polycopy sim batch custom 0x7c3d... 30 2.0".bright_black());
+    println!("{}", "  polycopy sim batch standard".bright_black());
+    println!("{}", "  polycopy sim batch sweep random --samples 300 --rank-by sharpe\n".bright_black());
+}
+
+pub async fn run(args: Vec<String>) -> Result<()> {
+
+    if args.is_empty() {
+        // Interactive mode - use standard preset
+        println!("{}", "\n🎮 Running standard preset simulations...\n".cyan());
+        let configs = generate_configs("standard", None);
+        run_batch(&configs).await?;
+        return Ok(());
+    }
+
+    let command = &args[0];
+
+    match command.as_str() {
+        "quick" => {
+            let configs = generate_configs("quick", None);
+            run_batch(&configs).await?;
+        }
+        "standard" | "std" => {
+            let configs = generate_configs("standard", None);
+            run_batch(&configs).await?;
+        }
+        "full" => {
+            let configs = generate_configs("full", None);
+            run_batch(&configs).await?;
+        }
+        "custom" => {
+            if args.len() < 2 {
+                println!("{}", "Error: Trader address required for custom mode".red());
+                println!("{}", "Usage: polycopy sim batch custom <trader_address> [days] [multiplier]".yellow());
+                return Ok(());
+            }
+
+            let trader = args[1].clone();
+            let days = args.get(2).and_then(|s| s.parse::<i32>().ok()).unwrap_or(30);
+            let multiplier = args.get(3).and_then(|s| s.parse::<f64>().ok()).unwrap_or(1.0);
+
+            let config = SimulationConfig {
+                trader_address: trader.to_lowercase(),
+                history_days: days,
+                multiplier,
+                min_order_size: 1.0,
+                max_trades: None,
+                tag: Some("custom".to_string()),
+            };
+
+            run_simulation(&config).await?;
+        }
+        "sweep" => {
+            let options = parse_sweep_options(&args[1..])?;
+            run_sweep_command(options).await?;
+        }
+        "help" | "--help" | "-h" => {
+            print_help();
+        }
+        _ => {
+            println!("{}", format!("Unknown command: {}\n", command).red());
+            print_help();
+        }
+    }
+
+    Ok(())
+}
@@ -5,8 +5,7 @@ use anyhow::Result;
 use polymarket_copy_trading_bot_rust::config::load_env;
 // use polymarket_copy_trading_bot_rust::utils::fetch_data; // Unused for now
 
-#[tokio::main]
-async fn main() -> Result<()> {
+pub async fn run() -> Result<()> {
     println!("🔍 Scanning Best Traders");
     println!("════════════════════════════════════════════════════\n");
 
@@ -16,9 +15,9 @@ async fn main() -> Result<()> {
     println!("   For detailed analysis, use find_best_traders or find_low_risk_traders.\n");
 
     println!("💡 Available trader analysis scripts:");
-    println!("   • cargo run --bin find_best_traders");
-    println!("   • cargo run --bin find_low_risk_traders");
-    println!("   • cargo run --bin scan_traders_from_markets\n");
+    println!("   • polycopy traders best");
+    println!("   • polycopy traders low-risk");
+    println!("   • polycopy traders scan-markets\n");
 
     Ok(())
 }
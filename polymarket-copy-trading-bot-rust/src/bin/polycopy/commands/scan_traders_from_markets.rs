@@ -0,0 +1,308 @@
+//! Scan traders from popular markets
+//! Pulls the most active markets from Gamma, collects their top holders and
+//! recent takers from the Data API, then simulates copying each wallet over
+//! its recent history and ranks the candidates.
+#![allow(dead_code)] // Struct fields used for JSON deserialization
+
+use anyhow::Result;
+use colored::*;
+use futures_util::stream::{self, StreamExt};
+use polymarket_copy_trading_bot_rust::config::{load_env, Env};
+use polymarket_copy_trading_bot_rust::utils::copy_simulator::{load_or_fetch_trades, simulate, SimParams, SimTrade};
+use polymarket_copy_trading_bot_rust::utils::data_api::TradesQuery;
+use polymarket_copy_trading_bot_rust::utils::DataApiClient;
+use polymarket_copy_trading_bot_rust::utils::gamma_api::{fetch_active_markets, GammaMarket};
+use polymarket_copy_trading_bot_rust::utils::market_resolution::ResolutionCache;
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::Path;
+use crate::common::get_env_usize;
+
+const STARTING_CAPITAL: f64 = 1000.0;
+const MAX_PARALLEL_FETCHES: usize = 5;
+
+/// Where a wallet was found
+#[derive(Debug, Default)]
+struct Candidate {
+    markets: HashSet<String>,
+    holder_of: usize,
+    taker_in: usize,
+}
+
+#[derive(Debug, serde::Serialize)]
+struct CandidateResult {
+    address: String,
+    roi: f64,
+    #[serde(rename = "totalPnl")]
+    total_pnl: f64,
+    #[serde(rename = "winRate")]
+    win_rate: f64,
+    #[serde(rename = "copiedTrades")]
+    copied_trades: usize,
+    status: String,
+    #[serde(rename = "maxDrawdownPercent")]
+    max_drawdown_percent: f64,
+    sharpe: f64,
+    #[serde(rename = "tradesCount")]
+    trades_count: usize,
+    volume: f64,
+    #[serde(rename = "marketsTraded")]
+    markets_traded: usize,
+    #[serde(rename = "lastTradeAt")]
+    last_trade_at: i64,
+    #[serde(rename = "foundInMarkets")]
+    found_in_markets: usize,
+    #[serde(rename = "holderOf")]
+    holder_of: usize,
+    #[serde(rename = "takerIn")]
+    taker_in: usize,
+}
+
+async fn fetch_holders(market: &GammaMarket, limit: usize, env: &Env) -> Result<Vec<String>> {
+    let holders = DataApiClient::new(env).holders(&market.condition_id, limit).await?;
+    Ok(holders
+        .into_iter()
+        .flat_map(|h| h.holders)
+        .map(|h| h.proxy_wallet.to_lowercase())
+        .collect())
+}
+
+async fn fetch_recent_takers(market: &GammaMarket, limit: usize, env: &Env) -> Result<Vec<String>> {
+    let query = TradesQuery::new().market(&market.condition_id).taker_only().limit(limit);
+    let trades = DataApiClient::new(env).trades(&query).await?;
+    Ok(trades.into_iter().map(|t| t.proxy_wallet.to_lowercase()).collect())
+}
+
+fn evaluate(
+    address: &str,
+    trades: &[SimTrade],
+    candidate: &Candidate,
+    params: &SimParams,
+    resolutions: &ResolutionCache,
+) -> CandidateResult {
+    let outcome = simulate(trades, params, Some(resolutions), None);
+    let markets: HashSet<&str> = trades
+        .iter()
+        .map(|t| t.condition_id.as_deref().unwrap_or(&t.asset))
+        .collect();
+
+    CandidateResult {
+        address: address.to_string(),
+        roi: outcome.roi,
+        total_pnl: outcome.total_pnl,
+        win_rate: outcome.win_rate,
+        copied_trades: outcome.copied_trades,
+        status: if outcome.roi > 0.0 { "profitable" } else { "unprofitable" }.to_string(),
+        max_drawdown_percent: outcome.max_drawdown_percent,
+        sharpe: outcome.sharpe,
+        trades_count: trades.len(),
+        volume: trades.iter().map(|t| t.usdc_size).sum(),
+        markets_traded: markets.len(),
+        last_trade_at: trades.last().map(|t| t.timestamp).unwrap_or(0),
+        found_in_markets: candidate.markets.len(),
+        holder_of: candidate.holder_of,
+        taker_in: candidate.taker_in,
+    }
+}
+
+pub async fn run() -> Result<()> {
+    println!("{}", "🔍 Scanning Traders from Markets".cyan());
+    println!("════════════════════════════════════════════════════\n");
+
+    let env = load_env()?;
+
+    let market_limit = get_env_usize("SCAN_MARKETS", 20);
+    let holders_per_market = get_env_usize("SCAN_HOLDERS_PER_MARKET", 20);
+    let takers_per_market = get_env_usize("SCAN_TAKERS_PER_MARKET", 100);
+    let max_traders = get_env_usize("SCAN_MAX_TRADERS", 50);
+    let history_days = get_env_usize("SCAN_HISTORY_DAYS", 30) as i32;
+    let min_trades = get_env_usize("SCAN_MIN_TRADES", 10);
+    let max_trades = get_env_usize("SIM_MAX_TRADES", 5000);
+
+    // 1. Most active markets
+    println!("{}", format!("📊 Fetching top {} markets by 24h volume...", market_limit).cyan());
+    let markets = fetch_active_markets(market_limit, &env).await?;
+    if markets.is_empty() {
+        println!("{}", "❌ No active markets returned by Gamma API".red());
+        return Ok(());
+    }
+    println!("{}", format!("✓ {} markets\n", markets.len()).green());
+
+    // 2. Holders and recent takers per market, deduplicated by wallet
+    let mut candidates: HashMap<String, Candidate> = HashMap::new();
+    for market in &markets {
+        let holders = fetch_holders(market, holders_per_market, &env).await.unwrap_or_else(|e| {
+            println!("{}", format!("  ⚠️  Holders unavailable for {}: {}", market.question, e).yellow());
+            Vec::new()
+        });
+        let takers = fetch_recent_takers(market, takers_per_market, &env).await.unwrap_or_else(|e| {
+            println!("{}", format!("  ⚠️  Trades unavailable for {}: {}", market.question, e).yellow());
+            Vec::new()
+        });
+
+        for wallet in holders.iter().collect::<HashSet<_>>() {
+            let candidate = candidates.entry(wallet.clone()).or_default();
+            candidate.markets.insert(market.condition_id.clone());
+            candidate.holder_of += 1;
+        }
+        for wallet in takers.iter().collect::<HashSet<_>>() {
+            let candidate = candidates.entry(wallet.clone()).or_default();
+            candidate.markets.insert(market.condition_id.clone());
+            candidate.taker_in += 1;
+        }
+
+        println!(
+            "{}",
+            format!(
+                "  {} — {} holders, {} takers",
+                market.question.chars().take(50).collect::<String>(),
+                holders.len(),
+                takers.len()
+            )
+            .bright_black()
+        );
+    }
+
+    // Wallets seen across the most markets first
+    let mut wallets: Vec<(String, Candidate)> = candidates.into_iter().collect();
+    wallets.sort_by(|a, b| {
+        b.1.markets
+            .len()
+            .cmp(&a.1.markets.len())
+            .then((b.1.holder_of + b.1.taker_in).cmp(&(a.1.holder_of + a.1.taker_in)))
+    });
+    let total_found = wallets.len();
+    wallets.truncate(max_traders);
+    println!(
+        "{}",
+        format!("\n✓ {} unique wallets found, analyzing top {}\n", total_found, wallets.len()).green()
+    );
+
+    // 3. History for each wallet (cached in trader_data_cache/)
+    let histories: Vec<(String, Candidate, Vec<SimTrade>)> = stream::iter(wallets)
+        .map(|(address, candidate)| {
+            let env = env.clone();
+            async move {
+                let trades = load_or_fetch_trades(&address, history_days, max_trades, &env).await;
+                (address, candidate, trades)
+            }
+        })
+        .buffer_unordered(MAX_PARALLEL_FETCHES)
+        .filter_map(|(address, candidate, trades)| async move {
+            match trades {
+                Ok(trades) if trades.len() >= min_trades => Some((address, candidate, trades)),
+                Ok(_) => None,
+                Err(e) => {
+                    println!("{}", format!("  ⚠️  {}: {}", address, e).yellow());
+                    None
+                }
+            }
+        })
+        .collect()
+        .await;
+    println!(
+        "{}",
+        format!("✓ {} wallets with at least {} trades in {} days", histories.len(), min_trades, history_days).green()
+    );
+
+    let mut resolutions = ResolutionCache::load();
+    let condition_ids: Vec<String> = histories
+        .iter()
+        .flat_map(|(_, _, trades)| trades.iter().filter_map(|t| t.condition_id.clone()))
+        .collect();
+    if let Err(e) = resolutions.refresh(&condition_ids, &env).await {
+        println!("{}", format!("⚠️  Could not refresh market resolutions: {}", e).yellow());
+    }
+    let _ = resolutions.save();
+
+    // 4. Simulate copying each wallet with the configured strategy
+    let params = SimParams {
+        strategy: env.copy_strategy_config.clone(),
+        take_profit_percent: env.take_profit_percent,
+        stop_loss_percent: env.stop_loss_percent,
+        starting_capital: STARTING_CAPITAL,
+        execution: None,
+    };
+    let mut results: Vec<CandidateResult> = histories
+        .iter()
+        .map(|(address, candidate, trades)| evaluate(address, trades, candidate, &params, &resolutions))
+        .collect();
+    results.sort_by(|a, b| b.roi.partial_cmp(&a.roi).unwrap_or(std::cmp::Ordering::Equal));
+
+    if results.is_empty() {
+        println!("{}", "\n❌ No candidates with enough trading history".red());
+        return Ok(());
+    }
+
+    println!("\n{}", "━".repeat(95));
+    println!("📊 CANDIDATE TRADERS (copy simulation, ${:.0} capital):\n", STARTING_CAPITAL);
+    println!(
+        "{}",
+        format!(
+            "  #  | {:<42} | {:>9} | {:>6} | {:>7} | {:>6} | Markets",
+            "Address", "ROI", "Win %", "Max DD", "Trades"
+        )
+        .bold()
+    );
+    for (i, r) in results.iter().take(20).enumerate() {
+        let roi = format!("{:+.2}%", r.roi);
+        println!(
+            "  {:<2} | {:<42} | {:>9} | {:>5.1}% | {:>6.1}% | {:>6} | {}",
+            i + 1,
+            r.address.blue(),
+            if r.roi >= 0.0 { roi.green() } else { roi.red() },
+            r.win_rate,
+            r.max_drawdown_percent,
+            r.trades_count,
+            r.found_in_markets
+        );
+    }
+    println!("{}", "━".repeat(95));
+
+    // 5. Save in the trader scan format read by aggregate_results
+    let profitable = results.iter().filter(|r| r.roi > 0.0).count();
+    let report = serde_json::json!({
+        "scanDate": chrono::Utc::now().to_rfc3339(),
+        "source": "markets",
+        "config": {
+            "historyDays": history_days,
+            "multiplier": env.trade_multiplier,
+            "minOrderSize": env.copy_strategy_config.min_order_size_usd,
+            "startingCapital": STARTING_CAPITAL,
+            "marketsScanned": markets.len(),
+            "walletsFound": total_found,
+        },
+        "summary": {
+            "totalAnalyzed": results.len(),
+            "profitable": profitable,
+            "avgROI": results.iter().map(|r| r.roi).sum::<f64>() / results.len() as f64,
+            "avgWinRate": results.iter().map(|r| r.win_rate).sum::<f64>() / results.len() as f64,
+        },
+        "traders": results,
+    });
+
+    let results_dir = Path::new("trader_scan_results");
+    fs::create_dir_all(results_dir)?;
+    let filepath = results_dir.join(format!(
+        "markets_scan_{}d_{}.json",
+        history_days,
+        chrono::Utc::now().format("%Y-%m-%d_%H%M%S")
+    ));
+    fs::write(&filepath, serde_json::to_string_pretty(&report)?)?;
+
+    println!("\n{}", format!("✓ Results saved to: {}", filepath.display()).green());
+    println!("\n💡 To copy these traders, add their addresses to USER_ADDRESSES in .env");
+    println!(
+        "   Example: USER_ADDRESSES={}",
+        results
+            .iter()
+            .filter(|r| r.roi > 0.0)
+            .take(3)
+            .map(|r| r.address.clone())
+            .collect::<Vec<_>>()
+            .join(",")
+    );
+    println!("   Aggregate with other scans: polycopy sim aggregate\n");
+
+    Ok(())
+}
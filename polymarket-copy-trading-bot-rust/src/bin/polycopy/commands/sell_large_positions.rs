@@ -14,30 +14,12 @@ use alloy::primitives::U256;
 use chrono::DateTime;
 use std::str::FromStr;
 use std::time::{SystemTime, UNIX_EPOCH};
+use crate::common::{fetch_positions, Position};
 
 const SELL_PERCENTAGE: f64 = 0.8; // 80%
 const MIN_POSITION_VALUE: f64 = 17.0; // Sell only positions > $17
 const MIN_ORDER_SIZE_TOKENS: f64 = 1.0;
 
-#[derive(Debug, Clone, serde::Deserialize)]
-struct Position {
-    asset: String,
-    condition_id: String,
-    size: f64,
-    avg_price: f64,
-    initial_value: f64,
-    current_value: f64,
-    cash_pnl: f64,
-    percent_pnl: f64,
-    total_bought: f64,
-    realized_pnl: f64,
-    percent_realized_pnl: f64,
-    cur_price: f64,
-    title: Option<String>,
-    slug: Option<String>,
-    outcome: Option<String>,
-}
-
 async fn sell_position(
     clob_client: &ClobClient<Authenticated<Normal>>,
     position: &Position,
@@ -173,8 +155,7 @@ async fn sell_position(
     }
 }
 
-#[tokio::main]
-async fn main() -> Result<()> {
+pub async fn run() -> Result<()> {
     println!("🚀 Sell Large Positions Script");
     println!("═══════════════════════════════════════════════\n");
 
@@ -189,15 +170,7 @@ async fn main() -> Result<()> {
 
     // Get all positions
     println!("📥 Fetching positions...");
-    let positions_url = format!("https://data-api.polymarket.com/positions?user={}", env.proxy_wallet);
-    let positions_json: serde_json::Value = fetch_data(&positions_url, &env).await?;
-    let positions: Vec<Position> = positions_json
-        .as_array()
-        .cloned()
-        .unwrap_or_default()
-        .into_iter()
-        .filter_map(|v| serde_json::from_value(v).ok())
-        .collect();
+    let positions = fetch_positions(&env.proxy_wallet, &env).await?;
     
     println!("Found {} position(s)\n", positions.len());
 
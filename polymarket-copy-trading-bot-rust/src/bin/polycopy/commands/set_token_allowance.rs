@@ -0,0 +1,54 @@
+//! Set USDC allowance and CTF (Conditional Token Framework) approval for every
+//! Polymarket spender. Sends from the EOA signer when it is the proxy wallet, or
+//! executes through the proxy wallet when it is a Safe owned by the signer.
+
+use anyhow::Result;
+use polymarket_copy_trading_bot_rust::config::load_env;
+use polymarket_copy_trading_bot_rust::utils::allowances::{
+    check_allowances, log_allowance_report, set_missing_allowances,
+};
+
+pub async fn run() -> Result<()> {
+    let env = load_env()?;
+
+    println!();
+    println!("🔑 Setting token allowances for Polymarket");
+    println!();
+
+    // Check current approval status
+    println!("🔍 Checking current approval status...");
+    let report = check_allowances(&env).await?;
+    log_allowance_report(&report);
+
+    if report.is_complete() {
+        println!("✅ All allowances already set. You can trade and sell positions.");
+        println!();
+        return Ok(());
+    }
+
+    let missing = report.missing_calls(&env)?;
+    println!();
+    println!("⚠️  Sending {} missing approval(s):", missing.len());
+    for (label, _) in &missing {
+        println!("   • {}", label);
+    }
+    println!();
+
+    for tx_hash in set_missing_allowances(&report, &env).await? {
+        println!("  Tx confirmed: https://polygonscan.com/tx/0x{:x}", tx_hash);
+    }
+    println!();
+
+    // Verify approvals
+    println!("🔍 Verifying approvals...");
+    let report = check_allowances(&env).await?;
+    log_allowance_report(&report);
+    if report.is_complete() {
+        println!("✅ Verification: all approvals confirmed on-chain");
+    } else {
+        eprintln!("⚠️  Warning: some approvals are still missing.");
+        std::process::exit(1);
+    }
+    println!();
+    Ok(())
+}
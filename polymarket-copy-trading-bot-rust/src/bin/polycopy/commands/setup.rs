@@ -31,8 +31,7 @@ fn is_valid_private_key(key: &str) -> bool {
     key.len() == 64 && key.chars().all(|c| c.is_ascii_hexdigit())
 }
 
-#[tokio::main]
-async fn main() -> Result<()> {
+pub async fn run() -> Result<()> {
     print_header();
 
     println!("{}", "\n━━━ STEP 1: TRADERS TO COPY ━━━\n".blue().bold());
@@ -188,7 +187,7 @@ TRADE_MULTIPLIER=1.0
     println!("{}\n", "━".repeat(65).green());
     println!("{}", "Next steps:".yellow());
     println!("  1. Review the .env file");
-    println!("  2. Run: cargo run --bin health_check");
+    println!("  2. Run: polycopy health check");
     println!("  3. Start the bot: cargo run --release\n");
 
     Ok(())
@@ -4,17 +4,24 @@
 use anyhow::Result;
 use colored::*;
 use polymarket_copy_trading_bot_rust::config::load_env;
-use polymarket_copy_trading_bot_rust::utils::fetch_data;
+use polymarket_copy_trading_bot_rust::utils::execution_model::{
+    DepthSource, ExecutionModel, FillOutcome, OrderBookCache, PriceTape,
+};
+use polymarket_copy_trading_bot_rust::utils::{ActivityQuery, DataApiClient};
+use polymarket_copy_trading_bot_rust::utils::market_resolution::{fetch_historical_price, ResolutionCache};
 use std::collections::HashMap;
 use std::env;
 use std::fs;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 struct Trade {
     id: String,
     timestamp: i64,
     market: Option<String>,
+    #[serde(rename = "conditionId")]
+    condition_id: Option<String>,
+    title: Option<String>,
     asset: String,
     side: String,
     price: f64,
@@ -24,31 +31,13 @@ struct Trade {
     outcome: Option<String>,
 }
 
-#[derive(Debug, serde::Serialize, serde::Deserialize)]
-struct Position {
-    #[serde(rename = "conditionId")]
-    condition_id: Option<String>,
-    market: Option<String>,
-    outcome: Option<String>,
-    #[serde(rename = "outcomeIndex")]
-    outcome_index: Option<i32>,
-    asset: String,
-    size: f64,
-    cost: Option<f64>,
-    #[serde(rename = "avgEntryPrice")]
-    avg_entry_price: Option<f64>,
-    #[serde(rename = "currentValue")]
-    current_value: f64,
-    #[serde(rename = "realizedPnl")]
-    realized_pnl: Option<f64>,
-    #[serde(rename = "unrealizedPnl")]
-    unrealized_pnl: Option<f64>,
-}
-
 #[derive(Debug, serde::Serialize, serde::Deserialize)]
 struct SimulatedPosition {
     market: String,
     outcome: String,
+    asset: String,
+    #[serde(rename = "conditionId")]
+    condition_id: Option<String>,
     #[serde(rename = "sharesHeld")]
     shares_held: f64,
     #[serde(rename = "entryPrice")]
@@ -60,6 +49,12 @@ struct SimulatedPosition {
     current_value: f64,
     pnl: f64,
     closed: bool,
+    #[serde(default)]
+    resolved: bool,
+    #[serde(rename = "markPrice")]
+    mark_price: Option<f64>,
+    #[serde(rename = "markSource", default)]
+    mark_source: String,
     trades: Vec<PositionTrade>,
 }
 
@@ -103,9 +98,19 @@ struct SimulationResult {
     realized_pnl: f64,
     #[serde(rename = "unrealizedPnl")]
     unrealized_pnl: f64,
+    #[serde(rename = "resolutionPnl", default)]
+    resolution_pnl: f64,
     #[serde(rename = "totalPnl")]
     total_pnl: f64,
     roi: f64,
+    #[serde(rename = "valuationTimestamp", default)]
+    valuation_timestamp: i64,
+    #[serde(rename = "executionModel", default)]
+    execution_model: Option<String>,
+    #[serde(rename = "totalFees", default)]
+    total_fees: f64,
+    #[serde(rename = "slippageRejections", default)]
+    slippage_rejections: usize,
     positions: Vec<SimulatedPosition>,
 }
 
@@ -133,29 +138,6 @@ fn get_env_var_int_or_default(key: &str, default: i32) -> i32 {
         .unwrap_or(default)
 }
 
-async fn fetch_batch(
-    trader_address: &str,
-    offset: usize,
-    limit: usize,
-    since_timestamp: i64,
-    env: &polymarket_copy_trading_bot_rust::config::Env,
-) -> Result<Vec<Trade>> {
-    let url = format!(
-        "https://data-api.polymarket.com/activity?user={}&type=TRADE&limit={}&offset={}",
-        trader_address, limit, offset
-    );
-    let json: serde_json::Value = fetch_data(&url, env).await?;
-    let trades: Vec<Trade> = json
-        .as_array()
-        .cloned()
-        .unwrap_or_default()
-        .into_iter()
-        .filter_map(|v| serde_json::from_value(v).ok())
-        .filter(|t: &Trade| t.timestamp >= since_timestamp)
-        .collect();
-    Ok(trades)
-}
-
 async fn fetch_trader_activity(
     trader_address: &str,
     history_days: i32,
@@ -180,7 +162,7 @@ async fn fetch_trader_activity(
     println!(
         "{}",
         format!(
-            "📊 Fetching trader activity from last {} days (with parallel requests)...",
+            "📊 Fetching trader activity from last {} days...",
             history_days
         )
         .cyan()
@@ -189,62 +171,33 @@ async fn fetch_trader_activity(
     let since_timestamp =
         (chrono::Utc::now().timestamp() - (history_days as i64 * 24 * 60 * 60)) as i64;
 
-    let first_batch = fetch_batch(trader_address, 0, 100, since_timestamp, env).await?;
-    let mut all_trades = first_batch.clone();
-
-    if first_batch.len() == 100 {
-        let batch_size = 100;
-        let max_parallel = 5;
-        let mut offset = 100;
-        let mut has_more = true;
-
-        while has_more && all_trades.len() < max_trades_limit {
-            let mut promises = Vec::new();
-            for i in 0..max_parallel {
-                let addr = trader_address.to_string();
-                let env_clone = env.clone();
-                let offset_val = offset + i * batch_size;
-                promises.push(tokio::spawn(async move {
-                    fetch_batch(&addr, offset_val, batch_size, since_timestamp, &env_clone).await
-                }));
-            }
-
-            let mut added_count = 0;
-            for result in futures_util::future::join_all(promises).await {
-                match result {
-                    Ok(Ok(batch)) => {
-                        if !batch.is_empty() {
-                            all_trades.extend(batch.clone());
-                            added_count += batch.len();
-                            if batch.len() < batch_size {
-                                has_more = false;
-                            }
-                        }
-                    }
-                    _ => {}
-                }
-            }
-
-            if added_count == 0 {
-                has_more = false;
-            }
-
-            if all_trades.len() >= max_trades_limit {
-                println!(
-                    "{}",
-                    format!(
-                        "⚠️  Reached trade limit ({}), stopping fetch...",
-                        max_trades_limit
-                    )
-                    .yellow()
-                );
-                all_trades.truncate(max_trades_limit);
-                has_more = false;
-            }
-
-            offset += max_parallel * batch_size;
-            println!("{}", format!("  Fetched {} trades so far...", all_trades.len()).bright_black());
-        }
+    let query = ActivityQuery::new(trader_address)
+        .kind("TRADE")
+        .start(since_timestamp)
+        .limit(max_trades_limit);
+    let mut all_trades: Vec<Trade> = DataApiClient::new(env)
+        .activity(&query)
+        .await?
+        .into_iter()
+        .map(|activity| Trade {
+            id: activity.transaction_hash,
+            timestamp: activity.timestamp,
+            market: activity.market,
+            condition_id: Some(activity.condition_id),
+            title: activity.title,
+            asset: activity.asset,
+            side: activity.side,
+            price: activity.price,
+            usdc_size: activity.usdc_size,
+            size: activity.size,
+            outcome: activity.outcome,
+        })
+        .collect();
+    if all_trades.len() >= max_trades_limit {
+        println!(
+            "{}",
+            format!("⚠️  Reached trade limit ({}), stopping fetch...", max_trades_limit).yellow()
+        );
     }
 
     all_trades.sort_by_key(|t| t.timestamp);
@@ -271,42 +224,118 @@ async fn fetch_trader_activity(
     Ok(all_trades)
 }
 
-async fn fetch_trader_positions(
-    trader_address: &str,
+/// Totals produced by marking simulated positions at the valuation time
+struct Valuation {
+    open_value: f64,
+    realized_pnl: f64,
+    unrealized_pnl: f64,
+    resolution_pnl: f64,
+}
+
+fn settled_by(end_date: Option<&str>, valuation_ts: i64) -> bool {
+    // Without an end date we trust the resolution flag as of now
+    end_date
+        .and_then(|d| chrono::DateTime::parse_from_rfc3339(d).ok())
+        .is_none_or(|d| d.timestamp() <= valuation_ts)
+}
+
+/// Mark every simulated position at `valuation_ts`:
+/// - positions in resolved markets settle at 1.0 / 0.0 per share
+/// - open positions in unresolved markets use the CLOB price history,
+///   falling back to the last copied trade price, then to cost basis
+async fn mark_positions(
+    positions: &mut HashMap<String, SimulatedPosition>,
+    valuation_ts: i64,
     env: &polymarket_copy_trading_bot_rust::config::Env,
-) -> Result<Vec<Position>> {
-    println!("{}", "📈 Fetching trader positions...".cyan());
-    let url = format!("https://data-api.polymarket.com/positions?user={}", trader_address);
-    let json: serde_json::Value = fetch_data(&url, env).await?;
-    
-    // Handle both array and object responses
-    let positions: Vec<Position> = if json.is_array() {
-        serde_json::from_value(json)?
-    } else {
-        // If it's an object, try to extract positions array
-        json.get("positions")
-            .or_else(|| json.get("data"))
-            .and_then(|v| v.as_array())
-            .map(|arr| {
-                arr.iter()
-                    .filter_map(|v| serde_json::from_value::<Position>(v.clone()).ok())
-                    .collect()
-            })
-            .unwrap_or_default()
+) -> Result<Valuation> {
+    println!("{}", "📈 Fetching market resolutions...".cyan());
+
+    let mut cache = ResolutionCache::load();
+    let condition_ids: Vec<String> = positions
+        .values()
+        .filter(|p| !p.closed)
+        .filter_map(|p| p.condition_id.clone())
+        .collect();
+    let fetched = cache.refresh(&condition_ids, env).await?;
+    if let Err(e) = cache.save() {
+        println!("{}", format!("⚠️  Could not save resolution cache: {}", e).yellow());
+    }
+    println!(
+        "{}",
+        format!("✓ {} market(s) fetched, {} cached", fetched, cache.len()).green()
+    );
+
+    let mut valuation = Valuation {
+        open_value: 0.0,
+        realized_pnl: 0.0,
+        unrealized_pnl: 0.0,
+        resolution_pnl: 0.0,
     };
-    
-    println!("{}", format!("✓ Fetched {} positions", positions.len()).green());
-    Ok(positions)
+
+    for pos in positions.values_mut() {
+        let total_sold: f64 = pos.trades
+            .iter()
+            .filter(|t| t.side == "SELL")
+            .map(|t| t.usdc_size)
+            .sum();
+
+        if pos.closed {
+            valuation.realized_pnl += pos.pnl;
+            continue;
+        }
+
+        let resolution = pos.condition_id.as_deref().and_then(|id| cache.get(id));
+        let settlement = resolution
+            .filter(|r| settled_by(r.end_date.as_deref(), valuation_ts))
+            .and_then(|r| r.settlement_price(&pos.asset, Some(&pos.outcome)));
+
+        if let Some(settle_price) = settlement {
+            pos.resolved = true;
+            pos.mark_price = Some(settle_price);
+            pos.mark_source = "resolution".to_string();
+            pos.exit_price = Some(settle_price);
+            pos.current_value = pos.shares_held * settle_price;
+            pos.pnl = total_sold + pos.current_value - pos.invested;
+            valuation.resolution_pnl += pos.pnl;
+        } else {
+            let historical = match fetch_historical_price(&pos.asset, valuation_ts, env).await {
+                Ok(price) => price,
+                Err(e) => {
+                    println!(
+                        "{}",
+                        format!("  Price history unavailable for {}: {}", pos.market, e).bright_black()
+                    );
+                    None
+                }
+            };
+            let (price, source) = match (historical, pos.trades.last()) {
+                (Some(p), _) => (p, "price_history"),
+                (None, Some(last)) => (last.price, "last_trade"),
+                (None, None) => (pos.entry_price, "cost_basis"),
+            };
+            pos.mark_price = Some(price);
+            pos.mark_source = source.to_string();
+            pos.current_value = pos.shares_held * price;
+            pos.pnl = total_sold + pos.current_value - pos.invested;
+            valuation.unrealized_pnl += pos.pnl;
+        }
+
+        valuation.open_value += pos.current_value;
+    }
+
+    Ok(valuation)
 }
 
 async fn simulate_copy_trading(
     trades: Vec<Trade>,
-    trader_address: &str,
-    copy_percentage: f64,
-    multiplier: f64,
-    min_order_size: f64,
+    settings: &SimSettings,
     env: &polymarket_copy_trading_bot_rust::config::Env,
 ) -> Result<SimulationResult> {
+    let trader_address = settings.trader_address.as_str();
+    let copy_percentage = settings.copy_percentage;
+    let multiplier = settings.multiplier;
+    let min_order_size = settings.min_order_size;
+    let valuation_ts = settings.valuation_ts;
     println!("{}", "\n🎮 Starting simulation...\n".cyan());
 
     let mut your_capital = STARTING_CAPITAL;
@@ -316,6 +345,27 @@ async fn simulate_copy_trading(
 
     let mut positions: HashMap<String, SimulatedPosition> = HashMap::new();
 
+    // Optional fill model: latency, book depth, fees and max slippage
+    let execution = ExecutionModel::from_env();
+    let tape = PriceTape::new(trades.iter().map(|t| (t.asset.as_str(), t.timestamp, t.price)));
+    let mut books = OrderBookCache::load();
+    if let Some(model) = &execution {
+        println!("{}", format!("⚙️  Execution model: {}", model.describe()).cyan());
+        if model.depth_source == DepthSource::Recorded {
+            let assets: Vec<String> = trades.iter().map(|t| t.asset.clone()).collect();
+            let fetched = books.refresh(&assets, env).await?;
+            if let Err(e) = books.save() {
+                println!("{}", format!("⚠️  Could not save order book cache: {}", e).yellow());
+            }
+            println!(
+                "{}",
+                format!("✓ {} order book(s) recorded, {} cached", fetched, books.len()).green()
+            );
+        }
+    }
+    let mut total_fees = 0.0;
+    let mut slippage_rejections = 0;
+
     for trade in &trades {
         // NEW LOGIC: Copy fixed percentage of trader's order size
         let base_order_size = trade.usdc_size * (copy_percentage / 100.0);
@@ -338,32 +388,60 @@ async fn simulate_copy_trading(
 
         let position_key = format!("{}:{}", trade.asset, trade.outcome.as_deref().unwrap_or("Unknown"));
 
+        let start_price = execution
+            .as_ref()
+            .and_then(|m| tape.price_at(&trade.asset, trade.timestamp + m.latency_secs))
+            .unwrap_or(trade.price);
+
         if trade.side == "BUY" {
-            let shares_received = order_size / trade.price;
+            let (fill_price, shares_received) = match &execution {
+                None => (trade.price, order_size / trade.price),
+                Some(model) => match model.fill_buy(trade.price, start_price, order_size, books.get(&trade.asset)) {
+                    FillOutcome::Filled(fill) => {
+                        total_fees += fill.fee;
+                        order_size = fill.amount;
+                        (fill.price, fill.shares)
+                    }
+                    FillOutcome::Rejected(_) => {
+                        slippage_rejections += 1;
+                        skipped_trades += 1;
+                        continue;
+                    }
+                },
+            };
 
             let pos = positions.entry(position_key.clone()).or_insert_with(|| {
                 SimulatedPosition {
-                    market: trade.market.clone().unwrap_or_else(|| trade.asset.clone()),
+                    market: trade
+                        .title
+                        .clone()
+                        .or_else(|| trade.market.clone())
+                        .unwrap_or_else(|| trade.asset.clone()),
                     outcome: trade.outcome.clone().unwrap_or_else(|| "Unknown".to_string()),
+                    asset: trade.asset.clone(),
+                    condition_id: trade.condition_id.clone(),
                     shares_held: 0.0,
-                    entry_price: trade.price,
+                    entry_price: fill_price,
                     exit_price: None,
                     invested: 0.0,
                     current_value: 0.0,
                     pnl: 0.0,
                     closed: false,
+                    resolved: false,
+                    mark_price: None,
+                    mark_source: String::new(),
                     trades: Vec::new(),
                 }
             });
 
             pos.shares_held += shares_received;
             pos.invested += order_size;
-            pos.current_value = pos.shares_held * trade.price;
+            pos.current_value = pos.shares_held * fill_price;
 
             pos.trades.push(PositionTrade {
                 timestamp: trade.timestamp,
                 side: "BUY".to_string(),
-                price: trade.price,
+                price: fill_price,
                 size: shares_received,
                 usdc_size: order_size,
                 trader_percent: (trade.usdc_size / 100000.0) * 100.0,
@@ -385,17 +463,31 @@ async fn simulate_copy_trading(
                 let trader_total_shares = trader_sell_shares / 0.1; // Estimate
                 let trader_sell_percent = (trader_sell_shares / trader_total_shares).min(1.0);
 
-                let shares_to_sell = (pos.shares_held * trader_sell_percent).min(pos.shares_held);
-                let sell_amount = shares_to_sell * trade.price;
+                let mut shares_to_sell = (pos.shares_held * trader_sell_percent).min(pos.shares_held);
+                let (fill_price, sell_amount) = match &execution {
+                    None => (trade.price, shares_to_sell * trade.price),
+                    Some(model) => match model.fill_sell(trade.price, start_price, shares_to_sell, books.get(&trade.asset)) {
+                        FillOutcome::Filled(fill) => {
+                            total_fees += fill.fee;
+                            shares_to_sell = fill.shares;
+                            (fill.price, fill.amount)
+                        }
+                        FillOutcome::Rejected(_) => {
+                            slippage_rejections += 1;
+                            skipped_trades += 1;
+                            continue;
+                        }
+                    },
+                };
 
                 pos.shares_held -= shares_to_sell;
-                pos.current_value = pos.shares_held * trade.price;
-                pos.exit_price = Some(trade.price);
+                pos.current_value = pos.shares_held * fill_price;
+                pos.exit_price = Some(fill_price);
 
                 pos.trades.push(PositionTrade {
                     timestamp: trade.timestamp,
                     side: "SELL".to_string(),
-                    price: trade.price,
+                    price: fill_price,
                     size: shares_to_sell,
                     usdc_size: sell_amount,
                     trader_percent: trader_sell_percent * 100.0,
@@ -426,44 +518,15 @@ async fn simulate_copy_trading(
         }
     }
 
-    // Calculate current values based on trader's current positions
-    let trader_positions = fetch_trader_positions(trader_address, env).await?;
-    let mut total_current_value = your_capital;
-    let mut unrealized_pnl = 0.0;
-    let mut realized_pnl = 0.0;
-
-    for (key, sim_pos) in positions.iter_mut() {
-        if !sim_pos.closed {
-            // Extract asset ID from position key (format: "asset:outcome")
-            let asset_id = key.split(':').next().unwrap_or("");
-            
-            // Try to find matching trader position by asset
-            if let Some(trader_pos) = trader_positions.iter().find(|tp| tp.asset == asset_id) {
-                if trader_pos.size > 0.0 {
-                    let current_price = trader_pos.current_value / trader_pos.size;
-                    sim_pos.current_value = sim_pos.shares_held * current_price;
-                }
-            }
-
-            sim_pos.pnl = sim_pos.current_value - sim_pos.invested;
-            unrealized_pnl += sim_pos.pnl;
-            total_current_value += sim_pos.current_value;
-        } else {
-            realized_pnl += sim_pos.pnl;
-        }
-    }
-
-    let current_capital = your_capital
-        + positions
-            .values()
-            .filter(|p| !p.closed)
-            .map(|p| p.current_value)
-            .sum::<f64>();
+    // Value positions: settle resolved markets, mark the rest to historical prices
+    let valuation = mark_positions(&mut positions, valuation_ts, env).await?;
+    let total_current_value = your_capital + valuation.open_value;
+    let current_capital = total_current_value;
 
     let total_pnl = current_capital - STARTING_CAPITAL;
     let roi = (total_pnl / STARTING_CAPITAL) * 100.0;
 
-    let history_days = get_env_var_int_or_default("SIM_HISTORY_DAYS", 7);
+    let history_days = settings.history_days;
     let copy_pct_str = format!("copy{}pct", copy_percentage);
     let result_id = format!(
         "sim_{}_{}",
@@ -490,15 +553,22 @@ async fn simulate_copy_trading(
         skipped_trades,
         total_invested,
         current_value: total_current_value,
-        realized_pnl,
-        unrealized_pnl,
+        realized_pnl: valuation.realized_pnl,
+        unrealized_pnl: valuation.unrealized_pnl,
+        resolution_pnl: valuation.resolution_pnl,
         total_pnl,
         roi,
+        valuation_timestamp: valuation_ts,
+        execution_model: execution.as_ref().map(|m| m.describe()),
+        total_fees,
+        slippage_rejections,
         positions: positions.into_values().collect(),
     })
 }
 
-fn print_report(result: &SimulationResult, copy_percentage: f64, multiplier: f64) {
+fn print_report(result: &SimulationResult, settings: &SimSettings) {
+    let copy_percentage = settings.copy_percentage;
+    let multiplier = settings.multiplier;
     println!("\n{}", "═".repeat(80).cyan());
     println!("{}", "  📊 COPY TRADING SIMULATION REPORT (FIXED ALGORITHM)".cyan());
     println!("{}\n", "═".repeat(80).cyan());
@@ -535,11 +605,26 @@ fn print_report(result: &SimulationResult, copy_percentage: f64, multiplier: f64
         if result.realized_pnl >= 0.0 { "+" } else { "" },
         result.realized_pnl
     );
+    println!(
+        "  Resolution:    {}{:.2}",
+        if result.resolution_pnl >= 0.0 { "+" } else { "" },
+        result.resolution_pnl
+    );
     println!(
         "  Unrealized:    {}{:.2}",
         if result.unrealized_pnl >= 0.0 { "+" } else { "" },
         result.unrealized_pnl
     );
+    println!(
+        "{}",
+        format!(
+            "  Valued at {}",
+            chrono::DateTime::from_timestamp(result.valuation_timestamp, 0)
+                .map(|d| d.to_rfc3339())
+                .unwrap_or_default()
+        )
+        .bright_black()
+    );
     println!();
 
     println!("{}", "Trades:".bold());
@@ -548,11 +633,20 @@ fn print_report(result: &SimulationResult, copy_percentage: f64, multiplier: f64
     println!(
         "  Skipped:       {} (below ${:.2} minimum)",
         result.skipped_trades.to_string().yellow(),
-        get_env_var_or_default("SIM_MIN_ORDER_USD", 1.0)
+        settings.min_order_size
     );
+    if let Some(model) = &result.execution_model {
+        println!(
+            "  Rejected:      {} (slippage/liquidity)",
+            result.slippage_rejections.to_string().yellow()
+        );
+        println!("  Fees paid:     ${:.2}", result.total_fees);
+        println!("{}", format!("  Fills: {}", model).bright_black());
+    }
     println!();
 
-    let open_positions: Vec<_> = result.positions.iter().filter(|p| !p.closed).collect();
+    let open_positions: Vec<_> = result.positions.iter().filter(|p| !p.closed && !p.resolved).collect();
+    let resolved_positions: Vec<_> = result.positions.iter().filter(|p| p.resolved).collect();
     let closed_positions: Vec<_> = result.positions.iter().filter(|p| p.closed).collect();
 
     println!("{}", "Open Positions:".bold());
@@ -567,8 +661,9 @@ fn print_report(result: &SimulationResult, copy_percentage: f64, multiplier: f64
         let market_label = pos.market.chars().take(50).collect::<String>();
         println!("  {}. {}", i + 1, market_label);
             println!(
-            "     Outcome: {} | Invested: ${:.2} | Value: ${:.2} | P&L: {}",
-            pos.outcome.clone(), pos.invested, pos.current_value, pnl_str
+            "     Outcome: {} | Invested: ${:.2} | Value: ${:.2} | P&L: {} {}",
+            pos.outcome.clone(), pos.invested, pos.current_value, pnl_str,
+            format!("({})", pos.mark_source).bright_black()
         );
     }
 
@@ -579,6 +674,41 @@ fn print_report(result: &SimulationResult, copy_percentage: f64, multiplier: f64
         );
     }
 
+    if !resolved_positions.is_empty() {
+        let won = resolved_positions.iter().filter(|p| p.mark_price == Some(1.0)).count();
+        println!("\n{}", "Resolved Positions:".bold());
+        println!(
+            "  Count: {} ({} won, {} lost)\n",
+            resolved_positions.len(),
+            won,
+            resolved_positions.len() - won
+        );
+
+        for (i, pos) in resolved_positions.iter().take(5).enumerate() {
+            let pnl_str = if pos.pnl >= 0.0 {
+                format!("+${:.2}", pos.pnl).green()
+            } else {
+                format!("-${:.2}", pos.pnl.abs()).red()
+            };
+            let market_label = pos.market.chars().take(50).collect::<String>();
+            println!("  {}. {}", i + 1, market_label);
+            println!(
+                "     Outcome: {} | Settled: ${:.0} | P&L: {}",
+                pos.outcome.clone(),
+                pos.mark_price.unwrap_or(0.0),
+                pnl_str
+            );
+        }
+
+        if resolved_positions.len() > 5 {
+            println!(
+                "{}",
+                format!("\n  ... and {} more resolved positions", resolved_positions.len() - 5)
+                    .bright_black()
+            );
+        }
+    }
+
     if !closed_positions.is_empty() {
         println!("\n{}", "Closed Positions:".bold());
         println!("  Count: {}\n", closed_positions.len());
@@ -606,18 +736,59 @@ fn print_report(result: &SimulationResult, copy_percentage: f64, multiplier: f64
     println!("\n{}\n", "═".repeat(80).cyan());
 }
 
-#[tokio::main]
-async fn main() -> Result<()> {
+/// Settings of one simulation run
+pub struct SimSettings {
+    pub trader_address: String,
+    pub history_days: i32,
+    /// Percent of each trader order to copy
+    pub copy_percentage: f64,
+    pub multiplier: f64,
+    pub min_order_size: f64,
+    pub max_trades: usize,
+    /// "As of" time for backtests
+    pub valuation_ts: i64,
+    /// Suffix for the result file name
+    pub tag: Option<String>,
+}
+
+impl SimSettings {
+    /// Settings from the SIM_* variables (plus COPY_PERCENTAGE / TRADE_MULTIPLIER)
+    pub fn from_env() -> Self {
+        Self {
+            trader_address: env::var("SIM_TRADER_ADDRESS")
+                .unwrap_or_else(|_| DEFAULT_TRADER_ADDRESS.to_string())
+                .to_lowercase(),
+            history_days: get_env_var_int_or_default("SIM_HISTORY_DAYS", 7),
+            copy_percentage: get_env_var_or_default("COPY_PERCENTAGE", 10.0),
+            multiplier: get_env_var_or_default("TRADE_MULTIPLIER", 1.0),
+            min_order_size: get_env_var_or_default("SIM_MIN_ORDER_USD", 1.0),
+            max_trades: get_env_var_int_or_default("SIM_MAX_TRADES", 5000) as usize,
+            // Defaults to now
+            valuation_ts: env::var("SIM_MARK_TIMESTAMP")
+                .ok()
+                .and_then(|s| s.parse::<i64>().ok())
+                .filter(|&ts| ts > 0)
+                .unwrap_or_else(|| chrono::Utc::now().timestamp()),
+            tag: env::var("SIM_RESULT_TAG").ok(),
+        }
+    }
+}
+
+pub async fn run() -> Result<()> {
+    run_with(&SimSettings::from_env()).await?;
+    Ok(())
+}
+
+/// Run one simulation and save it under simulation_results/; returns the result file
+pub async fn run_with(settings: &SimSettings) -> Result<PathBuf> {
     println!("{}", "\n🚀 POLYMARKET COPY TRADING PROFITABILITY SIMULATOR (FIXED)\n".cyan());
 
-    let trader_address = env::var("SIM_TRADER_ADDRESS")
-        .unwrap_or_else(|_| DEFAULT_TRADER_ADDRESS.to_string())
-        .to_lowercase();
-    let history_days = get_env_var_int_or_default("SIM_HISTORY_DAYS", 7);
-    let copy_percentage = get_env_var_or_default("COPY_PERCENTAGE", 10.0);
-    let multiplier = get_env_var_or_default("TRADE_MULTIPLIER", 1.0);
-    let min_order_size = get_env_var_or_default("SIM_MIN_ORDER_USD", 1.0);
-    let max_trades_limit = get_env_var_int_or_default("SIM_MAX_TRADES", 5000) as usize;
+    let trader_address = &settings.trader_address;
+    let history_days = settings.history_days;
+    let copy_percentage = settings.copy_percentage;
+    let multiplier = settings.multiplier;
+    let max_trades_limit = settings.max_trades;
+    let valuation_ts = settings.valuation_ts;
 
     println!("{}", format!("Trader: {}", trader_address).bright_black());
     println!("{}", format!("Starting Capital: ${:.2}", STARTING_CAPITAL).bright_black());
@@ -641,17 +812,10 @@ async fn main() -> Result<()> {
 
     let env = load_env()?;
 
-    let trades = fetch_trader_activity(&trader_address, history_days, max_trades_limit, &env).await?;
-    let result = simulate_copy_trading(
-        trades,
-        &trader_address,
-        copy_percentage,
-        multiplier,
-        min_order_size,
-        &env,
-    )
-    .await?;
-    print_report(&result, copy_percentage, multiplier);
+    let mut trades = fetch_trader_activity(trader_address, history_days, max_trades_limit, &env).await?;
+    trades.retain(|t| t.timestamp <= valuation_ts);
+    let result = simulate_copy_trading(trades, settings, &env).await?;
+    print_report(&result, settings);
 
     // Save to JSON file
     let results_dir = Path::new("simulation_results");
@@ -659,7 +823,9 @@ async fn main() -> Result<()> {
         fs::create_dir_all(results_dir)?;
     }
 
-    let tag = env::var("SIM_RESULT_TAG")
+    let tag = settings
+        .tag
+        .as_ref()
         .map(|t| format!("_{}", t.trim().replace(|c: char| !c.is_alphanumeric() && c != '-' && c != '_', "-")))
         .unwrap_or_default();
     let filename = format!(
@@ -676,5 +842,5 @@ async fn main() -> Result<()> {
     println!("{}", format!("✓ Results saved to: {}\n", filepath.display()).green());
 
     println!("{}", "✓ Simulation completed successfully!\n".green());
-    Ok(())
+    Ok(filepath)
 }
@@ -4,11 +4,12 @@
 use anyhow::Result;
 use colored::*;
 use polymarket_copy_trading_bot_rust::config::load_env;
-use polymarket_copy_trading_bot_rust::utils::fetch_data;
+use polymarket_copy_trading_bot_rust::utils::{ActivityQuery, DataApiClient};
 use std::collections::HashMap;
 use std::env;
 use std::fs;
 use std::path::Path;
+use crate::common::{fetch_positions, Position};
 
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 struct Trade {
@@ -24,27 +25,6 @@ struct Trade {
     outcome: Option<String>,
 }
 
-#[derive(Debug, serde::Serialize, serde::Deserialize)]
-struct Position {
-    #[serde(rename = "conditionId")]
-    condition_id: Option<String>,
-    market: Option<String>,
-    outcome: Option<String>,
-    #[serde(rename = "outcomeIndex")]
-    outcome_index: Option<i32>,
-    asset: String,
-    size: f64,
-    cost: Option<f64>,
-    #[serde(rename = "avgEntryPrice")]
-    avg_entry_price: Option<f64>,
-    #[serde(rename = "currentValue")]
-    current_value: f64,
-    #[serde(rename = "realizedPnl")]
-    realized_pnl: Option<f64>,
-    #[serde(rename = "unrealizedPnl")]
-    unrealized_pnl: Option<f64>,
-}
-
 #[derive(Debug, serde::Serialize, serde::Deserialize)]
 struct SimulatedPosition {
     market: String,
@@ -130,29 +110,6 @@ fn get_env_var_int_or_default(key: &str, default: i32) -> i32 {
         .unwrap_or(default)
 }
 
-async fn fetch_batch(
-    trader_address: &str,
-    offset: usize,
-    limit: usize,
-    since_timestamp: i64,
-    env: &polymarket_copy_trading_bot_rust::config::Env,
-) -> Result<Vec<Trade>> {
-    let url = format!(
-        "https://data-api.polymarket.com/activity?user={}&type=TRADE&limit={}&offset={}",
-        trader_address, limit, offset
-    );
-    let json: serde_json::Value = fetch_data(&url, env).await?;
-    let trades: Vec<Trade> = json
-        .as_array()
-        .cloned()
-        .unwrap_or_default()
-        .into_iter()
-        .filter_map(|v| serde_json::from_value(v).ok())
-        .filter(|t: &Trade| t.timestamp >= since_timestamp)
-        .collect();
-    Ok(trades)
-}
-
 async fn fetch_trader_activity(
     trader_address: &str,
     history_days: i32,
@@ -177,7 +134,7 @@ async fn fetch_trader_activity(
     println!(
         "{}",
         format!(
-            "📊 Fetching trader activity from last {} days (with parallel requests)...",
+            "📊 Fetching trader activity from last {} days...",
             history_days
         )
         .cyan()
@@ -186,62 +143,31 @@ async fn fetch_trader_activity(
     let since_timestamp =
         (chrono::Utc::now().timestamp() - (history_days as i64 * 24 * 60 * 60)) as i64;
 
-    let first_batch = fetch_batch(trader_address, 0, 100, since_timestamp, env).await?;
-    let mut all_trades = first_batch.clone();
-
-    if first_batch.len() == 100 {
-        let batch_size = 100;
-        let max_parallel = 5;
-        let mut offset = 100;
-        let mut has_more = true;
-
-        while has_more && all_trades.len() < max_trades_limit {
-            let mut promises = Vec::new();
-            for i in 0..max_parallel {
-                let addr = trader_address.to_string();
-                let env_clone = env.clone();
-                let offset_val = offset + i * batch_size;
-                promises.push(tokio::spawn(async move {
-                    fetch_batch(&addr, offset_val, batch_size, since_timestamp, &env_clone).await
-                }));
-            }
-
-            let mut added_count = 0;
-            for result in futures_util::future::join_all(promises).await {
-                match result {
-                    Ok(Ok(batch)) => {
-                        if !batch.is_empty() {
-                            all_trades.extend(batch.clone());
-                            added_count += batch.len();
-                            if batch.len() < batch_size {
-                                has_more = false;
-                            }
-                        }
-                    }
-                    _ => {}
-                }
-            }
-
-            if added_count == 0 {
-                has_more = false;
-            }
-
-            if all_trades.len() >= max_trades_limit {
-                println!(
-                    "{}",
-                    format!(
-                        "⚠️  Reached trade limit ({}), stopping fetch...",
-                        max_trades_limit
-                    )
-                    .yellow()
-                );
-                all_trades.truncate(max_trades_limit);
-                has_more = false;
-            }
-
-            offset += max_parallel * batch_size;
-            println!("{}", format!("  Fetched {} trades so far...", all_trades.len()).bright_black());
-        }
+    let query = ActivityQuery::new(trader_address)
+        .kind("TRADE")
+        .start(since_timestamp)
+        .limit(max_trades_limit);
+    let mut all_trades: Vec<Trade> = DataApiClient::new(env)
+        .activity(&query)
+        .await?
+        .into_iter()
+        .map(|activity| Trade {
+            id: activity.transaction_hash,
+            timestamp: activity.timestamp,
+            market: activity.market,
+            asset: activity.asset,
+            side: activity.side,
+            price: activity.price,
+            usdc_size: activity.usdc_size,
+            size: activity.size,
+            outcome: activity.outcome,
+        })
+        .collect();
+    if all_trades.len() >= max_trades_limit {
+        println!(
+            "{}",
+            format!("⚠️  Reached trade limit ({}), stopping fetch...", max_trades_limit).yellow()
+        );
     }
 
     all_trades.sort_by_key(|t| t.timestamp);
@@ -273,25 +199,7 @@ async fn fetch_trader_positions(
     env: &polymarket_copy_trading_bot_rust::config::Env,
 ) -> Result<Vec<Position>> {
     println!("{}", "📈 Fetching trader positions...".cyan());
-    let url = format!("https://data-api.polymarket.com/positions?user={}", trader_address);
-    let json: serde_json::Value = fetch_data(&url, env).await?;
-    
-    // Handle both array and object responses
-    let positions: Vec<Position> = if json.is_array() {
-        serde_json::from_value(json)?
-    } else {
-        // If it's an object, try to extract positions array
-        json.get("positions")
-            .or_else(|| json.get("data"))
-            .and_then(|v| v.as_array())
-            .map(|arr| {
-                arr.iter()
-                    .filter_map(|v| serde_json::from_value::<Position>(v.clone()).ok())
-                    .collect()
-            })
-            .unwrap_or_default()
-    };
-    
+    let positions = fetch_positions(trader_address, env).await?;
     println!("{}", format!("✓ Fetched {} positions", positions.len()).green());
     Ok(positions)
 }
@@ -611,8 +519,7 @@ fn print_report(result: &SimulationResult, multiplier: f64) {
     println!("\n{}\n", "═".repeat(80).cyan());
 }
 
-#[tokio::main]
-async fn main() -> Result<()> {
+pub async fn run() -> Result<()> {
     println!("{}", "\n🚀 POLYMARKET COPY TRADING PROFITABILITY SIMULATOR (OLD LOGIC)\n".cyan());
 
     let trader_address = env::var("SIM_TRADER_ADDRESS")
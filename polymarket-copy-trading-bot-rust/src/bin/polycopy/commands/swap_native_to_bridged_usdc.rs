@@ -0,0 +1,188 @@
+//! Swap native USDC to bridged USDC.e
+#![allow(dead_code)] // Struct fields used for JSON deserialization
+
+use anyhow::Result;
+use alloy::primitives::{Address, U256};
+use alloy::providers::{Provider, ProviderBuilder};
+use alloy::signers::local::PrivateKeySigner;
+use alloy::sol;
+use alloy::sol_types::SolCall;
+use polymarket_copy_trading_bot_rust::config::load_env;
+use polymarket_copy_trading_bot_rust::utils::gnosis_safe::{erc20_approve, to_base_units, SafeCall, SafeExecutor};
+
+use std::str::FromStr;
+
+const NATIVE_USDC_ADDRESS: &str = "0x3c499c542cEF5E3811e1192ce70d8cC03d5c3359";
+const BRIDGED_USDC_ADDRESS: &str = "0x2791Bca1f2de4661ED88A30C99A7a9449Aa84174";
+const QUICKSWAP_ROUTER: &str = "0xa5E0829CaCEd8fFDD4De3c43696c57F7D7A678ff";
+pub const DEFAULT_SLIPPAGE_PERCENT: f64 = 0.5;
+const DEADLINE_SECS: i64 = 300;
+
+sol! {
+    #[sol(rpc)]
+    interface IQuickSwapRouter {
+        function getAmountsOut(uint256 amountIn, address[] calldata path) external view returns (uint256[] memory amounts);
+        function swapExactTokensForTokens(uint256 amountIn, uint256 amountOutMin, address[] calldata path, address to, uint256 deadline) external returns (uint256[] memory amounts);
+    }
+
+    #[sol(rpc)]
+    interface IERC20Token {
+        function balanceOf(address owner) external view returns (uint256);
+        function allowance(address owner, address spender) external view returns (uint256);
+        function approve(address spender, uint256 amount) external returns (bool);
+    }
+}
+
+pub struct Options {
+    /// Native USDC to swap (default: full balance)
+    pub amount: Option<f64>,
+    /// Max loss vs 1:1 before aborting
+    pub slippage_percent: f64,
+    /// Swap from the signer EOA instead of the proxy wallet
+    pub from_eoa: bool,
+    /// Only quote the swap
+    pub dry_run: bool,
+}
+
+fn format_usdc(amount: U256) -> String {
+    format!("${:.6}", amount.saturating_to::<u128>() as f64 / 1_000_000.0)
+}
+
+pub async fn run(options: Options) -> Result<()> {
+    println!("🔄 Swapping Native USDC to Bridged USDC.e");
+    println!("════════════════════════════════════════════════════\n");
+
+    let env = load_env()?;
+    let private_key = if env.private_key.starts_with("0x") {
+        env.private_key.clone()
+    } else {
+        format!("0x{}", env.private_key)
+    };
+    let signer = PrivateKeySigner::from_str(&private_key)?;
+    let eoa = signer.address();
+    let native = Address::from_str(NATIVE_USDC_ADDRESS)?;
+    let bridged = Address::from_str(BRIDGED_USDC_ADDRESS)?;
+    let router_address = Address::from_str(QUICKSWAP_ROUTER)?;
+
+    let provider = ProviderBuilder::new()
+        .wallet(signer.clone())
+        .connect_http(env.rpc_url.parse()?);
+    let router = IQuickSwapRouter::new(router_address, &provider);
+    let native_token = IERC20Token::new(native, &provider);
+    let bridged_token = IERC20Token::new(bridged, &provider);
+
+    // Which wallet swaps: the EOA itself, or the proxy (directly if it is the EOA, else via the Safe)
+    let proxy = Address::from_str(&env.proxy_wallet)?;
+    let executor = if options.from_eoa || proxy == eoa {
+        None
+    } else {
+        let executor = SafeExecutor::from_env(&env)?;
+        if let Err(e) = executor.check_can_execute().await {
+            println!("❌ Cannot swap from proxy wallet: {}", e);
+            println!("   Swap from the signer instead with --from eoa\n");
+            return Ok(());
+        }
+        Some(executor)
+    };
+    let wallet = executor.as_ref().map(|e| e.safe()).unwrap_or(eoa);
+
+    println!("📍 Wallet: {}{}", wallet, if executor.is_some() { " (Safe)" } else { " (EOA)" });
+    println!("📍 Native USDC: {}", NATIVE_USDC_ADDRESS);
+    println!("📍 Bridged USDC.e: {}", BRIDGED_USDC_ADDRESS);
+    println!("📍 QuickSwap Router: {}\n", QUICKSWAP_ROUTER);
+
+    let native_before = native_token.balanceOf(wallet).call().await?;
+    let bridged_before = bridged_token.balanceOf(wallet).call().await?;
+    println!("💰 Native USDC: {}", format_usdc(native_before));
+    println!("💰 USDC.e:      {}\n", format_usdc(bridged_before));
+
+    let amount_in = options.amount.map(to_base_units).unwrap_or(native_before);
+    if amount_in.is_zero() {
+        println!("ℹ️  No native USDC to swap\n");
+        return Ok(());
+    }
+    if amount_in > native_before {
+        println!("❌ Cannot swap {} (balance {})\n", format_usdc(amount_in), format_usdc(native_before));
+        return Ok(());
+    }
+
+    // Quote, and bound the output against 1:1 since both sides are USDC
+    let path = vec![native, bridged];
+    let amounts = router.getAmountsOut(amount_in, path.clone()).call().await?;
+    let quoted_out = amounts.last().copied().unwrap_or_default();
+    let bps = U256::from((options.slippage_percent * 100.0).round() as u64);
+    let min_out = amount_in * (U256::from(10_000) - bps) / U256::from(10_000);
+
+    println!("📊 Quote: {} native USDC → {} USDC.e", format_usdc(amount_in), format_usdc(quoted_out));
+    println!("   Minimum out ({}% slippage): {}", options.slippage_percent, format_usdc(min_out));
+    if quoted_out < min_out {
+        println!("❌ Quote is below the minimum - aborting (raise --slippage to accept)\n");
+        return Ok(());
+    }
+    if options.dry_run {
+        println!("\n🧪 Dry run - no transactions sent\n");
+        return Ok(());
+    }
+
+    let deadline = U256::from(chrono::Utc::now().timestamp() + DEADLINE_SECS);
+    let swap = IQuickSwapRouter::swapExactTokensForTokensCall {
+        amountIn: amount_in,
+        amountOutMin: min_out,
+        path,
+        to: wallet,
+        deadline,
+    };
+
+    let needs_approval = native_token.allowance(wallet, router_address).call().await? < amount_in;
+    match &executor {
+        Some(executor) => {
+            let mut calls = Vec::new();
+            if needs_approval {
+                calls.push(erc20_approve(NATIVE_USDC_ADDRESS, router_address, amount_in)?);
+            }
+            calls.push(SafeCall::new(router_address, swap.abi_encode()));
+            println!("\n📤 Executing {} through the Safe...", if needs_approval { "approve + swap" } else { "swap" });
+            let tx_hash = executor.execute(&calls).await?;
+            println!("✅ Swap confirmed: https://polygonscan.com/tx/0x{:x}", tx_hash);
+        }
+        None => {
+            if needs_approval {
+                println!("\n📤 Approving router...");
+                let receipt = native_token
+                    .approve(router_address, amount_in)
+                    .send()
+                    .await?
+                    .get_receipt()
+                    .await?;
+                if !receipt.status() {
+                    anyhow::bail!("Approval 0x{:x} reverted", receipt.transaction_hash);
+                }
+            }
+            println!("📤 Swapping...");
+            let receipt = provider
+                .send_transaction(
+                    alloy::rpc::types::TransactionRequest::default()
+                        .to(router_address)
+                        .input(swap.abi_encode().into()),
+                )
+                .await?
+                .get_receipt()
+                .await?;
+            if !receipt.status() {
+                anyhow::bail!("Swap 0x{:x} reverted", receipt.transaction_hash);
+            }
+            println!("✅ Swap confirmed: https://polygonscan.com/tx/0x{:x}", receipt.transaction_hash);
+        }
+    }
+
+    let native_after = native_token.balanceOf(wallet).call().await?;
+    let bridged_after = bridged_token.balanceOf(wallet).call().await?;
+    println!("\n💰 Native USDC: {} → {}", format_usdc(native_before), format_usdc(native_after));
+    println!("💰 USDC.e:      {} → {}", format_usdc(bridged_before), format_usdc(bridged_after));
+    println!(
+        "   Received {} USDC.e\n",
+        format_usdc(bridged_after.saturating_sub(bridged_before))
+    );
+
+    Ok(())
+}
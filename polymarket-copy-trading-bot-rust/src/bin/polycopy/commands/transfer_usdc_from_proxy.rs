@@ -3,28 +3,14 @@
 
 use anyhow::Result;
 use polymarket_copy_trading_bot_rust::config::load_env;
+use polymarket_copy_trading_bot_rust::utils::get_my_balance;
+use polymarket_copy_trading_bot_rust::utils::gnosis_safe::{erc20_transfer, to_base_units, SafeExecutor};
 use alloy::signers::local::PrivateKeySigner;
 
 use std::str::FromStr;
+use crate::common::is_contract_address;
 
-async fn is_contract_address(rpc_url: &str, address: &str) -> Result<bool> {
-    let body = serde_json::json!({
-        "jsonrpc": "2.0",
-        "method": "eth_getCode",
-        "params": [address, "latest"],
-        "id": 1
-    });
-    let client = reqwest::Client::new();
-    let resp = client.post(rpc_url).json(&body).send().await?;
-    let json: serde_json::Value = resp.json().await?;
-    let result = json.get("result").and_then(|v| v.as_str())
-        .ok_or_else(|| anyhow::anyhow!("No result in RPC response"))?;
-    let code = result.trim_start_matches("0x");
-    Ok(!code.is_empty() && code.chars().any(|c| c != '0'))
-}
-
-#[tokio::main]
-async fn main() -> Result<()> {
+pub async fn run(amount: Option<f64>) -> Result<()> {
     println!("💸 Transferring USDC from Proxy Wallet to Private Key Wallet");
     println!("════════════════════════════════════════════════════\n");
 
@@ -46,15 +32,28 @@ async fn main() -> Result<()> {
     let signer_is_proxy = signer_address.to_lowercase() == env.proxy_wallet.to_lowercase();
 
     if is_contract && !signer_is_proxy {
-        println!("⚠️  WARNING: Proxy wallet is a contract (likely Gnosis Safe)");
-        println!("   You cannot directly transfer from a contract wallet using this script.\n");
-        println!("   📋 SOLUTION: Use Safe web interface\n");
-        println!("   1. Go to https://app.safe.global/");
-        println!("   2. Connect and find your Safe: {}", env.proxy_wallet);
-        println!("   3. Go to \"Send\" tab");
-        println!("   4. Select USDC token");
-        println!("   5. Enter recipient: {}", signer_address);
-        println!("   6. Enter amount and execute transaction\n");
+        println!("ℹ️  Proxy wallet is a contract (Gnosis Safe) - executing through the Safe\n");
+
+        let balance = get_my_balance(&env.proxy_wallet, &env).await?;
+        let amount = amount.unwrap_or(balance);
+        println!("💰 Proxy USDC balance: ${:.2}", balance);
+        if amount <= 0.0 || amount > balance {
+            println!("❌ Cannot transfer ${:.2} (balance ${:.2})\n", amount, balance);
+            return Ok(());
+        }
+
+        let executor = SafeExecutor::from_env(&env)?;
+        if let Err(e) = executor.check_can_execute().await {
+            println!("❌ {}", e);
+            println!("   Use the Safe web interface instead: https://app.safe.global/\n");
+            return Ok(());
+        }
+
+        println!("📤 Transferring ${:.2} USDC to {}...", amount, signer_address);
+        let call = erc20_transfer(&env.usdc_contract_address, signer.address(), to_base_units(amount))?;
+        let tx_hash = executor.execute(&[call]).await?;
+        println!("✅ Transfer confirmed");
+        println!("   https://polygonscan.com/tx/0x{:x}\n", tx_hash);
         return Ok(());
     }
 
@@ -68,14 +67,14 @@ async fn main() -> Result<()> {
     println!("   1. Building an ERC20 transfer transaction");
     println!("   2. Signing it with your private key");
     println!("   3. Sending it to the blockchain");
-    println!("\n   For contract wallets (Gnosis Safe), use the Safe web interface.");
+    println!("\n   For contract wallets (Gnosis Safe), this script executes through the Safe.");
     println!("   For EOA wallets, ensure the signer has permission to transfer from proxy.\n");
 
     println!("📋 Current Configuration:");
     println!("   Proxy Wallet: {}", env.proxy_wallet);
     println!("   Signer Address: {}", signer_address);
     println!("   USDC Contract: {}", env.usdc_contract_address);
-    println!("\n   To check balances, use: cargo run --bin check_my_stats\n");
+    println!("\n   To check balances, use: polycopy positions stats\n");
 
     Ok(())
 }
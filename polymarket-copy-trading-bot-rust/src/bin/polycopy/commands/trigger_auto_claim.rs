@@ -5,8 +5,7 @@ use anyhow::Result;
 use polymarket_copy_trading_bot_rust::config::load_env;
 use polymarket_copy_trading_bot_rust::services::auto_claim::trigger_auto_claim;
 
-#[tokio::main]
-async fn main() -> Result<()> {
+pub async fn run() -> Result<()> {
     println!("🚀 Manually triggering auto-claim check...\n");
     
     let env = load_env()?;
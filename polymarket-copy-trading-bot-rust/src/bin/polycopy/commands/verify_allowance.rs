@@ -0,0 +1,39 @@
+//! Verify USDC allowance and CTF approval status (exit code 1 if anything is missing)
+
+use anyhow::Result;
+use polymarket_copy_trading_bot_rust::config::load_env;
+use polymarket_copy_trading_bot_rust::utils::allowances::{check_allowances, log_allowance_report};
+
+use crate::output::print_json;
+
+pub async fn run(json: bool) -> Result<()> {
+    let env = load_env()?;
+    if json {
+        let report = check_allowances(&env).await?;
+        print_json(&report)?;
+        std::process::exit(if report.is_complete() { 0 } else { 1 });
+    }
+
+    println!("🔍 Verifying allowance status...\n");
+    let report = check_allowances(&env).await?;
+    log_allowance_report(&report);
+    println!();
+
+    if report.is_complete() {
+        println!("✅ SUCCESS: Allowances are properly set!");
+        println!("   You can start trading now.");
+        println!("\n🚀 Start the bot:");
+        println!("   cargo run --release");
+        std::process::exit(0);
+    }
+
+    println!("❌ PROBLEM: Some allowances are NOT set!");
+    for (label, _) in report.missing_calls(&env)? {
+        println!("   • {}", label);
+    }
+    println!("\n📝 TO FIX: Run the following command:");
+    println!("   polycopy wallet approve");
+    println!("\nOR wait for your pending transaction to confirm:");
+    println!("   https://polygonscan.com/address/{}", env.proxy_wallet);
+    std::process::exit(1);
+}
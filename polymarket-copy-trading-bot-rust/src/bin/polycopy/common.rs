@@ -0,0 +1,100 @@
+//! Types and helpers shared by polycopy commands
+
+use anyhow::Result;
+use mongodb::Database;
+use polymarket_copy_trading_bot_rust::config::{connect_db, get_account_database, Env, PRIMARY_ACCOUNT};
+use polymarket_copy_trading_bot_rust::utils::{ActivityQuery, DataApiClient, PositionsQuery};
+
+pub use polymarket_copy_trading_bot_rust::utils::data_api::{Activity, Position};
+
+pub async fn fetch_positions(address: &str, env: &Env) -> Result<Vec<Position>> {
+    DataApiClient::new(env).positions(&PositionsQuery::new(address)).await
+}
+
+/// Activities of `kind` (e.g. "TRADE"), newest first
+pub async fn fetch_activities(address: &str, kind: &str, env: &Env) -> Result<Vec<Activity>> {
+    DataApiClient::new(env).activity(&ActivityQuery::new(address).kind(kind)).await
+}
+
+pub async fn is_contract_address(rpc_url: &str, address: &str) -> Result<bool> {
+    let addr_trimmed = address.trim().trim_start_matches("0x");
+    let body = serde_json::json!({
+        "jsonrpc": "2.0",
+        "method": "eth_getCode",
+        "params": [format!("0x{}", addr_trimmed), "latest"],
+        "id": 1
+    });
+    let client = reqwest::Client::new();
+    let resp = client
+        .post(rpc_url)
+        .json(&body)
+        .timeout(std::time::Duration::from_secs(10))
+        .send()
+        .await?;
+    let json: serde_json::Value = resp.json().await?;
+    let result = json.get("result").and_then(|v| v.as_str())
+        .ok_or_else(|| anyhow::anyhow!("No result in RPC response"))?;
+    let code = result.trim_start_matches("0x");
+    Ok(!code.is_empty() && code.chars().any(|c| c != '0'))
+}
+
+/// Positive integer from the environment, else `default`
+pub fn get_env_usize(key: &str, default: usize) -> usize {
+    std::env::var(key)
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .filter(|&v| v > 0)
+        .unwrap_or(default)
+}
+
+pub fn get_env_f64(key: &str, default: f64) -> f64 {
+    std::env::var(key)
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(default)
+}
+
+/// Which execution accounts a command covers (`--account` / `--all-accounts`)
+pub enum AccountSelection {
+    /// One account by name; `primary` is the main wallet
+    One(String),
+    All,
+}
+
+/// One execution account a command works on: its settings and database
+pub struct AccountContext {
+    pub name: String,
+    pub env: Env,
+    pub db: Database,
+}
+
+/// Settings and database of each selected account. Secondary accounts keep
+/// their ledger, paper account and breaker state in `<db>_<name>`.
+pub async fn select_accounts(env: &Env, selection: &AccountSelection) -> Result<Vec<AccountContext>> {
+    let accounts = env.execution_accounts();
+    let selected: Vec<_> = match selection {
+        AccountSelection::All => accounts,
+        AccountSelection::One(name) => match accounts.iter().find(|a| a.name.eq_ignore_ascii_case(name)) {
+            Some(account) => vec![account.clone()],
+            None => anyhow::bail!(
+                "Unknown account \"{}\" (configured: {})",
+                name,
+                accounts.iter().map(|a| a.name.as_str()).collect::<Vec<_>>().join(", ")
+            ),
+        },
+    };
+
+    let db = connect_db(&env.mongo_uri).await?;
+    Ok(selected
+        .into_iter()
+        .map(|account| AccountContext {
+            db: if account.name == PRIMARY_ACCOUNT {
+                db.clone()
+            } else {
+                get_account_database(&db, &account.name)
+            },
+            env: env.for_account(&account),
+            name: account.name,
+        })
+        .collect())
+}
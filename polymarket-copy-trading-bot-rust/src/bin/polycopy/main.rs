@@ -0,0 +1,430 @@
+//! polycopy - operator CLI for the copy trading bot
+//!
+//! Wallet, position, trader research and simulation tools grouped under one
+//! binary. The old standalone binary names still work as aliases
+//! (`polycopy check_allowance` == `polycopy wallet allowance`).
+
+mod aliases;
+mod commands;
+mod common;
+mod output;
+
+use anyhow::Result;
+use clap::{Args, Parser, Subcommand, ValueEnum};
+use polymarket_copy_trading_bot_rust::config::PRIMARY_ACCOUNT;
+
+use commands::*;
+use output::require_text_output;
+
+#[derive(Parser)]
+#[command(name = "polycopy", version, about = "Polymarket copy trading bot tools")]
+struct Cli {
+    /// Print machine-readable JSON (some commands only, see --help)
+    ///
+    /// Supported by wallet allowance, wallet verify-allowance, positions list,
+    /// positions pnl, positions reconcile, sim paper, health check and health
+    /// breaker. Every other command rejects it.
+    #[arg(long, global = true)]
+    json: bool,
+
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Interactive configuration wizard
+    Setup,
+    /// Wallet, balance and allowance tools
+    #[command(subcommand)]
+    Wallet(WalletCommand),
+    /// Positions, activity and PnL
+    #[command(subcommand)]
+    Positions(PositionsCommand),
+    /// Sell or close positions
+    #[command(subcommand)]
+    Sell(SellCommand),
+    /// Redeem resolved positions
+    #[command(subcommand)]
+    Redeem(RedeemCommand),
+    /// Find and analyze traders to copy
+    #[command(subcommand)]
+    Traders(TradersCommand),
+    /// Backtests and simulation results
+    #[command(subcommand)]
+    Sim(SimCommand),
+    /// Health check and circuit breaker
+    #[command(subcommand)]
+    Health(HealthCommand),
+}
+
+#[derive(Subcommand)]
+enum WalletCommand {
+    /// Proxy wallet balance and positions
+    Proxy,
+    /// Compare the proxy wallet with the EOA or another address
+    Compare {
+        /// Address to compare against (default: the signer EOA)
+        address: Option<String>,
+    },
+    /// EOA address of the configured private key
+    Eoa,
+    /// Find the real proxy wallet of the EOA
+    FindProxy,
+    /// Find the Gnosis Safe proxy of the EOA
+    FindSafe,
+    /// Compute the Gnosis Safe address of the EOA
+    ComputeSafe,
+    /// Transfer USDC from the proxy wallet to the EOA
+    Transfer {
+        /// USDC to transfer (default: full balance)
+        amount: Option<f64>,
+    },
+    /// Swap native USDC to USDC.e
+    Swap(SwapArgs),
+    /// USDC allowance and CTF approval for every Polymarket contract
+    Allowance,
+    /// Exit non-zero unless all allowances are set
+    VerifyAllowance,
+    /// Set missing USDC and CTF approvals
+    Approve,
+}
+
+#[derive(Args)]
+struct SwapArgs {
+    /// Native USDC to swap (default: full balance)
+    amount: Option<f64>,
+    /// Max loss vs 1:1 in percent before aborting
+    #[arg(long, default_value_t = swap_native_to_bridged_usdc::DEFAULT_SLIPPAGE_PERCENT)]
+    slippage: f64,
+    /// Wallet holding the native USDC
+    #[arg(long, value_enum, default_value_t = SwapSource::Proxy)]
+    from: SwapSource,
+    /// Only quote the swap
+    #[arg(long)]
+    dry_run: bool,
+}
+
+#[derive(Clone, Copy, PartialEq, ValueEnum)]
+enum SwapSource {
+    Proxy,
+    Eoa,
+}
+
+#[derive(Subcommand)]
+enum PositionsCommand {
+    /// Trading statistics
+    Stats,
+    /// Open positions with details
+    List,
+    /// Recent trading activity
+    Activity,
+    /// Compare reported and computed PnL
+    PnlDiscrepancy,
+    /// PnL per copied trader, market and day
+    Pnl(PnlArgs),
+    /// Reconcile records vs Data API vs on-chain balances
+    Reconcile {
+        #[command(flatten)]
+        accounts: AccountArgs,
+    },
+}
+
+/// Execution accounts of commands that read the bot's records
+#[derive(Args)]
+struct AccountArgs {
+    /// Execution account by name (default: primary)
+    #[arg(long, global = true, conflicts_with = "all_accounts")]
+    account: Option<String>,
+    /// Every execution account
+    #[arg(long, global = true)]
+    all_accounts: bool,
+}
+
+impl AccountArgs {
+    /// The named account, all of them, or else `default_all` / the primary account
+    fn selection(self, default_all: bool) -> common::AccountSelection {
+        match self.account {
+            Some(name) => common::AccountSelection::One(name),
+            None if self.all_accounts || default_all => common::AccountSelection::All,
+            None => common::AccountSelection::One(PRIMARY_ACCOUNT.to_string()),
+        }
+    }
+}
+
+#[derive(Args)]
+struct PnlArgs {
+    #[arg(long, value_enum, default_value_t = PnlFormat::Table)]
+    format: PnlFormat,
+    /// Group by one dimension (default: all)
+    #[arg(long, value_enum)]
+    by: Option<PnlGroupBy>,
+    /// Only the last N days
+    #[arg(long)]
+    days: Option<i64>,
+    /// Skip importing redemptions from the Data API
+    #[arg(long)]
+    no_sync: bool,
+    /// Report simulated (preview and paper) fills instead of live ones
+    #[arg(long)]
+    simulated: bool,
+    #[command(flatten)]
+    accounts: AccountArgs,
+}
+
+#[derive(Clone, Copy, PartialEq, ValueEnum)]
+enum PnlFormat {
+    Table,
+    Csv,
+    Json,
+}
+
+#[derive(Clone, Copy, PartialEq, ValueEnum)]
+enum PnlGroupBy {
+    Trader,
+    Market,
+    Day,
+}
+
+#[derive(Subcommand)]
+enum SellCommand {
+    /// Sell a specific position
+    Manual,
+    /// Sell down oversized positions
+    Large,
+    /// Close stale positions
+    Stale,
+    /// Close positions in resolved markets
+    Resolved,
+}
+
+#[derive(Subcommand)]
+enum RedeemCommand {
+    /// Redeem resolved positions for USDC
+    Resolved,
+    /// Run the auto-claim check once
+    AutoClaim,
+    /// Auto-claim configuration diagnostics
+    CheckAutoClaim,
+}
+
+#[derive(Subcommand)]
+enum TradersCommand {
+    /// Find the best performing traders
+    Best,
+    /// Find low-risk traders
+    LowRisk,
+    /// Scan and analyze top traders
+    Scan,
+    /// Scan traders from popular markets
+    ScanMarkets,
+    /// Fetch historical trade data
+    FetchHistory,
+}
+
+#[derive(Subcommand)]
+enum SimCommand {
+    /// Simulate profitability with the current logic
+    Run,
+    /// Simulate with the old algorithm
+    RunOld,
+    /// Batch simulations (presets, custom runs, parameter sweeps)
+    Batch {
+        /// Passed through, e.g. `standard` or `sweep random --samples 300`
+        #[arg(trailing_var_arg = true, allow_hyphen_values = true)]
+        args: Vec<String>,
+    },
+    /// Audit the copy trading algorithm
+    Audit,
+    /// Audit the fixed copy trading algorithm
+    AuditFixed,
+    /// Aggregate simulation results
+    Aggregate,
+    /// Paper trading account: balance, equity and open positions
+    Paper {
+        /// Start over from PAPER_STARTING_BALANCE_USD (clears paper positions and simulated fills)
+        #[arg(long)]
+        reset: bool,
+        #[command(flatten)]
+        accounts: AccountArgs,
+    },
+    /// Compare simulation results
+    Compare {
+        #[command(subcommand)]
+        view: Option<CompareCommand>,
+    },
+}
+
+#[derive(Subcommand)]
+enum CompareCommand {
+    /// Top N results
+    Best {
+        #[arg(default_value_t = 10)]
+        n: usize,
+    },
+    /// Bottom N results
+    Worst {
+        #[arg(default_value_t = 10)]
+        n: usize,
+    },
+    /// Aggregate statistics
+    Stats,
+    /// Details of the first result whose name contains QUERY
+    Detail { query: String },
+}
+
+#[derive(Subcommand)]
+enum HealthCommand {
+    /// Verify configuration, database, RPC and APIs
+    Check,
+    /// Circuit breaker status / resume / kill switch (kill covers every account by default)
+    Breaker {
+        #[command(subcommand)]
+        action: Option<BreakerCommand>,
+        #[command(flatten)]
+        accounts: AccountArgs,
+    },
+}
+
+#[derive(Subcommand)]
+enum BreakerCommand {
+    /// Show breaker state and equity
+    Status,
+    /// Clear a trip and resume buying
+    Resume,
+    /// Kill switch: pause all new buys
+    Kill { reason: Option<String> },
+}
+
+async fn run_wallet(command: WalletCommand, json: bool) -> Result<()> {
+    if !matches!(command, WalletCommand::Allowance | WalletCommand::VerifyAllowance) {
+        require_text_output(json, "wallet")?;
+    }
+    match command {
+        WalletCommand::Allowance => check_allowance::run(json).await,
+        WalletCommand::VerifyAllowance => verify_allowance::run(json).await,
+        WalletCommand::Proxy => check_proxy_wallet::run().await,
+        WalletCommand::Compare { address } => check_both_wallets::run(address).await,
+        WalletCommand::Eoa => find_my_eoa::run().await,
+        WalletCommand::FindProxy => find_real_proxy_wallet::run().await,
+        WalletCommand::FindSafe => find_gnosis_safe_proxy::run().await,
+        WalletCommand::ComputeSafe => compute_gnosis_safe_address::run().await,
+        WalletCommand::Transfer { amount } => transfer_usdc_from_proxy::run(amount).await,
+        WalletCommand::Swap(args) => {
+            swap_native_to_bridged_usdc::run(swap_native_to_bridged_usdc::Options {
+                amount: args.amount,
+                slippage_percent: args.slippage,
+                from_eoa: args.from == SwapSource::Eoa,
+                dry_run: args.dry_run,
+            })
+            .await
+        }
+        WalletCommand::Approve => set_token_allowance::run().await,
+    }
+}
+
+async fn run_positions(command: PositionsCommand, json: bool) -> Result<()> {
+    match command {
+        PositionsCommand::List => check_positions_detailed::run(json).await,
+        PositionsCommand::Reconcile { accounts } => reconcile_positions::run(accounts.selection(false), json).await,
+        PositionsCommand::Pnl(args) => {
+            let format = match (json, args.format) {
+                (true, _) | (_, PnlFormat::Json) => pnl_report::Format::Json,
+                (_, PnlFormat::Table) => pnl_report::Format::Table,
+                (_, PnlFormat::Csv) => pnl_report::Format::Csv,
+            };
+            let group_by = match args.by {
+                None => pnl_report::GroupBy::All,
+                Some(PnlGroupBy::Trader) => pnl_report::GroupBy::Trader,
+                Some(PnlGroupBy::Market) => pnl_report::GroupBy::Market,
+                Some(PnlGroupBy::Day) => pnl_report::GroupBy::Day,
+            };
+            pnl_report::run(pnl_report::Options {
+                format,
+                group_by,
+                days: args.days,
+                sync: !args.no_sync,
+                simulated: args.simulated,
+                accounts: args.accounts.selection(false),
+            })
+            .await
+        }
+        PositionsCommand::Stats => {
+            require_text_output(json, "positions stats")?;
+            check_my_stats::run().await
+        }
+        PositionsCommand::Activity => {
+            require_text_output(json, "positions activity")?;
+            check_recent_activity::run().await
+        }
+        PositionsCommand::PnlDiscrepancy => {
+            require_text_output(json, "positions pnl-discrepancy")?;
+            check_pnl_discrepancy::run().await
+        }
+    }
+}
+
+async fn run_sim(command: SimCommand, json: bool) -> Result<()> {
+    if !matches!(command, SimCommand::Paper { .. }) {
+        require_text_output(json, "sim")?;
+    }
+    match command {
+        SimCommand::Run => simulate_profitability::run().await,
+        SimCommand::RunOld => simulate_profitability_old_logic::run().await,
+        SimCommand::Batch { args } => run_simulations::run(args).await,
+        SimCommand::Audit => audit_copy_trading_algorithm::run().await,
+        SimCommand::AuditFixed => audit_copy_trading_algorithm_fixed::run().await,
+        SimCommand::Aggregate => aggregate_results::run().await,
+        SimCommand::Paper { reset, accounts } => paper_account::run(reset, accounts.selection(false), json).await,
+        SimCommand::Compare { view } => {
+            let view = match view {
+                None => compare_results::CompareView::All,
+                Some(CompareCommand::Best { n }) => compare_results::CompareView::Best(n),
+                Some(CompareCommand::Worst { n }) => compare_results::CompareView::Worst(n),
+                Some(CompareCommand::Stats) => compare_results::CompareView::Stats,
+                Some(CompareCommand::Detail { query }) => compare_results::CompareView::Detail(query),
+            };
+            compare_results::run(view).await
+        }
+    }
+}
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    let cli = Cli::parse_from(aliases::expand_legacy_args(std::env::args().collect()));
+    let json = cli.json;
+
+    // Wallet, positions, sim and health commands decide per subcommand
+    if !matches!(cli.command, Command::Wallet(_) | Command::Positions(_) | Command::Sim(_) | Command::Health(_)) {
+        require_text_output(json, "this command")?;
+    }
+
+    match cli.command {
+        Command::Setup => setup::run().await,
+        Command::Wallet(command) => run_wallet(command, json).await,
+        Command::Positions(command) => run_positions(command, json).await,
+        Command::Sell(SellCommand::Manual) => manual_sell::run().await,
+        Command::Sell(SellCommand::Large) => sell_large_positions::run().await,
+        Command::Sell(SellCommand::Stale) => close_stale_positions::run().await,
+        Command::Sell(SellCommand::Resolved) => close_resolved_positions::run().await,
+        Command::Redeem(RedeemCommand::Resolved) => redeem_resolved_positions::run().await,
+        Command::Redeem(RedeemCommand::AutoClaim) => trigger_auto_claim::run().await,
+        Command::Redeem(RedeemCommand::CheckAutoClaim) => auto_claim_test::run().await,
+        Command::Traders(TradersCommand::Best) => find_best_traders::run().await,
+        Command::Traders(TradersCommand::LowRisk) => find_low_risk_traders::run().await,
+        Command::Traders(TradersCommand::Scan) => scan_best_traders::run().await,
+        Command::Traders(TradersCommand::ScanMarkets) => scan_traders_from_markets::run().await,
+        Command::Traders(TradersCommand::FetchHistory) => fetch_historical_trades::run().await,
+        Command::Sim(command) => run_sim(command, json).await,
+        Command::Health(HealthCommand::Check) => health_check::run(json).await,
+        Command::Health(HealthCommand::Breaker { action, accounts }) => {
+            let accounts = accounts.selection(matches!(action, Some(BreakerCommand::Kill { .. })));
+            let action = match action {
+                None | Some(BreakerCommand::Status) => circuit_breaker::BreakerAction::Status,
+                Some(BreakerCommand::Resume) => circuit_breaker::BreakerAction::Resume,
+                Some(BreakerCommand::Kill { reason }) => circuit_breaker::BreakerAction::Kill(reason),
+            };
+            circuit_breaker::run(action, accounts, json).await
+        }
+    }
+}
@@ -0,0 +1,31 @@
+//! Machine-readable output for `--json`
+
+use anyhow::Result;
+use serde::Serialize;
+
+pub fn print_json<T: Serialize + ?Sized>(value: &T) -> Result<()> {
+    println!("{}", serde_json::to_string_pretty(value)?);
+    Ok(())
+}
+
+/// Per-account results: the value itself for a single account, else an
+/// array of `{ "account": name, "result": value }`
+pub fn print_account_json<T: Serialize>(results: &[(String, T)]) -> Result<()> {
+    match results {
+        [(_, value)] => print_json(value),
+        _ => print_json(
+            &results
+                .iter()
+                .map(|(account, result)| serde_json::json!({ "account": account, "result": result }))
+                .collect::<Vec<_>>(),
+        ),
+    }
+}
+
+/// Commands without typed output reject `--json` instead of printing text
+pub fn require_text_output(json: bool, command: &str) -> Result<()> {
+    if json {
+        anyhow::bail!("`polycopy {}` does not support --json", command);
+    }
+    Ok(())
+}
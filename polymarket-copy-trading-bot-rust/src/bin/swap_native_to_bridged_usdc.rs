@@ -1,45 +0,0 @@
-//! Swap native USDC to bridged USDC.e
-#![allow(dead_code)] // Struct fields used for JSON deserialization
-
-use anyhow::Result;
-use polymarket_copy_trading_bot_rust::config::load_env;
-
-const NATIVE_USDC_ADDRESS: &str = "0x3c499c542cEF5E3811e1192ce70d8cC03d5c3359";
-const BRIDGED_USDC_ADDRESS: &str = "0x2791Bca1f2de4661ED88A30C99A7a9449Aa84174";
-const QUICKSWAP_ROUTER: &str = "0xa5E0829CaCEd8fFDD4De3c43696c57F7D7A678ff";
-
-#[tokio::main]
-async fn main() -> Result<()> {
-    println!("🔄 Swapping Native USDC to Bridged USDC.e");
-    println!("════════════════════════════════════════════════════\n");
-
-    let env = load_env()?;
-
-    println!("📍 Proxy Wallet: {}", env.proxy_wallet);
-    println!("📍 Native USDC: {}", NATIVE_USDC_ADDRESS);
-    println!("📍 Bridged USDC.e: {}", BRIDGED_USDC_ADDRESS);
-    println!("📍 QuickSwap Router: {}\n", QUICKSWAP_ROUTER);
-
-    println!("💡 NOTE: Token swapping requires:");
-    println!("   1. Approving the DEX router to spend your tokens");
-    println!("   2. Building a swap transaction");
-    println!("   3. Signing and sending it to the blockchain");
-    println!("\n   For contract wallets (Gnosis Safe), use the Safe web interface:");
-    println!("   https://app.safe.global/");
-    println!("\n   Recommended: Use QuickSwap or Uniswap through Safe interface\n");
-
-    println!("📋 SOLUTION OPTIONS:\n");
-    println!("   Option 1: Use Safe web interface (RECOMMENDED)");
-    println!("      1. Go to https://app.safe.global/");
-    println!("      2. Connect and find your Safe: {}", env.proxy_wallet);
-    println!("      3. Use Apps → QuickSwap or Uniswap");
-    println!("      4. Swap: Native USDC → USDC.e\n");
-    println!("   Option 2: Transfer tokens to signer wallet first");
-    println!("      - Transfer native USDC from proxy to signer");
-    println!("      - Swap on signer wallet");
-    println!("      - Transfer USDC.e back to proxy\n");
-    println!("   Option 3: Use 1inch API with Safe transaction builder\n");
-
-    Ok(())
-}
-
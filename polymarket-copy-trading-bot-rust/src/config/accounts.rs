@@ -0,0 +1,131 @@
+use super::copy_strategy::{validate_copy_strategy_config, CopyStrategy, CopyStrategyConfig};
+use super::env::is_valid_ethereum_address;
+use super::file::FieldKind::{self, Number, String as Str};
+use super::reader::ConfigReader;
+
+/// Name of the account built from PROXY_WALLET / PRIVATE_KEY and the global strategy
+pub const PRIMARY_ACCOUNT: &str = "primary";
+
+/// Per-account settings: config file key, ACCOUNT_<NAME>_<suffix> variable, kind
+pub const ACCOUNT_SETTINGS: [(&str, &str, FieldKind); 12] = [
+    ("proxy_wallet", "PROXY_WALLET", Str),
+    ("private_key", "PRIVATE_KEY", Str),
+    ("copy_strategy", "COPY_STRATEGY", Str),
+    ("copy_size", "COPY_SIZE", Number),
+    ("trade_multiplier", "TRADE_MULTIPLIER", Number),
+    ("max_order_size_usd", "MAX_ORDER_SIZE_USD", Number),
+    ("min_order_size_usd", "MIN_ORDER_SIZE_USD", Number),
+    ("max_position_size_usd", "MAX_POSITION_SIZE_USD", Number),
+    ("max_daily_volume_usd", "MAX_DAILY_VOLUME_USD", Number),
+    ("adaptive_min_percent", "ADAPTIVE_MIN_PERCENT", Number),
+    ("adaptive_max_percent", "ADAPTIVE_MAX_PERCENT", Number),
+    ("adaptive_threshold_usd", "ADAPTIVE_THRESHOLD_USD", Number),
+];
+
+/// A wallet copies are executed in, with its own signer and sizing
+#[derive(Debug, Clone)]
+pub struct ExecutionAccount {
+    pub name: String,
+    pub proxy_wallet: String,
+    pub private_key: String,
+    /// The global copy strategy with this account's overrides applied
+    pub copy_strategy_config: CopyStrategyConfig,
+}
+
+/// Environment variable of one account setting, e.g. ACCOUNT_HEDGE_COPY_SIZE
+pub fn account_env_key(name: &str, suffix: &str) -> String {
+    format!("ACCOUNT_{}_{}", name.to_uppercase().replace('-', "_"), suffix)
+}
+
+fn is_valid_account_name(name: &str) -> bool {
+    !name.is_empty() && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+}
+
+fn parse_account_strategy(reader: &mut ConfigReader, name: &str, base: &CopyStrategyConfig) -> CopyStrategyConfig {
+    let key = |suffix: &str| account_env_key(name, suffix);
+    let mut config = base.clone();
+
+    if let Some(strategy) = reader.get(&key("COPY_STRATEGY")) {
+        config.strategy = match strategy.to_uppercase().as_str() {
+            "PERCENTAGE" => CopyStrategy::Percentage,
+            "FIXED" => CopyStrategy::Fixed,
+            "ADAPTIVE" => CopyStrategy::Adaptive,
+            _ => {
+                reader.invalid(&key("COPY_STRATEGY"), format!("{} (expected PERCENTAGE, FIXED or ADAPTIVE)", strategy));
+                config.strategy
+            }
+        };
+    }
+    config.copy_size = reader.parse_or(&key("COPY_SIZE"), config.copy_size);
+    if let Some(multiplier) = reader.optional::<f64>(&key("TRADE_MULTIPLIER")) {
+        config.trade_multiplier = Some(multiplier).filter(|&m| m != 1.0);
+        config.tiered_multipliers = None;
+    }
+    config.max_order_size_usd = reader.parse_or(&key("MAX_ORDER_SIZE_USD"), config.max_order_size_usd);
+    config.min_order_size_usd = reader.parse_or(&key("MIN_ORDER_SIZE_USD"), config.min_order_size_usd);
+    if let Some(max) = reader.optional(&key("MAX_POSITION_SIZE_USD")) {
+        config.max_position_size_usd = Some(max);
+    }
+    if let Some(max) = reader.optional(&key("MAX_DAILY_VOLUME_USD")) {
+        config.max_daily_volume_usd = Some(max);
+    }
+
+    if config.strategy == CopyStrategy::Adaptive {
+        let min = config.adaptive_min_percent.unwrap_or(config.copy_size);
+        let max = config.adaptive_max_percent.unwrap_or(config.copy_size);
+        let threshold = config.adaptive_threshold.unwrap_or(500.0);
+        config.adaptive_min_percent = Some(reader.parse_or(&key("ADAPTIVE_MIN_PERCENT"), min));
+        config.adaptive_max_percent = Some(reader.parse_or(&key("ADAPTIVE_MAX_PERCENT"), max));
+        config.adaptive_threshold = Some(reader.parse_or(&key("ADAPTIVE_THRESHOLD_USD"), threshold));
+    }
+
+    for error in validate_copy_strategy_config(&config) {
+        reader.error(format!("Account {}: {}", name, error));
+    }
+    config
+}
+
+/// Extra accounts from EXECUTION_ACCOUNTS (names), each needing its own
+/// ACCOUNT_<NAME>_PROXY_WALLET and ACCOUNT_<NAME>_PRIVATE_KEY
+pub fn parse_execution_accounts(reader: &mut ConfigReader, base: &CopyStrategyConfig) -> Vec<ExecutionAccount> {
+    let mut accounts: Vec<ExecutionAccount> = Vec::new();
+    let mut wallets = vec![reader.get("PROXY_WALLET").unwrap_or_default().to_lowercase()];
+
+    for name in reader.list("EXECUTION_ACCOUNTS") {
+        if !is_valid_account_name(&name) || name == PRIMARY_ACCOUNT {
+            reader.invalid(
+                "EXECUTION_ACCOUNTS",
+                format!("{:?} (names use letters, digits, _ or -; \"{}\" is reserved)", name, PRIMARY_ACCOUNT),
+            );
+            continue;
+        }
+        if accounts.iter().any(|a| a.name == name) {
+            reader.invalid("EXECUTION_ACCOUNTS", format!("{} is listed twice", name));
+            continue;
+        }
+
+        let wallet_key = account_env_key(&name, "PROXY_WALLET");
+        let key_key = account_env_key(&name, "PRIVATE_KEY");
+        let proxy_wallet = reader.get(&wallet_key).unwrap_or_default();
+        let private_key = reader.get(&key_key).unwrap_or_default();
+        if proxy_wallet.is_empty() {
+            reader.error(format!("Missing required setting {}", wallet_key));
+        } else if !is_valid_ethereum_address(&proxy_wallet) {
+            reader.invalid(&wallet_key, format!("{} (expected 0x followed by 40 hexadecimal characters)", proxy_wallet));
+        } else if wallets.contains(&proxy_wallet.to_lowercase()) {
+            reader.invalid(&wallet_key, format!("{} is already used by another account", proxy_wallet));
+        }
+        if private_key.is_empty() {
+            reader.error(format!("Missing required setting {}", key_key));
+        }
+        wallets.push(proxy_wallet.to_lowercase());
+
+        accounts.push(ExecutionAccount {
+            copy_strategy_config: parse_account_strategy(reader, &name, base),
+            name,
+            proxy_wallet,
+            private_key,
+        });
+    }
+    accounts
+}
@@ -0,0 +1,56 @@
+use super::reader::ConfigReader;
+
+/// Equity-based circuit breaker. Equity is USDC balance plus position value;
+/// new buys are paused once a limit is hit until explicitly resumed.
+#[derive(Debug, Clone, Default)]
+pub struct CircuitBreakerConfig {
+    /// Max drop from the rolling high-water mark
+    pub max_drawdown_percent: Option<f64>,
+    /// Max drop from equity at the start of the UTC day
+    pub max_daily_loss_percent: Option<f64>,
+    pub max_daily_loss_usd: Option<f64>,
+    /// Days of daily highs that make up the high-water mark
+    pub hwm_window_days: u32,
+    /// Sell all positions when the breaker trips
+    pub flatten_on_trip: bool,
+    pub check_interval_ms: u64,
+}
+
+impl CircuitBreakerConfig {
+    pub fn is_enabled(&self) -> bool {
+        self.max_drawdown_percent.is_some()
+            || self.max_daily_loss_percent.is_some()
+            || self.max_daily_loss_usd.is_some()
+    }
+}
+
+pub fn parse_circuit_breaker(reader: &mut ConfigReader) -> CircuitBreakerConfig {
+    let config = CircuitBreakerConfig {
+        max_drawdown_percent: reader.optional("CIRCUIT_BREAKER_MAX_DRAWDOWN_PERCENT"),
+        max_daily_loss_percent: reader.optional("CIRCUIT_BREAKER_MAX_DAILY_LOSS_PERCENT"),
+        max_daily_loss_usd: reader.optional("CIRCUIT_BREAKER_MAX_DAILY_LOSS_USD"),
+        hwm_window_days: reader.parse_or("CIRCUIT_BREAKER_HWM_WINDOW_DAYS", 30),
+        flatten_on_trip: reader.parse_or("CIRCUIT_BREAKER_FLATTEN", false),
+        check_interval_ms: reader.parse_or("CIRCUIT_BREAKER_CHECK_INTERVAL_MS", 60000),
+    };
+
+    for (key, value) in [
+        ("CIRCUIT_BREAKER_MAX_DRAWDOWN_PERCENT", config.max_drawdown_percent),
+        ("CIRCUIT_BREAKER_MAX_DAILY_LOSS_PERCENT", config.max_daily_loss_percent),
+    ] {
+        if value.is_some_and(|v| v <= 0.0 || v >= 100.0) {
+            reader.invalid(key, "must be between 0 and 100");
+        }
+    }
+    if config.max_daily_loss_usd.is_some_and(|v| v <= 0.0) {
+        reader.invalid("CIRCUIT_BREAKER_MAX_DAILY_LOSS_USD", "must be positive");
+    }
+    if config.hwm_window_days == 0 {
+        reader.invalid("CIRCUIT_BREAKER_HWM_WINDOW_DAYS", "must be positive");
+    }
+    if config.check_interval_ms < 1000 {
+        reader.invalid("CIRCUIT_BREAKER_CHECK_INTERVAL_MS", "must be at least 1000ms");
+    }
+
+    config
+}
@@ -33,6 +33,12 @@ pub struct CopyStrategyConfig {
     pub min_order_size_usd: f64,
     pub max_position_size_usd: Option<f64>,
     pub max_daily_volume_usd: Option<f64>,
+    /// Max price move past the trader's fill, in percent of their price
+    #[serde(default)]
+    pub max_slippage_percent: Option<f64>,
+    /// Max price move past the trader's fill, in price units (e.g. 0.02)
+    #[serde(default)]
+    pub max_slippage_abs: Option<f64>,
 }
 
 #[derive(Debug, Clone)]
@@ -164,41 +170,40 @@ fn lerp(a: f64, b: f64, t: f64) -> f64 {
     a + (b - a) * clamped_t
 }
 
-#[allow(dead_code)] // May be used for validation in future
 pub fn validate_copy_strategy_config(config: &CopyStrategyConfig) -> Vec<String> {
     let mut errors = Vec::new();
 
-    // Validate copySize
+    // Validate copy size
     if config.copy_size <= 0.0 {
-        errors.push("copySize must be positive".to_string());
+        errors.push("COPY_SIZE must be positive".to_string());
     }
 
     if config.strategy == CopyStrategy::Percentage && config.copy_size > 100.0 {
-        errors.push("copySize for PERCENTAGE strategy should be <= 100".to_string());
+        errors.push("COPY_SIZE for PERCENTAGE strategy should be <= 100".to_string());
     }
 
     // Validate limits
     if config.max_order_size_usd <= 0.0 {
-        errors.push("maxOrderSizeUSD must be positive".to_string());
+        errors.push("MAX_ORDER_SIZE_USD must be positive".to_string());
     }
 
     if config.min_order_size_usd <= 0.0 {
-        errors.push("minOrderSizeUSD must be positive".to_string());
+        errors.push("MIN_ORDER_SIZE_USD must be positive".to_string());
     }
 
     if config.min_order_size_usd > config.max_order_size_usd {
-        errors.push("minOrderSizeUSD cannot be greater than maxOrderSizeUSD".to_string());
+        errors.push("MIN_ORDER_SIZE_USD cannot be greater than MAX_ORDER_SIZE_USD".to_string());
     }
 
     // Validate adaptive parameters
     if config.strategy == CopyStrategy::Adaptive {
         if config.adaptive_min_percent.is_none() || config.adaptive_max_percent.is_none() {
-            errors.push("ADAPTIVE strategy requires adaptiveMinPercent and adaptiveMaxPercent".to_string());
+            errors.push("ADAPTIVE strategy requires ADAPTIVE_MIN_PERCENT and ADAPTIVE_MAX_PERCENT".to_string());
         }
 
         if let (Some(min), Some(max)) = (config.adaptive_min_percent, config.adaptive_max_percent) {
             if min > max {
-                errors.push("adaptiveMinPercent cannot be greater than adaptiveMaxPercent".to_string());
+                errors.push("ADAPTIVE_MIN_PERCENT cannot be greater than ADAPTIVE_MAX_PERCENT".to_string());
             }
         }
     }
@@ -385,6 +390,28 @@ pub fn get_trade_multiplier(config: &CopyStrategyConfig, trader_order_size: f64)
     1.0
 }
 
+/// Highest price we'll pay when copying a buy at `trader_price`.
+/// When both slippage limits are set the tighter one wins; `None` = no limit.
+pub fn get_buy_limit_price(config: &CopyStrategyConfig, trader_price: f64) -> Option<f64> {
+    let by_percent = config.max_slippage_percent.map(|p| trader_price * (1.0 + p / 100.0));
+    let by_abs = config.max_slippage_abs.map(|a| trader_price + a);
+    match (by_percent, by_abs) {
+        (Some(p), Some(a)) => Some(p.min(a)),
+        (limit, None) | (None, limit) => limit,
+    }
+}
+
+/// Lowest price we'll accept when copying a sell at `trader_price`
+pub fn get_sell_limit_price(config: &CopyStrategyConfig, trader_price: f64) -> Option<f64> {
+    let by_percent = config.max_slippage_percent.map(|p| trader_price * (1.0 - p / 100.0));
+    let by_abs = config.max_slippage_abs.map(|a| trader_price - a);
+    match (by_percent, by_abs) {
+        (Some(p), Some(a)) => Some(p.max(a)),
+        (limit, None) | (None, limit) => limit,
+    }
+    .map(|limit| limit.max(0.0))
+}
+
 impl fmt::Display for CopyStrategy {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
@@ -57,12 +57,65 @@ pub async fn cleanup_database(
     Ok(())
 }
 
-#[allow(dead_code)] // May be used for future features
+/// Database of an execution account other than the primary one, so its PnL
+/// ledger, paper account, limit orders and breaker state stay separate
+pub fn get_account_database(db: &Database, account_name: &str) -> Database {
+    // The driver exposes the client through collections only
+    let client = db.collection::<mongodb::bson::Document>("accounts").client().clone();
+    client.database(&format!("{}_{}", db.name(), account_name.to_lowercase()))
+}
+
+/// Trader activity the bot has mirrored (MERGE / SPLIT / REDEEM / CONVERSION), keyed by event
 pub fn get_user_activity_collection(db: &Database, wallet_address: &str) -> mongodb::Collection<mongodb::bson::Document> {
     let collection_name = format!("user_activities_{}", wallet_address.to_lowercase());
     db.collection(&collection_name)
 }
 
+/// Trades that were not copied, with the filter that rejected them
+pub fn get_skipped_trades_collection(db: &Database) -> mongodb::Collection<mongodb::bson::Document> {
+    db.collection("skipped_trades")
+}
+
+/// Resting limit orders placed in passive copy mode, with their fills
+pub fn get_limit_orders_collection(db: &Database) -> mongodb::Collection<mongodb::bson::Document> {
+    db.collection("limit_orders")
+}
+
+/// Persisted circuit breaker / kill switch state (single document)
+pub fn get_circuit_breaker_collection(db: &Database) -> mongodb::Collection<mongodb::bson::Document> {
+    db.collection("circuit_breaker")
+}
+
+/// Periodic reconciliation reports (records vs Data API vs on-chain balances)
+pub fn get_reconciliation_reports_collection(db: &Database) -> mongodb::Collection<mongodb::bson::Document> {
+    db.collection("reconciliation_reports")
+}
+
+/// Copy fills, sells and redemptions with the trader they came from
+pub fn get_pnl_ledger_collection(db: &Database) -> mongodb::Collection<mongodb::bson::Document> {
+    db.collection("pnl_ledger")
+}
+
+/// Paper trading account (single document: virtual cash, equity history)
+pub fn get_paper_account_collection(db: &Database) -> mongodb::Collection<mongodb::bson::Document> {
+    db.collection("paper_account")
+}
+
+/// Open paper trading positions, one document per outcome token
+pub fn get_paper_positions_collection(db: &Database) -> mongodb::Collection<mongodb::bson::Document> {
+    db.collection("paper_positions")
+}
+
+/// Numeric field stored from Data API JSON (may be a double or an integer)
+pub fn get_number_field(doc: &mongodb::bson::Document, key: &str) -> f64 {
+    match doc.get(key) {
+        Some(mongodb::bson::Bson::Double(v)) => *v,
+        Some(mongodb::bson::Bson::Int64(v)) => *v as f64,
+        Some(mongodb::bson::Bson::Int32(v)) => f64::from(*v),
+        _ => 0.0,
+    }
+}
+
 pub fn get_user_position_collection(db: &Database, wallet_address: &str) -> mongodb::Collection<mongodb::bson::Document> {
     let collection_name = format!("user_positions_{}", wallet_address.to_lowercase());
     db.collection(&collection_name)
@@ -1,8 +1,19 @@
-use anyhow::{Context, Result};
+use anyhow::Result;
 use regex::Regex;
-use std::env;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
 
 use super::copy_strategy::{CopyStrategy, CopyStrategyConfig, parse_tiered_multipliers};
+use super::market_filter::{parse_market_filter, MarketFilterConfig};
+use super::limit_order::{parse_limit_order_config, LimitOrderConfig};
+use super::exposure_limits::{parse_exposure_limits, ExposureLimitsConfig};
+use super::circuit_breaker::{parse_circuit_breaker, CircuitBreakerConfig};
+use super::paper_trading::{parse_paper_trading, PaperTradingConfig};
+use super::health::{parse_health_config, HealthConfig};
+use super::rtds::{parse_rtds_config, RtdsConfig};
+use super::accounts::{parse_execution_accounts, ExecutionAccount, PRIMARY_ACCOUNT};
+use super::file::{find_config_file, read_config_file};
+use super::reader::ConfigReader;
 
 #[derive(Debug, Clone)]
 #[allow(dead_code)] // Some fields kept for backward compatibility or future use
@@ -12,6 +23,12 @@ pub struct Env {
     pub private_key: String,
     pub clob_http_url: String,
     pub clob_ws_url: String,
+    /// Data API base URL (positions, activity, trades, leaderboard)
+    pub data_api_url: String,
+    /// Gamma API base URL (market metadata)
+    pub gamma_api_url: String,
+    /// Real-time data stream the trade monitor subscribes to
+    pub rtds_url: String,
     pub fetch_interval: u64,
     pub too_old_timestamp: u64,
     pub retry_limit: u32,
@@ -20,417 +37,381 @@ pub struct Env {
     pub copy_strategy_config: CopyStrategyConfig,
     pub request_timeout_ms: u64,
     pub network_retry_limit: u32,
+    /// Per-host token bucket for outbound HTTP requests
+    pub http_rate_limit_per_second: f64,
+    pub http_rate_limit_burst: u32,
+    /// How long GET responses are shared between callers (0 disables the cache)
+    pub http_cache_ttl_ms: u64,
     pub trade_aggregation_enabled: bool,
     pub trade_aggregation_window_seconds: u64,
     pub mongo_uri: String,
     pub rpc_url: String,
     pub auto_claim_enabled: bool,
     pub auto_claim_interval_ms: u64,
+    pub reconcile_enabled: bool,
+    pub reconcile_interval_ms: u64,
+    pub reconcile_tolerance_tokens: f64,
+    /// Mirror the traders' MERGE / SPLIT / REDEEM / CONVERSION activity
+    pub copy_position_activity: bool,
+    pub activity_poll_interval_ms: u64,
     pub db_cleanup_enabled: bool,
     pub usdc_contract_address: String,
     pub take_profit_percent: Option<f64>,
     pub stop_loss_percent: Option<f64>,
     pub tp_sl_check_interval_ms: u64,
     pub preview_mode: bool,
+    pub market_filter: MarketFilterConfig,
+    pub limit_orders: LimitOrderConfig,
+    pub exposure_limits: ExposureLimitsConfig,
+    pub circuit_breaker: CircuitBreakerConfig,
+    pub paper_trading: PaperTradingConfig,
+    pub health: HealthConfig,
+    pub rtds: RtdsConfig,
+    /// Wallets copies fan out to besides the primary one (EXECUTION_ACCOUNTS)
+    pub accounts: Vec<ExecutionAccount>,
+    /// TOML/YAML file the settings were read from (environment variables override it)
+    pub config_file: Option<PathBuf>,
 }
 
-fn is_valid_ethereum_address(address: &str) -> bool {
-    let re = Regex::new(r"^0x[a-fA-F0-9]{40}$").unwrap();
-    re.is_match(address)
-}
-
-fn validate_required_env() -> Result<()> {
-    let required = vec![
-        "USER_ADDRESSES",
-        "PROXY_WALLET",
-        "PRIVATE_KEY",
-        "CLOB_HTTP_URL",
-        "CLOB_WS_URL",
-        "MONGO_URI",
-        "RPC_URL",
-        "USDC_CONTRACT_ADDRESS",
-    ];
-
-    let mut missing = Vec::new();
-    for key in &required {
-        if env::var(key).is_err() {
-            missing.push(*key);
+impl Env {
+    /// Current settings with the hot-reloadable ones (trader list, sizing
+    /// strategy and per-account overrides, market filters, exposure limits)
+    /// taken from `reloaded`
+    pub fn with_hot_reload(&self, reloaded: &Env) -> Env {
+        let accounts = self
+            .accounts
+            .iter()
+            .map(|account| ExecutionAccount {
+                copy_strategy_config: reloaded
+                    .accounts
+                    .iter()
+                    .find(|r| r.name == account.name)
+                    .map(|r| r.copy_strategy_config.clone())
+                    .unwrap_or_else(|| account.copy_strategy_config.clone()),
+                ..account.clone()
+            })
+            .collect();
+        Env {
+            user_addresses: reloaded.user_addresses.clone(),
+            trade_multiplier: reloaded.trade_multiplier,
+            copy_percentage: reloaded.copy_percentage,
+            copy_strategy_config: reloaded.copy_strategy_config.clone(),
+            market_filter: reloaded.market_filter.clone(),
+            exposure_limits: reloaded.exposure_limits.clone(),
+            accounts,
+            ..self.clone()
         }
     }
 
-    if !missing.is_empty() {
-        eprintln!("\n❌ Configuration Error: Missing required environment variables\n");
-        eprintln!("Missing variables: {}\n", missing.join(", "));
-        eprintln!("🔧 Quick fix:");
-        eprintln!("   1. Run the setup wizard: cargo run --bin setup");
-        eprintln!("   2. Or manually create .env file with all required variables\n");
-        eprintln!("📖 See docs/QUICK_START.md for detailed instructions\n");
-        anyhow::bail!("Missing required environment variables: {}", missing.join(", "));
-    }
-
-    Ok(())
-}
-
-fn validate_addresses() -> Result<()> {
-    if let Ok(proxy_wallet) = env::var("PROXY_WALLET") {
-        if !is_valid_ethereum_address(&proxy_wallet) {
-            eprintln!("\n❌ Invalid Wallet Address\n");
-            eprintln!("Your PROXY_WALLET: {}", proxy_wallet);
-            eprintln!("Expected format:    0x followed by 40 hexadecimal characters\n");
-            eprintln!("Example: 0x742d35Cc6634C0532925a3b844Bc9e7595f0bEb0\n");
-            anyhow::bail!("Invalid PROXY_WALLET address format: {}", proxy_wallet);
-        }
+    /// The primary account (PROXY_WALLET / PRIVATE_KEY) followed by EXECUTION_ACCOUNTS
+    pub fn execution_accounts(&self) -> Vec<ExecutionAccount> {
+        let primary = ExecutionAccount {
+            name: PRIMARY_ACCOUNT.to_string(),
+            proxy_wallet: self.proxy_wallet.clone(),
+            private_key: self.private_key.clone(),
+            copy_strategy_config: self.copy_strategy_config.clone(),
+        };
+        std::iter::once(primary).chain(self.accounts.iter().cloned()).collect()
     }
 
-    if let Ok(usdc_address) = env::var("USDC_CONTRACT_ADDRESS") {
-        if !is_valid_ethereum_address(&usdc_address) {
-            eprintln!("\n❌ Invalid USDC Contract Address\n");
-            eprintln!("Current value: {}", usdc_address);
-            eprintln!("Default value: 0x2791Bca1f2de4661ED88A30C99A7a9449Aa84174\n");
-            anyhow::bail!("Invalid USDC_CONTRACT_ADDRESS format: {}", usdc_address);
+    /// These settings as seen when executing in `account`: its wallet, signer
+    /// and copy strategy
+    pub fn for_account(&self, account: &ExecutionAccount) -> Env {
+        Env {
+            proxy_wallet: account.proxy_wallet.clone(),
+            private_key: account.private_key.clone(),
+            copy_strategy_config: account.copy_strategy_config.clone(),
+            accounts: Vec::new(),
+            ..self.clone()
         }
     }
-
-    Ok(())
 }
 
-fn validate_numeric_config() -> Result<()> {
-    let fetch_interval = env::var("FETCH_INTERVAL")
-        .unwrap_or_else(|_| "1".to_string())
-        .parse::<u64>()
-        .context("Invalid FETCH_INTERVAL")?;
-    if fetch_interval == 0 {
-        anyhow::bail!("Invalid FETCH_INTERVAL: must be positive");
-    }
-
-    let retry_limit = env::var("RETRY_LIMIT")
-        .unwrap_or_else(|_| "3".to_string())
-        .parse::<u32>()
-        .context("Invalid RETRY_LIMIT")?;
-    if retry_limit < 1 || retry_limit > 10 {
-        anyhow::bail!("Invalid RETRY_LIMIT: must be between 1 and 10");
-    }
+const DEFAULT_DATA_API_URL: &str = "https://data-api.polymarket.com";
+const DEFAULT_GAMMA_API_URL: &str = "https://gamma-api.polymarket.com";
+const DEFAULT_RTDS_URL: &str = "wss://ws-live-data.polymarket.com";
 
-    let too_old_timestamp = env::var("TOO_OLD_TIMESTAMP")
-        .unwrap_or_else(|_| "24".to_string())
-        .parse::<u64>()
-        .context("Invalid TOO_OLD_TIMESTAMP")?;
-    if too_old_timestamp == 0 {
-        anyhow::bail!("Invalid TOO_OLD_TIMESTAMP: must be positive");
-    }
-
-    let request_timeout = env::var("REQUEST_TIMEOUT_MS")
-        .unwrap_or_else(|_| "10000".to_string())
-        .parse::<u64>()
-        .context("Invalid REQUEST_TIMEOUT_MS")?;
-    if request_timeout < 1000 {
-        anyhow::bail!("Invalid REQUEST_TIMEOUT_MS: must be at least 1000ms");
-    }
-
-    let network_retry_limit = env::var("NETWORK_RETRY_LIMIT")
-        .unwrap_or_else(|_| "3".to_string())
-        .parse::<u32>()
-        .context("Invalid NETWORK_RETRY_LIMIT")?;
-    if network_retry_limit < 1 || network_retry_limit > 10 {
-        anyhow::bail!("Invalid NETWORK_RETRY_LIMIT: must be between 1 and 10");
-    }
-
-    Ok(())
+pub(super) fn is_valid_ethereum_address(address: &str) -> bool {
+    let re = Regex::new(r"^0x[a-fA-F0-9]{40}$").unwrap();
+    re.is_match(address)
 }
 
-fn validate_urls() -> Result<()> {
-    if let Ok(clob_http_url) = env::var("CLOB_HTTP_URL") {
-        if !clob_http_url.starts_with("http") {
-            eprintln!("\n❌ Invalid CLOB_HTTP_URL\n");
-            eprintln!("Current value: {}", clob_http_url);
-            eprintln!("Default value: https://clob.polymarket.com/\n");
-            anyhow::bail!("Invalid CLOB_HTTP_URL: must be a valid HTTP/HTTPS URL");
-        }
-    }
-
-    if let Ok(clob_ws_url) = env::var("CLOB_WS_URL") {
-        if !clob_ws_url.starts_with("ws") {
-            eprintln!("\n❌ Invalid CLOB_WS_URL\n");
-            eprintln!("Current value: {}", clob_ws_url);
-            eprintln!("Default value: wss://ws-subscriptions-clob.polymarket.com/ws\n");
-            anyhow::bail!("Invalid CLOB_WS_URL: must be a valid WebSocket URL");
+const REQUIRED_SETTINGS: [&str; 8] = [
+    "USER_ADDRESSES",
+    "PROXY_WALLET",
+    "PRIVATE_KEY",
+    "CLOB_HTTP_URL",
+    "CLOB_WS_URL",
+    "MONGO_URI",
+    "RPC_URL",
+    "USDC_CONTRACT_ADDRESS",
+];
+
+fn validate_required(reader: &mut ConfigReader) {
+    for key in REQUIRED_SETTINGS {
+        if !reader.is_set(key) {
+            reader.error(format!("Missing required setting {}", key));
         }
     }
+}
 
-    if let Ok(rpc_url) = env::var("RPC_URL") {
-        if !rpc_url.starts_with("http") {
-            eprintln!("\n❌ Invalid RPC_URL\n");
-            eprintln!("Current value: {}", rpc_url);
-            eprintln!("Must start with: http:// or https://\n");
-            anyhow::bail!("Invalid RPC_URL: must be a valid HTTP/HTTPS URL");
+fn validate_addresses(reader: &mut ConfigReader) {
+    for key in ["PROXY_WALLET", "USDC_CONTRACT_ADDRESS"] {
+        if let Some(address) = reader.get(key) {
+            if !is_valid_ethereum_address(&address) {
+                reader.invalid(key, format!("{} (expected 0x followed by 40 hexadecimal characters)", address));
+            }
         }
     }
+}
 
-    if let Ok(mongo_uri) = env::var("MONGO_URI") {
-        if !mongo_uri.starts_with("mongodb") {
-            eprintln!("\n❌ Invalid MONGO_URI\n");
-            eprintln!("Current value: {}", mongo_uri);
-            eprintln!("Must start with: mongodb:// or mongodb+srv://\n");
-            anyhow::bail!("Invalid MONGO_URI: must be a valid MongoDB connection string");
+fn validate_urls(reader: &mut ConfigReader) {
+    for (key, prefix, expected) in [
+        ("CLOB_HTTP_URL", "http", "a valid HTTP/HTTPS URL"),
+        ("CLOB_WS_URL", "ws", "a valid WebSocket URL"),
+        ("RPC_URL", "http", "a valid HTTP/HTTPS URL"),
+        ("DATA_API_URL", "http", "a valid HTTP/HTTPS URL"),
+        ("GAMMA_API_URL", "http", "a valid HTTP/HTTPS URL"),
+        ("RTDS_URL", "ws", "a valid WebSocket URL"),
+        ("MONGO_URI", "mongodb", "a valid MongoDB connection string"),
+    ] {
+        if let Some(url) = reader.get(key) {
+            if !url.starts_with(prefix) {
+                reader.invalid(key, format!("{} (must be {})", url, expected));
+            }
         }
     }
-
-    Ok(())
 }
 
-fn parse_user_addresses(input: &str) -> Result<Vec<String>> {
-    let trimmed = input.trim();
-    
-    // Check if it's JSON array format
-    if trimmed.starts_with('[') && trimmed.ends_with(']') {
-        let parsed: Vec<String> = serde_json::from_str(trimmed)
-            .context("Invalid JSON format for USER_ADDRESSES")?;
-        
-        let addresses: Vec<String> = parsed
-            .into_iter()
-            .map(|addr| addr.to_lowercase().trim().to_string())
-            .filter(|addr| !addr.is_empty())
-            .collect();
-        
-        // Validate each address
-        for addr in &addresses {
-            if !is_valid_ethereum_address(addr) {
-                eprintln!("\n❌ Invalid Trader Address in USER_ADDRESSES\n");
-                eprintln!("Invalid address: {}", addr);
-                eprintln!("Expected format: 0x followed by 40 hexadecimal characters\n");
-                anyhow::bail!("Invalid Ethereum address in USER_ADDRESSES: {}", addr);
+fn parse_user_addresses(reader: &mut ConfigReader) -> Vec<String> {
+    let Some(input) = reader.get("USER_ADDRESSES") else {
+        return Vec::new();
+    };
+
+    // JSON array or comma-separated
+    let addresses: Vec<String> = if input.starts_with('[') && input.ends_with(']') {
+        match serde_json::from_str::<Vec<String>>(&input) {
+            Ok(parsed) => parsed,
+            Err(e) => {
+                reader.invalid("USER_ADDRESSES", format!("invalid JSON array ({})", e));
+                return Vec::new();
             }
         }
-        
-        return Ok(addresses);
-    }
-    
-    // Otherwise treat as comma-separated
-    let addresses: Vec<String> = trimmed
-        .split(',')
+    } else {
+        input.split(',').map(str::to_string).collect()
+    };
+
+    let addresses: Vec<String> = addresses
+        .into_iter()
         .map(|addr| addr.to_lowercase().trim().to_string())
         .filter(|addr| !addr.is_empty())
         .collect();
-    
-    // Validate each address
+
     for addr in &addresses {
         if !is_valid_ethereum_address(addr) {
-            eprintln!("\n❌ Invalid Trader Address in USER_ADDRESSES\n");
-            eprintln!("Invalid address: {}", addr);
-            eprintln!("Expected format: 0x followed by 40 hexadecimal characters\n");
-            anyhow::bail!("Invalid Ethereum address in USER_ADDRESSES: {}", addr);
+            reader.invalid("USER_ADDRESSES", format!("{} is not an Ethereum address", addr));
         }
     }
-    
-    Ok(addresses)
+    addresses
 }
 
-fn parse_copy_strategy() -> Result<CopyStrategyConfig> {
+fn parse_tiers(reader: &mut ConfigReader, config: &mut CopyStrategyConfig) {
+    if let Some(tiers) = reader.get("TIERED_MULTIPLIERS") {
+        match parse_tiered_multipliers(&tiers) {
+            Ok(tiers) => {
+                println!("✓ Loaded {} tiered multipliers", tiers.len());
+                config.tiered_multipliers = Some(tiers);
+            }
+            Err(e) => reader.invalid("TIERED_MULTIPLIERS", format!("{:#}", e)),
+        }
+    }
+}
+
+fn parse_copy_strategy(reader: &mut ConfigReader) -> CopyStrategyConfig {
+    let mut config = CopyStrategyConfig {
+        max_order_size_usd: reader.parse_or("MAX_ORDER_SIZE_USD", 100.0),
+        min_order_size_usd: reader.parse_or("MIN_ORDER_SIZE_USD", 0.01),
+        max_position_size_usd: reader.optional("MAX_POSITION_SIZE_USD"),
+        max_daily_volume_usd: reader.optional("MAX_DAILY_VOLUME_USD"),
+        ..Default::default()
+    };
+
     // Support legacy COPY_PERCENTAGE + TRADE_MULTIPLIER for backward compatibility
-    let has_legacy_config = env::var("COPY_PERCENTAGE").is_ok() && env::var("COPY_STRATEGY").is_err();
+    let has_legacy_config = reader.is_set("COPY_PERCENTAGE") && !reader.is_set("COPY_STRATEGY");
 
     if has_legacy_config {
         eprintln!("⚠️  Using legacy COPY_PERCENTAGE configuration. Consider migrating to COPY_STRATEGY.");
-        let copy_percentage = env::var("COPY_PERCENTAGE")
-            .unwrap_or_else(|_| "10.0".to_string())
-            .parse::<f64>()
-            .context("Invalid COPY_PERCENTAGE")?;
-        let trade_multiplier = env::var("TRADE_MULTIPLIER")
-            .unwrap_or_else(|_| "1.0".to_string())
-            .parse::<f64>()
-            .context("Invalid TRADE_MULTIPLIER")?;
-        let effective_percentage = copy_percentage * trade_multiplier;
-
-        let mut config = CopyStrategyConfig {
-            strategy: CopyStrategy::Percentage,
-            copy_size: effective_percentage,
-            max_order_size_usd: env::var("MAX_ORDER_SIZE_USD")
-                .unwrap_or_else(|_| "100.0".to_string())
-                .parse::<f64>()
-                .context("Invalid MAX_ORDER_SIZE_USD")?,
-            min_order_size_usd: env::var("MIN_ORDER_SIZE_USD")
-                .unwrap_or_else(|_| "0.01".to_string())
-                .parse::<f64>()
-                .context("Invalid MIN_ORDER_SIZE_USD")?,
-            max_position_size_usd: env::var("MAX_POSITION_SIZE_USD")
-                .ok()
-                .and_then(|v| v.parse::<f64>().ok()),
-            max_daily_volume_usd: env::var("MAX_DAILY_VOLUME_USD")
-                .ok()
-                .and_then(|v| v.parse::<f64>().ok()),
-            ..Default::default()
-        };
+        let copy_percentage: f64 = reader.parse_or("COPY_PERCENTAGE", 10.0);
+        let trade_multiplier: f64 = reader.parse_or("TRADE_MULTIPLIER", 1.0);
+        config.strategy = CopyStrategy::Percentage;
+        config.copy_size = copy_percentage * trade_multiplier;
 
-        // Parse tiered multipliers if configured
-        if let Ok(tiered_multipliers_str) = env::var("TIERED_MULTIPLIERS") {
-            config.tiered_multipliers = Some(parse_tiered_multipliers(&tiered_multipliers_str)?);
-            println!("✓ Loaded {} tiered multipliers", config.tiered_multipliers.as_ref().unwrap().len());
-        } else if trade_multiplier != 1.0 {
+        parse_tiers(reader, &mut config);
+        if config.tiered_multipliers.is_none() && trade_multiplier != 1.0 {
             config.trade_multiplier = Some(trade_multiplier);
         }
-
-        return Ok(config);
+        parse_slippage_limits(reader, &mut config);
+        return config;
     }
 
-    // Parse new copy strategy configuration
-    let strategy_str = env::var("COPY_STRATEGY")
-        .unwrap_or_else(|_| "PERCENTAGE".to_string())
-        .to_uppercase();
-    let strategy = match strategy_str.as_str() {
+    let strategy = reader.get("COPY_STRATEGY").unwrap_or_else(|| "PERCENTAGE".to_string());
+    config.strategy = match strategy.to_uppercase().as_str() {
         "PERCENTAGE" => CopyStrategy::Percentage,
         "FIXED" => CopyStrategy::Fixed,
         "ADAPTIVE" => CopyStrategy::Adaptive,
-        _ => CopyStrategy::Percentage,
-    };
-
-    let mut config = CopyStrategyConfig {
-        strategy,
-        copy_size: env::var("COPY_SIZE")
-            .unwrap_or_else(|_| "10.0".to_string())
-            .parse::<f64>()
-            .context("Invalid COPY_SIZE")?,
-        max_order_size_usd: env::var("MAX_ORDER_SIZE_USD")
-            .unwrap_or_else(|_| "100.0".to_string())
-            .parse::<f64>()
-            .context("Invalid MAX_ORDER_SIZE_USD")?,
-        min_order_size_usd: env::var("MIN_ORDER_SIZE_USD")
-            .unwrap_or_else(|_| "0.01".to_string())
-            .parse::<f64>()
-            .context("Invalid MIN_ORDER_SIZE_USD")?,
-        max_position_size_usd: env::var("MAX_POSITION_SIZE_USD")
-            .ok()
-            .and_then(|v| v.parse::<f64>().ok()),
-        max_daily_volume_usd: env::var("MAX_DAILY_VOLUME_USD")
-            .ok()
-            .and_then(|v| v.parse::<f64>().ok()),
-        ..Default::default()
+        _ => {
+            reader.invalid("COPY_STRATEGY", format!("{} (expected PERCENTAGE, FIXED or ADAPTIVE)", strategy));
+            CopyStrategy::Percentage
+        }
     };
+    config.copy_size = reader.parse_or("COPY_SIZE", 10.0);
 
     // Add adaptive strategy parameters if applicable
-    if strategy == CopyStrategy::Adaptive {
-        config.adaptive_min_percent = Some(
-            env::var("ADAPTIVE_MIN_PERCENT")
-                .unwrap_or_else(|_| config.copy_size.to_string())
-                .parse::<f64>()
-                .context("Invalid ADAPTIVE_MIN_PERCENT")?,
-        );
-        config.adaptive_max_percent = Some(
-            env::var("ADAPTIVE_MAX_PERCENT")
-                .unwrap_or_else(|_| config.copy_size.to_string())
-                .parse::<f64>()
-                .context("Invalid ADAPTIVE_MAX_PERCENT")?,
-        );
-        config.adaptive_threshold = Some(
-            env::var("ADAPTIVE_THRESHOLD_USD")
-                .unwrap_or_else(|_| "500.0".to_string())
-                .parse::<f64>()
-                .context("Invalid ADAPTIVE_THRESHOLD_USD")?,
-        );
+    if config.strategy == CopyStrategy::Adaptive {
+        config.adaptive_min_percent = Some(reader.parse_or("ADAPTIVE_MIN_PERCENT", config.copy_size));
+        config.adaptive_max_percent = Some(reader.parse_or("ADAPTIVE_MAX_PERCENT", config.copy_size));
+        config.adaptive_threshold = Some(reader.parse_or("ADAPTIVE_THRESHOLD_USD", 500.0));
     }
 
-    // Parse tiered multipliers if configured
-    if let Ok(tiered_multipliers_str) = env::var("TIERED_MULTIPLIERS") {
-        config.tiered_multipliers = Some(parse_tiered_multipliers(&tiered_multipliers_str)?);
-        println!("✓ Loaded {} tiered multipliers", config.tiered_multipliers.as_ref().unwrap().len());
-    } else if let Ok(trade_multiplier_str) = env::var("TRADE_MULTIPLIER") {
-        let single_multiplier = trade_multiplier_str.parse::<f64>().context("Invalid TRADE_MULTIPLIER")?;
-        if single_multiplier != 1.0 {
-            config.trade_multiplier = Some(single_multiplier);
-            println!("✓ Using single trade multiplier: {}x", single_multiplier);
+    parse_tiers(reader, &mut config);
+    if config.tiered_multipliers.is_none() {
+        if let Some(single_multiplier) = reader.optional::<f64>("TRADE_MULTIPLIER") {
+            if single_multiplier != 1.0 {
+                config.trade_multiplier = Some(single_multiplier);
+                println!("✓ Using single trade multiplier: {}x", single_multiplier);
+            }
         }
     }
 
-    Ok(config)
+    parse_slippage_limits(reader, &mut config);
+    config
+}
+
+fn parse_slippage_limits(reader: &mut ConfigReader, config: &mut CopyStrategyConfig) {
+    config.max_slippage_percent = reader.optional("MAX_SLIPPAGE_PERCENT").filter(|&v: &f64| v >= 0.0);
+    config.max_slippage_abs = reader.optional("MAX_SLIPPAGE_ABS").filter(|&v: &f64| v >= 0.0);
+}
+
+fn validate_numeric_config(reader: &mut ConfigReader, env: &Env) {
+    if env.fetch_interval == 0 {
+        reader.invalid("FETCH_INTERVAL", "must be positive");
+    }
+    if !(1..=10).contains(&env.retry_limit) {
+        reader.invalid("RETRY_LIMIT", "must be between 1 and 10");
+    }
+    if env.too_old_timestamp == 0 {
+        reader.invalid("TOO_OLD_TIMESTAMP", "must be positive");
+    }
+    if env.activity_poll_interval_ms < 1000 {
+        reader.invalid("ACTIVITY_POLL_INTERVAL_MS", "must be at least 1000ms");
+    }
+    if env.request_timeout_ms < 1000 {
+        reader.invalid("REQUEST_TIMEOUT_MS", "must be at least 1000ms");
+    }
+    if !(1..=10).contains(&env.network_retry_limit) {
+        reader.invalid("NETWORK_RETRY_LIMIT", "must be between 1 and 10");
+    }
+    if env.http_rate_limit_per_second <= 0.0 {
+        reader.invalid("HTTP_RATE_LIMIT_PER_SECOND", "must be positive");
+    }
+    if env.http_rate_limit_burst == 0 {
+        reader.invalid("HTTP_RATE_LIMIT_BURST", "must be at least 1");
+    }
+    if env.rtds.replay_file.is_some() && !env.preview_mode {
+        reader.invalid("RTDS_REPLAY_FILE", "replay only runs in preview mode (PREVIEW_MODE=true)");
+    }
+    for error in super::copy_strategy::validate_copy_strategy_config(&env.copy_strategy_config) {
+        reader.error(error);
+    }
+}
+
+/// Build the configuration from the environment layered over `file_values`,
+/// reporting every invalid or missing setting at once
+pub fn build_env(file_values: HashMap<String, String>, file_errors: Vec<String>, config_file: Option<PathBuf>) -> Result<Env> {
+    let mut reader = ConfigReader::new(file_values);
+    for error in file_errors {
+        reader.error(error);
+    }
+
+    validate_required(&mut reader);
+    validate_addresses(&mut reader);
+    validate_urls(&mut reader);
+
+    let mut env = Env {
+        user_addresses: parse_user_addresses(&mut reader),
+        proxy_wallet: reader.get("PROXY_WALLET").unwrap_or_default(),
+        private_key: reader.get("PRIVATE_KEY").unwrap_or_default(),
+        clob_http_url: reader.get("CLOB_HTTP_URL").unwrap_or_default(),
+        clob_ws_url: reader.get("CLOB_WS_URL").unwrap_or_default(),
+        data_api_url: reader.get("DATA_API_URL").unwrap_or_else(|| DEFAULT_DATA_API_URL.to_string()),
+        gamma_api_url: reader.get("GAMMA_API_URL").unwrap_or_else(|| DEFAULT_GAMMA_API_URL.to_string()),
+        rtds_url: reader.get("RTDS_URL").unwrap_or_else(|| DEFAULT_RTDS_URL.to_string()),
+        fetch_interval: reader.parse_or("FETCH_INTERVAL", 1),
+        too_old_timestamp: reader.parse_or("TOO_OLD_TIMESTAMP", 24),
+        retry_limit: reader.parse_or("RETRY_LIMIT", 3),
+        trade_multiplier: reader.parse_or("TRADE_MULTIPLIER", 1.0),
+        copy_percentage: reader.parse_or("COPY_PERCENTAGE", 10.0),
+        copy_strategy_config: parse_copy_strategy(&mut reader),
+        request_timeout_ms: reader.parse_or("REQUEST_TIMEOUT_MS", 10000),
+        network_retry_limit: reader.parse_or("NETWORK_RETRY_LIMIT", 3),
+        http_rate_limit_per_second: reader.parse_or("HTTP_RATE_LIMIT_PER_SECOND", 10.0),
+        http_rate_limit_burst: reader.parse_or("HTTP_RATE_LIMIT_BURST", 20),
+        http_cache_ttl_ms: reader.parse_or("HTTP_CACHE_TTL_MS", 2000),
+        trade_aggregation_enabled: reader.parse_or("TRADE_AGGREGATION_ENABLED", false),
+        trade_aggregation_window_seconds: reader.parse_or("TRADE_AGGREGATION_WINDOW_SECONDS", 300),
+        mongo_uri: reader.get("MONGO_URI").unwrap_or_default(),
+        rpc_url: reader.get("RPC_URL").unwrap_or_default(),
+        auto_claim_enabled: reader.parse_or("AUTO_CLAIM_ENABLED", false),
+        auto_claim_interval_ms: reader.parse_or("AUTO_CLAIM_INTERVAL_MS", 3600000),
+        reconcile_enabled: reader.parse_or("RECONCILE_ENABLED", false),
+        reconcile_interval_ms: reader.parse_or("RECONCILE_INTERVAL_MS", 600000),
+        reconcile_tolerance_tokens: reader.parse_or("RECONCILE_TOLERANCE_TOKENS", 0.01),
+        copy_position_activity: reader.parse_or("COPY_POSITION_ACTIVITY", false),
+        activity_poll_interval_ms: reader.parse_or("ACTIVITY_POLL_INTERVAL_MS", 15000),
+        db_cleanup_enabled: reader.parse_or("DB_CLEANUP_ENABLED", true),
+        usdc_contract_address: reader.get("USDC_CONTRACT_ADDRESS").unwrap_or_default(),
+        take_profit_percent: reader.optional("TAKE_PROFIT_PERCENT").filter(|&v: &f64| v > 0.0),
+        stop_loss_percent: reader.optional("STOP_LOSS_PERCENT").filter(|&v: &f64| v > 0.0),
+        tp_sl_check_interval_ms: reader.parse_or("TP_SL_CHECK_INTERVAL_MS", 1000),
+        preview_mode: reader.parse_or("PREVIEW_MODE", true),
+        market_filter: parse_market_filter(&mut reader),
+        limit_orders: parse_limit_order_config(&mut reader),
+        exposure_limits: parse_exposure_limits(&mut reader),
+        circuit_breaker: parse_circuit_breaker(&mut reader),
+        paper_trading: parse_paper_trading(&mut reader),
+        health: parse_health_config(&mut reader),
+        rtds: parse_rtds_config(&mut reader),
+        accounts: Vec::new(),
+        config_file,
+    };
+    env.accounts = parse_execution_accounts(&mut reader, &env.copy_strategy_config);
+    validate_numeric_config(&mut reader, &env);
+
+    reader.finish()?;
+    Ok(env)
+}
+
+/// Read `path` and build the configuration from it plus the environment
+pub fn load_env_from_file(path: &Path) -> Result<Env> {
+    let file = read_config_file(path)?;
+    build_env(file.values, file.errors, Some(path.to_path_buf()))
 }
 
 pub fn load_env() -> Result<Env> {
     dotenvy::dotenv().ok(); // Load .env file if it exists
 
-    // Run all validations
-    validate_required_env()?;
-    validate_addresses()?;
-    validate_numeric_config()?;
-    validate_urls()?;
-
-    let user_addresses_str = env::var("USER_ADDRESSES")
-        .context("USER_ADDRESSES is required")?;
-    let user_addresses = parse_user_addresses(&user_addresses_str)?;
-
-    Ok(Env {
-        user_addresses,
-        proxy_wallet: env::var("PROXY_WALLET").context("PROXY_WALLET is required")?,
-        private_key: env::var("PRIVATE_KEY").context("PRIVATE_KEY is required")?,
-        clob_http_url: env::var("CLOB_HTTP_URL").context("CLOB_HTTP_URL is required")?,
-        clob_ws_url: env::var("CLOB_WS_URL").context("CLOB_WS_URL is required")?,
-        fetch_interval: env::var("FETCH_INTERVAL")
-            .unwrap_or_else(|_| "1".to_string())
-            .parse::<u64>()
-            .unwrap_or(1),
-        too_old_timestamp: env::var("TOO_OLD_TIMESTAMP")
-            .unwrap_or_else(|_| "24".to_string())
-            .parse::<u64>()
-            .unwrap_or(24),
-        retry_limit: env::var("RETRY_LIMIT")
-            .unwrap_or_else(|_| "3".to_string())
-            .parse::<u32>()
-            .unwrap_or(3),
-        trade_multiplier: env::var("TRADE_MULTIPLIER")
-            .unwrap_or_else(|_| "1.0".to_string())
-            .parse::<f64>()
-            .unwrap_or(1.0),
-        copy_percentage: env::var("COPY_PERCENTAGE")
-            .unwrap_or_else(|_| "10.0".to_string())
-            .parse::<f64>()
-            .unwrap_or(10.0),
-        copy_strategy_config: parse_copy_strategy()?,
-        request_timeout_ms: env::var("REQUEST_TIMEOUT_MS")
-            .unwrap_or_else(|_| "10000".to_string())
-            .parse::<u64>()
-            .unwrap_or(10000),
-        network_retry_limit: env::var("NETWORK_RETRY_LIMIT")
-            .unwrap_or_else(|_| "3".to_string())
-            .parse::<u32>()
-            .unwrap_or(3),
-        trade_aggregation_enabled: env::var("TRADE_AGGREGATION_ENABLED")
-            .unwrap_or_else(|_| "false".to_string())
-            .parse::<bool>()
-            .unwrap_or(false),
-        trade_aggregation_window_seconds: env::var("TRADE_AGGREGATION_WINDOW_SECONDS")
-            .unwrap_or_else(|_| "300".to_string())
-            .parse::<u64>()
-            .unwrap_or(300),
-        mongo_uri: env::var("MONGO_URI").context("MONGO_URI is required")?,
-        rpc_url: env::var("RPC_URL").context("RPC_URL is required")?,
-        auto_claim_enabled: env::var("AUTO_CLAIM_ENABLED")
-            .unwrap_or_else(|_| "false".to_string())
-            .parse::<bool>()
-            .unwrap_or(false),
-        auto_claim_interval_ms: env::var("AUTO_CLAIM_INTERVAL_MS")
-            .unwrap_or_else(|_| "3600000".to_string())
-            .parse::<u64>()
-            .unwrap_or(3600000),
-        db_cleanup_enabled: env::var("DB_CLEANUP_ENABLED")
-            .unwrap_or_else(|_| "true".to_string())
-            .parse::<bool>()
-            .unwrap_or(true),
-        usdc_contract_address: env::var("USDC_CONTRACT_ADDRESS")
-            .context("USDC_CONTRACT_ADDRESS is required")?,
-        take_profit_percent: env::var("TAKE_PROFIT_PERCENT")
-            .ok()
-            .and_then(|v| v.parse::<f64>().ok())
-            .filter(|&v| v > 0.0),
-        stop_loss_percent: env::var("STOP_LOSS_PERCENT")
-            .ok()
-            .and_then(|v| v.parse::<f64>().ok())
-            .filter(|&v| v > 0.0),
-        tp_sl_check_interval_ms: env::var("TP_SL_CHECK_INTERVAL_MS")
-            .unwrap_or_else(|_| "1000".to_string())
-            .parse::<u64>()
-            .unwrap_or(1000),
-        preview_mode: env::var("PREVIEW_MODE")
-            .unwrap_or_else(|_| "true".to_string())
-            .parse::<bool>()
-            .unwrap_or(true),
-    })
-}
+    let result = match find_config_file()? {
+        Some(path) => load_env_from_file(&path),
+        None => build_env(HashMap::new(), Vec::new(), None),
+    };
 
+    if let Err(e) = &result {
+        eprintln!("\n❌ Configuration Error\n");
+        eprintln!("{}\n", e);
+        eprintln!("🔧 Quick fix:");
+        eprintln!("   1. Run the setup wizard: polycopy setup");
+        eprintln!("   2. Or set the variables in .env / polycopy.toml (see polycopy.example.toml)\n");
+        eprintln!("📖 See docs/QUICK_START.md for detailed instructions\n");
+    }
+    result
+}
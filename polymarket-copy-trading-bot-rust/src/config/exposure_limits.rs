@@ -0,0 +1,53 @@
+use super::reader::ConfigReader;
+
+/// Portfolio-level caps checked before every copied buy. Exposure is the
+/// current value of open positions plus resting buy orders. `None` disables a cap.
+#[derive(Debug, Clone, Default)]
+pub struct ExposureLimitsConfig {
+    pub max_total_exposure_usd: Option<f64>,
+    /// Across all outcomes / markets of the same event
+    pub max_event_exposure_usd: Option<f64>,
+    /// Per market category (Gamma category, or first tag)
+    pub max_category_exposure_usd: Option<f64>,
+    /// Positions opened by copying each trader (FIFO lots in the PnL ledger)
+    pub max_trader_exposure_usd: Option<f64>,
+    pub max_open_positions: Option<usize>,
+}
+
+impl ExposureLimitsConfig {
+    pub fn is_enabled(&self) -> bool {
+        self.max_total_exposure_usd.is_some()
+            || self.max_event_exposure_usd.is_some()
+            || self.max_category_exposure_usd.is_some()
+            || self.max_trader_exposure_usd.is_some()
+            || self.max_open_positions.is_some()
+    }
+}
+
+pub fn parse_exposure_limits(reader: &mut ConfigReader) -> ExposureLimitsConfig {
+    let max_open_positions = reader.optional::<usize>("MAX_OPEN_POSITIONS");
+    if max_open_positions == Some(0) {
+        reader.invalid("MAX_OPEN_POSITIONS", "must be a positive integer");
+    }
+
+    let config = ExposureLimitsConfig {
+        max_total_exposure_usd: reader.optional("MAX_TOTAL_EXPOSURE_USD"),
+        max_event_exposure_usd: reader.optional("MAX_EVENT_EXPOSURE_USD"),
+        max_category_exposure_usd: reader.optional("MAX_CATEGORY_EXPOSURE_USD"),
+        max_trader_exposure_usd: reader.optional("MAX_TRADER_EXPOSURE_USD"),
+        max_open_positions: max_open_positions.filter(|&n| n > 0),
+    };
+
+    for (key, value) in [
+        ("MAX_TOTAL_EXPOSURE_USD", config.max_total_exposure_usd),
+        ("MAX_EVENT_EXPOSURE_USD", config.max_event_exposure_usd),
+        ("MAX_CATEGORY_EXPOSURE_USD", config.max_category_exposure_usd),
+        ("MAX_TRADER_EXPOSURE_USD", config.max_trader_exposure_usd),
+    ] {
+        if value.is_some_and(|v| v <= 0.0) {
+            reader.invalid(key, "must be positive");
+        }
+    }
+
+    config
+}
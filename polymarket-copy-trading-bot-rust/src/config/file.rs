@@ -0,0 +1,308 @@
+use anyhow::{Context, Result};
+use serde_json::Value;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+/// Config files looked up in the working directory when CONFIG_FILE is unset
+pub const DEFAULT_CONFIG_FILES: [&str; 3] = ["polycopy.toml", "polycopy.yaml", "polycopy.yml"];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FieldKind {
+    String,
+    Bool,
+    Integer,
+    /// Integer or float
+    Number,
+    /// Array of strings (or one comma-separated string)
+    List,
+}
+
+/// One config file setting and the environment variable that overrides it
+#[derive(Debug, Clone, Copy)]
+pub struct ConfigField {
+    pub section: &'static str,
+    pub key: &'static str,
+    pub env: &'static str,
+    pub kind: FieldKind,
+    /// Applied by the config watcher without a restart
+    pub hot_reload: bool,
+}
+
+const fn field(section: &'static str, key: &'static str, env: &'static str, kind: FieldKind) -> ConfigField {
+    ConfigField { section, key, env, kind, hot_reload: false }
+}
+
+const fn hot(section: &'static str, key: &'static str, env: &'static str, kind: FieldKind) -> ConfigField {
+    ConfigField { section, key, env, kind, hot_reload: true }
+}
+
+use FieldKind::{Bool, Integer, List, Number, String as Str};
+use super::accounts::{account_env_key, ACCOUNT_SETTINGS};
+
+/// Section of `[accounts.<name>]` tables, one per extra execution account
+pub const ACCOUNTS_SECTION: &str = "accounts";
+
+pub const CONFIG_FIELDS: &[ConfigField] = &[
+    hot("traders", "addresses", "USER_ADDRESSES", List),
+    field("wallet", "proxy_wallet", "PROXY_WALLET", Str),
+    field("wallet", "private_key", "PRIVATE_KEY", Str),
+    field("wallet", "usdc_contract_address", "USDC_CONTRACT_ADDRESS", Str),
+    field("network", "clob_http_url", "CLOB_HTTP_URL", Str),
+    field("network", "clob_ws_url", "CLOB_WS_URL", Str),
+    field("network", "data_api_url", "DATA_API_URL", Str),
+    field("network", "gamma_api_url", "GAMMA_API_URL", Str),
+    field("network", "rtds_url", "RTDS_URL", Str),
+    field("network", "rpc_url", "RPC_URL", Str),
+    field("network", "mongo_uri", "MONGO_URI", Str),
+    field("network", "request_timeout_ms", "REQUEST_TIMEOUT_MS", Integer),
+    field("network", "retry_limit", "NETWORK_RETRY_LIMIT", Integer),
+    field("network", "rate_limit_per_second", "HTTP_RATE_LIMIT_PER_SECOND", Number),
+    field("network", "rate_limit_burst", "HTTP_RATE_LIMIT_BURST", Integer),
+    field("network", "cache_ttl_ms", "HTTP_CACHE_TTL_MS", Integer),
+    field("bot", "preview_mode", "PREVIEW_MODE", Bool),
+    field("bot", "fetch_interval", "FETCH_INTERVAL", Integer),
+    field("bot", "too_old_timestamp", "TOO_OLD_TIMESTAMP", Integer),
+    field("bot", "retry_limit", "RETRY_LIMIT", Integer),
+    field("bot", "db_cleanup_enabled", "DB_CLEANUP_ENABLED", Bool),
+    field("bot", "trade_aggregation_enabled", "TRADE_AGGREGATION_ENABLED", Bool),
+    field("bot", "trade_aggregation_window_seconds", "TRADE_AGGREGATION_WINDOW_SECONDS", Integer),
+    hot("strategy", "copy_strategy", "COPY_STRATEGY", Str),
+    hot("strategy", "copy_size", "COPY_SIZE", Number),
+    hot("strategy", "copy_percentage", "COPY_PERCENTAGE", Number),
+    hot("strategy", "trade_multiplier", "TRADE_MULTIPLIER", Number),
+    hot("strategy", "tiered_multipliers", "TIERED_MULTIPLIERS", List),
+    hot("strategy", "max_order_size_usd", "MAX_ORDER_SIZE_USD", Number),
+    hot("strategy", "min_order_size_usd", "MIN_ORDER_SIZE_USD", Number),
+    hot("strategy", "max_position_size_usd", "MAX_POSITION_SIZE_USD", Number),
+    hot("strategy", "max_daily_volume_usd", "MAX_DAILY_VOLUME_USD", Number),
+    hot("strategy", "adaptive_min_percent", "ADAPTIVE_MIN_PERCENT", Number),
+    hot("strategy", "adaptive_max_percent", "ADAPTIVE_MAX_PERCENT", Number),
+    hot("strategy", "adaptive_threshold_usd", "ADAPTIVE_THRESHOLD_USD", Number),
+    hot("strategy", "max_slippage_percent", "MAX_SLIPPAGE_PERCENT", Number),
+    hot("strategy", "max_slippage_abs", "MAX_SLIPPAGE_ABS", Number),
+    hot("filters", "include_tags", "FILTER_INCLUDE_TAGS", List),
+    hot("filters", "exclude_tags", "FILTER_EXCLUDE_TAGS", List),
+    hot("filters", "include_keywords", "FILTER_INCLUDE_KEYWORDS", List),
+    hot("filters", "exclude_keywords", "FILTER_EXCLUDE_KEYWORDS", List),
+    hot("filters", "min_liquidity_usd", "FILTER_MIN_LIQUIDITY_USD", Number),
+    hot("filters", "min_volume_usd", "FILTER_MIN_VOLUME_USD", Number),
+    hot("filters", "min_hours_to_end", "FILTER_MIN_HOURS_TO_END", Number),
+    hot("filters", "max_hours_to_end", "FILTER_MAX_HOURS_TO_END", Number),
+    hot("filters", "min_price", "FILTER_MIN_PRICE", Number),
+    hot("filters", "max_price", "FILTER_MAX_PRICE", Number),
+    hot("exposure", "max_total_usd", "MAX_TOTAL_EXPOSURE_USD", Number),
+    hot("exposure", "max_event_usd", "MAX_EVENT_EXPOSURE_USD", Number),
+    hot("exposure", "max_category_usd", "MAX_CATEGORY_EXPOSURE_USD", Number),
+    hot("exposure", "max_trader_usd", "MAX_TRADER_EXPOSURE_USD", Number),
+    hot("exposure", "max_open_positions", "MAX_OPEN_POSITIONS", Integer),
+    field("limit_orders", "mode", "COPY_ORDER_MODE", Str),
+    field("limit_orders", "order_type", "LIMIT_ORDER_TYPE", Str),
+    field("limit_orders", "price_offset", "LIMIT_PRICE_OFFSET", Number),
+    field("limit_orders", "timeout_secs", "LIMIT_ORDER_TIMEOUT_SECS", Integer),
+    field("limit_orders", "on_timeout", "LIMIT_ORDER_ON_TIMEOUT", Str),
+    field("limit_orders", "max_reprices", "LIMIT_ORDER_MAX_REPRICES", Integer),
+    field("limit_orders", "check_interval_ms", "LIMIT_ORDER_CHECK_INTERVAL_MS", Integer),
+    field("take_profit_stop_loss", "take_profit_percent", "TAKE_PROFIT_PERCENT", Number),
+    field("take_profit_stop_loss", "stop_loss_percent", "STOP_LOSS_PERCENT", Number),
+    field("take_profit_stop_loss", "check_interval_ms", "TP_SL_CHECK_INTERVAL_MS", Integer),
+    field("circuit_breaker", "max_drawdown_percent", "CIRCUIT_BREAKER_MAX_DRAWDOWN_PERCENT", Number),
+    field("circuit_breaker", "max_daily_loss_percent", "CIRCUIT_BREAKER_MAX_DAILY_LOSS_PERCENT", Number),
+    field("circuit_breaker", "max_daily_loss_usd", "CIRCUIT_BREAKER_MAX_DAILY_LOSS_USD", Number),
+    field("circuit_breaker", "hwm_window_days", "CIRCUIT_BREAKER_HWM_WINDOW_DAYS", Integer),
+    field("circuit_breaker", "flatten", "CIRCUIT_BREAKER_FLATTEN", Bool),
+    field("circuit_breaker", "check_interval_ms", "CIRCUIT_BREAKER_CHECK_INTERVAL_MS", Integer),
+    field("auto_claim", "enabled", "AUTO_CLAIM_ENABLED", Bool),
+    field("auto_claim", "interval_ms", "AUTO_CLAIM_INTERVAL_MS", Integer),
+    field("reconcile", "enabled", "RECONCILE_ENABLED", Bool),
+    field("reconcile", "interval_ms", "RECONCILE_INTERVAL_MS", Integer),
+    field("reconcile", "tolerance_tokens", "RECONCILE_TOLERANCE_TOKENS", Number),
+    field("activity", "enabled", "COPY_POSITION_ACTIVITY", Bool),
+    field("activity", "poll_interval_ms", "ACTIVITY_POLL_INTERVAL_MS", Integer),
+    field("paper_trading", "enabled", "PAPER_TRADING_ENABLED", Bool),
+    field("paper_trading", "starting_balance_usd", "PAPER_STARTING_BALANCE_USD", Number),
+    field("paper_trading", "mark_interval_ms", "PAPER_MARK_INTERVAL_MS", Integer),
+    field("health", "check_interval_seconds", "HEALTH_CHECK_INTERVAL_SECONDS", Integer),
+    field("health", "port", "HEALTH_PORT", Integer),
+    field("health", "bind_address", "HEALTH_BIND_ADDRESS", Str),
+    field("health", "min_gas_balance", "HEALTH_MIN_GAS_BALANCE", Number),
+    field("health", "max_clock_skew_seconds", "HEALTH_MAX_CLOCK_SKEW_SECONDS", Integer),
+    field("health", "rtds_max_silence_seconds", "HEALTH_RTDS_MAX_SILENCE_SECONDS", Integer),
+    field("rtds", "record_file", "RTDS_RECORD_FILE", Str),
+    field("rtds", "replay_file", "RTDS_REPLAY_FILE", Str),
+    field("rtds", "replay_speed", "RTDS_REPLAY_SPEED", Number),
+];
+
+pub fn config_field_by_env(env: &str) -> Option<&'static ConfigField> {
+    CONFIG_FIELDS.iter().find(|f| f.env == env)
+}
+
+/// CONFIG_FILE, or the first default config file in the working directory
+pub fn find_config_file() -> Result<Option<PathBuf>> {
+    if let Ok(path) = std::env::var("CONFIG_FILE") {
+        let path = PathBuf::from(path.trim());
+        if !path.is_file() {
+            anyhow::bail!("CONFIG_FILE not found: {}", path.display());
+        }
+        return Ok(Some(path));
+    }
+    Ok(DEFAULT_CONFIG_FILES
+        .iter()
+        .map(PathBuf::from)
+        .find(|p| p.is_file()))
+}
+
+fn value_to_string(value: &Value, kind: FieldKind) -> Option<String> {
+    match (kind, value) {
+        (FieldKind::String, Value::String(s)) => Some(s.clone()),
+        (FieldKind::Bool, Value::Bool(b)) => Some(b.to_string()),
+        (FieldKind::Integer, Value::Number(n)) if n.is_i64() || n.is_u64() => Some(n.to_string()),
+        (FieldKind::Number, Value::Number(n)) => Some(n.to_string()),
+        (FieldKind::List, Value::String(s)) => Some(s.clone()),
+        (FieldKind::List, Value::Array(items)) => items
+            .iter()
+            .map(|item| item.as_str().map(str::to_string))
+            .collect::<Option<Vec<_>>>()
+            .map(|items| items.join(",")),
+        _ => None,
+    }
+}
+
+fn expected(kind: FieldKind) -> &'static str {
+    match kind {
+        FieldKind::String => "a string",
+        FieldKind::Bool => "true or false",
+        FieldKind::Integer => "an integer",
+        FieldKind::Number => "a number",
+        FieldKind::List => "a list of strings",
+    }
+}
+
+/// Config file values keyed by environment variable name, plus the schema
+/// errors (unknown settings, wrong types) of the entries that were skipped
+#[derive(Debug, Clone, Default)]
+pub struct ConfigFileValues {
+    pub values: HashMap<String, String>,
+    pub errors: Vec<String>,
+}
+
+/// `[accounts.<name>]` tables: EXECUTION_ACCOUNTS plus ACCOUNT_<NAME>_* values
+fn flatten_accounts(accounts: &Value, values: &mut HashMap<String, String>, errors: &mut Vec<String>) {
+    let Some(accounts) = accounts.as_object() else {
+        errors.push(format!("[{}] must be a table of accounts", ACCOUNTS_SECTION));
+        return;
+    };
+    let mut names = Vec::new();
+    for (name, entries) in accounts {
+        let Some(entries) = entries.as_object() else {
+            errors.push(format!("[{}.{}] must be a table", ACCOUNTS_SECTION, name));
+            continue;
+        };
+        names.push(name.clone());
+        for (key, value) in entries {
+            let Some(&(_, suffix, kind)) = ACCOUNT_SETTINGS.iter().find(|(k, _, _)| k == key) else {
+                errors.push(format!("Unknown setting {}.{}.{}", ACCOUNTS_SECTION, name, key));
+                continue;
+            };
+            match value_to_string(value, kind) {
+                Some(v) => {
+                    values.insert(account_env_key(name, suffix), v);
+                }
+                None => errors.push(format!(
+                    "Invalid {}.{}.{}: expected {}, got {}",
+                    ACCOUNTS_SECTION,
+                    name,
+                    key,
+                    expected(kind),
+                    value
+                )),
+            }
+        }
+    }
+    values.insert("EXECUTION_ACCOUNTS".to_string(), names.join(","));
+}
+
+/// Check a parsed config document against the schema
+pub fn flatten_config(document: &Value) -> ConfigFileValues {
+    let mut values = HashMap::new();
+    let mut errors = Vec::new();
+
+    let Some(sections) = document.as_object() else {
+        errors.push("Config file must be a table of sections".to_string());
+        return ConfigFileValues { values, errors };
+    };
+    for (section, entries) in sections {
+        if section == ACCOUNTS_SECTION {
+            flatten_accounts(entries, &mut values, &mut errors);
+            continue;
+        }
+        if !CONFIG_FIELDS.iter().any(|f| f.section == section) {
+            errors.push(format!("Unknown section [{}]", section));
+            continue;
+        }
+        let Some(entries) = entries.as_object() else {
+            errors.push(format!("[{}] must be a table", section));
+            continue;
+        };
+        for (key, value) in entries {
+            let Some(field) = CONFIG_FIELDS.iter().find(|f| f.section == section && f.key == key) else {
+                errors.push(format!("Unknown setting {}.{}", section, key));
+                continue;
+            };
+            match value_to_string(value, field.kind) {
+                Some(v) => {
+                    values.insert(field.env.to_string(), v);
+                }
+                None => errors.push(format!(
+                    "Invalid {}.{}: expected {}, got {}",
+                    section,
+                    key,
+                    expected(field.kind),
+                    value
+                )),
+            }
+        }
+    }
+
+    ConfigFileValues { values, errors }
+}
+
+/// Parse a TOML or YAML (by extension) config file into environment-style values
+pub fn read_config_file(path: &Path) -> Result<ConfigFileValues> {
+    let text = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read config file {}", path.display()))?;
+    let extension = path.extension().and_then(|e| e.to_str()).unwrap_or("").to_lowercase();
+    let document: Value = match extension.as_str() {
+        "yaml" | "yml" => serde_yaml::from_str(&text)
+            .with_context(|| format!("Invalid YAML in {}", path.display()))?,
+        _ => serde_json::to_value(
+            toml::from_str::<toml::Table>(&text)
+                .with_context(|| format!("Invalid TOML in {}", path.display()))?,
+        )?,
+    };
+    Ok(flatten_config(&document))
+}
+
+/// Settings that changed between two reads of the file but need a restart
+/// (ignoring ones overridden by the environment)
+pub fn restart_required_changes(old: &HashMap<String, String>, new: &HashMap<String, String>) -> Vec<String> {
+    let mut changes: Vec<String> = CONFIG_FIELDS
+        .iter()
+        .filter(|f| !f.hot_reload && std::env::var(f.env).is_err())
+        .filter(|f| old.get(f.env) != new.get(f.env))
+        .map(|f| format!("{}.{}", f.section, f.key))
+        .collect();
+
+    // Accounts can't be added, removed or re-keyed while running; their strategy overrides reload
+    let accounts_changed = old
+        .keys()
+        .chain(new.keys())
+        .filter(|key| {
+            key.as_str() == "EXECUTION_ACCOUNTS"
+                || (key.starts_with("ACCOUNT_") && (key.ends_with("_PROXY_WALLET") || key.ends_with("_PRIVATE_KEY")))
+        })
+        .any(|key| std::env::var(key).is_err() && old.get(key) != new.get(key));
+    if accounts_changed {
+        changes.push(ACCOUNTS_SECTION.to_string());
+    }
+    changes
+}
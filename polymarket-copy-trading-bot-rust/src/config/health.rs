@@ -0,0 +1,45 @@
+use super::reader::ConfigReader;
+use std::net::{IpAddr, Ipv4Addr};
+
+/// Periodic health checks and the `/healthz` / `/readyz` endpoints
+#[derive(Debug, Clone)]
+pub struct HealthConfig {
+    /// Seconds between checks while the bot runs (0 = startup check only)
+    pub check_interval_seconds: u64,
+    /// Port for `/healthz` and `/readyz`; no server when unset
+    pub port: Option<u16>,
+    /// Address the endpoints listen on; localhost unless the probes come from elsewhere
+    pub bind_address: IpAddr,
+    /// MATIC the signer should hold for gas (approvals, redemptions, Safe transactions)
+    pub min_gas_balance: f64,
+    /// Largest allowed difference between local time and CLOB server time
+    pub max_clock_skew_seconds: u64,
+    /// RTDS is considered dead after this long without a message
+    pub rtds_max_silence_seconds: u64,
+}
+
+pub fn parse_health_config(reader: &mut ConfigReader) -> HealthConfig {
+    let config = HealthConfig {
+        check_interval_seconds: reader.parse_or("HEALTH_CHECK_INTERVAL_SECONDS", 60),
+        port: reader.optional("HEALTH_PORT"),
+        bind_address: reader.parse_or("HEALTH_BIND_ADDRESS", IpAddr::V4(Ipv4Addr::LOCALHOST)),
+        min_gas_balance: reader.parse_or("HEALTH_MIN_GAS_BALANCE", 0.1),
+        max_clock_skew_seconds: reader.parse_or("HEALTH_MAX_CLOCK_SKEW_SECONDS", 5),
+        rtds_max_silence_seconds: reader.parse_or("HEALTH_RTDS_MAX_SILENCE_SECONDS", 120),
+    };
+
+    if config.check_interval_seconds > 0 && config.check_interval_seconds < 10 {
+        reader.invalid("HEALTH_CHECK_INTERVAL_SECONDS", "must be 0 (disabled) or at least 10");
+    }
+    if config.port == Some(0) {
+        reader.invalid("HEALTH_PORT", "must be between 1 and 65535");
+    }
+    if config.min_gas_balance < 0.0 {
+        reader.invalid("HEALTH_MIN_GAS_BALANCE", "must not be negative");
+    }
+    if config.rtds_max_silence_seconds == 0 {
+        reader.invalid("HEALTH_RTDS_MAX_SILENCE_SECONDS", "must be positive");
+    }
+
+    config
+}
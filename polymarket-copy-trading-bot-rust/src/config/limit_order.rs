@@ -0,0 +1,121 @@
+use std::fmt;
+
+use super::reader::ConfigReader;
+
+/// How copied trades are sent to the book
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum CopyOrderMode {
+    /// Walk the book until filled (default)
+    #[default]
+    Market,
+    /// Rest a limit order near the trader's price and track it
+    Limit,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum LimitOrderType {
+    /// Good-til-cancelled
+    #[default]
+    Gtc,
+    /// Good-til-date, expires on its own after the timeout
+    Gtd,
+}
+
+/// What to do with a resting order that hasn't filled within the timeout
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TimeoutAction {
+    #[default]
+    Cancel,
+    /// Move the price towards the market (bounded by the slippage limit) and keep waiting
+    Reprice,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct LimitOrderConfig {
+    pub mode: CopyOrderMode,
+    pub order_type: LimitOrderType,
+    /// Distance from the trader's price; positive is more passive
+    /// (buy below / sell above), negative more aggressive
+    pub price_offset: f64,
+    pub timeout_secs: u64,
+    pub on_timeout: TimeoutAction,
+    pub max_reprices: u32,
+    pub check_interval_ms: u64,
+}
+
+impl LimitOrderConfig {
+    pub fn is_enabled(&self) -> bool {
+        self.mode == CopyOrderMode::Limit
+    }
+}
+
+impl fmt::Display for LimitOrderType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LimitOrderType::Gtc => write!(f, "GTC"),
+            LimitOrderType::Gtd => write!(f, "GTD"),
+        }
+    }
+}
+
+impl fmt::Display for TimeoutAction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TimeoutAction::Cancel => write!(f, "CANCEL"),
+            TimeoutAction::Reprice => write!(f, "REPRICE"),
+        }
+    }
+}
+
+fn parse_choice<T: Copy>(reader: &mut ConfigReader, key: &str, default: T, choices: &[(&str, T)]) -> T {
+    let Some(value) = reader.get(key) else {
+        return default;
+    };
+    match choices.iter().find(|(name, _)| name.eq_ignore_ascii_case(&value)) {
+        Some((_, choice)) => *choice,
+        None => {
+            let names: Vec<&str> = choices.iter().map(|(name, _)| *name).collect();
+            reader.invalid(key, format!("{} (expected {})", value, names.join(" or ")));
+            default
+        }
+    }
+}
+
+pub fn parse_limit_order_config(reader: &mut ConfigReader) -> LimitOrderConfig {
+    let config = LimitOrderConfig {
+        mode: parse_choice(
+            reader,
+            "COPY_ORDER_MODE",
+            CopyOrderMode::Market,
+            &[("MARKET", CopyOrderMode::Market), ("LIMIT", CopyOrderMode::Limit)],
+        ),
+        order_type: parse_choice(
+            reader,
+            "LIMIT_ORDER_TYPE",
+            LimitOrderType::Gtc,
+            &[("GTC", LimitOrderType::Gtc), ("GTD", LimitOrderType::Gtd)],
+        ),
+        price_offset: reader.parse_or("LIMIT_PRICE_OFFSET", 0.0),
+        timeout_secs: reader.parse_or("LIMIT_ORDER_TIMEOUT_SECS", 300),
+        on_timeout: parse_choice(
+            reader,
+            "LIMIT_ORDER_ON_TIMEOUT",
+            TimeoutAction::Cancel,
+            &[("CANCEL", TimeoutAction::Cancel), ("REPRICE", TimeoutAction::Reprice)],
+        ),
+        max_reprices: reader.parse_or("LIMIT_ORDER_MAX_REPRICES", 3),
+        check_interval_ms: reader.parse_or("LIMIT_ORDER_CHECK_INTERVAL_MS", 2000),
+    };
+
+    if config.price_offset.abs() >= 1.0 {
+        reader.invalid("LIMIT_PRICE_OFFSET", "must be between -1 and 1");
+    }
+    if config.timeout_secs == 0 {
+        reader.invalid("LIMIT_ORDER_TIMEOUT_SECS", "must be positive");
+    }
+    if config.check_interval_ms < 500 {
+        reader.invalid("LIMIT_ORDER_CHECK_INTERVAL_MS", "must be at least 500ms");
+    }
+
+    config
+}
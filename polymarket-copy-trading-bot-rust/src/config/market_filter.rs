@@ -0,0 +1,73 @@
+use super::reader::ConfigReader;
+
+/// Market-level filters applied to trades before they are copied.
+/// Empty lists and `None` bounds disable the corresponding filter.
+#[derive(Debug, Clone, Default)]
+pub struct MarketFilterConfig {
+    /// Only copy markets with at least one of these tags / categories
+    pub include_tags: Vec<String>,
+    /// Never copy markets with any of these tags / categories (e.g. "sports")
+    pub exclude_tags: Vec<String>,
+    /// Only copy markets whose title or slug contains one of these keywords
+    pub include_keywords: Vec<String>,
+    /// Never copy markets whose title or slug contains one of these keywords
+    pub exclude_keywords: Vec<String>,
+    pub min_liquidity_usd: Option<f64>,
+    pub min_volume_usd: Option<f64>,
+    /// Skip markets resolving sooner than this
+    pub min_hours_to_end: Option<f64>,
+    /// Skip markets resolving later than this
+    pub max_hours_to_end: Option<f64>,
+    /// Don't buy below this price
+    pub min_price: Option<f64>,
+    /// Don't buy above this price
+    pub max_price: Option<f64>,
+}
+
+impl MarketFilterConfig {
+    pub fn is_enabled(&self) -> bool {
+        self.needs_market_data()
+            || !self.include_keywords.is_empty()
+            || !self.exclude_keywords.is_empty()
+            || self.min_price.is_some()
+            || self.max_price.is_some()
+    }
+
+    /// Whether any filter requires Gamma market metadata
+    pub fn needs_market_data(&self) -> bool {
+        !self.include_tags.is_empty()
+            || !self.exclude_tags.is_empty()
+            || self.min_liquidity_usd.is_some()
+            || self.min_volume_usd.is_some()
+            || self.min_hours_to_end.is_some()
+            || self.max_hours_to_end.is_some()
+    }
+}
+
+pub fn parse_market_filter(reader: &mut ConfigReader) -> MarketFilterConfig {
+    let config = MarketFilterConfig {
+        include_tags: reader.list("FILTER_INCLUDE_TAGS"),
+        exclude_tags: reader.list("FILTER_EXCLUDE_TAGS"),
+        include_keywords: reader.list("FILTER_INCLUDE_KEYWORDS"),
+        exclude_keywords: reader.list("FILTER_EXCLUDE_KEYWORDS"),
+        min_liquidity_usd: reader.optional("FILTER_MIN_LIQUIDITY_USD"),
+        min_volume_usd: reader.optional("FILTER_MIN_VOLUME_USD"),
+        min_hours_to_end: reader.optional("FILTER_MIN_HOURS_TO_END"),
+        max_hours_to_end: reader.optional("FILTER_MAX_HOURS_TO_END"),
+        min_price: reader.optional("FILTER_MIN_PRICE"),
+        max_price: reader.optional("FILTER_MAX_PRICE"),
+    };
+
+    if let (Some(min), Some(max)) = (config.min_price, config.max_price) {
+        if min >= max {
+            reader.error("FILTER_MIN_PRICE must be below FILTER_MAX_PRICE");
+        }
+    }
+    if let (Some(min), Some(max)) = (config.min_hours_to_end, config.max_hours_to_end) {
+        if min >= max {
+            reader.error("FILTER_MIN_HOURS_TO_END must be below FILTER_MAX_HOURS_TO_END");
+        }
+    }
+
+    config
+}
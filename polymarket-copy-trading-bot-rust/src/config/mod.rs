@@ -1,8 +1,25 @@
 pub mod env;
 pub mod copy_strategy;
 pub mod db;
+pub mod market_filter;
+pub mod limit_order;
+pub mod exposure_limits;
+pub mod circuit_breaker;
+pub mod paper_trading;
+pub mod health;
+pub mod rtds;
+pub mod accounts;
+pub mod file;
+pub mod reader;
 
 pub use env::*;
 pub use copy_strategy::*;
 pub use db::*;
+pub use market_filter::*;
+pub use limit_order::*;
+pub use exposure_limits::*;
+pub use circuit_breaker::*;
+pub use paper_trading::*;
+pub use accounts::*;
+pub use file::*;
 
@@ -0,0 +1,29 @@
+use super::reader::ConfigReader;
+
+/// Paper trading: copy orders fill against live order book depth and are
+/// applied to a virtual USDC balance and positions kept in the database.
+#[derive(Debug, Clone, Default)]
+pub struct PaperTradingConfig {
+    pub enabled: bool,
+    /// Virtual USDC the account starts with (and is reset to)
+    pub starting_balance_usd: f64,
+    /// How often positions are marked to market and checked for resolution
+    pub mark_interval_ms: u64,
+}
+
+pub fn parse_paper_trading(reader: &mut ConfigReader) -> PaperTradingConfig {
+    let config = PaperTradingConfig {
+        enabled: reader.parse_or("PAPER_TRADING_ENABLED", false),
+        starting_balance_usd: reader.parse_or("PAPER_STARTING_BALANCE_USD", 1000.0),
+        mark_interval_ms: reader.parse_or("PAPER_MARK_INTERVAL_MS", 60000),
+    };
+
+    if config.starting_balance_usd <= 0.0 {
+        reader.invalid("PAPER_STARTING_BALANCE_USD", "must be positive");
+    }
+    if config.mark_interval_ms < 1000 {
+        reader.invalid("PAPER_MARK_INTERVAL_MS", "must be at least 1000ms");
+    }
+
+    config
+}
@@ -0,0 +1,96 @@
+use anyhow::Result;
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::str::FromStr;
+
+use super::file::config_field_by_env;
+
+/// Configuration values keyed by environment variable name: the process
+/// environment (including `.env`) first, then the config file.
+/// Parse and validation errors are collected so they can be reported together.
+pub struct ConfigReader {
+    file_values: HashMap<String, String>,
+    errors: Vec<String>,
+}
+
+impl ConfigReader {
+    pub fn new(file_values: HashMap<String, String>) -> Self {
+        Self { file_values, errors: Vec::new() }
+    }
+
+    /// Raw value, `None` when unset or blank
+    pub fn get(&self, key: &str) -> Option<String> {
+        std::env::var(key)
+            .ok()
+            .or_else(|| self.file_values.get(key).cloned())
+            .map(|v| v.trim().to_string())
+            .filter(|v| !v.is_empty())
+    }
+
+    pub fn is_set(&self, key: &str) -> bool {
+        self.get(key).is_some()
+    }
+
+    /// Name used in error messages: the file key when the value came from the file
+    pub fn label(&self, key: &str) -> String {
+        let from_file = std::env::var(key).is_err() && self.file_values.contains_key(key);
+        match config_field_by_env(key) {
+            Some(field) if from_file => format!("{}.{} ({})", field.section, field.key, key),
+            _ => key.to_string(),
+        }
+    }
+
+    pub fn error(&mut self, message: impl Into<String>) {
+        self.errors.push(message.into());
+    }
+
+    /// Record "Invalid KEY: reason"
+    pub fn invalid(&mut self, key: &str, reason: impl Display) {
+        let message = format!("Invalid {}: {}", self.label(key), reason);
+        self.errors.push(message);
+    }
+
+    pub fn optional<T: FromStr>(&mut self, key: &str) -> Option<T>
+    where
+        T::Err: Display,
+    {
+        let value = self.get(key)?;
+        match value.parse::<T>() {
+            Ok(parsed) => Some(parsed),
+            Err(e) => {
+                self.invalid(key, format!("{:?} ({})", value, e));
+                None
+            }
+        }
+    }
+
+    pub fn parse_or<T: FromStr>(&mut self, key: &str, default: T) -> T
+    where
+        T::Err: Display,
+    {
+        self.optional(key).unwrap_or(default)
+    }
+
+    /// Comma-separated list, trimmed and lowercased
+    pub fn list(&self, key: &str) -> Vec<String> {
+        self.get(key)
+            .unwrap_or_default()
+            .split(',')
+            .map(|s| s.trim().to_lowercase())
+            .filter(|s| !s.is_empty())
+            .collect()
+    }
+
+    /// Fail with every collected error
+    pub fn finish(self) -> Result<()> {
+        if self.errors.is_empty() {
+            return Ok(());
+        }
+        anyhow::bail!(
+            "Invalid configuration ({} error{}):\n  - {}",
+            self.errors.len(),
+            if self.errors.len() == 1 { "" } else { "s" },
+            self.errors.join("\n  - ")
+        )
+    }
+}
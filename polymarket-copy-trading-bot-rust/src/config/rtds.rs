@@ -0,0 +1,30 @@
+use super::reader::ConfigReader;
+use std::path::PathBuf;
+
+/// Recording the RTDS stream and replaying recordings through the trade monitor
+#[derive(Debug, Clone, Default)]
+pub struct RtdsConfig {
+    /// Append every RTDS text frame, with its receive time, to this file
+    pub record_file: Option<PathBuf>,
+    /// Feed this recording to the trade monitor instead of connecting (preview mode only)
+    pub replay_file: Option<PathBuf>,
+    /// Replay speed multiplier: 1 = recorded pace, 10 = ten times faster, 0 = no delays
+    pub replay_speed: f64,
+}
+
+pub fn parse_rtds_config(reader: &mut ConfigReader) -> RtdsConfig {
+    let config = RtdsConfig {
+        record_file: reader.get("RTDS_RECORD_FILE").map(PathBuf::from),
+        replay_file: reader.get("RTDS_REPLAY_FILE").map(PathBuf::from),
+        replay_speed: reader.parse_or("RTDS_REPLAY_SPEED", 1.0),
+    };
+
+    if config.replay_speed < 0.0 || !config.replay_speed.is_finite() {
+        reader.invalid("RTDS_REPLAY_SPEED", "must be 0 (no delays) or a positive multiplier");
+    }
+    if config.record_file.is_some() && config.record_file == config.replay_file {
+        reader.invalid("RTDS_RECORD_FILE", "must differ from RTDS_REPLAY_FILE");
+    }
+
+    config
+}
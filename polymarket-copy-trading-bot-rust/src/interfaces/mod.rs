@@ -83,11 +83,47 @@ pub struct RtdsActivity {
     pub outcome: Option<String>,
     pub name: Option<String>,
     pub transaction_hash: Option<String>,
+    /// Sent by the Data API `/activity` endpoint, which shares this shape
+    #[serde(rename = "usdcSize")]
+    pub usdc: Option<f64>,
 }
 
 impl RtdsActivity {
     pub fn usdc_size(&self) -> f64 {
-        self.size.unwrap_or(0.0) * self.price.unwrap_or(0.0)
+        self.usdc
+            .unwrap_or_else(|| self.size.unwrap_or(0.0) * self.price.unwrap_or(0.0))
+    }
+}
+
+impl From<&RtdsActivity> for UserActivity {
+    fn from(activity: &RtdsActivity) -> Self {
+        UserActivity {
+            id: None,
+            proxy_wallet: activity.proxy_wallet.clone().unwrap_or_default(),
+            timestamp: activity.timestamp.unwrap_or(0),
+            condition_id: activity.condition_id.clone().unwrap_or_default(),
+            r#type: activity.activity_type.clone().unwrap_or_default(),
+            size: activity.size.unwrap_or(0.0),
+            usdc_size: activity.usdc_size(),
+            transaction_hash: activity.transaction_hash.clone().unwrap_or_default(),
+            price: activity.price.unwrap_or(0.0),
+            asset: activity.asset.clone().unwrap_or_default(),
+            side: activity.side.clone().unwrap_or_default(),
+            outcome_index: activity.outcome_index.unwrap_or(0),
+            title: activity.title.clone().unwrap_or_default(),
+            slug: activity.slug.clone().unwrap_or_default(),
+            icon: activity.icon.clone().unwrap_or_default(),
+            event_slug: activity.event_slug.clone().unwrap_or_default(),
+            outcome: activity.outcome.clone().unwrap_or_default(),
+            name: activity.name.clone().unwrap_or_default(),
+            pseudonym: String::new(),
+            bio: String::new(),
+            profile_image: String::new(),
+            profile_image_optimized: String::new(),
+            bot: false,
+            bot_executed_time: 0,
+            my_bought_size: None,
+        }
     }
 }
 
@@ -4,12 +4,21 @@ mod services;
 mod utils;
 
 use anyhow::Result;
-use crate::config::{load_env, connect_db, cleanup_database};
-use crate::services::{start_trade_monitor, start_trade_executor, start_auto_claim, start_take_profit_stop_loss};
-use crate::utils::{create_clob_client, perform_health_check, log_health_check, Logger};
+use crate::config::{load_env, connect_db, cleanup_database, Env};
+use crate::services::{start_trade_monitor, start_trade_executor, start_auto_claim, start_take_profit_stop_loss, start_limit_order_tracker, start_circuit_breaker, start_reconciliation, start_config_watcher, start_paper_trading, start_activity_mirror, connect_execution_accounts, start_health_monitor, serve_health_endpoints};
+use crate::services::accounts::AccountExecutor;
+use crate::services::circuit_breaker::buying_halted;
+use crate::utils::{perform_health_check, log_health_check, Logger};
+use crate::utils::allowances::ensure_trading_allowances;
 use tokio::signal;
-use std::sync::Arc;
+use tokio::sync::watch;
+use std::future::Future;
+use std::sync::{Arc, RwLock};
 use colored::Colorize;
+use mongodb::Database;
+use polymarket_client_sdk::auth::state::Authenticated;
+use polymarket_client_sdk::auth::Normal;
+use polymarket_client_sdk::clob::Client as ClobClient;
 
 #[tokio::main]
 async fn main() -> Result<()> {
@@ -19,7 +28,7 @@ async fn main() -> Result<()> {
     // Welcome message
     println!("\n{} First time running the bot?", "💡".yellow());
     println!("   Read the guide: {}", "GETTING_STARTED.md".cyan());
-    println!("   Run health check: {}\n", "cargo run --bin health_check".cyan());
+    println!("   Run health check: {}\n", "polycopy health check".cyan());
     
     // Connect to database
     let db = Arc::new(connect_db(&env.mongo_uri).await?);
@@ -30,6 +39,13 @@ async fn main() -> Result<()> {
     }
     
     Logger::startup(&env.user_addresses, &env.proxy_wallet);
+    for account in &env.accounts {
+        Logger::info(&format!(
+            "Also copying into account {} ({})",
+            account.name,
+            Logger::format_address(&account.proxy_wallet)
+        ));
+    }
     
     // Check if user tried to set PREVIEW_MODE=false (live trading is premium only)
     if !env.preview_mode {
@@ -54,55 +70,105 @@ async fn main() -> Result<()> {
     println!("   {}", "To contact the developer for this premium version, please contact \"https://t.me/soulcrancerdev.\"".bright_cyan().bold());
     println!();
     
+    // Initialize a CLOB client per execution account
+    Logger::info("Initializing CLOB client...");
+    let accounts = Arc::new(connect_execution_accounts(&env, &db).await?);
+    if accounts.len() > 1 {
+        let names: Vec<&str> = accounts.iter().map(|a| a.name()).collect();
+        Logger::success(&format!("CLOB clients ready for {} accounts: {}", accounts.len(), names.join(", ")));
+    } else {
+        Logger::success("CLOB client ready");
+    }
+
     // Perform initial health check
     Logger::info("Performing initial health check...");
-    let health_result = perform_health_check(&db, &env).await?;
+    let health_result = perform_health_check(&db, &env, Some(&accounts[0].clob_client)).await?;
     log_health_check(&health_result);
     
     if !health_result.healthy {
         Logger::warning("Health check failed, but continuing startup...");
     }
-    
-    // Initialize CLOB client
-    Logger::info("Initializing CLOB client...");
-    let (clob_client, signer) = create_clob_client(&env).await?;
-    let clob_client = Arc::new(clob_client);
-    let signer = Arc::new(signer);
-    Logger::success("CLOB client ready");
+
+    Logger::info("Checking trading allowances...");
+    for executor in accounts.iter() {
+        match executor.scope(ensure_trading_allowances(&executor.env(&env))).await {
+            Ok(()) => {}
+            Err(e) if env.preview_mode => {
+                executor.scope(async { Logger::warning(&format!("Could not check trading allowances: {}", e)) }).await
+            }
+            Err(e) => return Err(e.context(format!("Failed to set trading allowances for account {}", executor.name()))),
+        }
+    }
+
+    if env.market_filter.is_enabled() {
+        Logger::info("Market filters enabled for copied buys (skips are recorded in skipped_trades)");
+    }
+    if env.exposure_limits.is_enabled() {
+        Logger::info("Portfolio exposure limits enabled for copied buys");
+    }
+    for executor in accounts.iter() {
+        if let Some(reason) = buying_halted(&executor.db).await? {
+            executor.scope(async {
+                Logger::warning(&format!("⛔ Circuit breaker is tripped ({}) - new buys stay paused", reason));
+                Logger::warning("Resume with: polycopy health breaker resume");
+            }).await;
+        }
+    }
     
     Logger::separator();
+
+    // Trader list, strategy, filters and exposure limits can be hot-reloaded from the config file
+    let (env_tx, env_rx) = watch::channel(env.clone());
+    if let Some(config_file) = &env.config_file {
+        Logger::info(&format!("Loaded settings from {} (environment variables override it)", config_file.display()));
+        let _config_watcher_handle = tokio::spawn(async move {
+            if let Err(e) = start_config_watcher(env_tx).await {
+                Logger::error(&format!("Config watcher stopped: {}", e));
+            }
+        });
+    }
     
     // Start services
     Logger::info("Starting trade executor...");
     let _executor_handle = {
-        let clob_client = clob_client.clone();
-        let env = env.clone();
+        let clob_client = accounts[0].clob_client.clone();
+        let env_rx = env_rx.clone();
         let db = db.clone();
         tokio::spawn(async move {
-            start_trade_executor(clob_client, env, db).await
+            start_trade_executor(clob_client, env_rx, db).await
         })
     };
     
     Logger::info("Starting trade monitor...");
     let _monitor_handle = {
-        let env = env.clone();
+        let env_rx = env_rx.clone();
         let db = db.clone();
-        let clob_client = clob_client.clone();
-        let signer = signer.clone();
+        let accounts = accounts.clone();
         tokio::spawn(async move {
-            start_trade_monitor(env, db, clob_client, signer).await
+            start_trade_monitor(env_rx, db, accounts).await
         })
     };
     
-    // Start auto-claim service if enabled
-    if env.auto_claim_enabled {
-        Logger::info("Starting auto-claim service...");
-        let _claim_handle = {
-            let env = env.clone();
+    // Mirror merges, splits, redemptions and conversions of the traders
+    // (a replay only sees what was recorded, so live polling stays off)
+    if env.copy_position_activity && env.rtds.replay_file.is_none() {
+        Logger::info("Starting activity mirror...");
+        let _activity_handle = {
+            let env_rx = env_rx.clone();
+            let accounts = accounts.clone();
             tokio::spawn(async move {
-                start_auto_claim(env).await
+                start_activity_mirror(env_rx, accounts).await
             })
         };
+    } else {
+        Logger::info("Activity mirror is disabled (set COPY_POSITION_ACTIVITY=true to copy merges, splits and redemptions)");
+    }
+    
+    // Wallet services run once per execution account
+    // Start auto-claim service if enabled
+    if env.auto_claim_enabled {
+        Logger::info("Starting auto-claim service...");
+        spawn_per_account(&accounts, &env, |env, _, _| start_auto_claim(env));
     } else {
         Logger::info("Auto-claim service is disabled (set AUTO_CLAIM_ENABLED=true to enable)");
     }
@@ -110,16 +176,63 @@ async fn main() -> Result<()> {
     // Start Take Profit / Stop Loss monitor (if configured)
     if env.take_profit_percent.is_some() || env.stop_loss_percent.is_some() {
         Logger::info("Starting Take Profit / Stop Loss monitor...");
-        let _tp_sl_handle = {
-            let clob_client = clob_client.clone();
-            let env = env.clone();
-            tokio::spawn(async move {
-                start_take_profit_stop_loss(clob_client, env).await
-            })
-        };
+        spawn_per_account(&accounts, &env, |env, _, clob_client| start_take_profit_stop_loss(clob_client, env));
     } else {
         Logger::info("Take Profit / Stop Loss monitor disabled (set TAKE_PROFIT_PERCENT and/or STOP_LOSS_PERCENT in .env to enable)");
     }
+
+    // Start drawdown circuit breaker (if configured)
+    if env.circuit_breaker.is_enabled() {
+        Logger::info("Starting circuit breaker...");
+        spawn_per_account(&accounts, &env, |env, db, _| start_circuit_breaker(env, db));
+    } else {
+        Logger::info("Circuit breaker disabled (set CIRCUIT_BREAKER_MAX_DRAWDOWN_PERCENT and/or CIRCUIT_BREAKER_MAX_DAILY_LOSS_* in .env to enable)");
+    }
+
+    // Start position reconciliation service if enabled
+    if env.reconcile_enabled {
+        Logger::info("Starting position reconciliation service...");
+        spawn_per_account(&accounts, &env, |env, db, _| start_reconciliation(env, db));
+    } else {
+        Logger::info("Position reconciliation is disabled (set RECONCILE_ENABLED=true to enable)");
+    }
+
+    // Mark paper positions to market and settle them at resolution
+    if env.paper_trading.enabled {
+        Logger::info("Starting paper trading account...");
+        spawn_per_account(&accounts, &env, |env, db, _| start_paper_trading(env, db));
+    }
+
+    // Start limit order tracker for passive copy mode
+    if env.limit_orders.is_enabled() {
+        Logger::info("Starting limit order tracker...");
+        spawn_per_account(&accounts, &env, |env, db, _| start_limit_order_tracker(env, db));
+    }
+    
+    // Keep checking health while running and expose it to the orchestrator
+    let health = Arc::new(RwLock::new(health_result));
+    let check_interval = env.health.check_interval_seconds;
+    if check_interval > 0 {
+        let env = env.clone();
+        let db = db.clone();
+        let clob_client = accounts[0].clob_client.clone();
+        let health = health.clone();
+        tokio::spawn(async move {
+            if let Err(e) = start_health_monitor(env, db, clob_client, health).await {
+                Logger::error(&format!("Health monitor stopped: {}", e));
+            }
+        });
+    }
+    if let Some(port) = env.health.port {
+        // Liveness fails once a few periodic checks have been missed
+        let max_age = (check_interval > 0).then_some(check_interval * 3 + 60);
+        let address = std::net::SocketAddr::new(env.health.bind_address, port);
+        tokio::spawn(async move {
+            if let Err(e) = serve_health_endpoints(address, health, max_age).await {
+                Logger::error(&format!("Health endpoints stopped: {}", e));
+            }
+        });
+    }
     
     // Wait for shutdown signal
     match signal::ctrl_c().await {
@@ -139,3 +252,17 @@ async fn main() -> Result<()> {
     Logger::success("Graceful shutdown completed");
     Ok(())
 }
+
+/// Spawn one instance of a wallet service per execution account, with the
+/// account's settings, database and CLOB client, its log lines tagged
+fn spawn_per_account<F, Fut>(accounts: &Arc<Vec<AccountExecutor>>, env: &Env, service: F)
+where
+    F: Fn(Arc<Env>, Arc<Database>, Arc<ClobClient<Authenticated<Normal>>>) -> Fut,
+    Fut: Future<Output = Result<()>> + Send + 'static,
+{
+    for (index, executor) in accounts.iter().enumerate() {
+        let future = service(Arc::new(executor.env(env)), executor.db.clone(), executor.clob_client.clone());
+        let accounts = accounts.clone();
+        tokio::spawn(async move { accounts[index].scope(future).await });
+    }
+}
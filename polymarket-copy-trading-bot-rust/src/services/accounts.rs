@@ -0,0 +1,115 @@
+use anyhow::{Context, Result};
+use alloy::signers::local::PrivateKeySigner;
+use futures_util::future::join_all;
+use mongodb::Database;
+use polymarket_client_sdk::auth::state::Authenticated;
+use polymarket_client_sdk::auth::Normal;
+use polymarket_client_sdk::clob::Client as ClobClient;
+use std::future::Future;
+use std::sync::Arc;
+use crate::config::{get_account_database, Env, ExecutionAccount, PRIMARY_ACCOUNT};
+use crate::interfaces::UserActivity;
+use crate::services::activity_mirror::mirror_position_activity;
+use crate::services::trade_executor::execute_trade_directly;
+use crate::utils::{create_clob_client, logger::Logger};
+
+/// One execution account ready to trade: its CLOB session, signer and
+/// database (ledger, paper account, limit orders and breaker state)
+pub struct AccountExecutor {
+    pub account: ExecutionAccount,
+    pub db: Arc<Database>,
+    pub clob_client: Arc<ClobClient<Authenticated<Normal>>>,
+    pub signer: Arc<PrivateKeySigner>,
+    /// Log lines are tagged with the account name when copying into several accounts
+    tagged: bool,
+}
+
+impl AccountExecutor {
+    pub fn name(&self) -> &str {
+        &self.account.name
+    }
+
+    /// The latest settings as seen by this account (its strategy overrides
+    /// may have been hot-reloaded)
+    pub fn env(&self, env: &Env) -> Env {
+        let account = env
+            .execution_accounts()
+            .into_iter()
+            .find(|a| a.name == self.account.name)
+            .unwrap_or_else(|| self.account.clone());
+        env.for_account(&account)
+    }
+
+    /// Run `future` for this account, tagging its log lines
+    pub async fn scope<F: Future>(&self, future: F) -> F::Output {
+        if self.tagged {
+            Logger::account_scope(self.name(), future).await
+        } else {
+            future.await
+        }
+    }
+}
+
+/// Create a CLOB session for the primary account and every EXECUTION_ACCOUNTS entry
+pub async fn connect_execution_accounts(env: &Env, db: &Arc<Database>) -> Result<Vec<AccountExecutor>> {
+    let accounts = env.execution_accounts();
+    let tagged = accounts.len() > 1;
+    let mut executors = Vec::with_capacity(accounts.len());
+    for account in accounts {
+        let (clob_client, signer) = create_clob_client(&env.for_account(&account))
+            .await
+            .with_context(|| format!("Failed to create CLOB client for account {}", account.name))?;
+        let account_db = if account.name == PRIMARY_ACCOUNT {
+            db.clone()
+        } else {
+            Arc::new(get_account_database(db, &account.name))
+        };
+        executors.push(AccountExecutor {
+            account,
+            db: account_db,
+            clob_client: Arc::new(clob_client),
+            signer: Arc::new(signer),
+            tagged,
+        });
+    }
+    Ok(executors)
+}
+
+/// Copy one trade into every account concurrently. A failure in one account
+/// doesn't affect the others.
+pub async fn dispatch_trade(activity: &UserActivity, trader: &str, accounts: &[AccountExecutor], env: &Env) {
+    join_all(accounts.iter().map(|executor| {
+        executor.scope(async move {
+            let env = executor.env(env);
+            if let Err(e) =
+                execute_trade_directly(activity, trader, &executor.clob_client, &env, &executor.db, &executor.signer).await
+            {
+                Logger::error(&format!("Error processing trade: {}", e));
+            }
+        })
+    }))
+    .await;
+}
+
+/// Mirror one MERGE / SPLIT / REDEEM / CONVERSION, received at `received_at`
+/// (ms), into every account concurrently
+pub async fn dispatch_position_activity(
+    activity: &UserActivity,
+    trader: &str,
+    received_at: i64,
+    accounts: &[AccountExecutor],
+    env: &Env,
+) {
+    join_all(accounts.iter().map(|executor| {
+        executor.scope(async move {
+            let env = executor.env(env);
+            if let Err(e) =
+                mirror_position_activity(activity, trader, received_at, &executor.clob_client, &env, &executor.db, &executor.signer)
+                    .await
+            {
+                Logger::error(&format!("Error mirroring {}: {}", activity.r#type, e));
+            }
+        })
+    }))
+    .await;
+}
@@ -0,0 +1,641 @@
+use anyhow::Result;
+use futures_util::TryStreamExt;
+use alloy::primitives::{B256, U256};
+use alloy::signers::local::PrivateKeySigner;
+use mongodb::bson::doc;
+use mongodb::options::UpdateOptions;
+use mongodb::Database;
+use polymarket_client_sdk::auth::state::Authenticated;
+use polymarket_client_sdk::auth::Normal;
+use polymarket_client_sdk::clob::Client as ClobClient;
+use std::collections::HashMap;
+use std::sync::Arc;
+use tokio::sync::watch;
+use tokio::time::{interval, Duration};
+use crate::config::{calculate_order_size, get_user_activity_collection, Env};
+use crate::interfaces::{UserActivity, UserPosition};
+use crate::services::accounts::{dispatch_position_activity, AccountExecutor};
+use crate::services::circuit_breaker::buying_halted;
+use crate::services::paper_trading::{
+    apply_paper_fill, load_paper_account, load_paper_positions, mark_paper_account, PaperPosition,
+};
+use crate::services::pnl_ledger::{record_ledger_entry, LedgerEntry, KIND_BUY, KIND_SELL};
+use crate::services::trade_monitor::is_too_old;
+use crate::utils::gamma_api::{fetch_markets_by_condition_ids, GammaMarket};
+use crate::utils::gnosis_safe::{
+    ctf_merge_positions, ctf_redeem_positions, ctf_split_position, neg_risk_convert_positions,
+    neg_risk_merge_positions, neg_risk_redeem_positions, neg_risk_split_position, to_base_units, SafeCall,
+    SafeExecutor,
+};
+use crate::utils::market_resolution::MarketResolution;
+use crate::utils::{get_my_balance, logger::Logger, post_order, ActivityQuery, DataApiClient, PositionsQuery};
+
+pub const ACTIVITY_MERGE: &str = "MERGE";
+pub const ACTIVITY_SPLIT: &str = "SPLIT";
+pub const ACTIVITY_REDEEM: &str = "REDEEM";
+pub const ACTIVITY_CONVERSION: &str = "CONVERSION";
+
+/// Non-trade activity mirrored here; trades go through the trade executor
+pub const POSITION_ACTIVITY_TYPES: [&str; 4] = [ACTIVITY_MERGE, ACTIVITY_SPLIT, ACTIVITY_REDEEM, ACTIVITY_CONVERSION];
+
+const MIRROR_PENDING: &str = "pending";
+const MIRROR_RUNNING: &str = "running";
+const MIRROR_DONE: &str = "done";
+const MIRROR_FAILED: &str = "failed";
+/// Mirror attempts per event before it is left failed
+const MAX_MIRROR_ATTEMPTS: i32 = 3;
+
+/// Smallest merge / conversion worth a transaction
+const MIN_MIRROR_TOKENS: f64 = 1.0;
+const SIZE_EPSILON: f64 = 1e-9;
+
+pub fn is_position_activity(activity_type: &str) -> bool {
+    POSITION_ACTIVITY_TYPES.iter().any(|t| t.eq_ignore_ascii_case(activity_type))
+}
+
+/// Share of the trader's holding an event used up: `amount` out of what
+/// they held before it (`held_after + amount`)
+pub fn event_fraction(amount: f64, held_after: f64) -> f64 {
+    if amount <= 0.0 {
+        return 0.0;
+    }
+    (amount / (held_after.max(0.0) + amount)).min(1.0)
+}
+
+/// Full sets (one token of every outcome) that can be merged from `sizes`
+pub fn complete_sets(sizes: &[f64]) -> f64 {
+    if sizes.len() < 2 {
+        return 0.0;
+    }
+    sizes.iter().copied().fold(f64::INFINITY, f64::min).max(0.0)
+}
+
+/// Split one USDC of collateral across the legs of a set in proportion to
+/// `weights` (cost basis for merges, market prices for splits)
+pub fn unit_leg_prices(weights: &[f64]) -> Vec<f64> {
+    let total: f64 = weights.iter().map(|w| w.max(0.0)).sum();
+    if total <= SIZE_EPSILON {
+        return vec![1.0 / weights.len().max(1) as f64; weights.len()];
+    }
+    weights.iter().map(|w| w.max(0.0) / total).collect()
+}
+
+/// Index of a neg-risk question in its market (the last byte of the question ID)
+pub fn question_index(question_id: &str) -> Option<u8> {
+    let hex = question_id.trim().trim_start_matches("0x");
+    if hex.len() != 64 {
+        return None;
+    }
+    u8::from_str_radix(&hex[62..], 16).ok()
+}
+
+fn activity_key(activity: &UserActivity) -> String {
+    format!(
+        "{}:{}:{}",
+        activity.r#type.to_uppercase(),
+        activity.transaction_hash.to_lowercase(),
+        activity.condition_id.to_lowercase()
+    )
+}
+
+/// Record the event in the trader's activity collection and take it for
+/// mirroring. Returns false when it was already mirrored or is being mirrored
+/// (RTDS and the Data API poll can both deliver it); failed attempts can be
+/// taken again until `MAX_MIRROR_ATTEMPTS`.
+async fn claim_activity(db: &Database, trader: &str, activity: &UserActivity) -> Result<bool> {
+    let mut record = activity.clone();
+    record.id = Some(activity_key(activity));
+    record.bot = true;
+    record.bot_executed_time = chrono::Utc::now().timestamp();
+    let mut record = mongodb::bson::to_document(&record)?;
+    record.insert("mirrorStatus", MIRROR_PENDING);
+    record.insert("mirrorAttempts", 0);
+
+    let collection = get_user_activity_collection(db, trader);
+    collection
+        .update_one(
+            doc! { "_id": activity_key(activity) },
+            doc! { "$setOnInsert": record },
+            UpdateOptions::builder().upsert(true).build(),
+        )
+        .await?;
+    let result = collection
+        .update_one(
+            doc! {
+                "_id": activity_key(activity),
+                "mirrorStatus": { "$in": [MIRROR_PENDING, MIRROR_FAILED] },
+                "mirrorAttempts": { "$lt": MAX_MIRROR_ATTEMPTS },
+            },
+            doc! { "$set": { "mirrorStatus": MIRROR_RUNNING }, "$inc": { "mirrorAttempts": 1 } },
+            None,
+        )
+        .await?;
+    Ok(result.modified_count > 0)
+}
+
+/// Mark a claimed event mirrored, or failed so a later poll retries it
+async fn finish_activity(db: &Database, trader: &str, activity: &UserActivity, result: &Result<()>) -> Result<()> {
+    let update = match result {
+        Ok(()) => doc! { "$set": { "mirrorStatus": MIRROR_DONE }, "$unset": { "mirrorError": "" } },
+        Err(e) => doc! { "$set": { "mirrorStatus": MIRROR_FAILED, "mirrorError": e.to_string() } },
+    };
+    get_user_activity_collection(db, trader)
+        .update_one(doc! { "_id": activity_key(activity) }, update, None)
+        .await?;
+    Ok(())
+}
+
+/// Events of `trader` whose mirror failed and may be retried
+async fn failed_activity(db: &Database, trader: &str) -> Result<Vec<UserActivity>> {
+    let docs: Vec<mongodb::bson::Document> = get_user_activity_collection(db, trader)
+        .find(
+            doc! { "mirrorStatus": MIRROR_FAILED, "mirrorAttempts": { "$lt": MAX_MIRROR_ATTEMPTS } },
+            None,
+        )
+        .await?
+        .try_collect()
+        .await?;
+    docs.into_iter()
+        .map(|doc| Ok(mongodb::bson::from_document(doc)?))
+        .collect()
+}
+
+/// The trader's MERGE / SPLIT / REDEEM / CONVERSION activity since `start` (unix seconds), oldest first
+async fn fetch_position_activity(trader: &str, start: i64, env: &Env) -> Result<Vec<UserActivity>> {
+    let query = POSITION_ACTIVITY_TYPES
+        .iter()
+        .fold(ActivityQuery::new(trader), |query, kind| query.kind(kind))
+        .start(start)
+        .ascending();
+    Ok(DataApiClient::new(env)
+        .activity(&query)
+        .await?
+        .iter()
+        .map(UserActivity::from)
+        .filter(|activity| is_position_activity(&activity.r#type))
+        .collect())
+}
+
+/// Positions of `wallet` in one market, as reported by the Data API
+async fn condition_positions(wallet: &str, condition_id: &str, env: &Env) -> Result<Vec<UserPosition>> {
+    Ok(DataApiClient::new(env)
+        .positions(&PositionsQuery::new(wallet).market(condition_id))
+        .await?
+        .into_iter()
+        .map(UserPosition::from)
+        .filter(|p| p.condition_id.eq_ignore_ascii_case(condition_id) && p.size > SIZE_EPSILON)
+        .collect())
+}
+
+/// Our positions in one market (the paper positions when paper trading)
+async fn our_positions(condition_id: &str, env: &Env, db: &Database) -> Result<Vec<UserPosition>> {
+    if env.paper_trading.enabled {
+        return Ok(load_paper_positions(db)
+            .await?
+            .iter()
+            .filter(|p| p.condition_id.eq_ignore_ascii_case(condition_id))
+            .map(|p| p.as_user_position(&env.proxy_wallet))
+            .collect());
+    }
+    condition_positions(&env.proxy_wallet, condition_id, env).await
+}
+
+fn sizes(positions: &[UserPosition]) -> Vec<f64> {
+    positions.iter().map(|p| p.size).collect()
+}
+
+/// The event narrowed to one outcome token, for the ledger and post_order
+fn leg_activity(activity: &UserActivity, asset: &str, outcome: &str, outcome_index: i32) -> UserActivity {
+    UserActivity {
+        asset: asset.to_string(),
+        outcome: outcome.to_string(),
+        outcome_index,
+        ..activity.clone()
+    }
+}
+
+fn parse_condition_id(condition_id: &str) -> Result<B256> {
+    condition_id
+        .parse::<B256>()
+        .map_err(|e| anyhow::anyhow!("Invalid condition ID {}: {}", condition_id, e))
+}
+
+/// Send the calls through the proxy wallet (Gnosis Safe), or log them in preview mode
+async fn execute_position_calls(env: &Env, calls: &[SafeCall], description: &str) -> Result<()> {
+    if env.preview_mode {
+        Logger::order_result(true, &format!("[PREVIEW] Would {}", description));
+        return Ok(());
+    }
+    let executor = SafeExecutor::from_env(env)?;
+    let tx_hash = executor.execute(calls).await?;
+    Logger::order_result(true, &format!("Done: {} (tx {})", description, tx_hash));
+    Ok(())
+}
+
+/// Mirror one MERGE / SPLIT / REDEEM / CONVERSION of a copied trader, once.
+/// `received_at` (ms) is when it arrived. An RTDS replay mirrors every
+/// recorded event each time it runs, without claiming them, so it neither
+/// skips events the live run claimed nor claims events for the live run.
+pub async fn mirror_position_activity(
+    activity: &UserActivity,
+    trader: &str,
+    received_at: i64,
+    clob_client: &ClobClient<Authenticated<Normal>>,
+    env: &Env,
+    db: &Database,
+    signer: &PrivateKeySigner,
+) -> Result<()> {
+    if is_too_old(activity, received_at, env.too_old_timestamp) || activity.condition_id.is_empty() {
+        return Ok(());
+    }
+    // Replays run without the activity poll, so RTDS is the only source
+    let replaying = env.rtds.replay_file.is_some();
+    if !replaying && !claim_activity(db, trader, activity).await? {
+        return Ok(());
+    }
+
+    Logger::clear_line();
+    Logger::header(&format!("🔁 {} TO MIRROR", activity.r#type.to_uppercase()));
+    Logger::info(&format!(
+        "{} {} {:.2} in {}",
+        Logger::format_address(trader),
+        activity.r#type.to_lowercase(),
+        activity.size,
+        activity.title
+    ));
+    let result = mirror_claimed_activity(activity, trader, clob_client, env, db, signer).await;
+    Logger::separator();
+    if !replaying {
+        finish_activity(db, trader, activity, &result).await?;
+    }
+    result
+}
+
+async fn mirror_claimed_activity(
+    activity: &UserActivity,
+    trader: &str,
+    clob_client: &ClobClient<Authenticated<Normal>>,
+    env: &Env,
+    db: &Database,
+    signer: &PrivateKeySigner,
+) -> Result<()> {
+    let market = fetch_markets_by_condition_ids(std::slice::from_ref(&activity.condition_id), env)
+        .await?
+        .into_iter()
+        .find(|m| m.condition_id.eq_ignore_ascii_case(&activity.condition_id));
+    let Some(market) = market else {
+        Logger::warning("Market not found on Gamma - skipping");
+        return Ok(());
+    };
+
+    match activity.r#type.to_uppercase().as_str() {
+        ACTIVITY_MERGE => mirror_merge(activity, trader, &market, clob_client, env, db, signer).await,
+        ACTIVITY_SPLIT => mirror_split(activity, trader, &market, env, db).await,
+        ACTIVITY_REDEEM => mirror_redeem(activity, &market, env, db).await,
+        _ => mirror_conversion(activity, trader, &market, env, db).await,
+    }
+}
+
+/// Merge the same share of our full sets back into USDC. If the merge took
+/// the trader out of the market, sell whatever one-sided leg we have left.
+async fn mirror_merge(
+    activity: &UserActivity,
+    trader: &str,
+    market: &GammaMarket,
+    clob_client: &ClobClient<Authenticated<Normal>>,
+    env: &Env,
+    db: &Database,
+    signer: &PrivateKeySigner,
+) -> Result<()> {
+    let trader_positions = condition_positions(trader, &activity.condition_id, env).await?;
+    let fraction = event_fraction(activity.size, complete_sets(&sizes(&trader_positions)));
+    let legs = our_positions(&activity.condition_id, env, db).await?;
+    let our_sets = complete_sets(&sizes(&legs));
+    let amount = our_sets * fraction;
+    Logger::info(&format!(
+        "Trader merged {:.0}% of their sets - you hold {:.2} set(s)",
+        fraction * 100.0,
+        our_sets
+    ));
+
+    let merged = if amount >= MIN_MIRROR_TOKENS {
+        merge_sets(activity, trader, &legs, amount, market.neg_risk, env, db).await?;
+        amount
+    } else {
+        if our_sets > 0.0 {
+            Logger::warning(&format!("Merge of {:.2} set(s) too small to mirror - skipping", amount));
+        }
+        0.0
+    };
+
+    if !trader_positions.is_empty() {
+        return Ok(());
+    }
+    for leg in &legs {
+        let left = leg.size - merged;
+        if left < MIN_MIRROR_TOKENS {
+            continue;
+        }
+        Logger::info(&format!("Trader left the market - exiting your remaining {:.2} {} tokens", left, leg.outcome));
+        let position = UserPosition { size: left, ..leg.clone() };
+        let trade = leg_activity(activity, &leg.asset, &leg.outcome, leg.outcome_index);
+        post_order(
+            clob_client,
+            "merge",
+            Some(&position),
+            None,
+            &trade,
+            0.0,
+            0.0,
+            trader,
+            &env.copy_strategy_config,
+            env,
+            db,
+            signer,
+        )
+        .await?;
+    }
+    Ok(())
+}
+
+/// Merge `amount` full sets; each leg is booked as a sell at its share of the
+/// dollar by cost basis
+async fn merge_sets(
+    activity: &UserActivity,
+    trader: &str,
+    legs: &[UserPosition],
+    amount: f64,
+    neg_risk: bool,
+    env: &Env,
+    db: &Database,
+) -> Result<()> {
+    let prices = unit_leg_prices(&legs.iter().map(|p| p.avg_price).collect::<Vec<_>>());
+
+    if env.paper_trading.enabled {
+        for (leg, &price) in legs.iter().zip(&prices) {
+            let trade = leg_activity(activity, &leg.asset, &leg.outcome, leg.outcome_index);
+            let size = apply_paper_fill(db, PaperPosition::for_trade(&trade), KIND_SELL, price, amount, trader, &env.paper_trading).await?;
+            record_ledger_entry(db, &LedgerEntry::copy_fill(&trade, KIND_SELL, trader, price, size, true)).await;
+        }
+        Logger::order_result(true, &format!("[PAPER] Merged {:.2} set(s) into ${:.2} cash", amount, amount));
+        return Ok(());
+    }
+
+    let condition = parse_condition_id(&activity.condition_id)?;
+    let call = if neg_risk {
+        neg_risk_merge_positions(condition, to_base_units(amount))?
+    } else {
+        ctf_merge_positions(&env.usdc_contract_address, condition, to_base_units(amount))?
+    };
+    execute_position_calls(env, &[call], &format!("merge {:.2} set(s) into ${:.2} USDC", amount, amount)).await?;
+    for (leg, &price) in legs.iter().zip(&prices) {
+        let trade = leg_activity(activity, &leg.asset, &leg.outcome, leg.outcome_index);
+        record_ledger_entry(db, &LedgerEntry::copy_fill(&trade, KIND_SELL, trader, price, amount, env.preview_mode)).await;
+    }
+    Ok(())
+}
+
+/// Split USDC into full sets, sized by the copy strategy like a buy of the
+/// same dollar amount
+async fn mirror_split(activity: &UserActivity, trader: &str, market: &GammaMarket, env: &Env, db: &Database) -> Result<()> {
+    if let Some(reason) = buying_halted(db).await? {
+        Logger::warning(&format!("⛔ Circuit breaker tripped ({}) - skipping split", reason));
+        return Ok(());
+    }
+    let balance = if env.paper_trading.enabled {
+        load_paper_account(db, &env.paper_trading).await?.cash
+    } else {
+        get_my_balance(&env.proxy_wallet, env).await?
+    };
+    let order_calc = calculate_order_size(&env.copy_strategy_config, activity.size, balance, 0.0);
+    Logger::info(&format!("📊 {}", order_calc.reasoning));
+    if order_calc.final_amount == 0.0 {
+        Logger::warning(&format!("❌ Cannot split: {}", order_calc.reasoning));
+        return Ok(());
+    }
+    let amount = order_calc.final_amount;
+
+    let token_ids = market.token_ids();
+    let outcomes = market.outcome_names();
+    let prices = unit_leg_prices(&market.outcome_price_values());
+    if token_ids.len() < 2 || prices.len() != token_ids.len() {
+        Logger::warning("Market has no outcome tokens listed - skipping split");
+        return Ok(());
+    }
+    let legs: Vec<UserActivity> = token_ids
+        .iter()
+        .enumerate()
+        .map(|(i, asset)| leg_activity(activity, asset, outcomes.get(i).map(String::as_str).unwrap_or_default(), i as i32))
+        .collect();
+
+    if env.paper_trading.enabled {
+        for (trade, &price) in legs.iter().zip(&prices) {
+            let size = apply_paper_fill(db, PaperPosition::for_trade(trade), KIND_BUY, price, amount, trader, &env.paper_trading).await?;
+            record_ledger_entry(db, &LedgerEntry::copy_fill(trade, KIND_BUY, trader, price, size, true)).await;
+        }
+        Logger::order_result(true, &format!("[PAPER] Split ${:.2} into {:.2} full set(s)", amount, amount));
+        return Ok(());
+    }
+
+    let condition = parse_condition_id(&activity.condition_id)?;
+    let call = if market.neg_risk {
+        neg_risk_split_position(condition, to_base_units(amount))?
+    } else {
+        ctf_split_position(&env.usdc_contract_address, condition, to_base_units(amount))?
+    };
+    execute_position_calls(env, &[call], &format!("split ${:.2} into {:.2} full set(s)", amount, amount)).await?;
+    for (trade, &price) in legs.iter().zip(&prices) {
+        record_ledger_entry(db, &LedgerEntry::copy_fill(trade, KIND_BUY, trader, price, amount, env.preview_mode)).await;
+    }
+    Ok(())
+}
+
+/// Redeem our whole position in the market the trader redeemed
+async fn mirror_redeem(activity: &UserActivity, market: &GammaMarket, env: &Env, db: &Database) -> Result<()> {
+    let positions = our_positions(&activity.condition_id, env, db).await?;
+    if positions.is_empty() {
+        Logger::info("No position in this market to redeem");
+        return Ok(());
+    }
+    if env.paper_trading.enabled {
+        // Settles every resolved paper position
+        mark_paper_account(env, db).await?;
+        return Ok(());
+    }
+
+    let resolution = MarketResolution::from_gamma(market);
+    if !resolution.resolved {
+        Logger::warning("Market isn't resolved on Gamma yet - the auto-claim service will redeem it later");
+        return Ok(());
+    }
+    let tokens: f64 = positions.iter().map(|p| p.size).sum();
+    let payout: f64 = positions
+        .iter()
+        .map(|p| p.size * resolution.settlement_price(&p.asset, Some(&p.outcome)).unwrap_or(0.0))
+        .sum();
+
+    let condition = parse_condition_id(&activity.condition_id)?;
+    let call = if market.neg_risk {
+        let mut amounts = vec![U256::ZERO; 2];
+        for position in &positions {
+            if let Some(amount) = amounts.get_mut(position.outcome_index.max(0) as usize) {
+                *amount += to_base_units(position.size);
+            }
+        }
+        neg_risk_redeem_positions(condition, amounts)?
+    } else {
+        ctf_redeem_positions(&env.usdc_contract_address, condition)?
+    };
+    execute_position_calls(env, &[call], &format!("redeem {:.2} tokens for ${:.2}", tokens, payout)).await?;
+
+    // Live redemptions reach the ledger through the Data API import
+    if env.preview_mode {
+        for position in &positions {
+            let price = resolution.settlement_price(&position.asset, Some(&position.outcome)).unwrap_or(0.0);
+            let mut entry = LedgerEntry::redemption(
+                &position.asset,
+                &activity.condition_id,
+                &position.outcome,
+                price,
+                position.size,
+                &format!("preview-redeem-{}", activity.condition_id),
+                true,
+            );
+            entry.title = activity.title.clone();
+            entry.slug = activity.slug.clone();
+            record_ledger_entry(db, &entry).await;
+        }
+    }
+    Ok(())
+}
+
+/// Convert the same share of our NO tokens of a neg-risk question into YES
+/// tokens of the event's other questions
+async fn mirror_conversion(
+    activity: &UserActivity,
+    trader: &str,
+    market: &GammaMarket,
+    env: &Env,
+    db: &Database,
+) -> Result<()> {
+    let (Some(market_id), Some(index)) = (
+        market.neg_risk_market_id.as_deref(),
+        market.question_id.as_deref().and_then(question_index),
+    ) else {
+        Logger::warning("Gamma has no neg-risk market / question ID for this market - skipping conversion");
+        return Ok(());
+    };
+
+    let is_no = |p: &&UserPosition| p.outcome.eq_ignore_ascii_case("no");
+    let trader_no: f64 = condition_positions(trader, &activity.condition_id, env)
+        .await?
+        .iter()
+        .filter(is_no)
+        .map(|p| p.size)
+        .sum();
+    let our_no: f64 = our_positions(&activity.condition_id, env, db)
+        .await?
+        .iter()
+        .filter(is_no)
+        .map(|p| p.size)
+        .sum();
+    let fraction = event_fraction(activity.size, trader_no);
+    let amount = our_no * fraction;
+    Logger::info(&format!(
+        "Trader converted {:.0}% of their NO tokens - you hold {:.2}",
+        fraction * 100.0,
+        our_no
+    ));
+    if amount < MIN_MIRROR_TOKENS {
+        if our_no > 0.0 {
+            Logger::warning(&format!("Conversion of {:.2} tokens too small to mirror - skipping", amount));
+        }
+        return Ok(());
+    }
+    if env.paper_trading.enabled {
+        Logger::warning("[PAPER] Conversions aren't simulated - keeping the NO position");
+        return Ok(());
+    }
+
+    let call = neg_risk_convert_positions(
+        parse_condition_id(market_id)?,
+        U256::from(1) << index as usize,
+        to_base_units(amount),
+    )?;
+    execute_position_calls(env, &[call], &format!("convert {:.2} NO tokens into YES of the other outcomes", amount)).await
+}
+
+/// Poll the Data API for position activity RTDS doesn't deliver. Only events
+/// after startup are mirrored; earlier ones are already in the positions we copied.
+pub async fn start_activity_mirror(
+    env_rx: watch::Receiver<Arc<Env>>,
+    accounts: Arc<Vec<AccountExecutor>>,
+) -> Result<()> {
+    let env = env_rx.borrow().clone();
+    Logger::success(&format!(
+        "Mirroring trader merges, splits, redemptions and conversions (polling every {}s)",
+        env.activity_poll_interval_ms / 1000
+    ));
+
+    // Per trader: timestamp of the newest event seen (the Data API `start` is inclusive)
+    let mut cursors: HashMap<String, i64> = HashMap::new();
+    let mut poll = interval(Duration::from_millis(env.activity_poll_interval_ms));
+    loop {
+        poll.tick().await;
+        let env = env_rx.borrow().clone();
+        for trader in &env.user_addresses {
+            let cursor = cursors
+                .entry(trader.to_lowercase())
+                .or_insert_with(|| chrono::Utc::now().timestamp());
+            let activities = match fetch_position_activity(trader, *cursor, &env).await {
+                Ok(activities) => activities,
+                Err(e) => {
+                    Logger::error(&format!("Error fetching activity for {}: {}", Logger::format_address(trader), e));
+                    continue;
+                }
+            };
+            let received_at = chrono::Utc::now().timestamp_millis();
+            for activity in activities {
+                *cursor = (*cursor).max(activity.timestamp);
+                dispatch_position_activity(&activity, trader, received_at, &accounts, &env).await;
+            }
+        }
+        retry_failed_activity(&accounts, &env).await;
+    }
+}
+
+/// Mirror again the events whose last attempt failed, in the account it failed in
+async fn retry_failed_activity(accounts: &[AccountExecutor], env: &Env) {
+    for executor in accounts {
+        executor
+            .scope(async {
+                let env = executor.env(env);
+                for trader in &env.user_addresses {
+                    let failed = match failed_activity(&executor.db, trader).await {
+                        Ok(failed) => failed,
+                        Err(e) => {
+                            Logger::error(&format!("Error loading failed activity: {}", e));
+                            continue;
+                        }
+                    };
+                    for activity in failed {
+                        Logger::info(&format!("Retrying {} from {}", activity.r#type, Logger::format_address(trader)));
+                        if let Err(e) = mirror_position_activity(
+                            &activity,
+                            trader,
+                            chrono::Utc::now().timestamp_millis(),
+                            &executor.clob_client,
+                            &env,
+                            &executor.db,
+                            &executor.signer,
+                        )
+                        .await
+                        {
+                            Logger::error(&format!("Error mirroring {}: {}", activity.r#type, e));
+                        }
+                    }
+                }
+            })
+            .await;
+    }
+}
@@ -2,7 +2,7 @@ use anyhow::Result;
 use std::sync::Arc;
 use tokio::time::{interval, Duration};
 use crate::config::Env;
-use crate::utils::{fetch_data, logger::Logger};
+use crate::utils::{logger::Logger, DataApiClient, PositionsQuery};
 
 const RESOLVED_HIGH: f64 = 0.99;
 const RESOLVED_LOW: f64 = 0.01;
@@ -31,19 +31,12 @@ async fn check_and_redeem(env: &Env) -> Result<()> {
     Logger::info("🔍 Auto-claim: Checking for redeemable positions...");
 
     // Load positions
-    let positions_url = format!("https://data-api.polymarket.com/positions?user={}", env.proxy_wallet);
-    let all_positions: Vec<serde_json::Value> = fetch_data(&positions_url, env).await?
-        .as_array()
-        .cloned()
-        .unwrap_or_default();
-
+    let all_positions = DataApiClient::new(env)
+        .positions(&PositionsQuery::new(&env.proxy_wallet))
+        .await?;
     let all_positions: Vec<_> = all_positions
         .into_iter()
-        .filter(|pos| {
-            pos.get("size")
-                .and_then(|v| v.as_f64())
-                .unwrap_or(0.0) > ZERO_THRESHOLD
-        })
+        .filter(|pos| pos.size > ZERO_THRESHOLD)
         .collect();
 
     if all_positions.is_empty() {
@@ -56,11 +49,7 @@ async fn check_and_redeem(env: &Env) -> Result<()> {
     // Filter for resolved and redeemable positions
     let redeemable_positions: Vec<_> = all_positions
         .iter()
-        .filter(|pos| {
-            let cur_price = pos.get("curPrice").and_then(|v| v.as_f64()).unwrap_or(0.0);
-            let redeemable = pos.get("redeemable").and_then(|v| v.as_bool()).unwrap_or(false);
-            (cur_price >= RESOLVED_HIGH || cur_price <= RESOLVED_LOW) && redeemable
-        })
+        .filter(|pos| (pos.cur_price >= RESOLVED_HIGH || pos.cur_price <= RESOLVED_LOW) && pos.redeemable)
         .collect();
 
     if redeemable_positions.is_empty() {
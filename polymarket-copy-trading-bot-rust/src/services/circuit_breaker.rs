@@ -0,0 +1,276 @@
+use anyhow::Result;
+use chrono::{DateTime, Duration as ChronoDuration, Utc};
+use mongodb::bson::doc;
+use mongodb::options::UpdateOptions;
+use mongodb::Database;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::sync::Arc;
+use tokio::time::{interval, Duration};
+use crate::config::{get_circuit_breaker_collection, CircuitBreakerConfig, Env};
+use crate::services::paper_trading::{flatten_paper_positions, load_paper_account};
+use crate::utils::data_api::Position;
+use crate::utils::{get_my_balance, logger::Logger, DataApiClient, PositionsQuery};
+
+const STATE_ID: &str = "state";
+
+/// Breaker state, persisted so a restart doesn't clear a trip or the high-water mark
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BreakerState {
+    pub tripped: bool,
+    pub reason: Option<String>,
+    pub tripped_at: Option<i64>,
+    /// Highest equity seen per UTC day (YYYY-MM-DD), pruned to the HWM window
+    #[serde(default)]
+    pub daily_highs: BTreeMap<String, f64>,
+    pub day: Option<String>,
+    pub day_start_equity: Option<f64>,
+    pub last_equity: Option<f64>,
+    #[serde(default)]
+    pub updated_at: i64,
+}
+
+impl BreakerState {
+    pub fn high_water_mark(&self) -> Option<f64> {
+        self.daily_highs.values().copied().reduce(f64::max)
+    }
+
+    pub fn drawdown_percent(&self, equity: f64) -> f64 {
+        match self.high_water_mark() {
+            Some(hwm) if hwm > 0.0 => ((hwm - equity) / hwm * 100.0).max(0.0),
+            _ => 0.0,
+        }
+    }
+
+    pub fn daily_loss(&self, equity: f64) -> f64 {
+        self.day_start_equity.map(|start| (start - equity).max(0.0)).unwrap_or(0.0)
+    }
+
+    /// Record an equity sample and return why the breaker should trip, if it should
+    pub fn update(&mut self, equity: f64, now: DateTime<Utc>, config: &CircuitBreakerConfig) -> Option<String> {
+        let today = now.format("%Y-%m-%d").to_string();
+        if self.day.as_deref() != Some(today.as_str()) {
+            self.day = Some(today.clone());
+            self.day_start_equity = Some(equity);
+        }
+        let high = self.daily_highs.entry(today).or_insert(equity);
+        *high = high.max(equity);
+
+        let cutoff = (now - ChronoDuration::days(config.hwm_window_days as i64 - 1))
+            .format("%Y-%m-%d")
+            .to_string();
+        self.daily_highs.retain(|day, _| *day >= cutoff);
+        self.last_equity = Some(equity);
+        self.updated_at = now.timestamp();
+
+        if let Some(max) = config.max_drawdown_percent {
+            let drawdown = self.drawdown_percent(equity);
+            if drawdown >= max {
+                return Some(format!(
+                    "drawdown {:.1}% from high-water mark ${:.2} (limit {}%)",
+                    drawdown,
+                    self.high_water_mark().unwrap_or(0.0),
+                    max
+                ));
+            }
+        }
+        let loss = self.daily_loss(equity);
+        let start = self.day_start_equity.unwrap_or(equity);
+        if let Some(max) = config.max_daily_loss_usd.filter(|&max| loss >= max) {
+            return Some(format!("daily loss ${:.2} (limit ${:.2})", loss, max));
+        }
+        if let Some(max) = config.max_daily_loss_percent {
+            let loss_percent = if start > 0.0 { loss / start * 100.0 } else { 0.0 };
+            if loss_percent >= max {
+                return Some(format!("daily loss {:.1}% of ${:.2} (limit {}%)", loss_percent, start, max));
+            }
+        }
+        None
+    }
+}
+
+pub async fn load_breaker_state(db: &Database) -> Result<BreakerState> {
+    let doc = get_circuit_breaker_collection(db)
+        .find_one(doc! { "_id": STATE_ID }, None)
+        .await?;
+    Ok(match doc {
+        Some(doc) => mongodb::bson::from_document(doc)?,
+        None => BreakerState::default(),
+    })
+}
+
+/// Why new buys are paused, or `None` if copying may continue
+pub async fn buying_halted(db: &Database) -> Result<Option<String>> {
+    let state = load_breaker_state(db).await?;
+    Ok(state
+        .tripped
+        .then(|| state.reason.unwrap_or_else(|| "circuit breaker tripped".to_string())))
+}
+
+/// Set `fields` on the state document. With `only_if_clear` nothing is written
+/// once the breaker is tripped; returns whether the document was updated.
+/// Field-level updates keep a concurrent kill switch or resume from being
+/// overwritten by a stale copy of the state.
+async fn update_breaker_state(db: &Database, fields: mongodb::bson::Document, only_if_clear: bool) -> Result<bool> {
+    let collection = get_circuit_breaker_collection(db);
+    // Make sure the document exists, so the conditional update below never upserts a duplicate
+    collection
+        .update_one(
+            doc! { "_id": STATE_ID },
+            doc! { "$setOnInsert": { "tripped": false } },
+            UpdateOptions::builder().upsert(true).build(),
+        )
+        .await?;
+    let filter = if only_if_clear {
+        doc! { "_id": STATE_ID, "tripped": { "$ne": true } }
+    } else {
+        doc! { "_id": STATE_ID }
+    };
+    let result = collection.update_one(filter, doc! { "$set": fields }, None).await?;
+    Ok(result.matched_count > 0)
+}
+
+fn trip_fields(reason: &str) -> mongodb::bson::Document {
+    doc! { "tripped": true, "reason": reason, "trippedAt": Utc::now().timestamp() }
+}
+
+/// Trip the breaker via the kill switch
+#[allow(dead_code)] // Used by the circuit_breaker binary
+pub async fn trip_breaker(db: &Database, reason: &str) -> Result<BreakerState> {
+    update_breaker_state(db, trip_fields(reason), false).await?;
+    load_breaker_state(db).await
+}
+
+/// Clear a trip. The high-water mark and day start are reset so the breaker
+/// re-baselines from current equity instead of tripping again immediately.
+#[allow(dead_code)] // Used by the circuit_breaker binary
+pub async fn resume_breaker(db: &Database) -> Result<BreakerState> {
+    let fields = doc! {
+        "tripped": false,
+        "reason": null,
+        "trippedAt": null,
+        "dailyHighs": {},
+        "day": null,
+        "dayStartEquity": null,
+    };
+    update_breaker_state(db, fields, false).await?;
+    load_breaker_state(db).await
+}
+
+/// Store the equity tracking fields of `state` unless the breaker has been
+/// tripped since `state` was loaded. Returns whether they were stored.
+async fn save_equity_sample(db: &Database, state: &BreakerState) -> Result<bool> {
+    let fields = doc! {
+        "dailyHighs": mongodb::bson::to_bson(&state.daily_highs)?,
+        "day": &state.day,
+        "dayStartEquity": state.day_start_equity,
+        "lastEquity": state.last_equity,
+        "updatedAt": state.updated_at,
+    };
+    update_breaker_state(db, fields, true).await
+}
+
+async fn fetch_open_positions(env: &Env) -> Result<Vec<Position>> {
+    Ok(DataApiClient::new(env)
+        .positions(&PositionsQuery::new(&env.proxy_wallet))
+        .await?
+        .into_iter()
+        .filter(|p| p.size > 0.0)
+        .collect())
+}
+
+/// (USDC balance, open position value) - of the paper account when paper trading
+pub async fn current_equity(env: &Env, db: &Database) -> Result<(f64, f64)> {
+    if env.paper_trading.enabled {
+        let account = load_paper_account(db, &env.paper_trading).await?;
+        return Ok((account.cash, account.positions_value));
+    }
+    let balance = get_my_balance(&env.proxy_wallet, env).await?;
+    let positions_value = fetch_open_positions(env)
+        .await?
+        .iter()
+        .map(|p| p.current_value)
+        .sum();
+    Ok((balance, positions_value))
+}
+
+async fn flatten_positions(env: &Env, db: &Database) -> Result<()> {
+    if env.paper_trading.enabled {
+        return flatten_paper_positions(env, db).await;
+    }
+    let positions = fetch_open_positions(env).await?;
+    Logger::info(&format!("Flattening {} position(s)...", positions.len()));
+    for position in &positions {
+        // Preview mode: simulate the sell without placing an order
+        Logger::order_result(
+            true,
+            &format!(
+                "[PREVIEW] Would sell {:.2} tokens of {} at ~${:.4}",
+                position.size,
+                position.slug.as_deref().unwrap_or("Unknown"),
+                position.cur_price
+            ),
+        );
+    }
+    Ok(())
+}
+
+async fn check_breaker(env: &Env, db: &Database) -> Result<()> {
+    let config = &env.circuit_breaker;
+    let mut state = load_breaker_state(db).await?;
+    if state.tripped {
+        return Ok(());
+    }
+
+    let (balance, positions_value) = current_equity(env, db).await?;
+    let equity = balance + positions_value;
+    let trip_reason = state.update(equity, Utc::now(), config);
+    // Tripped (e.g. by the kill switch) while equity was being fetched
+    if !save_equity_sample(db, &state).await? {
+        return Ok(());
+    }
+
+    if let Some(reason) = trip_reason {
+        if !update_breaker_state(db, trip_fields(&reason), true).await? {
+            return Ok(());
+        }
+        Logger::header("⛔ CIRCUIT BREAKER TRIPPED");
+        Logger::warning(&format!("Reason: {}", reason));
+        Logger::warning(&format!(
+            "Equity: ${:.2} (${:.2} USDC + ${:.2} positions)",
+            equity, balance, positions_value
+        ));
+        Logger::warning("New buys are paused. Resume with: polycopy health breaker resume");
+        if config.flatten_on_trip {
+            flatten_positions(env, db).await?;
+        }
+    }
+    Ok(())
+}
+
+pub async fn start_circuit_breaker(env: Arc<Env>, db: Arc<Database>) -> Result<()> {
+    let config = &env.circuit_breaker;
+    Logger::success("Circuit breaker started");
+    if let Some(dd) = config.max_drawdown_percent {
+        Logger::info(&format!("Max drawdown: {}% from {}-day high-water mark", dd, config.hwm_window_days));
+    }
+    if let Some(loss) = config.max_daily_loss_percent {
+        Logger::info(&format!("Max daily loss: {}%", loss));
+    }
+    if let Some(loss) = config.max_daily_loss_usd {
+        Logger::info(&format!("Max daily loss: ${:.2}", loss));
+    }
+    Logger::info(&format!(
+        "Flatten on trip: {}",
+        if config.flatten_on_trip { "Enabled" } else { "Disabled" }
+    ));
+
+    let mut check_interval = interval(Duration::from_millis(config.check_interval_ms));
+    loop {
+        check_interval.tick().await;
+        if let Err(e) = check_breaker(&env, &db).await {
+            Logger::error(&format!("Error checking circuit breaker: {}", e));
+        }
+    }
+}
@@ -0,0 +1,76 @@
+use anyhow::Result;
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::Arc;
+use std::time::SystemTime;
+use tokio::sync::watch;
+use tokio::time::{interval, Duration};
+use crate::config::{build_env, read_config_file, restart_required_changes, Env};
+use crate::utils::logger::Logger;
+
+const CONFIG_POLL_INTERVAL_MS: u64 = 2000;
+
+fn modified_at(path: &Path) -> Option<SystemTime> {
+    std::fs::metadata(path).and_then(|m| m.modified()).ok()
+}
+
+/// Re-read the config file; returns the env to publish, or None to keep the current one
+fn reload(path: &Path, current: &Env, last_values: &mut HashMap<String, String>) -> Option<Env> {
+    let file = match read_config_file(path) {
+        Ok(file) => file,
+        Err(e) => {
+            Logger::error(&format!("Config reload rejected, keeping current settings: {:#}", e));
+            return None;
+        }
+    };
+    let reloaded = match build_env(file.values.clone(), file.errors, Some(path.to_path_buf())) {
+        Ok(env) => env,
+        Err(e) => {
+            Logger::error(&format!("Config reload rejected, keeping current settings: {}", e));
+            return None;
+        }
+    };
+
+    let restart_needed = restart_required_changes(last_values, &file.values);
+    if !restart_needed.is_empty() {
+        Logger::warning(&format!(
+            "Config changes that need a restart were not applied: {}",
+            restart_needed.join(", ")
+        ));
+    }
+    *last_values = file.values;
+    Some(current.with_hot_reload(&reloaded))
+}
+
+/// Watch the config file and publish strategy, trader list, filter and exposure
+/// changes to the running services. The RTDS connection is left untouched.
+pub async fn start_config_watcher(env_tx: watch::Sender<Arc<Env>>) -> Result<()> {
+    let Some(path) = env_tx.borrow().config_file.clone() else {
+        return Ok(());
+    };
+    let mut last_values = read_config_file(&path)?.values;
+    let mut last_modified = modified_at(&path);
+    Logger::info(&format!("Watching {} for config changes", path.display()));
+
+    let mut poll_interval = interval(Duration::from_millis(CONFIG_POLL_INTERVAL_MS));
+    loop {
+        poll_interval.tick().await;
+        let modified = modified_at(&path);
+        if modified.is_none() || modified == last_modified {
+            continue;
+        }
+        last_modified = modified;
+
+        let current = env_tx.borrow().clone();
+        if let Some(env) = reload(&path, &current, &mut last_values) {
+            Logger::success(&format!(
+                "Config reloaded from {}: {} trader(s), {} strategy, copy size {}",
+                path.display(),
+                env.user_addresses.len(),
+                env.copy_strategy_config.strategy,
+                env.copy_strategy_config.copy_size
+            ));
+            env_tx.send_replace(Arc::new(env));
+        }
+    }
+}
@@ -0,0 +1,289 @@
+use anyhow::Result;
+use futures_util::TryStreamExt;
+use mongodb::Database;
+use std::collections::{HashMap, HashSet};
+use std::sync::{Mutex, OnceLock};
+use crate::config::{get_number_field, get_user_position_collection, Env, ExposureLimitsConfig};
+use crate::interfaces::UserActivity;
+use crate::services::limit_orders::open_limit_orders;
+use crate::services::paper_trading::load_paper_positions;
+use crate::services::pnl_ledger::{load_ledger, open_lots_by_trader};
+use crate::utils::gamma_api::fetch_market_with_tags;
+use crate::utils::{logger::Logger, DataApiClient, PositionsQuery};
+
+/// Something we hold or are bidding for, valued in USD
+#[derive(Debug, Clone)]
+pub struct ExposureItem {
+    pub asset: String,
+    pub condition_id: String,
+    pub event_slug: String,
+    pub value: f64,
+    /// Copied traders this exposure is attributed to, with their share of it
+    pub traders: Vec<(String, f64)>,
+}
+
+/// Split evenly between `traders`
+fn even_shares(traders: Vec<String>) -> Vec<(String, f64)> {
+    let share = 1.0 / traders.len().max(1) as f64;
+    traders.into_iter().map(|t| (t, share)).collect()
+}
+
+/// Current exposure in USD from open positions plus resting buy orders
+#[derive(Debug, Clone, Default)]
+pub struct Exposure {
+    pub total: f64,
+    pub by_event: HashMap<String, f64>,
+    pub by_category: HashMap<String, f64>,
+    pub by_trader: HashMap<String, f64>,
+    /// Assets with an open position or a resting buy
+    pub open_assets: HashSet<String>,
+}
+
+impl Exposure {
+    /// Count `item` towards every bucket; `category` is only needed for the category cap
+    pub fn add(&mut self, item: &ExposureItem, category: Option<String>) {
+        self.total += item.value;
+        self.open_assets.insert(item.asset.clone());
+        if !item.event_slug.is_empty() {
+            *self.by_event.entry(item.event_slug.clone()).or_insert(0.0) += item.value;
+        }
+        if let Some(category) = category {
+            *self.by_category.entry(category).or_insert(0.0) += item.value;
+        }
+        for (trader, share) in &item.traders {
+            *self.by_trader.entry(trader.clone()).or_insert(0.0) += item.value * share;
+        }
+    }
+}
+
+/// Market categories never change, so lookups are cached for the process lifetime
+fn category_cache() -> &'static Mutex<HashMap<String, String>> {
+    static CACHE: OnceLock<Mutex<HashMap<String, String>>> = OnceLock::new();
+    CACHE.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+async fn market_category(condition_id: &str, env: &Env) -> Result<String> {
+    if let Some(category) = category_cache().lock().unwrap().get(condition_id) {
+        return Ok(category.clone());
+    }
+    let category = fetch_market_with_tags(condition_id, env)
+        .await?
+        .map(|m| m.primary_category())
+        .unwrap_or_else(|| "uncategorized".to_string());
+    category_cache()
+        .lock()
+        .unwrap()
+        .insert(condition_id.to_string(), category.clone());
+    Ok(category)
+}
+
+/// Assets currently held by each copied trader, from the positions the trade
+/// monitor stores. Only used for positions that predate the PnL ledger.
+async fn trader_assets(env: &Env, db: &Database) -> Result<HashMap<String, HashSet<String>>> {
+    let mut assets = HashMap::new();
+    for address in &env.user_addresses {
+        let docs: Vec<mongodb::bson::Document> = get_user_position_collection(db, address)
+            .find(mongodb::bson::doc! {}, None)
+            .await?
+            .try_collect()
+            .await?;
+        let held: HashSet<String> = docs
+            .iter()
+            .filter(|d| get_number_field(d, "size") > 0.0)
+            .filter_map(|d| d.get_str("asset").ok().map(str::to_string))
+            .collect();
+        assets.insert(address.to_lowercase(), held);
+    }
+    Ok(assets)
+}
+
+/// Paper positions, attributed to the traders whose buys built them
+async fn paper_exposure_items(db: &Database) -> Result<Vec<ExposureItem>> {
+    Ok(load_paper_positions(db)
+        .await?
+        .into_iter()
+        .filter(|p| p.value() > 0.0)
+        .map(|p| ExposureItem {
+            value: p.value(),
+            asset: p.asset,
+            condition_id: p.condition_id,
+            event_slug: p.event_slug,
+            traders: even_shares(p.traders),
+        })
+        .collect())
+}
+
+async fn exposure_items(env: &Env, db: &Database) -> Result<Vec<ExposureItem>> {
+    if env.paper_trading.enabled {
+        let mut items = paper_exposure_items(db).await?;
+        items.extend(resting_buy_items(db).await?);
+        return Ok(items);
+    }
+
+    let positions = DataApiClient::new(env)
+        .positions(&PositionsQuery::new(&env.proxy_wallet))
+        .await?;
+    // Attribute each position to the traders whose copied buys opened it,
+    // even after they have exited themselves
+    let ledger = load_ledger(db).await?;
+    let originators = open_lots_by_trader(ledger.iter().filter(|e| !e.simulated));
+    let held_by_trader = trader_assets(env, db).await?;
+
+    let mut items: Vec<ExposureItem> = positions
+        .into_iter()
+        .filter(|p| !p.redeemable && p.current_value > 0.0 && !p.asset.is_empty())
+        .map(|p| {
+            let traders = match originators.get(&p.asset) {
+                Some(lots) => lot_shares(lots),
+                None => even_shares(
+                    held_by_trader
+                        .iter()
+                        .filter(|(_, held)| held.contains(&p.asset))
+                        .map(|(trader, _)| trader.clone())
+                        .collect(),
+                ),
+            };
+            ExposureItem {
+                condition_id: p.condition_id,
+                event_slug: p.event_slug.unwrap_or_default(),
+                asset: p.asset,
+                value: p.current_value,
+                traders,
+            }
+        })
+        .collect();
+
+    items.extend(resting_buy_items(db).await?);
+
+    Ok(items)
+}
+
+/// Share of an asset's open lots opened by each trader
+pub fn lot_shares(lots: &HashMap<String, f64>) -> Vec<(String, f64)> {
+    let total: f64 = lots.values().sum();
+    if total <= 0.0 {
+        return Vec::new();
+    }
+    lots.iter().map(|(trader, size)| (trader.clone(), size / total)).collect()
+}
+
+async fn resting_buy_items(db: &Database) -> Result<Vec<ExposureItem>> {
+    Ok(open_limit_orders(db)
+        .await?
+        .into_iter()
+        .filter(|o| o.is_buy())
+        .map(|o| ExposureItem {
+            value: o.remaining() * o.price,
+            asset: o.asset,
+            condition_id: o.condition_id,
+            event_slug: o.event_slug,
+            traders: vec![(o.trader_address, 1.0)],
+        })
+        .collect())
+}
+
+/// Compute current exposure. Categories are only resolved when a category cap is set.
+pub async fn current_exposure(env: &Env, db: &Database) -> Result<Exposure> {
+    let mut exposure = Exposure::default();
+    let with_categories = env.exposure_limits.max_category_exposure_usd.is_some();
+
+    for item in exposure_items(env, db).await? {
+        let category = if with_categories && !item.condition_id.is_empty() {
+            Some(market_category(&item.condition_id, env).await?)
+        } else {
+            None
+        };
+        exposure.add(&item, category);
+    }
+
+    Ok(exposure)
+}
+
+/// Shrink `allowed` to the headroom left under `cap`, remembering which cap bound
+fn apply_cap(allowed: &mut f64, binding: &mut Option<String>, cap: Option<f64>, used: f64, label: String) {
+    let Some(cap) = cap else {
+        return;
+    };
+    let headroom = (cap - used).max(0.0);
+    if headroom < *allowed {
+        *allowed = headroom;
+        *binding = Some(format!("{} ${:.2} of ${:.2}", label, used, cap));
+    }
+}
+
+/// Largest buy (USD, at most `amount`) the caps allow for `trade` given
+/// `exposure`, and the cap that bound it. `category` is the trade's market
+/// category, needed only when a category cap is set.
+pub fn exposure_headroom(
+    limits: &ExposureLimitsConfig,
+    exposure: &Exposure,
+    trade: &UserActivity,
+    amount: f64,
+    user_address: &str,
+    category: Option<&str>,
+) -> (f64, Option<String>) {
+    let mut allowed = amount;
+    let mut binding = None;
+
+    apply_cap(&mut allowed, &mut binding, limits.max_total_exposure_usd, exposure.total, "total exposure".to_string());
+    apply_cap(
+        &mut allowed,
+        &mut binding,
+        limits.max_event_exposure_usd,
+        exposure.by_event.get(&trade.event_slug).copied().unwrap_or(0.0),
+        format!("event \"{}\"", trade.event_slug),
+    );
+    if let Some(category) = category {
+        apply_cap(
+            &mut allowed,
+            &mut binding,
+            limits.max_category_exposure_usd,
+            exposure.by_category.get(category).copied().unwrap_or(0.0),
+            format!("category \"{}\"", category),
+        );
+    }
+    apply_cap(
+        &mut allowed,
+        &mut binding,
+        limits.max_trader_exposure_usd,
+        exposure.by_trader.get(&user_address.to_lowercase()).copied().unwrap_or(0.0),
+        format!("trader {}", Logger::format_address(user_address)),
+    );
+
+    if let Some(max) = limits.max_open_positions {
+        let open = exposure.open_assets.len();
+        if !exposure.open_assets.contains(&trade.asset) && open >= max {
+            allowed = 0.0;
+            binding = Some(format!("{} of {} open positions", open, max));
+        }
+    }
+
+    (allowed, binding)
+}
+
+/// Largest buy (USD, at most `amount`) the exposure caps still allow for `trade`
+pub async fn cap_by_exposure_limits(
+    trade: &UserActivity,
+    amount: f64,
+    user_address: &str,
+    env: &Env,
+    db: &Database,
+) -> Result<f64> {
+    let limits: &ExposureLimitsConfig = &env.exposure_limits;
+    let exposure = current_exposure(env, db).await?;
+    let category = if limits.max_category_exposure_usd.is_some() {
+        Some(market_category(&trade.condition_id, env).await?)
+    } else {
+        None
+    };
+
+    let (allowed, binding) = exposure_headroom(limits, &exposure, trade, amount, user_address, category.as_deref());
+    if let Some(binding) = binding {
+        Logger::warning(&format!(
+            "🛡️  Exposure limit ({}): buy capped ${:.2} → ${:.2}",
+            binding, amount, allowed
+        ));
+    }
+
+    Ok(allowed)
+}
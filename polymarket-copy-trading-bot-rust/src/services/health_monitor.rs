@@ -0,0 +1,131 @@
+use anyhow::Result;
+use mongodb::Database;
+use polymarket_client_sdk::auth::state::Authenticated;
+use polymarket_client_sdk::auth::Normal;
+use polymarket_client_sdk::clob::Client as ClobClient;
+use std::net::SocketAddr;
+use std::sync::{Arc, RwLock};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpListener;
+use tokio::time::{interval, Duration};
+use crate::config::Env;
+use crate::utils::health_check::{log_health_check, perform_health_check, HealthCheckResult};
+use crate::utils::logger::Logger;
+
+/// Latest health check result, shared by the periodic checks and the HTTP endpoints
+pub type SharedHealth = Arc<RwLock<HealthCheckResult>>;
+
+/// Status code and JSON body for a `/healthz` or `/readyz` request.
+///
+/// `/healthz` (liveness) fails when checks have stopped running (`max_age_seconds`)
+/// or the RTDS stream is dead, i.e. when a restart would help. `/readyz` fails
+/// whenever the latest check is unhealthy. Neither exposes the check details
+/// (wallets, balances); those are in `polycopy health check`.
+pub fn health_response(path: &str, latest: &HealthCheckResult, now: i64, max_age_seconds: Option<u64>) -> (u16, String) {
+    match path.split('?').next().unwrap_or(path) {
+        "/healthz" => {
+            let age = (now - latest.timestamp).max(0) as u64;
+            let problem = match (max_age_seconds, &latest.checks.rtds) {
+                (Some(max), _) if age > max => Some(format!("last health check was {}s ago", age)),
+                (_, Some(rtds)) if rtds.status == "error" => Some(format!("RTDS: {}", rtds.message)),
+                _ => None,
+            };
+            match problem {
+                None => (200, serde_json::json!({ "status": "ok" }).to_string()),
+                Some(message) => (503, serde_json::json!({ "status": "error", "message": message }).to_string()),
+            }
+        }
+        "/readyz" if latest.healthy => (200, serde_json::json!({ "status": "ok" }).to_string()),
+        "/readyz" => (503, serde_json::json!({ "status": "error" }).to_string()),
+        _ => (404, serde_json::json!({ "status": "error", "message": "not found" }).to_string()),
+    }
+}
+
+/// Re-run the health checks every HEALTH_CHECK_INTERVAL_SECONDS and log when the outcome changes
+pub async fn start_health_monitor(
+    env: Arc<Env>,
+    db: Arc<Database>,
+    clob_client: Arc<ClobClient<Authenticated<Normal>>>,
+    health: SharedHealth,
+) -> Result<()> {
+    let mut ticker = interval(Duration::from_secs(env.health.check_interval_seconds));
+    ticker.tick().await; // The startup check already ran
+
+    loop {
+        ticker.tick().await;
+        let result = match perform_health_check(&db, &env, Some(&clob_client)).await {
+            Ok(result) => result,
+            Err(e) => {
+                Logger::error(&format!("Health check failed to run: {}", e));
+                continue;
+            }
+        };
+
+        let changed = {
+            let previous = health.read().unwrap();
+            statuses(&previous) != statuses(&result)
+        };
+        if changed {
+            if result.healthy {
+                Logger::success("Health check status changed - all checks passing");
+            } else {
+                Logger::warning("Health check status changed - bot is unhealthy");
+            }
+            log_health_check(&result);
+        }
+        *health.write().unwrap() = result;
+    }
+}
+
+fn statuses(result: &HealthCheckResult) -> Vec<&str> {
+    let checks = &result.checks;
+    [
+        &checks.database,
+        &checks.rpc,
+        &checks.polymarket_api,
+        &checks.allowances,
+        &checks.clob_api_key,
+        &checks.clock_skew,
+        &checks.wallet,
+    ]
+    .into_iter()
+    .chain(checks.rtds.as_ref())
+    .map(|check| check.status.as_str())
+    .chain([checks.balance.status.as_str(), checks.gas.status.as_str()])
+    .collect()
+}
+
+/// Serve `/healthz` and `/readyz` on HEALTH_BIND_ADDRESS:HEALTH_PORT for container orchestration
+pub async fn serve_health_endpoints(address: SocketAddr, health: SharedHealth, max_age_seconds: Option<u64>) -> Result<()> {
+    let listener = TcpListener::bind(address).await?;
+    Logger::info(&format!("Health endpoints listening on {} (/healthz, /readyz)", address));
+
+    loop {
+        let (mut stream, _) = listener.accept().await?;
+        let health = health.clone();
+        tokio::spawn(async move {
+            let mut buffer = [0u8; 2048];
+            let read = stream.read(&mut buffer).await.unwrap_or(0);
+            let request = String::from_utf8_lossy(&buffer[..read]);
+            let path = request.split_whitespace().nth(1).unwrap_or("/").to_string();
+
+            let (status, body) = {
+                let latest = health.read().unwrap();
+                health_response(&path, &latest, chrono::Utc::now().timestamp(), max_age_seconds)
+            };
+            let reason = match status {
+                200 => "OK",
+                404 => "Not Found",
+                _ => "Service Unavailable",
+            };
+            let response = format!(
+                "HTTP/1.1 {} {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                status,
+                reason,
+                body.len(),
+                body
+            );
+            let _ = stream.write_all(response.as_bytes()).await;
+        });
+    }
+}
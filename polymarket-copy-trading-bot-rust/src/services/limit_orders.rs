@@ -0,0 +1,505 @@
+use anyhow::Result;
+use futures_util::TryStreamExt;
+use mongodb::bson::{doc, oid::ObjectId};
+use mongodb::options::UpdateOptions;
+use mongodb::Database;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use tokio::time::{interval, Duration};
+use crate::config::{
+    get_limit_orders_collection, get_user_position_collection, Env, LimitOrderType, TimeoutAction,
+};
+use crate::interfaces::UserActivity;
+use crate::services::paper_trading::{apply_paper_fill, PaperPosition};
+use crate::services::pnl_ledger::{record_ledger_entry, LedgerEntry};
+use crate::utils::logger::Logger;
+use crate::utils::post_order::{get_order_book, parse_book_levels};
+
+pub const STATUS_OPEN: &str = "OPEN";
+pub const STATUS_PARTIALLY_FILLED: &str = "PARTIALLY_FILLED";
+pub const STATUS_FILLED: &str = "FILLED";
+pub const STATUS_CANCELLED: &str = "CANCELLED";
+pub const STATUS_EXPIRED: &str = "EXPIRED";
+
+const DEFAULT_TICK_SIZE: f64 = 0.01;
+/// The CLOB rejects GTD orders expiring less than a minute out
+const GTD_SECURITY_SECS: i64 = 60;
+const SIZE_EPSILON: f64 = 1e-6;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LimitOrderFill {
+    pub price: f64,
+    pub size: f64,
+    pub timestamp: i64,
+}
+
+/// Opposite-side depth at one price level that a simulated fill already used
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ConsumedDepth {
+    pub price: f64,
+    pub size: f64,
+}
+
+/// A copy order resting on the book (simulated in preview mode)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LimitOrder {
+    #[serde(rename = "_id", skip_serializing_if = "Option::is_none")]
+    pub id: Option<ObjectId>,
+    pub trader_address: String,
+    pub source_transaction_hash: String,
+    pub asset: String,
+    pub condition_id: String,
+    #[serde(default)]
+    pub event_slug: String,
+    pub title: String,
+    pub slug: String,
+    pub outcome: String,
+    pub side: String,
+    pub order_type: String,
+    pub trader_price: f64,
+    pub price: f64,
+    /// Worst price re-pricing may move to
+    pub price_cap: f64,
+    /// Order size in tokens
+    pub size: f64,
+    pub filled_size: f64,
+    pub fills: Vec<LimitOrderFill>,
+    /// Resting depth already filled against, so later polls don't fill
+    /// against the same liquidity again
+    #[serde(default)]
+    pub consumed_depth: Vec<ConsumedDepth>,
+    pub status: String,
+    pub reprice_count: i32,
+    pub placed_at: i64,
+    /// When the current price was set (start of the timeout window)
+    pub priced_at: i64,
+    pub expires_at: Option<i64>,
+    pub closed_at: Option<i64>,
+}
+
+impl LimitOrder {
+    pub fn is_buy(&self) -> bool {
+        self.side == "BUY"
+    }
+
+    /// Unfilled size in tokens
+    pub fn remaining(&self) -> f64 {
+        (self.size - self.filled_size).max(0.0)
+    }
+
+    fn avg_fill_price(&self) -> f64 {
+        if self.filled_size <= 0.0 {
+            return 0.0;
+        }
+        self.fills.iter().map(|f| f.price * f.size).sum::<f64>() / self.filled_size
+    }
+
+    /// Opposite-side levels that reach our price, best first
+    fn crossing_levels(&self, order_book: &serde_json::Value) -> Vec<(f64, f64)> {
+        let mut levels: Vec<(f64, f64)> = if self.is_buy() {
+            parse_book_levels(order_book, "asks")
+                .into_iter()
+                .filter(|(price, _)| *price <= self.price)
+                .collect()
+        } else {
+            parse_book_levels(order_book, "bids")
+                .into_iter()
+                .filter(|(price, _)| *price >= self.price)
+                .collect()
+        };
+        if self.is_buy() {
+            levels.sort_by(|a, b| a.0.total_cmp(&b.0));
+        } else {
+            levels.sort_by(|a, b| b.0.total_cmp(&a.0));
+        }
+        levels
+    }
+
+    fn consumed_at(&self, price: f64) -> f64 {
+        self.consumed_depth
+            .iter()
+            .filter(|level| (level.price - price).abs() < SIZE_EPSILON)
+            .map(|level| level.size)
+            .sum()
+    }
+
+    /// Crossing depth that no earlier poll has filled against
+    pub fn fresh_depth(&self, order_book: &serde_json::Value) -> f64 {
+        self.crossing_levels(order_book)
+            .into_iter()
+            .map(|(price, size)| (size - self.consumed_at(price)).max(0.0))
+            .sum()
+    }
+
+    /// Mark `size` of the fresh crossing depth as used, best price first.
+    /// Levels that left the book are forgotten and shrunk levels are capped
+    /// at what's still resting.
+    pub fn consume_depth(&mut self, order_book: &serde_json::Value, size: f64) {
+        let mut left = size;
+        let mut consumed = Vec::new();
+        for (price, level_size) in self.crossing_levels(order_book) {
+            let used = self.consumed_at(price).min(level_size);
+            let take = (level_size - used).min(left);
+            left -= take;
+            if used + take > SIZE_EPSILON {
+                consumed.push(ConsumedDepth { price, size: used + take });
+            }
+        }
+        self.consumed_depth = consumed;
+    }
+}
+
+fn tick_size(order_book: &serde_json::Value) -> f64 {
+    order_book
+        .get("tick_size")
+        .and_then(|t| t.as_str())
+        .and_then(|s| s.parse().ok())
+        .unwrap_or(DEFAULT_TICK_SIZE)
+}
+
+/// Round to the tick grid on the passive side (down for buys, up for sells)
+fn round_to_tick(price: f64, tick: f64, is_buy: bool) -> f64 {
+    let ticks = price / tick;
+    let rounded = if is_buy { (ticks + 1e-9).floor() } else { (ticks - 1e-9).ceil() };
+    (rounded * tick).clamp(tick, 1.0 - tick)
+}
+
+/// Rest a copy order at the trader's price (shifted by LIMIT_PRICE_OFFSET)
+/// instead of taking liquidity. `amount` is USDC for buys and tokens for
+/// sells; `slippage_limit` bounds both the initial price and any re-pricing.
+pub async fn place_limit_order(
+    trade: &UserActivity,
+    side: &str,
+    amount: f64,
+    slippage_limit: Option<f64>,
+    user_address: &str,
+    env: &Env,
+    db: &Database,
+) -> Result<()> {
+    let config = &env.limit_orders;
+    let is_buy = side == "BUY";
+
+    let order_book = get_order_book(env, &trade.asset).await?;
+    let tick = tick_size(&order_book);
+
+    let target = if is_buy {
+        trade.price - config.price_offset
+    } else {
+        trade.price + config.price_offset
+    };
+    let price_cap = match slippage_limit {
+        Some(limit) => limit,
+        None if is_buy => target.max(trade.price),
+        None => target.min(trade.price),
+    };
+    let target = if is_buy { target.min(price_cap) } else { target.max(price_cap) };
+    let price = round_to_tick(target, tick, is_buy);
+    let size = if is_buy { amount / price } else { amount };
+
+    let now = chrono::Utc::now().timestamp();
+    let expires_at = (config.order_type == LimitOrderType::Gtd)
+        .then(|| now + config.timeout_secs as i64 + GTD_SECURITY_SECS);
+
+    let order = LimitOrder {
+        id: None,
+        trader_address: user_address.to_lowercase(),
+        source_transaction_hash: trade.transaction_hash.clone(),
+        asset: trade.asset.clone(),
+        condition_id: trade.condition_id.clone(),
+        event_slug: trade.event_slug.clone(),
+        title: trade.title.clone(),
+        slug: trade.slug.clone(),
+        outcome: trade.outcome.clone(),
+        side: side.to_string(),
+        order_type: config.order_type.to_string(),
+        trader_price: trade.price,
+        price,
+        price_cap,
+        size,
+        filled_size: 0.0,
+        fills: Vec::new(),
+        consumed_depth: Vec::new(),
+        status: STATUS_OPEN.to_string(),
+        reprice_count: 0,
+        placed_at: now,
+        priced_at: now,
+        expires_at,
+        closed_at: None,
+    };
+    get_limit_orders_collection(db)
+        .insert_one(mongodb::bson::to_document(&order)?, None)
+        .await?;
+
+    // Preview mode: the order is only tracked locally
+    Logger::order_result(
+        true,
+        &format!(
+            "[PREVIEW] Would place {} {} limit: {:.2} tokens @ ${:.4} (${:.2}, trader ${:.4})",
+            order.order_type,
+            side,
+            size,
+            price,
+            size * price,
+            trade.price
+        ),
+    );
+    Logger::info(&format!(
+        "Tracking order - {} after {}s if unfilled",
+        config.on_timeout.to_string().to_lowercase(),
+        config.timeout_secs
+    ));
+
+    Ok(())
+}
+
+pub async fn start_limit_order_tracker(env: Arc<Env>, db: Arc<Database>) -> Result<()> {
+    let config = &env.limit_orders;
+    Logger::success("Limit order tracker started");
+    Logger::info(&format!(
+        "Order type: {}, offset: {}, timeout: {}s, on timeout: {} (max {} reprices)",
+        config.order_type, config.price_offset, config.timeout_secs, config.on_timeout, config.max_reprices
+    ));
+
+    let mut check_interval = interval(Duration::from_millis(config.check_interval_ms));
+    loop {
+        check_interval.tick().await;
+        if let Err(e) = check_open_orders(&env, &db).await {
+            Logger::error(&format!("Error checking limit orders: {}", e));
+        }
+    }
+}
+
+/// Orders still resting on the book (open or partially filled)
+pub async fn open_limit_orders(db: &Database) -> Result<Vec<LimitOrder>> {
+    let filter = doc! { "status": { "$in": [STATUS_OPEN, STATUS_PARTIALLY_FILLED] } };
+    let docs: Vec<mongodb::bson::Document> = get_limit_orders_collection(db)
+        .find(filter, None)
+        .await?
+        .try_collect()
+        .await?;
+
+    docs.into_iter()
+        .map(|doc| Ok(mongodb::bson::from_document(doc)?))
+        .collect()
+}
+
+async fn check_open_orders(env: &Env, db: &Database) -> Result<()> {
+    for order in open_limit_orders(db).await? {
+        if let Err(e) = check_order(order, env, db).await {
+            Logger::error(&format!("Error checking limit order: {}", e));
+        }
+    }
+    Ok(())
+}
+
+async fn check_order(mut order: LimitOrder, env: &Env, db: &Database) -> Result<()> {
+    let config = &env.limit_orders;
+    let now = chrono::Utc::now().timestamp();
+    let order_book = get_order_book(env, &order.asset).await?;
+
+    // Simulated fill: opposite-side depth that reaches our price, less what
+    // earlier polls already filled against. Queue position isn't modelled,
+    // so resting fills are optimistic.
+    let fill_size = order.fresh_depth(&order_book).min(order.remaining());
+    let fill_size = if fill_size > SIZE_EPSILON {
+        record_fill(&mut order, fill_size, now, env, db).await?
+    } else {
+        0.0
+    };
+    order.consume_depth(&order_book, fill_size);
+    if order.status == STATUS_FILLED {
+        return save_order(&order, db).await;
+    }
+
+    // GTD orders lapse on the exchange by themselves
+    if order.expires_at.is_some_and(|expires_at| now >= expires_at) {
+        return close_order(order, STATUS_EXPIRED, now, db).await;
+    }
+
+    if now - order.priced_at < config.timeout_secs as i64 {
+        if fill_size > SIZE_EPSILON {
+            save_order(&order, db).await?;
+        }
+        return Ok(());
+    }
+
+    if config.on_timeout == TimeoutAction::Reprice && order.reprice_count < config.max_reprices as i32 {
+        if let Some(new_price) = reprice_target(&order, &order_book) {
+            Logger::info(&format!(
+                "🔁 Re-pricing {} limit on {}: ${:.4} → ${:.4} ({}/{})",
+                order.side,
+                order.slug,
+                order.price,
+                new_price,
+                order.reprice_count + 1,
+                config.max_reprices
+            ));
+            order.price = new_price;
+            order.priced_at = now;
+            order.reprice_count += 1;
+            if order.expires_at.is_some() {
+                order.expires_at = Some(now + config.timeout_secs as i64 + GTD_SECURITY_SECS);
+            }
+            return save_order(&order, db).await;
+        }
+    }
+
+    close_order(order, STATUS_CANCELLED, now, db).await
+}
+
+/// Move towards the touch, never past the order's price cap. `None` if that
+/// wouldn't improve the current price.
+fn reprice_target(order: &LimitOrder, order_book: &serde_json::Value) -> Option<f64> {
+    let tick = tick_size(order_book);
+    if order.is_buy() {
+        let best_ask = parse_book_levels(order_book, "asks")
+            .into_iter()
+            .map(|(price, _)| price)
+            .reduce(f64::min)?;
+        let new_price = round_to_tick(best_ask.min(order.price_cap), tick, true);
+        (new_price > order.price + SIZE_EPSILON).then_some(new_price)
+    } else {
+        let best_bid = parse_book_levels(order_book, "bids")
+            .into_iter()
+            .map(|(price, _)| price)
+            .reduce(f64::max)?;
+        let new_price = round_to_tick(best_bid.max(order.price_cap), tick, false);
+        (new_price < order.price - SIZE_EPSILON).then_some(new_price)
+    }
+}
+
+/// Add a fill to the order and apply it to our tracked position and the PnL
+/// ledger. Returns the size filled, which paper trading caps at what the
+/// virtual cash covers.
+async fn record_fill(
+    order: &mut LimitOrder,
+    size: f64,
+    timestamp: i64,
+    env: &Env,
+    db: &Database,
+) -> Result<f64> {
+    let size = if env.paper_trading.enabled {
+        let market = PaperPosition {
+            asset: order.asset.clone(),
+            condition_id: order.condition_id.clone(),
+            title: order.title.clone(),
+            slug: order.slug.clone(),
+            event_slug: order.event_slug.clone(),
+            outcome: order.outcome.clone(),
+            ..Default::default()
+        };
+        apply_paper_fill(db, market, &order.side, order.price, size, &order.trader_address, &env.paper_trading).await?
+    } else {
+        size
+    };
+    if size <= SIZE_EPSILON {
+        Logger::warning(&format!("[PAPER] Nothing left to fill the {} limit on {} (cash or position used up)", order.side, order.slug));
+        return Ok(0.0);
+    }
+
+    order.fills.push(LimitOrderFill { price: order.price, size, timestamp });
+    order.filled_size += size;
+    order.status = if order.remaining() <= SIZE_EPSILON {
+        STATUS_FILLED.to_string()
+    } else {
+        STATUS_PARTIALLY_FILLED.to_string()
+    };
+
+    // Simulated fills never reach the wallet, so they stay out of our positions
+    if !env.preview_mode {
+        let (size_delta, bought_delta) = if order.is_buy() {
+            (size, size * order.price)
+        } else {
+            (-size, 0.0)
+        };
+        get_user_position_collection(db, &env.proxy_wallet)
+            .update_one(
+                doc! { "asset": &order.asset, "conditionId": &order.condition_id },
+                doc! {
+                    "$inc": { "size": size_delta, "totalBought": bought_delta },
+                    "$set": {
+                        "proxyWallet": &env.proxy_wallet,
+                        "title": &order.title,
+                        "slug": &order.slug,
+                        "outcome": &order.outcome,
+                    },
+                },
+                UpdateOptions::builder().upsert(true).build(),
+            )
+            .await?;
+    }
+
+    record_ledger_entry(
+        db,
+        &LedgerEntry {
+            kind: order.side.clone(),
+            trader_address: Some(order.trader_address.clone()),
+            asset: order.asset.clone(),
+            condition_id: order.condition_id.clone(),
+            title: order.title.clone(),
+            slug: order.slug.clone(),
+            outcome: order.outcome.clone(),
+            price: order.price,
+            size,
+            usdc: order.price * size,
+            timestamp,
+            transaction_hash: order.source_transaction_hash.clone(),
+            simulated: env.preview_mode,
+        },
+    )
+    .await;
+
+    Logger::order_result(
+        true,
+        &format!(
+            "[{}] {} limit filled {:.2} tokens @ ${:.4} ({:.2}/{:.2}) - {}",
+            if env.paper_trading.enabled { "PAPER" } else { "PREVIEW" },
+            order.side,
+            size,
+            order.price,
+            order.filled_size,
+            order.size,
+            order.slug
+        ),
+    );
+    Ok(size)
+}
+
+async fn close_order(mut order: LimitOrder, status: &str, now: i64, db: &Database) -> Result<()> {
+    order.status = status.to_string();
+    order.closed_at = Some(now);
+    save_order(&order, db).await?;
+
+    let summary = if order.filled_size > 0.0 {
+        format!(
+            "filled {:.2}/{:.2} tokens @ avg ${:.4}",
+            order.filled_size,
+            order.size,
+            order.avg_fill_price()
+        )
+    } else {
+        "nothing filled".to_string()
+    };
+    Logger::warning(&format!(
+        "{} limit on {} {} after {}s: {}",
+        order.side,
+        order.slug,
+        status.to_lowercase(),
+        now - order.placed_at,
+        summary
+    ));
+    Ok(())
+}
+
+async fn save_order(order: &LimitOrder, db: &Database) -> Result<()> {
+    let Some(id) = order.id else {
+        anyhow::bail!("Limit order has no _id");
+    };
+    get_limit_orders_collection(db)
+        .replace_one(doc! { "_id": id }, mongodb::bson::to_document(order)?, None)
+        .await?;
+    Ok(())
+}
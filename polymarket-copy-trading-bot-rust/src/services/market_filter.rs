@@ -0,0 +1,198 @@
+use anyhow::Result;
+use mongodb::Database;
+use crate::config::{get_skipped_trades_collection, Env, MarketFilterConfig};
+use crate::interfaces::UserActivity;
+use crate::utils::gamma_api::{fetch_market_with_tags, GammaMarket};
+use crate::utils::logger::Logger;
+
+/// Why a trade was not copied
+#[derive(Debug, Clone)]
+pub struct FilterRejection {
+    pub filter: &'static str,
+    pub reason: String,
+}
+
+impl FilterRejection {
+    fn new(filter: &'static str, reason: String) -> Self {
+        Self { filter, reason }
+    }
+}
+
+fn contains_any(haystacks: &[&str], needles: &[String]) -> Option<String> {
+    needles
+        .iter()
+        .find(|needle| haystacks.iter().any(|h| h.to_lowercase().contains(needle.as_str())))
+        .cloned()
+}
+
+/// Run every configured filter against `trade`. `market` is the Gamma record
+/// for the trade's condition ID, required only by tag/liquidity/volume/end-date filters.
+pub fn check_market_filters(
+    config: &MarketFilterConfig,
+    trade: &UserActivity,
+    market: Option<&GammaMarket>,
+    now: chrono::DateTime<chrono::Utc>,
+) -> Result<(), FilterRejection> {
+    // Price bounds
+    if let Some(min_price) = config.min_price.filter(|&p| trade.price < p) {
+        return Err(FilterRejection::new(
+            "min_price",
+            format!("price {:.3} below {:.3}", trade.price, min_price),
+        ));
+    }
+    if let Some(max_price) = config.max_price.filter(|&p| trade.price > p) {
+        return Err(FilterRejection::new(
+            "max_price",
+            format!("price {:.3} above {:.3}", trade.price, max_price),
+        ));
+    }
+
+    // Keywords on title / slugs
+    let texts = [trade.title.as_str(), trade.slug.as_str(), trade.event_slug.as_str()];
+    if let Some(keyword) = contains_any(&texts, &config.exclude_keywords) {
+        return Err(FilterRejection::new(
+            "exclude_keywords",
+            format!("matches excluded keyword \"{}\"", keyword),
+        ));
+    }
+    if !config.include_keywords.is_empty() && contains_any(&texts, &config.include_keywords).is_none() {
+        return Err(FilterRejection::new(
+            "include_keywords",
+            "no included keyword in title or slug".to_string(),
+        ));
+    }
+
+    if !config.needs_market_data() {
+        return Ok(());
+    }
+    let Some(market) = market else {
+        return Err(FilterRejection::new(
+            "market_data",
+            format!("market {} not found on Gamma", trade.condition_id),
+        ));
+    };
+
+    // Tags / category
+    let tags = market.tag_names();
+    if let Some(tag) = config.exclude_tags.iter().find(|t| tags.contains(t)) {
+        return Err(FilterRejection::new("exclude_tags", format!("tagged \"{}\"", tag)));
+    }
+    if !config.include_tags.is_empty() && !config.include_tags.iter().any(|t| tags.contains(t)) {
+        return Err(FilterRejection::new(
+            "include_tags",
+            format!("tags [{}] not in include list", tags.join(", ")),
+        ));
+    }
+
+    // Liquidity / volume
+    if let Some(min) = config.min_liquidity_usd {
+        let liquidity = market.liquidity_num.unwrap_or(0.0);
+        if liquidity < min {
+            return Err(FilterRejection::new(
+                "min_liquidity",
+                format!("liquidity ${:.0} below ${:.0}", liquidity, min),
+            ));
+        }
+    }
+    if let Some(min) = config.min_volume_usd {
+        let volume = market.volume_num.unwrap_or(0.0);
+        if volume < min {
+            return Err(FilterRejection::new(
+                "min_volume",
+                format!("volume ${:.0} below ${:.0}", volume, min),
+            ));
+        }
+    }
+
+    // Time to resolution
+    if config.min_hours_to_end.is_some() || config.max_hours_to_end.is_some() {
+        let end = market
+            .end_date
+            .as_deref()
+            .and_then(|d| chrono::DateTime::parse_from_rfc3339(d).ok());
+        let Some(end) = end else {
+            return Err(FilterRejection::new("end_date", "market has no end date".to_string()));
+        };
+        let hours_left = (end.timestamp() - now.timestamp()) as f64 / 3600.0;
+        if let Some(min) = config.min_hours_to_end.filter(|&h| hours_left < h) {
+            return Err(FilterRejection::new(
+                "min_hours_to_end",
+                format!("ends in {:.1}h (< {}h)", hours_left, min),
+            ));
+        }
+        if let Some(max) = config.max_hours_to_end.filter(|&h| hours_left > h) {
+            return Err(FilterRejection::new(
+                "max_hours_to_end",
+                format!("ends in {:.1}h (> {}h)", hours_left, max),
+            ));
+        }
+    }
+
+    Ok(())
+}
+
+async fn record_skipped_trade(
+    db: &Database,
+    trade: &UserActivity,
+    user_address: &str,
+    rejection: &FilterRejection,
+) -> Result<()> {
+    let doc = mongodb::bson::doc! {
+        "traderAddress": user_address.to_lowercase(),
+        "transactionHash": &trade.transaction_hash,
+        "conditionId": &trade.condition_id,
+        "asset": &trade.asset,
+        "side": &trade.side,
+        "price": trade.price,
+        "usdcSize": trade.usdc_size,
+        "title": &trade.title,
+        "slug": &trade.slug,
+        "filter": rejection.filter,
+        "reason": &rejection.reason,
+        "tradeTimestamp": trade.timestamp,
+        "skippedAt": chrono::Utc::now().timestamp(),
+    };
+    get_skipped_trades_collection(db).insert_one(doc, None).await?;
+    Ok(())
+}
+
+/// Apply market filters to a trade about to be copied. Returns `false` (and
+/// records the skip) if the trade should not be copied. Sells always pass so
+/// existing positions can still be exited.
+pub async fn passes_market_filters(
+    trade: &UserActivity,
+    user_address: &str,
+    env: &Env,
+    db: &Database,
+) -> bool {
+    let config = &env.market_filter;
+    if !config.is_enabled() || trade.side != "BUY" {
+        return true;
+    }
+
+    let market = if config.needs_market_data() {
+        match fetch_market_with_tags(&trade.condition_id, env).await {
+            Ok(market) => market,
+            Err(e) => {
+                Logger::warning(&format!("Could not fetch market data for filters: {}", e));
+                None
+            }
+        }
+    } else {
+        None
+    };
+
+    match check_market_filters(config, trade, market.as_ref(), chrono::Utc::now()) {
+        Ok(()) => true,
+        Err(rejection) => {
+            Logger::warning(&format!(
+                "⏭️  Skipping trade ({}): {}",
+                rejection.filter, rejection.reason
+            ));
+            if let Err(e) = record_skipped_trade(db, trade, user_address, &rejection).await {
+                Logger::error(&format!("Failed to record skipped trade: {}", e));
+            }
+            false
+        }
+    }
+}
@@ -2,9 +2,29 @@ pub mod trade_monitor;
 pub mod trade_executor;
 pub mod auto_claim;
 pub mod take_profit_stop_loss;
+pub mod market_filter;
+pub mod limit_orders;
+pub mod exposure_limits;
+pub mod circuit_breaker;
+pub mod reconciliation;
+pub mod config_reload;
+pub mod paper_trading;
+pub mod activity_mirror;
+pub mod accounts;
+pub mod health_monitor;
+#[allow(dead_code)] // Reports are built by the pnl_report binary
+pub mod pnl_ledger;
 
 pub use trade_monitor::*;
 pub use trade_executor::*;
 pub use auto_claim::*;
 pub use take_profit_stop_loss::*;
+pub use limit_orders::start_limit_order_tracker;
+pub use circuit_breaker::start_circuit_breaker;
+pub use reconciliation::start_reconciliation;
+pub use config_reload::start_config_watcher;
+pub use paper_trading::start_paper_trading;
+pub use activity_mirror::start_activity_mirror;
+pub use accounts::connect_execution_accounts;
+pub use health_monitor::{serve_health_endpoints, start_health_monitor};
 
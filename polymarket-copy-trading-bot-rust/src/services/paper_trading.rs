@@ -0,0 +1,565 @@
+use anyhow::Result;
+use futures_util::TryStreamExt;
+use mongodb::bson::{doc, Document};
+use mongodb::options::{FindOptions, ReplaceOptions};
+use mongodb::Database;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::sync::Arc;
+use tokio::time::{interval, Duration};
+use crate::config::{
+    get_paper_account_collection, get_paper_positions_collection, get_pnl_ledger_collection, Env, PaperTradingConfig,
+};
+use crate::interfaces::{UserActivity, UserPosition};
+use crate::services::pnl_ledger::{fetch_midpoints, record_ledger_entry, LedgerEntry, KIND_BUY, KIND_SELL};
+use crate::utils::gamma_api::fetch_markets_by_condition_ids;
+use crate::utils::logger::Logger;
+use crate::utils::market_resolution::MarketResolution;
+use crate::utils::post_order::{get_order_book, parse_book_levels};
+
+const ACCOUNT_ID: &str = "account";
+const SIZE_EPSILON: f64 = 1e-9;
+
+/// Virtual USDC balance, persisted so a paper run can span restarts
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PaperAccount {
+    pub starting_balance: f64,
+    pub cash: f64,
+    pub realized_pnl: f64,
+    /// Position value at the last mark
+    pub positions_value: f64,
+    /// Equity at the last mark of each UTC day (YYYY-MM-DD)
+    #[serde(default)]
+    pub daily_equity: BTreeMap<String, f64>,
+    pub created_at: i64,
+    pub updated_at: i64,
+}
+
+impl PaperAccount {
+    pub fn new(starting_balance: f64) -> Self {
+        let now = chrono::Utc::now().timestamp();
+        Self {
+            starting_balance,
+            cash: starting_balance,
+            created_at: now,
+            updated_at: now,
+            ..Default::default()
+        }
+    }
+
+    pub fn equity(&self) -> f64 {
+        self.cash + self.positions_value
+    }
+
+    pub fn return_percent(&self) -> f64 {
+        if self.starting_balance > 0.0 {
+            (self.equity() - self.starting_balance) / self.starting_balance * 100.0
+        } else {
+            0.0
+        }
+    }
+}
+
+/// Paper holding of one outcome token
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PaperPosition {
+    pub asset: String,
+    pub condition_id: String,
+    pub title: String,
+    pub slug: String,
+    pub event_slug: String,
+    pub outcome: String,
+    pub size: f64,
+    pub avg_price: f64,
+    /// Last mark (midpoint), or the last fill price before the first mark
+    pub cur_price: f64,
+    /// Copied traders whose buys built this position
+    #[serde(default)]
+    pub traders: Vec<String>,
+    pub opened_at: i64,
+    pub updated_at: i64,
+}
+
+impl PaperPosition {
+    /// Empty position in the market of `trade`
+    pub fn for_trade(trade: &UserActivity) -> Self {
+        Self {
+            asset: trade.asset.clone(),
+            condition_id: trade.condition_id.clone(),
+            title: trade.title.clone(),
+            slug: trade.slug.clone(),
+            event_slug: trade.event_slug.clone(),
+            outcome: trade.outcome.clone(),
+            ..Default::default()
+        }
+    }
+
+    pub fn cost(&self) -> f64 {
+        self.size * self.avg_price
+    }
+
+    pub fn value(&self) -> f64 {
+        self.size * self.cur_price
+    }
+
+    pub fn unrealized_pnl(&self) -> f64 {
+        self.value() - self.cost()
+    }
+
+    /// Add bought tokens at the average cost
+    pub fn apply_buy(&mut self, size: f64, price: f64, trader: &str) {
+        let total = self.size + size;
+        if total > SIZE_EPSILON {
+            self.avg_price = (self.cost() + size * price) / total;
+        }
+        self.size = total;
+        self.cur_price = price;
+        let trader = trader.to_lowercase();
+        if !trader.is_empty() && !self.traders.contains(&trader) {
+            self.traders.push(trader);
+        }
+        let now = chrono::Utc::now().timestamp();
+        if self.opened_at == 0 {
+            self.opened_at = now;
+        }
+        self.updated_at = now;
+    }
+
+    /// Remove sold tokens (capped at the holding); returns (tokens sold, realized PnL)
+    pub fn apply_sell(&mut self, size: f64, price: f64) -> (f64, f64) {
+        let sold = size.min(self.size);
+        self.size -= sold;
+        if self.size <= SIZE_EPSILON {
+            self.size = 0.0;
+        }
+        self.cur_price = price;
+        self.updated_at = chrono::Utc::now().timestamp();
+        (sold, (price - self.avg_price) * sold)
+    }
+
+    /// The shape `post_order` expects for our own position
+    pub fn as_user_position(&self, wallet: &str) -> UserPosition {
+        UserPosition {
+            id: None,
+            proxy_wallet: wallet.to_string(),
+            asset: self.asset.clone(),
+            condition_id: self.condition_id.clone(),
+            size: self.size,
+            avg_price: self.avg_price,
+            initial_value: self.cost(),
+            current_value: self.value(),
+            cash_pnl: self.unrealized_pnl(),
+            percent_pnl: if self.cost() > 0.0 { self.unrealized_pnl() / self.cost() * 100.0 } else { 0.0 },
+            total_bought: self.cost(),
+            realized_pnl: 0.0,
+            percent_realized_pnl: 0.0,
+            cur_price: self.cur_price,
+            redeemable: false,
+            mergeable: false,
+            title: self.title.clone(),
+            slug: self.slug.clone(),
+            icon: String::new(),
+            event_slug: self.event_slug.clone(),
+            outcome: self.outcome.clone(),
+            outcome_index: 0,
+            opposite_outcome: String::new(),
+            opposite_asset: String::new(),
+            end_date: String::new(),
+            negative_risk: false,
+        }
+    }
+}
+
+/// Result of walking the book for one paper order
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct PaperFill {
+    /// Tokens filled
+    pub size: f64,
+    pub usdc: f64,
+    /// Book levels touched
+    pub levels: usize,
+}
+
+impl PaperFill {
+    pub fn avg_price(&self) -> f64 {
+        if self.size > 0.0 { self.usdc / self.size } else { 0.0 }
+    }
+}
+
+/// Fill an order against book levels, best price first, without going past
+/// `limit`. `amount` is USDC for buys (levels are asks) and tokens for sells
+/// (levels are bids).
+pub fn fill_against_levels(levels: &[(f64, f64)], is_buy: bool, amount: f64, limit: Option<f64>) -> PaperFill {
+    let mut levels: Vec<(f64, f64)> = levels
+        .iter()
+        .copied()
+        .filter(|&(price, size)| price > 0.0 && size > 0.0)
+        .filter(|&(price, _)| match limit {
+            Some(limit) if is_buy => price <= limit,
+            Some(limit) => price >= limit,
+            None => true,
+        })
+        .collect();
+    levels.sort_by(|a, b| {
+        let order = a.0.partial_cmp(&b.0).unwrap_or(std::cmp::Ordering::Equal);
+        if is_buy { order } else { order.reverse() }
+    });
+
+    let mut fill = PaperFill::default();
+    let mut remaining = amount;
+    for (price, size) in levels {
+        if remaining <= SIZE_EPSILON {
+            break;
+        }
+        let tokens = if is_buy { size.min(remaining / price) } else { size.min(remaining) };
+        fill.size += tokens;
+        fill.usdc += tokens * price;
+        fill.levels += 1;
+        remaining -= if is_buy { tokens * price } else { tokens };
+    }
+    fill
+}
+
+/// Load the paper account, opening it with the starting balance on first use
+pub async fn load_paper_account(db: &Database, config: &PaperTradingConfig) -> Result<PaperAccount> {
+    let doc = get_paper_account_collection(db)
+        .find_one(doc! { "_id": ACCOUNT_ID }, None)
+        .await?;
+    match doc {
+        Some(doc) => Ok(mongodb::bson::from_document(doc)?),
+        None => {
+            let account = PaperAccount::new(config.starting_balance_usd);
+            save_paper_account(db, &account).await?;
+            Ok(account)
+        }
+    }
+}
+
+pub async fn save_paper_account(db: &Database, account: &PaperAccount) -> Result<()> {
+    let mut doc = mongodb::bson::to_document(account)?;
+    doc.insert("_id", ACCOUNT_ID);
+    get_paper_account_collection(db)
+        .replace_one(doc! { "_id": ACCOUNT_ID }, doc, ReplaceOptions::builder().upsert(true).build())
+        .await?;
+    Ok(())
+}
+
+/// Apply a field-level update (`$inc` / `$set`) to the account, so concurrent
+/// fills and marks don't overwrite each other's changes
+async fn update_paper_account(db: &Database, update: Document) -> Result<()> {
+    get_paper_account_collection(db)
+        .update_one(doc! { "_id": ACCOUNT_ID }, update, None)
+        .await?;
+    Ok(())
+}
+
+/// Open paper positions, oldest first
+pub async fn load_paper_positions(db: &Database) -> Result<Vec<PaperPosition>> {
+    let options = FindOptions::builder().sort(doc! { "openedAt": 1 }).build();
+    let docs: Vec<mongodb::bson::Document> = get_paper_positions_collection(db)
+        .find(doc! {}, options)
+        .await?
+        .try_collect()
+        .await?;
+    docs.into_iter()
+        .map(|doc| Ok(mongodb::bson::from_document(doc)?))
+        .collect()
+}
+
+pub async fn load_paper_position(db: &Database, asset: &str) -> Result<Option<PaperPosition>> {
+    let doc = get_paper_positions_collection(db)
+        .find_one(doc! { "_id": asset }, None)
+        .await?;
+    Ok(match doc {
+        Some(doc) => Some(mongodb::bson::from_document(doc)?),
+        None => None,
+    })
+}
+
+/// Store a position, or remove it once nothing is held
+async fn save_paper_position(db: &Database, position: &PaperPosition) -> Result<()> {
+    let collection = get_paper_positions_collection(db);
+    if position.size <= SIZE_EPSILON {
+        collection.delete_one(doc! { "_id": &position.asset }, None).await?;
+        return Ok(());
+    }
+    let mut doc = mongodb::bson::to_document(position)?;
+    doc.insert("_id", &position.asset);
+    collection
+        .replace_one(doc! { "_id": &position.asset }, doc, ReplaceOptions::builder().upsert(true).build())
+        .await?;
+    Ok(())
+}
+
+/// Remove a position, returning it as it stood at removal
+async fn take_paper_position(db: &Database, asset: &str) -> Result<Option<PaperPosition>> {
+    let doc = get_paper_positions_collection(db)
+        .find_one_and_delete(doc! { "_id": asset }, None)
+        .await?;
+    Ok(match doc {
+        Some(doc) => Some(mongodb::bson::from_document(doc)?),
+        None => None,
+    })
+}
+
+/// Start a fresh paper run: starting balance, no positions, no simulated ledger entries
+#[allow(dead_code)] // Used by `polycopy sim paper --reset`
+pub async fn reset_paper_account(db: &Database, config: &PaperTradingConfig) -> Result<PaperAccount> {
+    get_paper_positions_collection(db).delete_many(doc! {}, None).await?;
+    get_pnl_ledger_collection(db).delete_many(doc! { "simulated": true }, None).await?;
+    let account = PaperAccount::new(config.starting_balance_usd);
+    save_paper_account(db, &account).await?;
+    Ok(account)
+}
+
+/// Apply a fill to the paper account and position. Buys are capped by cash.
+/// Returns the tokens actually applied.
+pub async fn apply_paper_fill(
+    db: &Database,
+    market: PaperPosition,
+    side: &str,
+    price: f64,
+    size: f64,
+    trader: &str,
+    config: &PaperTradingConfig,
+) -> Result<f64> {
+    let account = load_paper_account(db, config).await?;
+    let mut position = load_paper_position(db, &market.asset).await?.unwrap_or(market);
+
+    let (applied, cash, realized) = if side == KIND_BUY {
+        let size = size.min(account.cash / price);
+        position.apply_buy(size, price, trader);
+        (size, -size * price, 0.0)
+    } else {
+        let (sold, realized) = position.apply_sell(size, price);
+        (sold, sold * price, realized)
+    };
+
+    save_paper_position(db, &position).await?;
+    update_paper_account(
+        db,
+        doc! {
+            "$inc": { "cash": cash, "realizedPnl": realized },
+            "$set": { "updatedAt": chrono::Utc::now().timestamp() },
+        },
+    )
+    .await?;
+    Ok(applied)
+}
+
+/// Fill a copy order against the live book and apply it to the paper account.
+/// `amount` is USDC for buys and tokens for sells; `limit` is the slippage limit.
+pub async fn execute_paper_order(
+    trade: &UserActivity,
+    side: &str,
+    amount: f64,
+    limit: Option<f64>,
+    user_address: &str,
+    env: &Env,
+    db: &Database,
+) -> Result<()> {
+    let is_buy = side == KIND_BUY;
+    let amount = if is_buy {
+        let cash = load_paper_account(db, &env.paper_trading).await?.cash;
+        if cash < amount {
+            Logger::warning(&format!("Paper balance ${:.2} below order ${:.2} - filling what it covers", cash, amount));
+        }
+        amount.min(cash)
+    } else {
+        let held = load_paper_position(db, &trade.asset).await?.map(|p| p.size).unwrap_or(0.0);
+        amount.min(held)
+    };
+    if amount <= SIZE_EPSILON {
+        Logger::warning(if is_buy { "No paper cash left - skipping buy" } else { "No paper position to sell" });
+        return Ok(());
+    }
+
+    let order_book = get_order_book(env, &trade.asset).await?;
+    let levels = parse_book_levels(&order_book, if is_buy { "asks" } else { "bids" });
+    let fill = fill_against_levels(&levels, is_buy, amount, limit);
+    if fill.size <= SIZE_EPSILON {
+        Logger::warning(&format!("No {} depth to fill the paper order", if is_buy { "ask" } else { "bid" }));
+        return Ok(());
+    }
+
+    let price = fill.avg_price();
+    let size = apply_paper_fill(db, PaperPosition::for_trade(trade), side, price, fill.size, user_address, &env.paper_trading).await?;
+    let entry = LedgerEntry::copy_fill(trade, side, user_address, price, size, true);
+    record_ledger_entry(db, &entry).await;
+
+    let account = load_paper_account(db, &env.paper_trading).await?;
+    Logger::order_result(
+        true,
+        &format!(
+            "[PAPER] {} {:.2} tokens @ avg ${:.4} across {} level(s) (${:.2}) - cash ${:.2}",
+            if is_buy { "Bought" } else { "Sold" },
+            size,
+            price,
+            fill.levels,
+            size * price,
+            account.cash
+        ),
+    );
+    Ok(())
+}
+
+/// Credit resolved markets, mark the rest at the midpoint and record today's equity.
+/// Only settlements, prices and the mark fields are written, so fills made
+/// meanwhile are kept.
+pub async fn mark_paper_account(env: &Env, db: &Database) -> Result<PaperAccount> {
+    let config = &env.paper_trading;
+    let positions = load_paper_positions(db).await?;
+
+    let mut condition_ids: Vec<String> = positions.iter().map(|p| p.condition_id.clone()).collect();
+    condition_ids.sort();
+    condition_ids.dedup();
+    let resolutions: HashMap<String, MarketResolution> = if condition_ids.is_empty() {
+        HashMap::new()
+    } else {
+        match fetch_markets_by_condition_ids(&condition_ids, env).await {
+            Ok(markets) => markets
+                .iter()
+                .map(MarketResolution::from_gamma)
+                .map(|r| (r.condition_id.clone(), r))
+                .collect(),
+            Err(e) => {
+                Logger::warning(&format!("Could not check paper positions for resolution: {}", e));
+                HashMap::new()
+            }
+        }
+    };
+
+    // Settle resolved markets, each token at its own price. Positions are
+    // removed as they stand now, so tokens bought since the load are paid too.
+    for position in &positions {
+        let Some(resolution) = resolutions.get(&position.condition_id.to_lowercase()).filter(|r| r.resolved) else {
+            continue;
+        };
+        let Some(position) = take_paper_position(db, &position.asset).await? else {
+            continue;
+        };
+        let price = resolution.settlement_price(&position.asset, Some(&position.outcome)).unwrap_or(0.0);
+        let payout = position.size * price;
+        let pnl = payout - position.cost();
+
+        update_paper_account(db, doc! { "$inc": { "cash": payout, "realizedPnl": pnl } }).await?;
+        let mut entry = LedgerEntry::redemption(
+            &position.asset,
+            &position.condition_id,
+            &position.outcome,
+            price,
+            position.size,
+            &format!("paper-settlement-{}", position.condition_id),
+            true,
+        );
+        entry.title = position.title.clone();
+        entry.slug = position.slug.clone();
+        record_ledger_entry(db, &entry).await;
+        Logger::success(&format!(
+            "[PAPER] Settled {} ({}): {:.2} tokens paid ${:.2} (PnL {:+.2})",
+            position.title, position.outcome, position.size, payout, pnl
+        ));
+    }
+
+    // Only the price is written, a fill in between keeps its size
+    let positions = load_paper_positions(db).await?;
+    let assets: Vec<String> = positions.iter().map(|p| p.asset.clone()).collect();
+    let prices = fetch_midpoints(&assets, env).await;
+    for (asset, price) in &prices {
+        get_paper_positions_collection(db)
+            .update_one(doc! { "_id": asset }, doc! { "$set": { "curPrice": price } }, None)
+            .await?;
+    }
+    let positions_value: f64 = positions
+        .iter()
+        .map(|p| p.size * prices.get(&p.asset).copied().unwrap_or(p.cur_price))
+        .sum();
+
+    let now = chrono::Utc::now();
+    // Opens the account on first use
+    load_paper_account(db, config).await?;
+    update_paper_account(db, doc! { "$set": { "positionsValue": positions_value, "updatedAt": now.timestamp() } }).await?;
+    let mut account = load_paper_account(db, config).await?;
+    let day = now.format("%Y-%m-%d").to_string();
+    let mut equity = Document::new();
+    equity.insert(format!("dailyEquity.{}", day), account.equity());
+    update_paper_account(db, doc! { "$set": equity }).await?;
+    account.daily_equity.insert(day, account.equity());
+    Ok(account)
+}
+
+/// Sell every paper position into the live bids (circuit breaker flatten)
+pub async fn flatten_paper_positions(env: &Env, db: &Database) -> Result<()> {
+    let positions = load_paper_positions(db).await?;
+    Logger::info(&format!("[PAPER] Flattening {} position(s)...", positions.len()));
+    for position in positions {
+        let order_book = match get_order_book(env, &position.asset).await {
+            Ok(book) => book,
+            Err(e) => {
+                Logger::warning(&format!("[PAPER] No order book for {}: {}", position.slug, e));
+                continue;
+            }
+        };
+        let fill = fill_against_levels(&parse_book_levels(&order_book, "bids"), false, position.size, None);
+        if fill.size <= SIZE_EPSILON {
+            Logger::warning(&format!("[PAPER] No bid depth to flatten {}", position.slug));
+            continue;
+        }
+
+        let price = fill.avg_price();
+        let size = apply_paper_fill(db, position.clone(), KIND_SELL, price, fill.size, "", &env.paper_trading).await?;
+        let now = chrono::Utc::now().timestamp();
+        let entry = LedgerEntry {
+            kind: KIND_SELL.to_string(),
+            trader_address: None,
+            asset: position.asset.clone(),
+            condition_id: position.condition_id.clone(),
+            title: position.title.clone(),
+            slug: position.slug.clone(),
+            outcome: position.outcome.clone(),
+            price,
+            size,
+            usdc: price * size,
+            timestamp: now,
+            transaction_hash: format!("paper-flatten-{}-{}", position.asset, now),
+            simulated: true,
+        };
+        record_ledger_entry(db, &entry).await;
+        Logger::order_result(
+            true,
+            &format!("[PAPER] Sold {:.2} tokens of {} @ avg ${:.4} (${:.2})", size, position.slug, price, size * price),
+        );
+    }
+    Ok(())
+}
+
+pub async fn start_paper_trading(env: Arc<Env>, db: Arc<Database>) -> Result<()> {
+    let config = &env.paper_trading;
+    let account = load_paper_account(&db, config).await?;
+    Logger::success(&format!(
+        "Paper trading started: ${:.2} cash, equity ${:.2} ({:+.2}% since {})",
+        account.cash,
+        account.equity(),
+        account.return_percent(),
+        chrono::DateTime::from_timestamp(account.created_at, 0)
+            .map(|t| t.format("%Y-%m-%d").to_string())
+            .unwrap_or_default()
+    ));
+
+    let mut mark_interval = interval(Duration::from_millis(config.mark_interval_ms));
+    loop {
+        mark_interval.tick().await;
+        if let Err(e) = mark_paper_account(&env, &db).await {
+            Logger::error(&format!("Error marking paper positions: {}", e));
+        }
+    }
+}
+
+/// Our paper position in `asset`, in place of the wallet position
+pub async fn paper_user_position(env: &Env, db: &Database, asset: &str) -> Result<Option<UserPosition>> {
+    Ok(load_paper_position(db, asset)
+        .await?
+        .map(|p| p.as_user_position(&env.proxy_wallet)))
+}
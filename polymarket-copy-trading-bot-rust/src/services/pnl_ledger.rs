@@ -0,0 +1,525 @@
+use anyhow::Result;
+use futures_util::TryStreamExt;
+use mongodb::bson::doc;
+use mongodb::options::{FindOneOptions, FindOptions, UpdateOptions};
+use mongodb::Database;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap, VecDeque};
+use crate::config::{get_pnl_ledger_collection, Env};
+use crate::interfaces::UserActivity;
+use crate::utils::gamma_api::fetch_markets_by_condition_ids;
+use crate::utils::market_resolution::MarketResolution;
+use crate::utils::data_api::Activity;
+use crate::utils::{fetch_data, logger::Logger, ActivityQuery, DataApiClient};
+
+pub const KIND_BUY: &str = "BUY";
+pub const KIND_SELL: &str = "SELL";
+pub const KIND_REDEEM: &str = "REDEEM";
+
+const SIZE_EPSILON: f64 = 1e-9;
+
+/// One fill, sell or redemption on our wallet
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LedgerEntry {
+    pub kind: String,
+    /// Copied trader behind the fill. Redemptions have none and are
+    /// attributed through the FIFO lots they close.
+    pub trader_address: Option<String>,
+    /// Outcome token. Redemptions are recorded per token at its settlement
+    /// price; one without a token covers the whole condition pro rata.
+    pub asset: String,
+    pub condition_id: String,
+    pub title: String,
+    pub slug: String,
+    pub outcome: String,
+    /// Fill price, or the payout per token of a redemption
+    pub price: f64,
+    /// Tokens
+    pub size: f64,
+    /// USDC paid (buys) or received (sells / redemptions)
+    pub usdc: f64,
+    pub timestamp: i64,
+    /// Copied trade for fills, redemption transaction for redemptions
+    pub transaction_hash: String,
+    /// Preview-mode fill that never reached the exchange
+    pub simulated: bool,
+}
+
+impl LedgerEntry {
+    /// Entry for a copy of `trade` that filled `size` tokens at `price`
+    pub fn copy_fill(trade: &UserActivity, side: &str, user_address: &str, price: f64, size: f64, simulated: bool) -> Self {
+        Self {
+            kind: side.to_string(),
+            trader_address: Some(user_address.to_lowercase()),
+            asset: trade.asset.clone(),
+            condition_id: trade.condition_id.clone(),
+            title: trade.title.clone(),
+            slug: trade.slug.clone(),
+            outcome: trade.outcome.clone(),
+            price,
+            size,
+            usdc: price * size,
+            timestamp: chrono::Utc::now().timestamp(),
+            transaction_hash: trade.transaction_hash.clone(),
+            simulated,
+        }
+    }
+
+    /// Redemption of `size` tokens of one outcome at its settlement `price`
+    pub fn redemption(
+        asset: &str,
+        condition_id: &str,
+        outcome: &str,
+        price: f64,
+        size: f64,
+        transaction_hash: &str,
+        simulated: bool,
+    ) -> Self {
+        Self {
+            kind: KIND_REDEEM.to_string(),
+            trader_address: None,
+            asset: asset.to_string(),
+            condition_id: condition_id.to_string(),
+            title: String::new(),
+            slug: String::new(),
+            outcome: outcome.to_string(),
+            price,
+            size,
+            usdc: price * size,
+            timestamp: chrono::Utc::now().timestamp(),
+            transaction_hash: transaction_hash.to_string(),
+            simulated,
+        }
+    }
+}
+
+/// Append to the ledger. Failures are logged rather than failing the trade.
+pub async fn record_ledger_entry(db: &Database, entry: &LedgerEntry) {
+    let result = match mongodb::bson::to_document(entry) {
+        Ok(doc) => get_pnl_ledger_collection(db).insert_one(doc, None).await.map(|_| ()).map_err(anyhow::Error::from),
+        Err(e) => Err(e.into()),
+    };
+    if let Err(e) = result {
+        Logger::error(&format!("Failed to record {} in PnL ledger: {}", entry.kind, e));
+    }
+}
+
+/// Full ledger, oldest first
+pub async fn load_ledger(db: &Database) -> Result<Vec<LedgerEntry>> {
+    let options = FindOptions::builder().sort(doc! { "timestamp": 1 }).build();
+    let docs: Vec<mongodb::bson::Document> = get_pnl_ledger_collection(db)
+        .find(doc! {}, options)
+        .await?
+        .try_collect()
+        .await?;
+    docs.into_iter()
+        .map(|doc| Ok(mongodb::bson::from_document(doc)?))
+        .collect()
+}
+
+/// Import redemptions of the proxy wallet from the Data API (idempotent).
+/// Each is split into one entry per outcome token we held, at the token's
+/// settlement price; without resolution data it covers the condition pro rata.
+pub async fn sync_redemptions(env: &Env, db: &Database) -> Result<usize> {
+    // Page forward from the newest redemption already imported (inclusive,
+    // it is skipped below), or through the whole history on the first sync
+    let collection = get_pnl_ledger_collection(db);
+    let newest = collection
+        .find_one(
+            doc! { "kind": KIND_REDEEM, "simulated": false },
+            FindOneOptions::builder().sort(doc! { "timestamp": -1 }).build(),
+        )
+        .await?
+        .and_then(|doc| doc.get("timestamp").and_then(mongodb::bson::Bson::as_i64));
+    let mut query = ActivityQuery::new(&env.proxy_wallet).kind("REDEEM").ascending();
+    if let Some(newest) = newest {
+        query = query.start(newest);
+    }
+    let activities: Vec<_> = DataApiClient::new(env)
+        .activity(&query)
+        .await?
+        .into_iter()
+        .filter(|a| !a.condition_id.is_empty() && !a.transaction_hash.is_empty())
+        .collect();
+    if activities.is_empty() {
+        return Ok(0);
+    }
+
+    let ledger = load_ledger(db).await?;
+    let mut condition_ids: Vec<String> = activities.iter().map(|a| a.condition_id.clone()).collect();
+    condition_ids.sort();
+    condition_ids.dedup();
+    let resolutions: HashMap<String, MarketResolution> = match fetch_markets_by_condition_ids(&condition_ids, env).await {
+        Ok(markets) => markets
+            .iter()
+            .map(MarketResolution::from_gamma)
+            .filter(|r| r.resolved)
+            .map(|r| (r.condition_id.clone(), r))
+            .collect(),
+        Err(e) => {
+            Logger::warning(&format!("Could not fetch resolutions for redemptions: {}", e));
+            HashMap::new()
+        }
+    };
+
+    let mut inserted = 0;
+    for activity in &activities {
+        let imported = doc! {
+            "kind": KIND_REDEEM,
+            "transactionHash": &activity.transaction_hash,
+            "conditionId": &activity.condition_id,
+        };
+        if collection.count_documents(imported, None).await? > 0 {
+            continue;
+        }
+        let resolution = resolutions.get(&activity.condition_id.to_lowercase());
+        for entry in redemption_entries(activity, &ledger, resolution) {
+            let result = collection
+                .update_one(
+                    doc! {
+                        "kind": KIND_REDEEM,
+                        "transactionHash": &entry.transaction_hash,
+                        "conditionId": &entry.condition_id,
+                        "asset": &entry.asset,
+                    },
+                    doc! { "$setOnInsert": mongodb::bson::to_document(&entry)? },
+                    UpdateOptions::builder().upsert(true).build(),
+                )
+                .await?;
+            if result.upserted_id.is_some() {
+                inserted += 1;
+            }
+        }
+    }
+    Ok(inserted)
+}
+
+/// Ledger entries for one of the wallet's redemptions: one per outcome token
+/// the live (non-simulated) fills hold in the market, at its settlement price,
+/// or a single entry covering the condition pro rata without resolution data.
+/// Simulated fills never back a real redemption.
+pub fn redemption_entries(activity: &Activity, ledger: &[LedgerEntry], resolution: Option<&MarketResolution>) -> Vec<LedgerEntry> {
+    let live: Vec<&LedgerEntry> = ledger.iter().filter(|e| !e.simulated).collect();
+    let outcome_of = |asset: &str| {
+        live.iter()
+            .find(|e| e.asset == asset)
+            .map(|e| e.outcome.clone())
+            .unwrap_or_default()
+    };
+    let per_token: Option<Vec<LedgerEntry>> = resolution.and_then(|resolution| {
+        held_tokens(live.iter().copied())
+            .into_iter()
+            .filter(|(_, (condition_id, _))| condition_id.eq_ignore_ascii_case(&activity.condition_id))
+            .map(|(asset, (_, size))| {
+                let outcome = outcome_of(asset);
+                let price = resolution.settlement_price(asset, Some(&outcome))?;
+                Some(LedgerEntry::redemption(asset, &activity.condition_id, &outcome, price, size, &activity.transaction_hash, false))
+            })
+            .collect()
+    });
+    let mut entries = match per_token {
+        Some(entries) if !entries.is_empty() => entries,
+        _ => {
+            let outcome = activity.outcome.clone().unwrap_or_default();
+            let mut entry = LedgerEntry::redemption("", &activity.condition_id, &outcome, 0.0, activity.size, &activity.transaction_hash, false);
+            entry.usdc = activity.usdc_size;
+            vec![entry]
+        }
+    };
+    for entry in &mut entries {
+        entry.title = activity.title.clone().unwrap_or_default();
+        entry.slug = activity.slug.clone().unwrap_or_default();
+        entry.timestamp = activity.timestamp;
+    }
+    entries
+}
+
+/// PnL totals for one trader, market or day
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PnlBucket {
+    pub key: String,
+    pub label: String,
+    pub fills: usize,
+    pub bought_usd: f64,
+    /// Sell and redemption proceeds
+    pub proceeds_usd: f64,
+    pub realized_pnl: f64,
+    pub unrealized_pnl: f64,
+    /// Cost basis of tokens still held
+    pub open_cost_usd: f64,
+}
+
+impl PnlBucket {
+    pub fn total_pnl(&self) -> f64 {
+        self.realized_pnl + self.unrealized_pnl
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PnlReport {
+    pub generated_at: i64,
+    /// Fills and realized PnL before this time are excluded (unrealized is always current)
+    pub since: Option<i64>,
+    pub total: PnlBucket,
+    pub by_trader: Vec<PnlBucket>,
+    pub by_market: Vec<PnlBucket>,
+    pub by_day: Vec<PnlBucket>,
+}
+
+#[derive(Debug, Clone)]
+struct Lot {
+    trader: String,
+    condition_id: String,
+    size: f64,
+    price: f64,
+}
+
+#[derive(Default)]
+struct Buckets {
+    total: PnlBucket,
+    by_trader: BTreeMap<String, PnlBucket>,
+    by_market: BTreeMap<String, PnlBucket>,
+    by_day: BTreeMap<String, PnlBucket>,
+}
+
+impl Buckets {
+    /// Apply `f` to the total and to the trader / market (/ day) buckets
+    fn update(&mut self, trader: &str, market: (&str, &str), day: Option<&str>, f: impl Fn(&mut PnlBucket)) {
+        f(&mut self.total);
+        f(self.by_trader.entry(trader.to_string()).or_insert_with(|| PnlBucket {
+            key: trader.to_string(),
+            label: trader.to_string(),
+            ..Default::default()
+        }));
+        f(self.by_market.entry(market.0.to_string()).or_insert_with(|| PnlBucket {
+            key: market.0.to_string(),
+            label: market.1.to_string(),
+            ..Default::default()
+        }));
+        if let Some(day) = day {
+            f(self.by_day.entry(day.to_string()).or_insert_with(|| PnlBucket {
+                key: day.to_string(),
+                label: day.to_string(),
+                ..Default::default()
+            }));
+        }
+    }
+}
+
+fn day_of(timestamp: i64) -> String {
+    chrono::DateTime::from_timestamp(timestamp, 0)
+        .map(|t| t.format("%Y-%m-%d").to_string())
+        .unwrap_or_default()
+}
+
+/// FIFO cost basis over the ledger. `prices` maps outcome token -> current
+/// price for unrealized PnL; tokens without a price are marked at their last fill.
+/// Sells of tokens bought before the ledger existed are ignored.
+pub fn compute_pnl(entries: &[LedgerEntry], prices: &HashMap<String, f64>, since: Option<i64>) -> PnlReport {
+    let mut lots: HashMap<String, VecDeque<Lot>> = HashMap::new();
+    let mut last_price: HashMap<String, f64> = HashMap::new();
+    let mut titles: HashMap<String, String> = HashMap::new();
+    let mut buckets = Buckets::default();
+    let unknown_trader = "unattributed".to_string();
+
+    for entry in entries {
+        titles.entry(entry.condition_id.clone()).or_insert_with(|| entry.title.clone());
+        let title = titles[&entry.condition_id].clone();
+        let market = (entry.condition_id.as_str(), title.as_str());
+        let in_range = since.is_none_or(|s| entry.timestamp >= s);
+        let day = day_of(entry.timestamp);
+
+        match entry.kind.as_str() {
+            KIND_BUY => {
+                let trader = entry.trader_address.as_ref().unwrap_or(&unknown_trader);
+                lots.entry(entry.asset.clone()).or_default().push_back(Lot {
+                    trader: trader.clone(),
+                    condition_id: entry.condition_id.clone(),
+                    size: entry.size,
+                    price: entry.price,
+                });
+                last_price.insert(entry.asset.clone(), entry.price);
+                if in_range {
+                    buckets.update(trader, market, Some(&day), |b| {
+                        b.fills += 1;
+                        b.bought_usd += entry.usdc;
+                    });
+                }
+            }
+            KIND_SELL | KIND_REDEEM => {
+                // Redemptions close every lot of their token at its settlement
+                // price; one without a token closes the whole condition pro rata
+                let (assets, payout_per_token): (Vec<String>, f64) = if entry.kind == KIND_SELL {
+                    last_price.insert(entry.asset.clone(), entry.price);
+                    (vec![entry.asset.clone()], entry.price)
+                } else if !entry.asset.is_empty() {
+                    (vec![entry.asset.clone()], entry.price)
+                } else {
+                    let assets: Vec<String> = lots
+                        .iter()
+                        .filter(|(_, queue)| queue.iter().any(|l| l.condition_id == entry.condition_id))
+                        .map(|(asset, _)| asset.clone())
+                        .collect();
+                    let held: f64 = assets.iter().flat_map(|a| lots[a].iter()).map(|l| l.size).sum();
+                    (assets, if held > SIZE_EPSILON { entry.usdc / held } else { 0.0 })
+                };
+
+                for asset in assets {
+                    let Some(queue) = lots.get_mut(&asset) else {
+                        continue;
+                    };
+                    let mut to_close = if entry.kind == KIND_REDEEM { f64::INFINITY } else { entry.size };
+                    while to_close > SIZE_EPSILON {
+                        let Some(lot) = queue.front_mut() else {
+                            break;
+                        };
+                        let take = lot.size.min(to_close);
+                        let realized = (payout_per_token - lot.price) * take;
+                        let trader = lot.trader.clone();
+                        lot.size -= take;
+                        to_close -= take;
+                        if lot.size <= SIZE_EPSILON {
+                            queue.pop_front();
+                        }
+                        if in_range {
+                            buckets.update(&trader, market, Some(&day), |b| {
+                                b.fills += 1;
+                                b.proceeds_usd += payout_per_token * take;
+                                b.realized_pnl += realized;
+                            });
+                        }
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    // Mark what's still held
+    for (asset, queue) in &lots {
+        let price = prices
+            .get(asset)
+            .or_else(|| last_price.get(asset))
+            .copied()
+            .unwrap_or(0.0);
+        for lot in queue {
+            let title = titles.get(&lot.condition_id).cloned().unwrap_or_default();
+            buckets.update(&lot.trader, (&lot.condition_id, &title), None, |b| {
+                b.unrealized_pnl += (price - lot.price) * lot.size;
+                b.open_cost_usd += lot.price * lot.size;
+            });
+        }
+    }
+
+    let sorted = |map: BTreeMap<String, PnlBucket>| {
+        let mut buckets: Vec<PnlBucket> = map.into_values().collect();
+        buckets.sort_by(|a, b| b.total_pnl().partial_cmp(&a.total_pnl()).unwrap_or(std::cmp::Ordering::Equal));
+        buckets
+    };
+    buckets.total.key = "total".to_string();
+    buckets.total.label = "Total".to_string();
+
+    PnlReport {
+        generated_at: chrono::Utc::now().timestamp(),
+        since,
+        total: buckets.total,
+        by_trader: sorted(buckets.by_trader),
+        by_market: sorted(buckets.by_market),
+        // Days stay in chronological order
+        by_day: buckets.by_day.into_values().collect(),
+    }
+}
+
+/// Tokens still held per asset, split by the copied trader whose buys opened
+/// the remaining FIFO lots (sells and redemptions close lots as in `compute_pnl`)
+pub fn open_lots_by_trader<'a>(entries: impl IntoIterator<Item = &'a LedgerEntry>) -> HashMap<String, HashMap<String, f64>> {
+    let mut lots: HashMap<String, VecDeque<Lot>> = HashMap::new();
+    for entry in entries {
+        match entry.kind.as_str() {
+            KIND_BUY => lots.entry(entry.asset.clone()).or_default().push_back(Lot {
+                trader: entry.trader_address.clone().unwrap_or_else(|| "unattributed".to_string()),
+                condition_id: entry.condition_id.clone(),
+                size: entry.size,
+                price: entry.price,
+            }),
+            KIND_SELL => {
+                let Some(queue) = lots.get_mut(&entry.asset) else {
+                    continue;
+                };
+                let mut to_close = entry.size;
+                while to_close > SIZE_EPSILON {
+                    let Some(lot) = queue.front_mut() else {
+                        break;
+                    };
+                    let take = lot.size.min(to_close);
+                    lot.size -= take;
+                    to_close -= take;
+                    if lot.size <= SIZE_EPSILON {
+                        queue.pop_front();
+                    }
+                }
+            }
+            KIND_REDEEM if !entry.asset.is_empty() => {
+                lots.remove(&entry.asset);
+            }
+            KIND_REDEEM => {
+                for queue in lots.values_mut() {
+                    queue.retain(|l| l.condition_id != entry.condition_id);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    lots.into_iter()
+        .filter_map(|(asset, queue)| {
+            let mut by_trader: HashMap<String, f64> = HashMap::new();
+            for lot in queue {
+                *by_trader.entry(lot.trader).or_insert(0.0) += lot.size;
+            }
+            (!by_trader.is_empty()).then_some((asset, by_trader))
+        })
+        .collect()
+}
+
+/// Tokens still held per outcome token according to the ledger: (condition, size)
+fn held_tokens<'a>(entries: impl IntoIterator<Item = &'a LedgerEntry>) -> HashMap<&'a str, (&'a str, f64)> {
+    let mut held: HashMap<&str, (&str, f64)> = HashMap::new();
+    for entry in entries {
+        match entry.kind.as_str() {
+            KIND_BUY => held.entry(entry.asset.as_str()).or_insert((entry.condition_id.as_str(), 0.0)).1 += entry.size,
+            KIND_SELL => held.entry(entry.asset.as_str()).or_insert((entry.condition_id.as_str(), 0.0)).1 -= entry.size,
+            KIND_REDEEM if !entry.asset.is_empty() => {
+                held.remove(entry.asset.as_str());
+            }
+            KIND_REDEEM => held.retain(|_, (condition_id, _)| *condition_id != entry.condition_id),
+            _ => {}
+        }
+    }
+    held.retain(|_, (_, size)| *size > SIZE_EPSILON);
+    held
+}
+
+/// Outcome tokens still held according to the ledger
+pub fn open_assets(entries: &[LedgerEntry]) -> Vec<String> {
+    held_tokens(entries).into_keys().map(str::to_string).collect()
+}
+
+/// Current midpoint for each token (missing ones are left out)
+pub async fn fetch_midpoints(assets: &[String], env: &Env) -> HashMap<String, f64> {
+    let mut prices = HashMap::new();
+    for asset in assets {
+        let url = format!("{}/midpoint?token_id={}", env.clob_http_url.trim_end_matches('/'), asset);
+        let mid = fetch_data(&url, env)
+            .await
+            .ok()
+            .and_then(|v| v.get("mid").and_then(|m| m.as_str()).and_then(|s| s.parse::<f64>().ok()));
+        if let Some(mid) = mid {
+            prices.insert(asset.clone(), mid);
+        }
+    }
+    prices
+}
@@ -0,0 +1,236 @@
+use anyhow::Result;
+use alloy::signers::local::PrivateKeySigner;
+use futures_util::TryStreamExt;
+use mongodb::Database;
+use serde::Serialize;
+use std::collections::{BTreeMap, BTreeSet};
+use std::str::FromStr;
+use std::sync::Arc;
+use tokio::time::{interval, Duration};
+use crate::config::{
+    get_number_field, get_reconciliation_reports_collection, get_user_position_collection, Env,
+};
+use crate::utils::ctf_balance::get_ctf_balance;
+use crate::utils::{logger::Logger, DataApiClient, PositionsQuery};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum DriftKind {
+    /// Tokens on-chain that the records don't show
+    MissingFill,
+    /// Recorded position with no tokens on-chain
+    PhantomPosition,
+    /// Both sides hold the asset but the sizes differ
+    SizeMismatch,
+    /// Outcome tokens sitting in the EOA instead of the proxy wallet
+    HeldByEoa,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Drift {
+    pub kind: DriftKind,
+    /// Which record disagrees with the chain: "data_api", "mongodb" or "eoa"
+    pub source: String,
+    pub asset: String,
+    pub title: String,
+    pub recorded: f64,
+    pub on_chain: f64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReconciliationReport {
+    pub timestamp: i64,
+    pub proxy_wallet: String,
+    pub eoa: Option<String>,
+    pub assets_checked: usize,
+    /// MongoDB records are simulated in preview mode and not compared
+    pub mongodb_compared: bool,
+    pub drifts: Vec<Drift>,
+}
+
+/// asset -> (size, title) from the Data API
+async fn fetch_api_positions(address: &str, env: &Env) -> Result<BTreeMap<String, (f64, String)>> {
+    Ok(DataApiClient::new(env)
+        .positions(&PositionsQuery::new(address))
+        .await?
+        .into_iter()
+        .filter(|p| !p.asset.is_empty())
+        .map(|p| (p.asset, (p.size, p.title.unwrap_or_default())))
+        .collect())
+}
+
+/// asset -> size from the proxy wallet's MongoDB position records
+async fn fetch_db_positions(env: &Env, db: &Database) -> Result<BTreeMap<String, f64>> {
+    let docs: Vec<mongodb::bson::Document> = get_user_position_collection(db, &env.proxy_wallet)
+        .find(mongodb::bson::doc! {}, None)
+        .await?
+        .try_collect()
+        .await?;
+    Ok(docs
+        .iter()
+        .filter_map(|d| Some((d.get_str("asset").ok()?.to_string(), get_number_field(d, "size"))))
+        .collect())
+}
+
+/// Compare a recorded size against the on-chain balance
+fn compare(recorded: f64, on_chain: f64, tolerance: f64) -> Option<DriftKind> {
+    if recorded > tolerance && on_chain <= tolerance {
+        Some(DriftKind::PhantomPosition)
+    } else if recorded <= tolerance && on_chain > tolerance {
+        Some(DriftKind::MissingFill)
+    } else if (recorded - on_chain).abs() > tolerance {
+        Some(DriftKind::SizeMismatch)
+    } else {
+        None
+    }
+}
+
+/// One reconciliation pass over MongoDB records, Data API positions and
+/// on-chain ERC-1155 balances for the proxy wallet (and the signer's EOA)
+pub async fn reconcile_positions(env: &Env, db: &Database) -> Result<ReconciliationReport> {
+    let tolerance = env.reconcile_tolerance_tokens;
+    let api_positions = fetch_api_positions(&env.proxy_wallet, env).await?;
+    let mongodb_compared = !env.preview_mode;
+    let db_positions = if mongodb_compared {
+        fetch_db_positions(env, db).await?
+    } else {
+        BTreeMap::new()
+    };
+
+    let assets: BTreeSet<String> = api_positions.keys().chain(db_positions.keys()).cloned().collect();
+    let title_of = |asset: &str| {
+        api_positions
+            .get(asset)
+            .map(|(_, title)| title.clone())
+            .unwrap_or_default()
+    };
+
+    let mut drifts = Vec::new();
+    for asset in &assets {
+        let on_chain = get_ctf_balance(&env.proxy_wallet, asset, env).await?;
+
+        let api_size = api_positions.get(asset).map(|(size, _)| *size).unwrap_or(0.0);
+        if let Some(kind) = compare(api_size, on_chain, tolerance) {
+            drifts.push(Drift {
+                kind,
+                source: "data_api".to_string(),
+                asset: asset.clone(),
+                title: title_of(asset),
+                recorded: api_size,
+                on_chain,
+            });
+        }
+
+        if mongodb_compared {
+            let db_size = db_positions.get(asset).copied().unwrap_or(0.0);
+            if let Some(kind) = compare(db_size, on_chain, tolerance) {
+                drifts.push(Drift {
+                    kind,
+                    source: "mongodb".to_string(),
+                    asset: asset.clone(),
+                    title: title_of(asset),
+                    recorded: db_size,
+                    on_chain,
+                });
+            }
+        }
+    }
+
+    // EOA-vs-proxy mixups: positions that ended up on the signer's own address
+    let eoa = PrivateKeySigner::from_str(&env.private_key)
+        .ok()
+        .map(|signer| format!("0x{:x}", signer.address()));
+    if let Some(eoa) = eoa.as_deref().filter(|eoa| !eoa.eq_ignore_ascii_case(&env.proxy_wallet)) {
+        let eoa_positions = fetch_api_positions(eoa, env).await?;
+        let eoa_assets: BTreeSet<&String> = assets.iter().chain(eoa_positions.keys()).collect();
+        for asset in eoa_assets {
+            let on_chain = get_ctf_balance(eoa, asset, env).await?;
+            if on_chain > tolerance {
+                drifts.push(Drift {
+                    kind: DriftKind::HeldByEoa,
+                    source: "eoa".to_string(),
+                    asset: asset.clone(),
+                    title: eoa_positions
+                        .get(asset)
+                        .map(|(_, title)| title.clone())
+                        .unwrap_or_else(|| title_of(asset)),
+                    recorded: 0.0,
+                    on_chain,
+                });
+            }
+        }
+    }
+
+    Ok(ReconciliationReport {
+        timestamp: chrono::Utc::now().timestamp(),
+        proxy_wallet: env.proxy_wallet.clone(),
+        eoa,
+        assets_checked: assets.len(),
+        mongodb_compared,
+        drifts,
+    })
+}
+
+pub fn log_reconciliation_report(report: &ReconciliationReport) {
+    if report.drifts.is_empty() {
+        Logger::success(&format!(
+            "Reconciliation OK: {} position(s) match on-chain balances",
+            report.assets_checked
+        ));
+        return;
+    }
+
+    Logger::header("⚠️  POSITION DRIFT DETECTED");
+    for drift in &report.drifts {
+        let label = match drift.kind {
+            DriftKind::MissingFill => "missing fill",
+            DriftKind::PhantomPosition => "phantom position",
+            DriftKind::SizeMismatch => "size mismatch",
+            DriftKind::HeldByEoa => "tokens held by EOA, not proxy wallet",
+        };
+        Logger::warning(&format!(
+            "[{}] {}: recorded {:.2} vs on-chain {:.2} - {}",
+            drift.source,
+            label,
+            drift.recorded,
+            drift.on_chain,
+            if drift.title.is_empty() { &drift.asset } else { &drift.title }
+        ));
+    }
+    Logger::warning(&format!(
+        "{} drift(s) across {} position(s)",
+        report.drifts.len(),
+        report.assets_checked
+    ));
+}
+
+async fn run_reconciliation(env: &Env, db: &Database) -> Result<()> {
+    let report = reconcile_positions(env, db).await?;
+    log_reconciliation_report(&report);
+    get_reconciliation_reports_collection(db)
+        .insert_one(mongodb::bson::to_document(&report)?, None)
+        .await?;
+    Ok(())
+}
+
+pub async fn start_reconciliation(env: Arc<Env>, db: Arc<Database>) -> Result<()> {
+    Logger::success("Position reconciliation service started");
+    Logger::info(&format!(
+        "Interval: {}s, tolerance: {} tokens",
+        env.reconcile_interval_ms / 1000,
+        env.reconcile_tolerance_tokens
+    ));
+    if env.preview_mode {
+        Logger::info("Preview mode: MongoDB position records are simulated and not reconciled");
+    }
+
+    let mut reconcile_interval = interval(Duration::from_millis(env.reconcile_interval_ms));
+    loop {
+        reconcile_interval.tick().await;
+        if let Err(e) = run_reconciliation(&env, &db).await {
+            Logger::error(&format!("Error reconciling positions: {}", e));
+        }
+    }
+}
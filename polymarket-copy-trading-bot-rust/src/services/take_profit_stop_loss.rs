@@ -2,7 +2,8 @@ use anyhow::Result;
 use std::sync::Arc;
 use tokio::time::{interval, Duration};
 use crate::config::Env;
-use crate::utils::{fetch_data, logger::Logger};
+use crate::utils::data_api::Position;
+use crate::utils::{logger::Logger, DataApiClient, PositionsQuery};
 use polymarket_client_sdk::clob::Client as ClobClient;
 use polymarket_client_sdk::auth::state::Authenticated;
 use polymarket_client_sdk::auth::Normal;
@@ -44,11 +45,9 @@ pub async fn start_take_profit_stop_loss(
 }
 
 async fn monitor_positions(env: &Env) -> Result<()> {
-    let positions_url = format!("https://data-api.polymarket.com/positions?user={}", env.proxy_wallet);
-    let positions: Vec<serde_json::Value> = fetch_data(&positions_url, env).await?
-        .as_array()
-        .cloned()
-        .unwrap_or_default();
+    let positions = DataApiClient::new(env)
+        .positions(&PositionsQuery::new(&env.proxy_wallet))
+        .await?;
 
     if positions.is_empty() {
         return Ok(());
@@ -62,9 +61,9 @@ async fn monitor_positions(env: &Env) -> Result<()> {
     Ok(())
 }
 
-async fn check_position(position: &serde_json::Value, env: &Env) -> Result<()> {
-    let avg_price = position.get("avgPrice").and_then(|v| v.as_f64()).unwrap_or(0.0);
-    let current_price = position.get("curPrice").and_then(|v| v.as_f64()).unwrap_or(0.0);
+async fn check_position(position: &Position, env: &Env) -> Result<()> {
+    let avg_price = position.avg_price;
+    let current_price = position.cur_price;
 
     if avg_price <= 0.0 || current_price <= 0.0 {
         return Ok(());
@@ -77,13 +76,7 @@ async fn check_position(position: &serde_json::Value, env: &Env) -> Result<()> {
     if let Some(tp_percent) = env.take_profit_percent {
         if price_change_percent >= tp_percent {
             Logger::header("🎯 TAKE PROFIT TRIGGERED");
-            Logger::info(&format!(
-                "Position: {}",
-                position
-                    .get("slug")
-                    .and_then(|v| v.as_str())
-                    .unwrap_or("Unknown")
-            ));
+            Logger::info(&format!("Position: {}", position.slug.as_deref().unwrap_or("Unknown")));
             Logger::info(&format!("Entry Price: ${:.4}", avg_price));
             Logger::info(&format!("Current Price: ${:.4}", current_price));
             Logger::info(&format!(
@@ -101,13 +94,7 @@ async fn check_position(position: &serde_json::Value, env: &Env) -> Result<()> {
     if let Some(sl_percent) = env.stop_loss_percent {
         if price_change_percent <= -sl_percent {
             Logger::header("🛑 STOP LOSS TRIGGERED");
-            Logger::info(&format!(
-                "Position: {}",
-                position
-                    .get("slug")
-                    .and_then(|v| v.as_str())
-                    .unwrap_or("Unknown")
-            ));
+            Logger::info(&format!("Position: {}", position.slug.as_deref().unwrap_or("Unknown")));
             Logger::info(&format!("Entry Price: ${:.4}", avg_price));
             Logger::info(&format!("Current Price: ${:.4}", current_price));
             Logger::info(&format!(
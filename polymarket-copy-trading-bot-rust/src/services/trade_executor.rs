@@ -1,9 +1,12 @@
 use anyhow::Result;
 use std::sync::Arc;
+use tokio::sync::watch;
 use tokio::time::{interval, Duration};
 use crate::config::Env;
 use crate::interfaces::UserActivity;
-use crate::utils::{fetch_data, get_my_balance, post_order, logger::{Logger, TradeDetails}};
+use crate::services::market_filter::passes_market_filters;
+use crate::services::paper_trading::{load_paper_account, paper_user_position};
+use crate::utils::{get_my_balance, post_order, logger::{Logger, TradeDetails}, DataApiClient, PositionsQuery};
 use mongodb::Database;
 use polymarket_client_sdk::clob::Client as ClobClient;
 use polymarket_client_sdk::auth::state::Authenticated;
@@ -11,9 +14,10 @@ use polymarket_client_sdk::auth::Normal;
 
 pub async fn start_trade_executor(
     _clob_client: Arc<ClobClient<Authenticated<Normal>>>,
-    env: Arc<Env>,
+    env_rx: watch::Receiver<Arc<Env>>,
     _db: Arc<Database>,
 ) -> Result<()> {
+    let env = env_rx.borrow().clone();
     Logger::success(&format!(
         "Trade executor ready for {} trader(s)",
         env.user_addresses.len()
@@ -32,6 +36,8 @@ pub async fn start_trade_executor(
 
     loop {
         check_interval.tick().await;
+        // Trader list may change on config reload
+        let env = env_rx.borrow().clone();
 
         // Check for ready aggregated trades if enabled
         if env.trade_aggregation_enabled {
@@ -75,32 +81,32 @@ pub async fn execute_trade_directly(
     };
     Logger::trade(user_address, &trade.side, &trade_details);
 
-    // Fetch positions
-    let my_positions_url = format!("https://data-api.polymarket.com/positions?user={}", env.proxy_wallet);
-    let my_positions: Vec<serde_json::Value> = fetch_data(&my_positions_url, env).await?
-        .as_array()
-        .cloned()
-        .unwrap_or_default();
-
-    let user_positions_url = format!("https://data-api.polymarket.com/positions?user={}", user_address);
-    let user_positions: Vec<serde_json::Value> = fetch_data(&user_positions_url, env).await?
-        .as_array()
-        .cloned()
-        .unwrap_or_default();
-
-    let _my_position = my_positions
-        .iter()
-        .find(|p| p.get("conditionId").and_then(|v| v.as_str()) == Some(&trade.condition_id));
-
-    let _user_position = user_positions
-        .iter()
-        .find(|p| p.get("conditionId").and_then(|v| v.as_str()) == Some(&trade.condition_id));
+    if !passes_market_filters(trade, user_address, env, _db).await {
+        Logger::separator();
+        return Ok(());
+    }
 
-    // Get balances
-    let my_balance = get_my_balance(&env.proxy_wallet, env).await?;
+    // Fetch positions
+    let data_api = DataApiClient::new(env);
+    let my_positions = data_api.positions(&PositionsQuery::new(&env.proxy_wallet)).await?;
+    let user_positions = data_api.positions(&PositionsQuery::new(user_address)).await?;
+
+    let _my_position = my_positions.iter().find(|p| p.condition_id == trade.condition_id);
+
+    let _user_position = user_positions.iter().find(|p| p.condition_id == trade.condition_id);
+
+    // Get balances (the virtual account stands in for the wallet when paper trading)
+    let (my_balance, paper_position) = if env.paper_trading.enabled {
+        (
+            load_paper_account(_db, &env.paper_trading).await?.cash,
+            paper_user_position(env, _db, &trade.asset).await?,
+        )
+    } else {
+        (get_my_balance(&env.proxy_wallet, env).await?, None)
+    };
     let user_balance: f64 = user_positions
         .iter()
-        .map(|p| p.get("currentValue").and_then(|v| v.as_f64()).unwrap_or(0.0))
+        .map(|p| p.current_value)
         .sum();
 
     Logger::balance(my_balance, user_balance, user_address);
@@ -110,7 +116,7 @@ pub async fn execute_trade_directly(
     post_order(
         clob_client,
         if trade.side == "BUY" { "buy" } else { "sell" },
-        None, // my_position
+        paper_position.as_ref(), // my_position
         None, // user_position
         trade,
         my_balance,
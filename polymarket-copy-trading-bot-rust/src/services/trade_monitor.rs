@@ -1,26 +1,28 @@
 use anyhow::Result;
 use std::sync::Arc;
+use tokio::sync::watch;
 use tokio_tungstenite::{connect_async, tungstenite::Message};
 use futures_util::{SinkExt, StreamExt};
 use crate::config::Env;
 use crate::interfaces::{RtdsActivity, UserActivity};
-use crate::utils::{fetch_data, logger::Logger, get_my_balance};
+use crate::services::accounts::{dispatch_position_activity, dispatch_trade, AccountExecutor};
+use crate::services::activity_mirror::is_position_activity;
+use crate::utils::health_check::{record_rtds_connected, record_rtds_disconnected, record_rtds_message, record_rtds_monitor_started};
+use crate::utils::rtds_recording::{read_recording, replay_delay, RtdsRecorder};
+use crate::utils::{logger::Logger, get_my_balance, DataApiClient, PositionsQuery};
+use std::path::Path;
 use mongodb::Database;
-use polymarket_client_sdk::clob::Client as ClobClient;
-use polymarket_client_sdk::auth::state::Authenticated;
-use polymarket_client_sdk::auth::Normal;
 use serde_json::Value;
 
-const RTDS_URL: &str = "wss://ws-live-data.polymarket.com";
 const MAX_RECONNECT_ATTEMPTS: u32 = 10;
 const RECONNECT_DELAY_SECS: u64 = 5;
 
 pub async fn start_trade_monitor(
-    env: Arc<Env>,
+    env_rx: watch::Receiver<Arc<Env>>,
     db: Arc<Database>,
-    clob_client: Arc<ClobClient<Authenticated<Normal>>>,
-    signer: Arc<alloy::signers::local::PrivateKeySigner>,
+    accounts: Arc<Vec<AccountExecutor>>,
 ) -> Result<()> {
+    let env = env_rx.borrow().clone();
     Logger::clear_line();
     Logger::info(&format!(
         "Initializing trade monitor for {} trader(s)...",
@@ -28,7 +30,9 @@ pub async fn start_trade_monitor(
     ));
 
     // Show your own positions first
-    show_my_positions(&env).await?;
+    for executor in accounts.iter() {
+        executor.scope(show_my_positions(&executor.env(&env))).await?;
+    }
 
     // Show current positions for traders
     show_traders_positions(&env, &db).await?;
@@ -39,10 +43,26 @@ pub async fn start_trade_monitor(
     ));
     Logger::separator();
 
+    if let Some(path) = &env.rtds.replay_file {
+        return replay_rtds(path, env.rtds.replay_speed, &env_rx, &accounts).await;
+    }
+
+    // One recording across reconnects
+    let mut recorder = match &env.rtds.record_file {
+        Some(path) => {
+            Logger::info(&format!("Recording RTDS frames to {}", path.display()));
+            Some(RtdsRecorder::open(path)?)
+        }
+        None => None,
+    };
+
     // Connect to RTDS
+    record_rtds_monitor_started();
     let mut reconnect_attempts = 0;
     loop {
-        match connect_rtds(&env, &db, &clob_client, &signer).await {
+        let result = connect_rtds(&env_rx, &db, &accounts, &mut recorder).await;
+        record_rtds_disconnected();
+        match result {
             Ok(_) => {
                 reconnect_attempts = 0;
                 Logger::success("RTDS WebSocket connected");
@@ -69,21 +89,20 @@ pub async fn start_trade_monitor(
     }
 }
 
-async fn connect_rtds(
-    env: &Env,
-    db: &Database,
-    clob_client: &ClobClient<Authenticated<Normal>>,
-    signer: &alloy::signers::local::PrivateKeySigner,
-) -> Result<()> {
-    Logger::info(&format!("Connecting to RTDS at {}...", RTDS_URL));
-
-    let (mut ws_stream, _) = connect_async(RTDS_URL).await?;
-    Logger::success("RTDS WebSocket connected");
+/// What an RTDS frame means to the trade monitor
+#[derive(Debug)]
+pub enum RtdsFrame {
+    /// Subscription confirmation
+    Subscribed,
+    /// A trade or position activity by one of `traders`
+    TraderActivity { trader: String, activity: Box<UserActivity> },
+    /// Other wallets, other topics and anything that doesn't parse
+    Ignored,
+}
 
-    // Subscribe to activity/trades for each trader address
-    let subscriptions: Vec<Value> = env
-        .user_addresses
-        .iter()
+/// Subscription sent after connecting: `activity`/`trades` once per trader
+pub fn rtds_subscribe_message(trader_count: usize) -> Value {
+    let subscriptions: Vec<Value> = (0..trader_count)
         .map(|_| {
             serde_json::json!({
                 "topic": "activity",
@@ -92,10 +111,116 @@ async fn connect_rtds(
         })
         .collect();
 
-    let subscribe_message = serde_json::json!({
+    serde_json::json!({
         "action": "subscribe",
         "subscriptions": subscriptions
-    });
+    })
+}
+
+/// Classify a text frame; `traders` are the tracked proxy wallets
+pub fn parse_rtds_frame(text: &str, traders: &[String]) -> RtdsFrame {
+    let Ok(parsed) = serde_json::from_str::<Value>(text) else {
+        return RtdsFrame::Ignored;
+    };
+
+    // Handle subscription confirmation
+    if parsed.get("action").and_then(|a| a.as_str()) == Some("subscribed")
+        || parsed.get("status").and_then(|s| s.as_str()) == Some("subscribed")
+    {
+        return RtdsFrame::Subscribed;
+    }
+
+    // Check: topic === 'activity' && type === 'trades' && payload exists
+    if parsed.get("topic").and_then(|t| t.as_str()) != Some("activity")
+        || parsed.get("type").and_then(|t| t.as_str()) != Some("trades")
+    {
+        return RtdsFrame::Ignored;
+    }
+    let Some(rtds_activity) = parsed
+        .get("payload")
+        .and_then(|payload| serde_json::from_value::<RtdsActivity>(payload.clone()).ok())
+    else {
+        return RtdsFrame::Ignored;
+    };
+
+    // Extract proxy wallet from RTDS activity
+    let proxy = rtds_activity.proxy_wallet.as_deref().unwrap_or("").to_lowercase();
+    if !traders.iter().any(|a| a.to_lowercase() == proxy) {
+        return RtdsFrame::Ignored;
+    }
+    RtdsFrame::TraderActivity { trader: proxy, activity: Box::new(UserActivity::from(&rtds_activity)) }
+}
+
+/// Act on one RTDS text frame received at `received_at` (ms): copy trades and
+/// mirror position activity of the tracked traders
+async fn handle_rtds_text(
+    text: &str,
+    received_at: i64,
+    env_rx: &watch::Receiver<Arc<Env>>,
+    accounts: &[AccountExecutor],
+) {
+    // Latest settings: the trader list and strategy may be hot-reloaded
+    let env = env_rx.borrow().clone();
+    match parse_rtds_frame(text, &env.user_addresses) {
+        RtdsFrame::Subscribed => Logger::info("RTDS subscription confirmed"),
+        RtdsFrame::TraderActivity { trader, activity } => {
+            // Merges, splits, redemptions and conversions are mirrored, not traded
+            if is_position_activity(&activity.r#type) {
+                if env.copy_position_activity {
+                    dispatch_position_activity(&activity, &trader, received_at, accounts, &env).await;
+                }
+                return;
+            }
+
+            Logger::info(&format!(
+                "📊 Trade detected from {}",
+                crate::utils::logger::Logger::format_address(&trader)
+            ));
+
+            process_trade_activity(&activity, &trader, received_at, accounts, &env).await;
+        }
+        RtdsFrame::Ignored => {}
+    }
+}
+
+/// Feed a recorded session through the live message handling, paced by the
+/// recorded receive times divided by `speed`
+async fn replay_rtds(
+    path: &Path,
+    speed: f64,
+    env_rx: &watch::Receiver<Arc<Env>>,
+    accounts: &[AccountExecutor],
+) -> Result<()> {
+    let frames = read_recording(path)?;
+    let pace = if speed > 0.0 { format!("{}x speed", speed) } else { "full speed".to_string() };
+    Logger::info(&format!("Replaying {} RTDS frame(s) from {} at {}", frames.len(), path.display(), pace));
+
+    let mut previous = None;
+    for frame in &frames {
+        tokio::time::sleep(replay_delay(previous, frame, speed)).await;
+        handle_rtds_text(&frame.frame, frame.received_at, env_rx, accounts).await;
+        previous = Some(frame);
+    }
+
+    Logger::success("RTDS replay finished");
+    Ok(())
+}
+
+async fn connect_rtds(
+    env_rx: &watch::Receiver<Arc<Env>>,
+    db: &Database,
+    accounts: &[AccountExecutor],
+    recorder: &mut Option<RtdsRecorder>,
+) -> Result<()> {
+    let env = env_rx.borrow().clone();
+    Logger::info(&format!("Connecting to RTDS at {}...", env.rtds_url));
+
+    let (mut ws_stream, _) = connect_async(env.rtds_url.as_str()).await?;
+    Logger::success("RTDS WebSocket connected");
+    record_rtds_connected();
+
+    // Subscribe to activity/trades for each trader address
+    let subscribe_message = rtds_subscribe_message(env.user_addresses.len());
 
     ws_stream
         .send(Message::Text(subscribe_message.to_string()))
@@ -107,13 +232,14 @@ async fn connect_rtds(
     ));
 
     // Update positions periodically (every 30 seconds)
-    let env_clone = env.clone();
+    let env_rx_clone = env_rx.clone();
     let db_clone = db.clone();
     let position_update_handle = tokio::spawn(async move {
         let mut interval = tokio::time::interval(tokio::time::Duration::from_secs(30));
         loop {
             interval.tick().await;
-            if let Err(e) = update_positions(&env_clone, &db_clone).await {
+            let env = env_rx_clone.borrow().clone();
+            if let Err(e) = update_positions(&env, &db_clone).await {
                 Logger::error(&format!("Error updating positions: {}", e));
             }
         }
@@ -121,76 +247,18 @@ async fn connect_rtds(
 
     // Process messages
     while let Some(msg) = ws_stream.next().await {
-        match msg? {
+        let msg = msg?;
+        record_rtds_message();
+        match msg {
             Message::Text(text) => {
-                if let Ok(parsed) = serde_json::from_str::<Value>(&text) {
-                    // Handle subscription confirmation
-                    if parsed.get("action").and_then(|a| a.as_str()) == Some("subscribed")
-                        || parsed.get("status").and_then(|s| s.as_str()) == Some("subscribed")
-                    {
-                        Logger::info("RTDS subscription confirmed");
-                        continue;
-                    }
-
-                    // Handle trade activity messages
-                    // Check: topic === 'activity' && type === 'trades' && payload exists
-                    if parsed.get("topic").and_then(|t| t.as_str()) == Some("activity")
-                        && parsed.get("type").and_then(|t| t.as_str()) == Some("trades")
-                    {
-                        if let Some(payload) = parsed.get("payload") {
-                            if let Ok(rtds_activity) = serde_json::from_value::<RtdsActivity>(payload.clone()) {
-                                // Extract proxy wallet from RTDS activity
-                                let proxy = rtds_activity
-                                    .proxy_wallet
-                                    .as_deref()
-                                    .unwrap_or("")
-                                    .to_lowercase();
-                                
-                                if env.user_addresses.iter().any(|a| a.to_lowercase() == proxy) {
-                                    // Convert RtdsActivity to UserActivity for processing
-                                    let activity = UserActivity {
-                                        id: None,
-                                        proxy_wallet: rtds_activity.proxy_wallet.clone().unwrap_or_default(),
-                                        timestamp: rtds_activity.timestamp.unwrap_or(0),
-                                        condition_id: rtds_activity.condition_id.clone().unwrap_or_default(),
-                                        r#type: rtds_activity.activity_type.clone().unwrap_or_default(),
-                                        size: rtds_activity.size.unwrap_or(0.0),
-                                        usdc_size: rtds_activity.usdc_size(),
-                                        transaction_hash: rtds_activity.transaction_hash.clone().unwrap_or_default(),
-                                        price: rtds_activity.price.unwrap_or(0.0),
-                                        asset: rtds_activity.asset.clone().unwrap_or_default(),
-                                        side: rtds_activity.side.clone().unwrap_or_default(),
-                                        outcome_index: rtds_activity.outcome_index.unwrap_or(0),
-                                        title: rtds_activity.title.clone().unwrap_or_default(),
-                                        slug: rtds_activity.slug.clone().unwrap_or_default(),
-                                        icon: rtds_activity.icon.clone().unwrap_or_default(),
-                                        event_slug: rtds_activity.event_slug.clone().unwrap_or_default(),
-                                        outcome: rtds_activity.outcome.clone().unwrap_or_default(),
-                                        name: rtds_activity.name.clone().unwrap_or_default(),
-                                        pseudonym: String::new(),
-                                        bio: String::new(),
-                                        profile_image: String::new(),
-                                        profile_image_optimized: String::new(),
-                                        bot: false,
-                                        bot_executed_time: 0,
-                                        my_bought_size: None,
-                                    };
-
-                                    Logger::info(&format!(
-                                        "📊 Trade detected from {}",
-                                        crate::utils::logger::Logger::format_address(&proxy)
-                                    ));
-
-                                    let env_arc = Arc::new(env.clone());
-                                    let db_arc = Arc::new(db.clone());
-                                    if let Err(e) = process_trade_activity(&activity, &proxy, clob_client, env_arc, db_arc, signer).await {
-                                        Logger::error(&format!("Error processing trade: {}", e));
-                                    }
-                                }
-                            }
-                        }
+                let received_at = chrono::Utc::now().timestamp_millis();
+                if let Some(active) = recorder {
+                    if let Err(e) = active.record(&text, received_at) {
+                        Logger::error(&format!("Stopped recording RTDS frames: {}", e));
+                        *recorder = None;
                     }
                 }
+                handle_rtds_text(&text, received_at, env_rx, accounts).await;
             }
             Message::Close(_) => {
                 Logger::warning("RTDS WebSocket closed");
@@ -204,50 +272,50 @@ async fn connect_rtds(
     Ok(())
 }
 
-async fn process_trade_activity(
-    activity: &UserActivity,
-    address: &str,
-    clob_client: &ClobClient<Authenticated<Normal>>,
-    env: Arc<Env>,
-    db: Arc<Database>,
-    signer: &alloy::signers::local::PrivateKeySigner,
-) -> Result<()> {
-    // Skip if too old
+/// Whether an activity is older than `max_hours` when it was received at
+/// `received_at` (ms). Judged against the receive time rather than the clock
+/// so a replay skips exactly what the live run skipped.
+pub fn is_too_old(activity: &UserActivity, received_at: i64, max_hours: u64) -> bool {
     let activity_timestamp = if activity.timestamp > 1000000000000 {
         activity.timestamp
     } else {
         activity.timestamp * 1000
     };
-    let hours_ago = (chrono::Utc::now().timestamp_millis() - activity_timestamp) as f64 / (1000.0 * 60.0 * 60.0);
-    if hours_ago > env.too_old_timestamp as f64 {
-        return Ok(());
+    let hours_ago = (received_at - activity_timestamp) as f64 / (1000.0 * 60.0 * 60.0);
+    hours_ago > max_hours as f64
+}
+
+async fn process_trade_activity(
+    activity: &UserActivity,
+    address: &str,
+    received_at: i64,
+    accounts: &[AccountExecutor],
+    env: &Env,
+) {
+    if is_too_old(activity, received_at, env.too_old_timestamp) {
+        return;
     }
 
-    // Execute trade directly
-    crate::services::trade_executor::execute_trade_directly(activity, address, clob_client, &env, &db, signer).await?;
+    // Copy into every execution account
+    dispatch_trade(activity, address, accounts, env).await;
     Logger::info(&format!(
         "Trade executed for {}...{}",
         &address[..6.min(address.len())],
         &address[address.len().saturating_sub(4)..]
     ));
-
-    Ok(())
 }
 
 async fn update_positions(env: &Env, db: &Database) -> Result<()> {
+    let data_api = DataApiClient::new(env);
     for address in &env.user_addresses {
-        let positions_url = format!("https://data-api.polymarket.com/positions?user={}", address);
-        let positions: Vec<Value> = fetch_data(&positions_url, env).await?
-            .as_array()
-            .cloned()
-            .unwrap_or_default();
+        let positions = data_api.positions(&PositionsQuery::new(address)).await?;
 
         if !positions.is_empty() {
             let collection = crate::config::get_user_position_collection(db, address);
             for position in positions {
                 let filter = mongodb::bson::doc! {
-                    "asset": position.get("asset").and_then(|v| v.as_str()),
-                    "conditionId": position.get("conditionId").and_then(|v| v.as_str())
+                    "asset": &position.asset,
+                    "conditionId": &position.condition_id
                 };
                 let update = mongodb::bson::to_document(&position)?;
                 collection.update_one(filter, mongodb::bson::doc! { "$set": update }, None).await?;
@@ -258,11 +326,9 @@ async fn update_positions(env: &Env, db: &Database) -> Result<()> {
 }
 
 async fn show_my_positions(env: &Env) -> Result<()> {
-    let my_positions_url = format!("https://data-api.polymarket.com/positions?user={}", env.proxy_wallet);
-    let my_positions: Vec<Value> = fetch_data(&my_positions_url, env).await?
-        .as_array()
-        .cloned()
-        .unwrap_or_default();
+    let my_positions = DataApiClient::new(env)
+        .positions(&PositionsQuery::new(&env.proxy_wallet))
+        .await?;
 
     let current_balance = get_my_balance(&env.proxy_wallet, env).await.unwrap_or(0.0);
 
@@ -277,15 +343,12 @@ async fn show_my_positions(env: &Env) -> Result<()> {
         let mut top_positions: Vec<crate::utils::logger::PositionDisplay> = Vec::new();
         
         for pos in &my_positions {
-            let value = pos.get("currentValue").and_then(|v| v.as_f64()).unwrap_or(0.0);
-            let initial = pos.get("initialValue").and_then(|v| v.as_f64()).unwrap_or(0.0);
-            let pnl = pos.get("percentPnl").and_then(|v| v.as_f64()).unwrap_or(0.0);
-            total_value += value;
-            initial_value += initial;
-            weighted_pnl += value * pnl;
+            total_value += pos.current_value;
+            initial_value += pos.initial_value;
+            weighted_pnl += pos.current_value * pos.percent_pnl;
             
             // Collect position for display
-            top_positions.push(crate::utils::logger::PositionDisplay::from_json_value(pos));
+            top_positions.push(crate::utils::logger::PositionDisplay::from_position(pos));
         }
         
         // Sort by current value (descending) and take top 5
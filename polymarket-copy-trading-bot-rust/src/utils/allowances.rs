@@ -0,0 +1,199 @@
+use anyhow::Result;
+use alloy::primitives::{Address, B256, U256};
+use alloy::providers::{Provider, ProviderBuilder};
+use alloy::signers::local::PrivateKeySigner;
+use alloy::sol;
+use serde::Serialize;
+use std::str::FromStr;
+use crate::config::Env;
+use crate::utils::ctf_balance::CTF_CONTRACT;
+use crate::utils::gnosis_safe::{
+    ctf_set_approval_for_all, erc20_approve, SafeCall, SafeExecutor, NEG_RISK_ADAPTER,
+};
+use crate::utils::logger::Logger;
+
+/// Contracts that move USDC and outcome tokens on behalf of the trading wallet
+pub const POLYMARKET_SPENDERS: [(&str, &str); 3] = [
+    ("CTF Exchange", "0x4bFb41d5B3570DeFd03C39a9A4D8dE6Bd8B8982E"),
+    ("NegRisk CTF Exchange", "0xC5d563A36AE78145C45a50134d48A1215220f80a"),
+    ("NegRisk Adapter", NEG_RISK_ADAPTER),
+];
+
+sol! {
+    #[sol(rpc)]
+    interface IAllowanceToken {
+        function balanceOf(address owner) external view returns (uint256);
+        function allowance(address owner, address spender) external view returns (uint256);
+    }
+
+    #[sol(rpc)]
+    interface ICtfApproval {
+        function isApprovedForAll(address account, address operator) external view returns (bool);
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SpenderStatus {
+    pub name: &'static str,
+    pub spender: Address,
+    pub usdc_allowance: U256,
+    pub ctf_approved: bool,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AllowanceReport {
+    /// Wallet holding the funds (the proxy wallet)
+    pub wallet: Address,
+    pub usdc_balance: U256,
+    pub spenders: Vec<SpenderStatus>,
+}
+
+impl SpenderStatus {
+    /// Allowance covers the current balance (and is set at all)
+    pub fn usdc_sufficient(&self, balance: U256) -> bool {
+        !self.usdc_allowance.is_zero() && self.usdc_allowance >= balance
+    }
+}
+
+impl AllowanceReport {
+    pub fn is_complete(&self) -> bool {
+        self.spenders
+            .iter()
+            .all(|s| s.usdc_sufficient(self.usdc_balance) && s.ctf_approved)
+    }
+
+    /// Calls that set whatever is missing: unlimited USDC approval and CTF setApprovalForAll
+    pub fn missing_calls(&self, env: &Env) -> Result<Vec<(String, SafeCall)>> {
+        let mut calls = Vec::new();
+        for status in &self.spenders {
+            if !status.usdc_sufficient(self.usdc_balance) {
+                calls.push((
+                    format!("USDC approve → {}", status.name),
+                    erc20_approve(&env.usdc_contract_address, status.spender, U256::MAX)?,
+                ));
+            }
+            if !status.ctf_approved {
+                calls.push((
+                    format!("CTF setApprovalForAll → {}", status.name),
+                    ctf_set_approval_for_all(status.spender, true)?,
+                ));
+            }
+        }
+        Ok(calls)
+    }
+}
+
+fn parse_address(address: &str) -> Result<Address> {
+    Address::from_str(address.trim()).map_err(|e| anyhow::anyhow!("Invalid address {}: {}", address, e))
+}
+
+pub fn signer_from_env(env: &Env) -> Result<PrivateKeySigner> {
+    let private_key = if env.private_key.starts_with("0x") {
+        env.private_key.clone()
+    } else {
+        format!("0x{}", env.private_key)
+    };
+    PrivateKeySigner::from_str(&private_key).map_err(|e| anyhow::anyhow!("Invalid private key: {}", e))
+}
+
+/// USDC allowance and CTF approval of the proxy wallet for every Polymarket spender
+pub async fn check_allowances(env: &Env) -> Result<AllowanceReport> {
+    let provider = ProviderBuilder::new().connect_http(env.rpc_url.parse()?);
+    let wallet = parse_address(&env.proxy_wallet)?;
+    let usdc = IAllowanceToken::new(parse_address(&env.usdc_contract_address)?, &provider);
+    let ctf = ICtfApproval::new(parse_address(CTF_CONTRACT)?, &provider);
+
+    let usdc_balance = usdc.balanceOf(wallet).call().await?;
+    let mut spenders = Vec::new();
+    for (name, address) in POLYMARKET_SPENDERS {
+        let spender = parse_address(address)?;
+        spenders.push(SpenderStatus {
+            name,
+            spender,
+            usdc_allowance: usdc.allowance(wallet, spender).call().await?,
+            ctf_approved: ctf.isApprovedForAll(wallet, spender).call().await?,
+        });
+    }
+    Ok(AllowanceReport { wallet, usdc_balance, spenders })
+}
+
+/// Send the missing approvals: directly when the proxy wallet is the signer's EOA,
+/// otherwise batched through the Safe. Returns the transaction hashes.
+pub async fn set_missing_allowances(report: &AllowanceReport, env: &Env) -> Result<Vec<B256>> {
+    let calls: Vec<SafeCall> = report.missing_calls(env)?.into_iter().map(|(_, call)| call).collect();
+    if calls.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let signer = signer_from_env(env)?;
+    if signer.address() != report.wallet {
+        let executor = SafeExecutor::from_env(env)?;
+        return Ok(vec![executor.execute(&calls).await?]);
+    }
+
+    let provider = ProviderBuilder::new()
+        .wallet(signer)
+        .connect_http(env.rpc_url.parse()?);
+    let mut tx_hashes = Vec::new();
+    for call in calls {
+        let tx = alloy::rpc::types::TransactionRequest::default()
+            .to(call.to)
+            .input(call.data.into());
+        let receipt = provider.send_transaction(tx).await?.get_receipt().await?;
+        if !receipt.status() {
+            anyhow::bail!("Approval 0x{:x} reverted", receipt.transaction_hash);
+        }
+        tx_hashes.push(receipt.transaction_hash);
+    }
+    Ok(tx_hashes)
+}
+
+fn format_usdc(amount: U256) -> String {
+    if amount >= U256::MAX >> 1 {
+        "unlimited".to_string()
+    } else {
+        format!("{:.2} USDC", amount.saturating_to::<u128>() as f64 / 1_000_000.0)
+    }
+}
+
+pub fn log_allowance_report(report: &AllowanceReport) {
+    Logger::header("🔑 TRADING ALLOWANCES");
+    Logger::info(&format!("Wallet: {}", report.wallet));
+    Logger::info(&format!("USDC balance: {}", format_usdc(report.usdc_balance)));
+    for status in &report.spenders {
+        Logger::info(&format!(
+            "{} ({}): USDC {} {} | CTF {}",
+            status.name,
+            status.spender,
+            if status.usdc_sufficient(report.usdc_balance) { "✅" } else { "❌" },
+            format_usdc(status.usdc_allowance),
+            if status.ctf_approved { "✅ approved" } else { "❌ not approved" }
+        ));
+    }
+}
+
+/// Startup check: set missing approvals when live trading, report them in preview mode
+pub async fn ensure_trading_allowances(env: &Env) -> Result<()> {
+    let report = check_allowances(env).await?;
+    if report.is_complete() {
+        Logger::success("Trading allowances set for all Polymarket contracts");
+        return Ok(());
+    }
+
+    log_allowance_report(&report);
+    let missing = report.missing_calls(env)?;
+    if env.preview_mode {
+        for (label, _) in &missing {
+            Logger::info(&format!("[PREVIEW] Would set {}", label));
+        }
+        return Ok(());
+    }
+
+    Logger::info(&format!("Setting {} missing allowance(s)...", missing.len()));
+    for tx_hash in set_missing_allowances(&report, env).await? {
+        Logger::success(&format!("Allowance tx confirmed: 0x{:x}", tx_hash));
+    }
+    Ok(())
+}
@@ -0,0 +1,552 @@
+//! In-memory copy-trading simulator.
+//! Replays a trader's activity through the live bot's order sizing
+//! (`calculate_order_size`) plus optional take-profit / stop-loss, and
+//! tracks cash, positions and an equity curve for risk metrics.
+//! With an `ExecutionModel` fills pay latency, depth slippage and fees.
+//! Pure and synchronous so many configurations can run in parallel.
+
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+use crate::config::{calculate_order_size, get_trade_multiplier, CopyStrategyConfig, Env};
+use crate::utils::execution_model::{ExecutionModel, FillOutcome, OrderBookCache, PriceTape};
+use crate::utils::{ActivityQuery, DataApiClient};
+use crate::utils::market_resolution::ResolutionCache;
+use crate::utils::risk_metrics::{daily_closes, daily_returns, max_drawdown_percent, sharpe_ratio};
+
+const TRADE_CACHE_DIR: &str = "trader_data_cache";
+const DUST_SHARES: f64 = 0.01;
+
+/// Trader activity row as stored in `trader_data_cache/` by the simulators
+/// and `fetch_historical_trades`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SimTrade {
+    pub timestamp: i64,
+    #[serde(rename = "conditionId", default)]
+    pub condition_id: Option<String>,
+    pub asset: String,
+    pub side: String,
+    pub price: f64,
+    #[serde(rename = "usdcSize", alias = "usdc_size")]
+    pub usdc_size: f64,
+    pub size: f64,
+    #[serde(default)]
+    pub outcome: Option<String>,
+    #[serde(default)]
+    pub title: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct CachedTrades {
+    trades: Vec<SimTrade>,
+}
+
+#[derive(Debug, Clone)]
+pub struct SimParams {
+    pub strategy: CopyStrategyConfig,
+    pub take_profit_percent: Option<f64>,
+    pub stop_loss_percent: Option<f64>,
+    pub starting_capital: f64,
+    /// `None` fills at the trader's price
+    pub execution: Option<ExecutionModel>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SimPosition {
+    pub market: String,
+    pub outcome: String,
+    pub asset: String,
+    pub condition_id: Option<String>,
+    pub shares_held: f64,
+    pub cost_basis: f64,
+    pub invested: f64,
+    pub proceeds: f64,
+    pub last_price: f64,
+    pub current_value: f64,
+    pub pnl: f64,
+    pub closed: bool,
+    pub resolved: bool,
+    pub exit_reason: Option<String>,
+}
+
+impl SimPosition {
+    fn avg_price(&self) -> f64 {
+        if self.shares_held > 0.0 {
+            self.cost_basis / self.shares_held
+        } else {
+            0.0
+        }
+    }
+
+    /// Close `shares` for `amount` USDC; returns (amount, realized pnl)
+    fn sell(&mut self, shares: f64, amount: f64) -> (f64, f64) {
+        let shares = shares.min(self.shares_held);
+        let cost_released = self.avg_price() * shares;
+        self.shares_held -= shares;
+        self.cost_basis -= cost_released;
+        self.proceeds += amount;
+        if self.shares_held < DUST_SHARES {
+            self.shares_held = 0.0;
+            self.cost_basis = 0.0;
+            self.closed = true;
+        }
+        (amount, amount - cost_released)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct SimOutcome {
+    pub total_trades: usize,
+    pub copied_trades: usize,
+    pub skipped_trades: usize,
+    pub take_profit_exits: usize,
+    pub stop_loss_exits: usize,
+    pub slippage_rejections: usize,
+    pub total_fees: f64,
+    pub avg_slippage_percent: f64,
+    pub total_invested: f64,
+    pub final_cash: f64,
+    pub final_equity: f64,
+    pub realized_pnl: f64,
+    pub unrealized_pnl: f64,
+    pub resolution_pnl: f64,
+    pub total_pnl: f64,
+    pub roi: f64,
+    pub win_rate: f64,
+    pub max_drawdown_percent: f64,
+    pub sharpe: f64,
+    pub positions: Vec<SimPosition>,
+}
+
+impl SimOutcome {
+    /// Serialize in the `simulation_results/` format read by `compare_results`
+    pub fn to_simulation_result(
+        &self,
+        id: &str,
+        name: &str,
+        logic: &str,
+        trader_address: &str,
+        starting_capital: f64,
+    ) -> serde_json::Value {
+        serde_json::json!({
+            "id": id,
+            "name": name,
+            "logic": logic,
+            "timestamp": chrono::Utc::now().timestamp(),
+            "traderAddress": trader_address,
+            "startingCapital": starting_capital,
+            "currentCapital": self.final_equity,
+            "totalTrades": self.total_trades,
+            "copiedTrades": self.copied_trades,
+            "skippedTrades": self.skipped_trades,
+            "totalInvested": self.total_invested,
+            "currentValue": self.final_equity,
+            "realizedPnl": self.realized_pnl,
+            "unrealizedPnl": self.unrealized_pnl,
+            "resolutionPnl": self.resolution_pnl,
+            "totalPnl": self.total_pnl,
+            "roi": self.roi,
+            "winRate": self.win_rate,
+            "maxDrawdownPercent": self.max_drawdown_percent,
+            "sharpe": self.sharpe,
+            "slippageRejections": self.slippage_rejections,
+            "totalFees": self.total_fees,
+            "avgSlippagePercent": self.avg_slippage_percent,
+            "positions": self.positions,
+        })
+    }
+}
+
+fn equity(cash: f64, positions: &HashMap<String, SimPosition>) -> f64 {
+    cash + positions
+        .values()
+        .map(|p| p.shares_held * p.last_price)
+        .sum::<f64>()
+}
+
+/// Fill a sell of `shares` through the execution model (or at `price` without one).
+/// Returns (shares sold, USDC received, fee, slippage %) or `None` if rejected.
+fn execute_sell(
+    model: Option<&ExecutionModel>,
+    reference_price: f64,
+    start_price: f64,
+    shares: f64,
+    books: Option<&OrderBookCache>,
+    asset: &str,
+) -> Option<(f64, f64, f64, f64)> {
+    match model {
+        None => Some((shares, shares * reference_price, 0.0, 0.0)),
+        Some(model) => match model.fill_sell(reference_price, start_price, shares, books.and_then(|b| b.get(asset))) {
+            FillOutcome::Filled(fill) => Some((fill.shares, fill.amount, fill.fee, fill.slippage_percent)),
+            FillOutcome::Rejected(_) => None,
+        },
+    }
+}
+
+/// Replay `trades` (sorted by timestamp) under `params`.
+/// Resolved markets in `resolutions` settle at 1.0 / 0.0; everything else is
+/// marked at the last price seen for the asset. `books` supplies recorded
+/// depth when the execution model uses it.
+pub fn simulate(
+    trades: &[SimTrade],
+    params: &SimParams,
+    resolutions: Option<&ResolutionCache>,
+    books: Option<&OrderBookCache>,
+) -> SimOutcome {
+    let config = &params.strategy;
+    let model = params.execution.as_ref();
+    let tape = model.map(|_| PriceTape::new(trades.iter().map(|t| (t.asset.as_str(), t.timestamp, t.price))));
+    let mut cash = params.starting_capital;
+    let mut positions: HashMap<String, SimPosition> = HashMap::new();
+    // Trader's own holdings per asset, reconstructed from their activity
+    let mut trader_shares: HashMap<String, f64> = HashMap::new();
+    let mut daily_volume: HashMap<i64, f64> = HashMap::new();
+    let mut equity_points: Vec<(i64, f64)> = vec![(
+        trades.first().map(|t| t.timestamp).unwrap_or(0),
+        params.starting_capital,
+    )];
+
+    let mut copied_trades = 0;
+    let mut skipped_trades = 0;
+    let mut take_profit_exits = 0;
+    let mut stop_loss_exits = 0;
+    let mut slippage_rejections = 0;
+    let mut total_fees = 0.0;
+    let mut slippage_sum = 0.0;
+    let mut fills = 0;
+    let mut total_invested = 0.0;
+    let mut realized_pnl = 0.0;
+
+    for trade in trades {
+        if trade.price <= 0.0 {
+            skipped_trades += 1;
+            continue;
+        }
+
+        let held_before = trader_shares.get(&trade.asset).copied().unwrap_or(0.0);
+        let trader_held = trader_shares.entry(trade.asset.clone()).or_insert(0.0);
+        if trade.side == "BUY" {
+            *trader_held += trade.size;
+        } else {
+            *trader_held = (*trader_held - trade.size).max(0.0);
+        }
+
+        if let Some(pos) = positions.get_mut(&trade.asset) {
+            pos.last_price = trade.price;
+        }
+
+        // Price our copy would see after the latency
+        let start_price = match (model, tape.as_ref()) {
+            (Some(model), Some(tape)) => tape
+                .price_at(&trade.asset, trade.timestamp + model.latency_secs)
+                .unwrap_or(trade.price),
+            _ => trade.price,
+        };
+
+        if trade.side == "BUY" {
+            let day = trade.timestamp / (24 * 60 * 60);
+            let current_position_value = positions
+                .get(&trade.asset)
+                .map(|p| p.cost_basis)
+                .unwrap_or(0.0);
+            let order = calculate_order_size(config, trade.usdc_size, cash, current_position_value);
+            let volume_today = daily_volume.get(&day).copied().unwrap_or(0.0);
+            let over_daily_limit = config
+                .max_daily_volume_usd
+                .is_some_and(|limit| volume_today + order.final_amount > limit);
+
+            let fill = if order.final_amount <= 0.0 || order.final_amount > cash || over_daily_limit {
+                None
+            } else {
+                match model {
+                    None => Some((order.final_amount, order.final_amount / trade.price)),
+                    Some(model) => match model.fill_buy(
+                        trade.price,
+                        start_price,
+                        order.final_amount,
+                        books.and_then(|b| b.get(&trade.asset)),
+                    ) {
+                        FillOutcome::Filled(fill) => {
+                            total_fees += fill.fee;
+                            slippage_sum += fill.slippage_percent;
+                            fills += 1;
+                            Some((fill.amount, fill.shares))
+                        }
+                        FillOutcome::Rejected(_) => {
+                            slippage_rejections += 1;
+                            None
+                        }
+                    },
+                }
+            };
+
+            if let Some((amount, shares)) = fill {
+                let pos = positions.entry(trade.asset.clone()).or_insert_with(|| SimPosition {
+                    market: trade.title.clone().unwrap_or_else(|| trade.asset.clone()),
+                    outcome: trade.outcome.clone().unwrap_or_else(|| "Unknown".to_string()),
+                    asset: trade.asset.clone(),
+                    condition_id: trade.condition_id.clone(),
+                    shares_held: 0.0,
+                    cost_basis: 0.0,
+                    invested: 0.0,
+                    proceeds: 0.0,
+                    last_price: trade.price,
+                    current_value: 0.0,
+                    pnl: 0.0,
+                    closed: false,
+                    resolved: false,
+                    exit_reason: None,
+                });
+                pos.shares_held += shares;
+                pos.cost_basis += amount;
+                pos.invested += amount;
+                pos.closed = false;
+                cash -= amount;
+                total_invested += amount;
+                *daily_volume.entry(day).or_insert(0.0) += amount;
+                copied_trades += 1;
+            } else {
+                skipped_trades += 1;
+            }
+        } else if let Some(pos) = positions.get_mut(&trade.asset).filter(|p| p.shares_held > 0.0) {
+            // Sell the same fraction of our position the trader sold of theirs
+            let sell_fraction = if held_before > 0.0 {
+                (trade.size / held_before).min(1.0)
+            } else {
+                1.0
+            };
+            let multiplier = get_trade_multiplier(config, trade.usdc_size);
+            let shares = (pos.shares_held * sell_fraction * multiplier).min(pos.shares_held);
+            match execute_sell(model, trade.price, start_price, shares, books, &trade.asset) {
+                Some((sold, amount, fee, slippage)) => {
+                    let (amount, pnl) = pos.sell(sold, amount);
+                    if pos.closed {
+                        pos.exit_reason = Some("trader_exit".to_string());
+                    }
+                    cash += amount;
+                    realized_pnl += pnl;
+                    copied_trades += 1;
+                    if model.is_some() {
+                        total_fees += fee;
+                        slippage_sum += slippage;
+                        fills += 1;
+                    }
+                }
+                None => {
+                    slippage_rejections += 1;
+                    skipped_trades += 1;
+                }
+            }
+        } else {
+            skipped_trades += 1;
+        }
+
+        // Take profit / stop loss on the asset whose price we just observed
+        if let Some(pos) = positions.get_mut(&trade.asset).filter(|p| p.shares_held > 0.0) {
+            let avg_price = pos.avg_price();
+            let change_percent = if avg_price > 0.0 {
+                (pos.last_price - avg_price) / avg_price * 100.0
+            } else {
+                0.0
+            };
+            let exit_reason = if params.take_profit_percent.is_some_and(|tp| change_percent >= tp) {
+                Some("take_profit")
+            } else if params.stop_loss_percent.is_some_and(|sl| change_percent <= -sl) {
+                Some("stop_loss")
+            } else {
+                None
+            };
+
+            if let Some(reason) = exit_reason {
+                // Our own exit: no copy latency, but still pays depth and fees
+                let price = pos.last_price;
+                if let Some((sold, amount, fee, slippage)) =
+                    execute_sell(model, price, price, pos.shares_held, books, &trade.asset)
+                {
+                    let (amount, pnl) = pos.sell(sold, amount);
+                    pos.exit_reason = Some(reason.to_string());
+                    cash += amount;
+                    realized_pnl += pnl;
+                    if reason == "take_profit" {
+                        take_profit_exits += 1;
+                    } else {
+                        stop_loss_exits += 1;
+                    }
+                    if model.is_some() {
+                        total_fees += fee;
+                        slippage_sum += slippage;
+                        fills += 1;
+                    }
+                }
+            }
+        }
+
+        equity_points.push((trade.timestamp, equity(cash, &positions)));
+    }
+
+    // Final valuation
+    let mut unrealized_pnl = 0.0;
+    let mut resolution_pnl = 0.0;
+    let mut open_value = 0.0;
+    for pos in positions.values_mut() {
+        if pos.shares_held > 0.0 {
+            let settlement = resolutions.and_then(|cache| {
+                let condition_id = pos.condition_id.as_deref()?;
+                cache.get(condition_id)?.settlement_price(&pos.asset, Some(&pos.outcome))
+            });
+            match settlement {
+                Some(price) => {
+                    pos.resolved = true;
+                    pos.last_price = price;
+                    pos.current_value = pos.shares_held * price;
+                    resolution_pnl += pos.current_value - pos.cost_basis;
+                }
+                None => {
+                    pos.current_value = pos.shares_held * pos.last_price;
+                    unrealized_pnl += pos.current_value - pos.cost_basis;
+                }
+            }
+            open_value += pos.current_value;
+        }
+        pos.pnl = pos.proceeds + pos.current_value - pos.invested;
+    }
+
+    let final_equity = cash + open_value;
+    if let Some(&(last_ts, _)) = equity_points.last() {
+        equity_points.push((last_ts, final_equity));
+    }
+
+    let curve: Vec<f64> = equity_points.iter().map(|&(_, v)| v).collect();
+    let returns = daily_returns(&daily_closes(&equity_points));
+    let total_pnl = final_equity - params.starting_capital;
+    let settled: Vec<&SimPosition> = positions
+        .values()
+        .filter(|p| p.closed || p.resolved)
+        .collect();
+    let win_rate = if settled.is_empty() {
+        0.0
+    } else {
+        settled.iter().filter(|p| p.pnl > 0.0).count() as f64 / settled.len() as f64 * 100.0
+    };
+
+    SimOutcome {
+        total_trades: trades.len(),
+        copied_trades,
+        skipped_trades,
+        take_profit_exits,
+        stop_loss_exits,
+        slippage_rejections,
+        total_fees,
+        avg_slippage_percent: if fills > 0 { slippage_sum / fills as f64 } else { 0.0 },
+        total_invested,
+        final_cash: cash,
+        final_equity,
+        realized_pnl,
+        unrealized_pnl,
+        resolution_pnl,
+        total_pnl,
+        roi: if params.starting_capital > 0.0 {
+            total_pnl / params.starting_capital * 100.0
+        } else {
+            0.0
+        },
+        win_rate,
+        max_drawdown_percent: max_drawdown_percent(&curve),
+        sharpe: sharpe_ratio(&returns),
+        positions: positions.into_values().collect(),
+    }
+}
+
+/// Most recent cached activity for a trader covering at least `history_days`
+pub fn load_cached_trades(trader_address: &str, history_days: i32) -> Option<Vec<SimTrade>> {
+    let cache_dir = Path::new(TRADE_CACHE_DIR);
+    let prefix = format!("{}_", trader_address.to_lowercase());
+    let since = chrono::Utc::now().timestamp() - history_days as i64 * 24 * 60 * 60;
+
+    let mut candidates: Vec<(String, i32)> = fs::read_dir(cache_dir)
+        .ok()?
+        .filter_map(|e| e.ok())
+        .filter_map(|e| e.file_name().to_str().map(|s| s.to_string()))
+        .filter(|name| name.to_lowercase().starts_with(&prefix) && name.ends_with(".json"))
+        .filter_map(|name| {
+            // Format: {address}_{days}d_{YYYY-MM-DD}.json
+            let days = name[prefix.len()..]
+                .split('d')
+                .next()
+                .and_then(|d| d.parse::<i32>().ok())?;
+            Some((name, days))
+        })
+        .filter(|(_, days)| *days >= history_days)
+        .collect();
+    // Newest file first (date suffix sorts lexicographically)
+    candidates.sort_by(|a, b| b.0[b.0.len() - 15..].cmp(&a.0[a.0.len() - 15..]));
+
+    let (file_name, _) = candidates.first()?;
+    let content = fs::read_to_string(cache_dir.join(file_name)).ok()?;
+    let cached: CachedTrades = serde_json::from_str(&content).ok()?;
+
+    let mut trades: Vec<SimTrade> = cached
+        .trades
+        .into_iter()
+        .filter(|t| t.timestamp >= since)
+        .collect();
+    trades.sort_by_key(|t| t.timestamp);
+    Some(trades)
+}
+
+/// Load trader activity from cache, fetching (and caching) it if missing
+pub async fn load_or_fetch_trades(
+    trader_address: &str,
+    history_days: i32,
+    max_trades: usize,
+    env: &Env,
+) -> Result<Vec<SimTrade>> {
+    if let Some(trades) = load_cached_trades(trader_address, history_days) {
+        return Ok(trades);
+    }
+
+    let since = chrono::Utc::now().timestamp() - history_days as i64 * 24 * 60 * 60;
+    let query = ActivityQuery::new(trader_address).kind("TRADE").start(since).limit(max_trades);
+    let mut trades: Vec<SimTrade> = DataApiClient::new(env)
+        .activity(&query)
+        .await?
+        .into_iter()
+        .map(|activity| SimTrade {
+            timestamp: activity.timestamp,
+            condition_id: Some(activity.condition_id).filter(|c| !c.is_empty()),
+            asset: activity.asset,
+            side: activity.side,
+            price: activity.price,
+            usdc_size: activity.usdc_size,
+            size: activity.size,
+            outcome: activity.outcome,
+            title: activity.title,
+        })
+        .collect();
+
+    trades.sort_by_key(|t| t.timestamp);
+
+    let cache_dir = Path::new(TRADE_CACHE_DIR);
+    fs::create_dir_all(cache_dir)?;
+    let today = chrono::Utc::now().format("%Y-%m-%d").to_string();
+    let cache_file = cache_dir.join(format!(
+        "{}_{}d_{}.json",
+        trader_address.to_lowercase(),
+        history_days,
+        today
+    ));
+    let cache_data = serde_json::json!({
+        "name": format!("trader_{}_{}d_{}", &trader_address[..6.min(trader_address.len())], history_days, today),
+        "traderAddress": trader_address,
+        "fetchedAt": chrono::Utc::now().to_rfc3339(),
+        "period": format!("{}_days", history_days),
+        "totalTrades": trades.len(),
+        "trades": trades
+    });
+    fs::write(&cache_file, serde_json::to_string_pretty(&cache_data)?)?;
+
+    Ok(trades)
+}
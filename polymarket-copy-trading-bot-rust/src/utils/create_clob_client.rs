@@ -18,7 +18,7 @@ async fn is_contract_address(rpc_url: &str, address: &str) -> Result<bool> {
         "params": [format!("0x{}", addr_trimmed), "latest"],
         "id": 1
     });
-    let client = reqwest::Client::new();
+    let client = crate::utils::http::http_client();
     let resp = client
         .post(rpc_url)
         .json(&body)
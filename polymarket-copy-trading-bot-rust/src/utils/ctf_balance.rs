@@ -0,0 +1,46 @@
+use anyhow::Result;
+use alloy::primitives::U256;
+use crate::config::Env;
+use crate::utils::http::{http_client, throttle};
+
+/// Conditional Tokens (ERC-1155) contract holding outcome shares
+pub const CTF_CONTRACT: &str = "0x4D97DCd97eC945f40cF65F87097ACe5EA0476045";
+
+/// On-chain outcome token balance of `owner` for CLOB token ID `token_id` (decimal string)
+pub async fn get_ctf_balance(owner: &str, token_id: &str, env: &Env) -> Result<f64> {
+    let client = http_client();
+
+    // balanceOf(address,uint256) function selector: 0x00fdd58e
+    let padded_owner = format!("{:0>64}", owner.trim_start_matches("0x"));
+    let token = U256::from_str_radix(token_id, 10)
+        .map_err(|e| anyhow::anyhow!("Invalid token ID {}: {}", token_id, e))?;
+    let data = format!("0x00fdd58e{}{}", padded_owner, hex::encode(token.to_be_bytes::<32>()));
+
+    let payload = serde_json::json!({
+        "jsonrpc": "2.0",
+        "method": "eth_call",
+        "params": [{
+            "to": CTF_CONTRACT,
+            "data": data
+        }, "latest"],
+        "id": 1
+    });
+
+    throttle(&env.rpc_url, env).await;
+    let response: serde_json::Value = client
+        .post(&env.rpc_url)
+        .json(&payload)
+        .send()
+        .await?
+        .json()
+        .await?;
+
+    if let Some(result) = response.get("result").and_then(|r| r.as_str()) {
+        let balance = U256::from_str_radix(result.trim_start_matches("0x"), 16)
+            .map_err(|e| anyhow::anyhow!("Failed to parse balance: {}", e))?;
+        // Outcome tokens use the same 6 decimals as USDC
+        return Ok(balance.saturating_to::<u128>() as f64 / 1_000_000.0);
+    }
+
+    anyhow::bail!("Failed to get CTF balance from RPC response: {}", response)
+}
@@ -0,0 +1,604 @@
+//! Data API client (positions, activity, trades, holders, leaderboard and
+//! portfolio value).
+//! List endpoints are paged with `limit`/`offset`; the client walks the pages
+//! until the query's limit or the end of the list is reached. Null fields are
+//! dropped before decoding so they fall back to the record's defaults.
+//! Responses go through the shared HTTP cache, so services polling the same
+//! wallet's positions within HTTP_CACHE_TTL_MS share one request.
+
+use anyhow::Result;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use crate::config::Env;
+use crate::interfaces::{UserActivity, UserPosition};
+use crate::utils::fetch_data::fetch_data_cached;
+use crate::utils::logger::Logger;
+
+/// Largest `limit` the list endpoints accept
+pub const MAX_PAGE_SIZE: usize = 500;
+
+/// The list endpoints reject offsets past this
+const MAX_OFFSET: usize = 10_000;
+
+/// Position record (`GET /positions`)
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct Position {
+    pub proxy_wallet: String,
+    pub asset: String,
+    pub condition_id: String,
+    pub size: f64,
+    pub avg_price: f64,
+    pub initial_value: f64,
+    pub current_value: f64,
+    pub cash_pnl: f64,
+    pub percent_pnl: f64,
+    pub total_bought: f64,
+    pub realized_pnl: f64,
+    pub percent_realized_pnl: f64,
+    pub cur_price: f64,
+    pub redeemable: bool,
+    pub mergeable: bool,
+    pub negative_risk: bool,
+    pub outcome_index: Option<usize>,
+    pub title: Option<String>,
+    pub slug: Option<String>,
+    pub icon: Option<String>,
+    pub event_slug: Option<String>,
+    pub outcome: Option<String>,
+    pub opposite_outcome: Option<String>,
+    pub opposite_asset: Option<String>,
+    pub end_date: Option<String>,
+}
+
+impl Position {
+    pub fn title(&self) -> &str {
+        self.title.as_deref().unwrap_or("Unknown")
+    }
+
+    pub fn outcome(&self) -> &str {
+        self.outcome.as_deref().unwrap_or("Unknown")
+    }
+}
+
+impl From<Position> for UserPosition {
+    fn from(position: Position) -> Self {
+        UserPosition {
+            id: None,
+            proxy_wallet: position.proxy_wallet,
+            asset: position.asset,
+            condition_id: position.condition_id,
+            size: position.size,
+            avg_price: position.avg_price,
+            initial_value: position.initial_value,
+            current_value: position.current_value,
+            cash_pnl: position.cash_pnl,
+            percent_pnl: position.percent_pnl,
+            total_bought: position.total_bought,
+            realized_pnl: position.realized_pnl,
+            percent_realized_pnl: position.percent_realized_pnl,
+            cur_price: position.cur_price,
+            redeemable: position.redeemable,
+            mergeable: position.mergeable,
+            title: position.title.unwrap_or_default(),
+            slug: position.slug.unwrap_or_default(),
+            icon: position.icon.unwrap_or_default(),
+            event_slug: position.event_slug.unwrap_or_default(),
+            outcome: position.outcome.unwrap_or_default(),
+            outcome_index: position.outcome_index.unwrap_or(0) as i32,
+            opposite_outcome: position.opposite_outcome.unwrap_or_default(),
+            opposite_asset: position.opposite_asset.unwrap_or_default(),
+            end_date: position.end_date.unwrap_or_default(),
+            negative_risk: position.negative_risk,
+        }
+    }
+}
+
+/// Activity record (`GET /activity`): trades, merges, splits, redeems, conversions and rewards
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct Activity {
+    pub proxy_wallet: Option<String>,
+    pub timestamp: i64,
+    pub condition_id: String,
+    #[serde(rename = "type")]
+    pub activity_type: String,
+    pub size: f64,
+    pub usdc_size: f64,
+    pub transaction_hash: String,
+    pub price: f64,
+    pub asset: String,
+    pub side: String,
+    pub outcome_index: Option<i32>,
+    pub title: Option<String>,
+    pub slug: Option<String>,
+    pub icon: Option<String>,
+    pub event_slug: Option<String>,
+    pub outcome: Option<String>,
+    pub name: Option<String>,
+    pub market: Option<String>,
+}
+
+impl From<&Activity> for UserActivity {
+    fn from(activity: &Activity) -> Self {
+        UserActivity {
+            id: None,
+            proxy_wallet: activity.proxy_wallet.clone().unwrap_or_default(),
+            timestamp: activity.timestamp,
+            condition_id: activity.condition_id.clone(),
+            r#type: activity.activity_type.clone(),
+            size: activity.size,
+            usdc_size: activity.usdc_size,
+            transaction_hash: activity.transaction_hash.clone(),
+            price: activity.price,
+            asset: activity.asset.clone(),
+            side: activity.side.clone(),
+            outcome_index: activity.outcome_index.unwrap_or(0),
+            title: activity.title.clone().unwrap_or_default(),
+            slug: activity.slug.clone().unwrap_or_default(),
+            icon: activity.icon.clone().unwrap_or_default(),
+            event_slug: activity.event_slug.clone().unwrap_or_default(),
+            outcome: activity.outcome.clone().unwrap_or_default(),
+            name: activity.name.clone().unwrap_or_default(),
+            pseudonym: String::new(),
+            bio: String::new(),
+            profile_image: String::new(),
+            profile_image_optimized: String::new(),
+            bot: false,
+            bot_executed_time: 0,
+            my_bought_size: None,
+        }
+    }
+}
+
+/// Fill record (`GET /trades`)
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct Trade {
+    pub proxy_wallet: String,
+    pub side: String,
+    pub asset: String,
+    pub condition_id: String,
+    pub size: f64,
+    pub price: f64,
+    pub timestamp: i64,
+    pub title: Option<String>,
+    pub outcome: Option<String>,
+    pub transaction_hash: Option<String>,
+}
+
+/// Top holders of one outcome token (`GET /holders`)
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct MarketHolders {
+    pub token: String,
+    pub holders: Vec<Holder>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct Holder {
+    pub proxy_wallet: String,
+    pub asset: String,
+    pub amount: f64,
+    pub outcome_index: Option<usize>,
+    pub name: Option<String>,
+    pub pseudonym: Option<String>,
+}
+
+/// Leaderboard row (`GET /leaderboard`)
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct LeaderboardEntry {
+    #[serde(alias = "proxyWallet")]
+    pub address: String,
+    #[serde(alias = "userName")]
+    pub name: Option<String>,
+    pub pnl: Option<f64>,
+    #[serde(alias = "vol")]
+    pub volume: Option<f64>,
+}
+
+/// Query for `GET /positions`
+#[derive(Debug, Clone, Default)]
+pub struct PositionsQuery {
+    user: String,
+    markets: Vec<String>,
+    size_threshold: Option<f64>,
+    redeemable: Option<bool>,
+    limit: Option<usize>,
+}
+
+impl PositionsQuery {
+    pub fn new(user: &str) -> Self {
+        Self { user: user.to_string(), ..Default::default() }
+    }
+
+    /// Only positions in this market (condition ID); may be repeated
+    pub fn market(mut self, condition_id: &str) -> Self {
+        self.markets.push(condition_id.to_string());
+        self
+    }
+
+    /// Only positions of at least this many tokens
+    pub fn size_threshold(mut self, size: f64) -> Self {
+        self.size_threshold = Some(size);
+        self
+    }
+
+    pub fn redeemable(mut self, redeemable: bool) -> Self {
+        self.redeemable = Some(redeemable);
+        self
+    }
+
+    /// Stop after this many positions (all pages by default)
+    pub fn limit(mut self, limit: usize) -> Self {
+        self.limit = Some(limit);
+        self
+    }
+
+    pub fn params(&self) -> Vec<(&'static str, String)> {
+        let mut params = vec![("user", self.user.clone())];
+        if !self.markets.is_empty() {
+            params.push(("market", self.markets.join(",")));
+        }
+        if let Some(size) = self.size_threshold {
+            params.push(("sizeThreshold", size.to_string()));
+        }
+        if let Some(redeemable) = self.redeemable {
+            params.push(("redeemable", redeemable.to_string()));
+        }
+        params
+    }
+}
+
+/// Query for `GET /activity`
+#[derive(Debug, Clone, Default)]
+pub struct ActivityQuery {
+    user: String,
+    types: Vec<String>,
+    markets: Vec<String>,
+    side: Option<String>,
+    start: Option<i64>,
+    end: Option<i64>,
+    ascending: bool,
+    limit: Option<usize>,
+}
+
+impl ActivityQuery {
+    pub fn new(user: &str) -> Self {
+        Self { user: user.to_string(), ..Default::default() }
+    }
+
+    /// Only activity of this type (TRADE, MERGE, SPLIT, REDEEM, ...); may be repeated
+    pub fn kind(mut self, activity_type: &str) -> Self {
+        self.types.push(activity_type.to_string());
+        self
+    }
+
+    /// Only activity in this market (condition ID); may be repeated
+    pub fn market(mut self, condition_id: &str) -> Self {
+        self.markets.push(condition_id.to_string());
+        self
+    }
+
+    /// BUY or SELL
+    pub fn side(mut self, side: &str) -> Self {
+        self.side = Some(side.to_string());
+        self
+    }
+
+    /// Only activity at or after this unix timestamp (seconds)
+    pub fn start(mut self, timestamp: i64) -> Self {
+        self.start = Some(timestamp);
+        self
+    }
+
+    /// Only activity at or before this unix timestamp (seconds)
+    pub fn end(mut self, timestamp: i64) -> Self {
+        self.end = Some(timestamp);
+        self
+    }
+
+    /// Oldest first (newest first by default)
+    pub fn ascending(mut self) -> Self {
+        self.ascending = true;
+        self
+    }
+
+    /// Stop after this many records (all pages by default)
+    pub fn limit(mut self, limit: usize) -> Self {
+        self.limit = Some(limit);
+        self
+    }
+
+    pub fn params(&self) -> Vec<(&'static str, String)> {
+        let mut params = vec![("user", self.user.clone())];
+        if !self.types.is_empty() {
+            params.push(("type", self.types.join(",")));
+        }
+        if !self.markets.is_empty() {
+            params.push(("market", self.markets.join(",")));
+        }
+        if let Some(side) = &self.side {
+            params.push(("side", side.clone()));
+        }
+        if let Some(start) = self.start {
+            params.push(("start", start.to_string()));
+        }
+        if let Some(end) = self.end {
+            params.push(("end", end.to_string()));
+        }
+        params.push(("sortBy", "TIMESTAMP".to_string()));
+        params.push(("sortDirection", if self.ascending { "ASC" } else { "DESC" }.to_string()));
+        params
+    }
+}
+
+/// Query for `GET /trades`
+#[derive(Debug, Clone, Default)]
+pub struct TradesQuery {
+    user: Option<String>,
+    markets: Vec<String>,
+    side: Option<String>,
+    taker_only: bool,
+    limit: Option<usize>,
+}
+
+impl TradesQuery {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn user(mut self, user: &str) -> Self {
+        self.user = Some(user.to_string());
+        self
+    }
+
+    /// Only fills in this market (condition ID); may be repeated
+    pub fn market(mut self, condition_id: &str) -> Self {
+        self.markets.push(condition_id.to_string());
+        self
+    }
+
+    /// BUY or SELL
+    pub fn side(mut self, side: &str) -> Self {
+        self.side = Some(side.to_string());
+        self
+    }
+
+    /// Only the taker side of each fill
+    pub fn taker_only(mut self) -> Self {
+        self.taker_only = true;
+        self
+    }
+
+    /// Stop after this many fills (all pages by default)
+    pub fn limit(mut self, limit: usize) -> Self {
+        self.limit = Some(limit);
+        self
+    }
+
+    pub fn params(&self) -> Vec<(&'static str, String)> {
+        let mut params = Vec::new();
+        if let Some(user) = &self.user {
+            params.push(("user", user.clone()));
+        }
+        if !self.markets.is_empty() {
+            params.push(("market", self.markets.join(",")));
+        }
+        if let Some(side) = &self.side {
+            params.push(("side", side.clone()));
+        }
+        params.push(("takerOnly", self.taker_only.to_string()));
+        params
+    }
+}
+
+/// Query for `GET /leaderboard`; defaults to the monthly overall PnL ranking
+#[derive(Debug, Clone)]
+pub struct LeaderboardQuery {
+    category: String,
+    time_period: String,
+    order_by: String,
+    limit: usize,
+}
+
+impl Default for LeaderboardQuery {
+    fn default() -> Self {
+        Self {
+            category: "OVERALL".to_string(),
+            time_period: "MONTH".to_string(),
+            order_by: "PNL".to_string(),
+            limit: 50,
+        }
+    }
+}
+
+impl LeaderboardQuery {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn category(mut self, category: &str) -> Self {
+        self.category = category.to_string();
+        self
+    }
+
+    /// DAY, WEEK, MONTH or ALL
+    pub fn time_period(mut self, period: &str) -> Self {
+        self.time_period = period.to_string();
+        self
+    }
+
+    /// PNL or VOL
+    pub fn order_by(mut self, order_by: &str) -> Self {
+        self.order_by = order_by.to_string();
+        self
+    }
+
+    pub fn limit(mut self, limit: usize) -> Self {
+        self.limit = limit;
+        self
+    }
+
+    pub fn params(&self) -> Vec<(&'static str, String)> {
+        vec![
+            ("category", self.category.clone()),
+            ("timePeriod", self.time_period.clone()),
+            ("orderBy", self.order_by.clone()),
+            ("limit", self.limit.to_string()),
+        ]
+    }
+}
+
+/// Data API client; the base URL comes from DATA_API_URL unless overridden
+pub struct DataApiClient<'a> {
+    env: &'a Env,
+    base_url: String,
+}
+
+impl<'a> DataApiClient<'a> {
+    pub fn new(env: &'a Env) -> Self {
+        Self {
+            env,
+            base_url: env.data_api_url.trim_end_matches('/').to_string(),
+        }
+    }
+
+    pub fn with_base_url(mut self, base_url: &str) -> Self {
+        self.base_url = base_url.trim_end_matches('/').to_string();
+        self
+    }
+
+    /// Request URL for `path` with URL-encoded query parameters
+    pub fn url(&self, path: &str, params: &[(&str, String)]) -> String {
+        let query = url::form_urlencoded::Serializer::new(String::new())
+            .extend_pairs(params)
+            .finish();
+        if query.is_empty() {
+            format!("{}{}", self.base_url, path)
+        } else {
+            format!("{}{}?{}", self.base_url, path, query)
+        }
+    }
+
+    async fn get_list<T: DeserializeOwned>(&self, path: &str, params: &[(&str, String)]) -> Result<Vec<T>> {
+        Ok(decode_list(fetch_data_cached(&self.url(path, params), self.env).await?))
+    }
+
+    /// Walk `limit`/`offset` pages until `limit` records or a short page
+    async fn get_pages<T: DeserializeOwned>(
+        &self,
+        path: &str,
+        params: Vec<(&str, String)>,
+        limit: Option<usize>,
+    ) -> Result<Vec<T>> {
+        let wanted = limit.unwrap_or(usize::MAX);
+        let mut records = Vec::new();
+        let mut offset = 0;
+
+        while records.len() < wanted {
+            if offset > MAX_OFFSET {
+                Logger::warning(&format!(
+                    "{}: stopped after {} records, the Data API serves no offsets past {}",
+                    path,
+                    records.len(),
+                    MAX_OFFSET
+                ));
+                break;
+            }
+            let page_size = MAX_PAGE_SIZE.min(wanted - records.len());
+            let mut page_params = params.clone();
+            page_params.push(("limit", page_size.to_string()));
+            page_params.push(("offset", offset.to_string()));
+
+            let page = fetch_data_cached(&self.url(path, &page_params), self.env).await?;
+            let page_len = page.as_array().map(|a| a.len()).unwrap_or(0);
+            records.extend(decode_list::<T>(page));
+            if page_len < page_size {
+                break;
+            }
+            offset += page_len;
+        }
+
+        records.truncate(wanted);
+        Ok(records)
+    }
+
+    pub async fn positions(&self, query: &PositionsQuery) -> Result<Vec<Position>> {
+        self.get_pages("/positions", query.params(), query.limit).await
+    }
+
+    pub async fn activity(&self, query: &ActivityQuery) -> Result<Vec<Activity>> {
+        self.get_pages("/activity", query.params(), query.limit).await
+    }
+
+    pub async fn trades(&self, query: &TradesQuery) -> Result<Vec<Trade>> {
+        self.get_pages("/trades", query.params(), query.limit).await
+    }
+
+    /// Top `limit` holders of each outcome token of a market
+    pub async fn holders(&self, condition_id: &str, limit: usize) -> Result<Vec<MarketHolders>> {
+        self.get_list("/holders", &[("market", condition_id.to_string()), ("limit", limit.to_string())])
+            .await
+    }
+
+    pub async fn leaderboard(&self, query: &LeaderboardQuery) -> Result<Vec<LeaderboardEntry>> {
+        self.get_list("/leaderboard", &query.params()).await
+    }
+
+    /// Total current value of a wallet's positions in USD
+    pub async fn value(&self, user: &str) -> Result<f64> {
+        let json = fetch_data_cached(&self.url("/value", &[("user", user.to_string())]), self.env).await?;
+        Ok(json
+            .as_array()
+            .and_then(|a| a.first())
+            .and_then(|v| v.get("value"))
+            .and_then(|v| v.as_f64())
+            .unwrap_or(0.0))
+    }
+}
+
+/// Decode each element of a JSON array, skipping (and warning about) the ones
+/// that don't fit `T`
+pub fn decode_list<T: DeserializeOwned>(json: Value) -> Vec<T> {
+    let Value::Array(items) = json else {
+        return Vec::new();
+    };
+    let total = items.len();
+    let mut first_error = None;
+    let records: Vec<T> = items
+        .into_iter()
+        .filter_map(|v| {
+            serde_json::from_value(strip_nulls(v))
+                .map_err(|e| first_error.get_or_insert(e))
+                .ok()
+        })
+        .collect();
+    if let Some(error) = first_error {
+        let name = std::any::type_name::<T>().rsplit("::").next().unwrap_or("record");
+        Logger::warning(&format!(
+            "Skipped {} of {} {} record(s) that failed to decode: {}",
+            total - records.len(),
+            total,
+            name,
+            error
+        ));
+    }
+    records
+}
+
+fn strip_nulls(value: Value) -> Value {
+    match value {
+        Value::Object(map) => Value::Object(
+            map.into_iter()
+                .filter(|(_, v)| !v.is_null())
+                .map(|(k, v)| (k, strip_nulls(v)))
+                .collect(),
+        ),
+        Value::Array(items) => Value::Array(items.into_iter().map(strip_nulls).collect()),
+        other => other,
+    }
+}
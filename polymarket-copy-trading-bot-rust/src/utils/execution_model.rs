@@ -0,0 +1,383 @@
+//! Execution model for simulated fills.
+//! Instead of filling every copied trade at the trader's price, a fill
+//! starts from the price observed after the copy latency, walks order-book
+//! depth (synthetic levels or the shape of a recorded CLOB book), pays the
+//! taker fee and is rejected if it lands beyond the max slippage.
+
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+use crate::config::Env;
+use crate::utils::fetch_data;
+
+const ORDER_BOOK_CACHE_FILE: &str = "trader_data_cache/order_books.json";
+const MIN_PRICE: f64 = 0.001;
+const MAX_PRICE: f64 = 0.999;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DepthSource {
+    /// Evenly spaced levels of `level_usd` liquidity every `tick`
+    Synthetic,
+    /// Level spacing and sizes from a recorded CLOB book, anchored at the fill price
+    Recorded,
+}
+
+#[derive(Debug, Clone)]
+pub struct ExecutionModel {
+    pub latency_secs: i64,
+    pub depth_source: DepthSource,
+    pub level_usd: f64,
+    pub tick: f64,
+    pub levels: usize,
+    pub taker_fee_bps: f64,
+    pub max_slippage_percent: f64,
+}
+
+impl Default for ExecutionModel {
+    fn default() -> Self {
+        Self {
+            latency_secs: 5,
+            depth_source: DepthSource::Synthetic,
+            level_usd: 500.0,
+            tick: 0.01,
+            levels: 10,
+            taker_fee_bps: 0.0,
+            max_slippage_percent: 5.0,
+        }
+    }
+}
+
+fn env_f64(key: &str, default: f64) -> f64 {
+    env::var(key)
+        .ok()
+        .and_then(|s| s.parse::<f64>().ok())
+        .filter(|&v| v >= 0.0)
+        .unwrap_or(default)
+}
+
+impl ExecutionModel {
+    /// Build from environment variables; `None` unless `SIM_EXECUTION_MODEL=true`
+    /// (fills at the trader's price). Settings: `SIM_COPY_LATENCY_SECS`,
+    /// `SIM_BOOK_DEPTH` (synthetic|recorded), `SIM_BOOK_LEVEL_USD`, `SIM_BOOK_TICK`,
+    /// `SIM_BOOK_LEVELS`, `SIM_TAKER_FEE_BPS`, `SIM_MAX_SLIPPAGE_PERCENT`
+    pub fn from_env() -> Option<Self> {
+        let enabled = env::var("SIM_EXECUTION_MODEL")
+            .map(|v| v.to_lowercase() == "true")
+            .unwrap_or(false);
+        if !enabled {
+            return None;
+        }
+
+        let defaults = Self::default();
+        let depth_source = match env::var("SIM_BOOK_DEPTH")
+            .unwrap_or_else(|_| "synthetic".to_string())
+            .to_lowercase()
+            .as_str()
+        {
+            "recorded" => DepthSource::Recorded,
+            _ => DepthSource::Synthetic,
+        };
+
+        Some(Self {
+            latency_secs: env_f64("SIM_COPY_LATENCY_SECS", defaults.latency_secs as f64) as i64,
+            depth_source,
+            level_usd: env_f64("SIM_BOOK_LEVEL_USD", defaults.level_usd),
+            tick: env_f64("SIM_BOOK_TICK", defaults.tick).max(0.001),
+            levels: env_f64("SIM_BOOK_LEVELS", defaults.levels as f64).max(1.0) as usize,
+            taker_fee_bps: env_f64("SIM_TAKER_FEE_BPS", defaults.taker_fee_bps),
+            max_slippage_percent: env_f64("SIM_MAX_SLIPPAGE_PERCENT", defaults.max_slippage_percent),
+        })
+    }
+
+    pub fn describe(&self) -> String {
+        format!(
+            "latency {}s, {} depth, fee {}bps, max slippage {}%",
+            self.latency_secs,
+            match self.depth_source {
+                DepthSource::Synthetic => format!("synthetic ${}/{}", self.level_usd, self.tick),
+                DepthSource::Recorded => "recorded".to_string(),
+            },
+            self.taker_fee_bps,
+            self.max_slippage_percent
+        )
+    }
+
+    /// Price levels (price, shares) to walk, best first, starting at `start_price`
+    fn levels_for(&self, is_buy: bool, start_price: f64, book: Option<&BookSnapshot>) -> Vec<(f64, f64)> {
+        let recorded = book
+            .filter(|_| self.depth_source == DepthSource::Recorded)
+            .map(|b| if is_buy { &b.asks } else { &b.bids })
+            .filter(|levels| !levels.is_empty());
+
+        match recorded {
+            Some(levels) => {
+                // Keep the recorded spacing relative to the best level
+                let best = levels[0].price;
+                levels
+                    .iter()
+                    .map(|l| {
+                        let offset = (l.price - best).abs();
+                        let price = if is_buy { start_price + offset } else { start_price - offset };
+                        (price, l.size)
+                    })
+                    .filter(|(p, _)| (MIN_PRICE..=MAX_PRICE).contains(p))
+                    .collect()
+            }
+            None => (0..self.levels)
+                .map(|i| {
+                    let offset = self.tick * i as f64;
+                    let price = if is_buy { start_price + offset } else { start_price - offset };
+                    (price, self.level_usd / price.max(MIN_PRICE))
+                })
+                .filter(|(p, _)| (MIN_PRICE..=MAX_PRICE).contains(p))
+                .collect(),
+        }
+    }
+
+    /// Polymarket taker fee: `bps * min(p, 1 - p) * shares`
+    fn fee(&self, price: f64, shares: f64) -> f64 {
+        self.taker_fee_bps / 10_000.0 * price.min(1.0 - price) * shares
+    }
+
+    /// Buy `amount_usd` of an outcome the trader bought at `trader_price`;
+    /// the book is walked from `start_price` (the price after latency)
+    pub fn fill_buy(
+        &self,
+        trader_price: f64,
+        start_price: f64,
+        amount_usd: f64,
+        book: Option<&BookSnapshot>,
+    ) -> FillOutcome {
+        let mut remaining = amount_usd;
+        let mut shares = 0.0;
+        for (price, size) in self.levels_for(true, start_price, book) {
+            if remaining <= 0.0 {
+                break;
+            }
+            let take = (remaining / price).min(size);
+            shares += take;
+            remaining -= take * price;
+        }
+        self.finish(true, trader_price, amount_usd - remaining.max(0.0), shares)
+    }
+
+    /// Sell `shares` the trader sold at `trader_price`
+    pub fn fill_sell(
+        &self,
+        trader_price: f64,
+        start_price: f64,
+        shares: f64,
+        book: Option<&BookSnapshot>,
+    ) -> FillOutcome {
+        let mut remaining = shares;
+        let mut amount = 0.0;
+        for (price, size) in self.levels_for(false, start_price, book) {
+            if remaining <= 0.0 {
+                break;
+            }
+            let take = remaining.min(size);
+            amount += take * price;
+            remaining -= take;
+        }
+        self.finish(false, trader_price, amount, shares - remaining.max(0.0))
+    }
+
+    fn finish(&self, is_buy: bool, trader_price: f64, notional: f64, shares: f64) -> FillOutcome {
+        if shares <= 0.0 || notional <= 0.0 {
+            return FillOutcome::Rejected("no liquidity".to_string());
+        }
+
+        let avg_price = notional / shares;
+        let slippage_percent = if is_buy {
+            (avg_price - trader_price) / trader_price * 100.0
+        } else {
+            (trader_price - avg_price) / trader_price * 100.0
+        };
+        if slippage_percent > self.max_slippage_percent {
+            return FillOutcome::Rejected(format!(
+                "slippage {:.2}% > {:.2}%",
+                slippage_percent, self.max_slippage_percent
+            ));
+        }
+
+        let fee = self.fee(avg_price, shares);
+        let (shares, amount) = if is_buy {
+            // Fee comes out of the shares received
+            (shares - fee / avg_price, notional)
+        } else {
+            (shares, notional - fee)
+        };
+
+        FillOutcome::Filled(Fill {
+            price: avg_price,
+            shares,
+            amount,
+            fee,
+            slippage_percent,
+        })
+    }
+}
+
+/// Result of one simulated fill. For buys `amount` is USDC spent,
+/// for sells it's USDC received after fees.
+#[derive(Debug, Clone)]
+pub struct Fill {
+    pub price: f64,
+    pub shares: f64,
+    pub amount: f64,
+    pub fee: f64,
+    pub slippage_percent: f64,
+}
+
+#[derive(Debug, Clone)]
+pub enum FillOutcome {
+    Filled(Fill),
+    Rejected(String),
+}
+
+/// Observed trade prices per asset, used to price fills after the copy latency
+pub struct PriceTape {
+    by_asset: HashMap<String, Vec<(i64, f64)>>,
+}
+
+impl PriceTape {
+    pub fn new<'a>(points: impl Iterator<Item = (&'a str, i64, f64)>) -> Self {
+        let mut by_asset: HashMap<String, Vec<(i64, f64)>> = HashMap::new();
+        for (asset, timestamp, price) in points {
+            if price > 0.0 {
+                by_asset.entry(asset.to_string()).or_default().push((timestamp, price));
+            }
+        }
+        for series in by_asset.values_mut() {
+            series.sort_by_key(|&(t, _)| t);
+        }
+        Self { by_asset }
+    }
+
+    /// Last observed price at or before `timestamp`
+    pub fn price_at(&self, asset: &str, timestamp: i64) -> Option<f64> {
+        let series = self.by_asset.get(asset)?;
+        let idx = series.partition_point(|&(t, _)| t <= timestamp);
+        idx.checked_sub(1).map(|i| series[i].1)
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BookLevel {
+    pub price: f64,
+    pub size: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BookSnapshot {
+    pub asset: String,
+    pub bids: Vec<BookLevel>,
+    pub asks: Vec<BookLevel>,
+    pub fetched_at: i64,
+}
+
+/// On-disk cache of CLOB order book snapshots keyed by token ID
+pub struct OrderBookCache {
+    path: PathBuf,
+    entries: HashMap<String, BookSnapshot>,
+}
+
+impl OrderBookCache {
+    pub fn load() -> Self {
+        Self::load_from(Path::new(ORDER_BOOK_CACHE_FILE))
+    }
+
+    pub fn load_from(path: &Path) -> Self {
+        let entries = fs::read_to_string(path)
+            .ok()
+            .and_then(|content| serde_json::from_str::<Vec<BookSnapshot>>(&content).ok())
+            .unwrap_or_default()
+            .into_iter()
+            .map(|b| (b.asset.clone(), b))
+            .collect();
+
+        Self {
+            path: path.to_path_buf(),
+            entries,
+        }
+    }
+
+    pub fn get(&self, asset: &str) -> Option<&BookSnapshot> {
+        self.entries.get(asset)
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Record books for assets not cached yet. Markets without a book
+    /// (closed or delisted) are skipped and fall back to synthetic depth.
+    pub async fn refresh(&mut self, assets: &[String], env: &Env) -> Result<usize> {
+        let mut to_fetch: Vec<&String> = assets.iter().filter(|a| !self.entries.contains_key(*a)).collect();
+        to_fetch.sort();
+        to_fetch.dedup();
+
+        let mut fetched = 0;
+        for asset in to_fetch {
+            let url = format!("{}/book?token_id={}", env.clob_http_url.trim_end_matches('/'), asset);
+            let Ok(json) = fetch_data(&url, env).await else {
+                continue;
+            };
+            let parse_side = |key: &str| -> Vec<BookLevel> {
+                json.get(key)
+                    .and_then(|v| v.as_array())
+                    .map(|levels| {
+                        levels
+                            .iter()
+                            .filter_map(|l| {
+                                Some(BookLevel {
+                                    price: l.get("price")?.as_str()?.parse().ok()?,
+                                    size: l.get("size")?.as_str()?.parse().ok()?,
+                                })
+                            })
+                            .collect()
+                    })
+                    .unwrap_or_default()
+            };
+            let mut bids = parse_side("bids");
+            let mut asks = parse_side("asks");
+            if bids.is_empty() && asks.is_empty() {
+                continue;
+            }
+            // Best level first
+            bids.sort_by(|a, b| b.price.partial_cmp(&a.price).unwrap_or(std::cmp::Ordering::Equal));
+            asks.sort_by(|a, b| a.price.partial_cmp(&b.price).unwrap_or(std::cmp::Ordering::Equal));
+
+            self.entries.insert(
+                asset.clone(),
+                BookSnapshot {
+                    asset: asset.clone(),
+                    bids,
+                    asks,
+                    fetched_at: chrono::Utc::now().timestamp(),
+                },
+            );
+            fetched += 1;
+        }
+
+        Ok(fetched)
+    }
+
+    pub fn save(&self) -> Result<()> {
+        if let Some(parent) = self.path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let mut entries: Vec<&BookSnapshot> = self.entries.values().collect();
+        entries.sort_by(|a, b| a.asset.cmp(&b.asset));
+        fs::write(&self.path, serde_json::to_string_pretty(&entries)?)?;
+        Ok(())
+    }
+}
@@ -1,20 +1,19 @@
 use anyhow::Result;
-use reqwest::Client;
+use reqwest::StatusCode;
 use std::time::Duration;
 use crate::config::Env;
+use crate::utils::http::{back_off_host, cached, http_client, retry_after, throttle};
 
 pub async fn fetch_data(url: &str, env: &Env) -> Result<serde_json::Value> {
     let retries = env.network_retry_limit;
     let timeout = Duration::from_millis(env.request_timeout_ms);
     let retry_delay = Duration::from_secs(1);
 
-    let client = Client::builder()
-        .timeout(timeout)
-        .build()?;
-
     for attempt in 1..=retries {
-        match client
+        throttle(url, env).await;
+        match http_client()
             .get(url)
+            .timeout(timeout)
             .header("User-Agent", "Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36")
             .send()
             .await
@@ -22,6 +21,17 @@ pub async fn fetch_data(url: &str, env: &Env) -> Result<serde_json::Value> {
             Ok(response) => {
                 if response.status().is_success() {
                     return Ok(response.json().await?);
+                } else if response.status() == StatusCode::TOO_MANY_REQUESTS && attempt < retries {
+                    // Hold every caller of this host, not just this request
+                    let delay = retry_after(response.headers()).unwrap_or(retry_delay * (1 << (attempt - 1)));
+                    back_off_host(url, delay, env);
+                    eprintln!(
+                        "⚠️  Rate limited (attempt {}/{}), retrying in {:?}...",
+                        attempt,
+                        retries,
+                        delay
+                    );
+                    continue;
                 } else if attempt < retries {
                     let delay = retry_delay * (1 << (attempt - 1)); // Exponential backoff
                     eprintln!(
@@ -64,3 +74,8 @@ pub async fn fetch_data(url: &str, env: &Env) -> Result<serde_json::Value> {
     unreachable!()
 }
 
+/// `fetch_data` through the short-TTL response cache, for data several
+/// services poll (e.g. the proxy wallet's positions)
+pub async fn fetch_data_cached(url: &str, env: &Env) -> Result<serde_json::Value> {
+    cached(url, env, || fetch_data(url, env)).await
+}
@@ -0,0 +1,178 @@
+//! Gamma API (market metadata) helpers.
+//! Markets are looked up by condition ID; list fields that the API returns as
+//! JSON-encoded strings (outcomes, prices, token IDs) are decoded here.
+
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use crate::config::Env;
+use crate::utils::fetch_data;
+
+/// Max condition IDs per `/markets` request (keeps URLs well under length limits)
+const CONDITION_BATCH_SIZE: usize = 20;
+
+/// Market record as returned by `GET /markets`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GammaMarket {
+    #[serde(default)]
+    pub condition_id: String,
+    #[serde(default)]
+    pub question: String,
+    #[serde(default)]
+    pub slug: String,
+    pub end_date: Option<String>,
+    #[serde(default)]
+    pub closed: bool,
+    #[serde(default)]
+    pub active: bool,
+    #[serde(rename = "volume24hr", default)]
+    pub volume_24hr: Option<f64>,
+    #[serde(default)]
+    pub liquidity_num: Option<f64>,
+    #[serde(default)]
+    pub volume_num: Option<f64>,
+    #[serde(default)]
+    pub category: Option<String>,
+    #[serde(default)]
+    pub tags: Vec<GammaTag>, // Only present with include_tag=true
+    #[serde(default)]
+    pub outcomes: serde_json::Value, // Can be string or array
+    #[serde(default)]
+    pub outcome_prices: serde_json::Value, // Can be string or array
+    #[serde(default)]
+    pub clob_token_ids: serde_json::Value, // Can be string or array
+    #[serde(default)]
+    pub neg_risk: bool,
+    /// Neg-risk market this question belongs to
+    #[serde(rename = "negRiskMarketID", default)]
+    pub neg_risk_market_id: Option<String>,
+    /// Neg-risk question ID: the market ID with the question index in the last byte
+    #[serde(rename = "questionID", default)]
+    pub question_id: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GammaTag {
+    #[serde(default)]
+    pub label: Option<String>,
+    #[serde(default)]
+    pub slug: Option<String>,
+}
+
+impl GammaMarket {
+    pub fn outcome_names(&self) -> Vec<String> {
+        parse_list_field(&self.outcomes)
+    }
+
+    pub fn outcome_price_values(&self) -> Vec<f64> {
+        parse_list_field::<String>(&self.outcome_prices)
+            .iter()
+            .filter_map(|p| p.parse::<f64>().ok())
+            .collect()
+    }
+
+    pub fn token_ids(&self) -> Vec<String> {
+        parse_list_field(&self.clob_token_ids)
+    }
+
+    /// Lowercased tag labels, tag slugs and category
+    pub fn tag_names(&self) -> Vec<String> {
+        self.tags
+            .iter()
+            .flat_map(|t| [t.label.clone(), t.slug.clone()])
+            .chain(std::iter::once(self.category.clone()))
+            .flatten()
+            .map(|t| t.to_lowercase())
+            .collect()
+    }
+
+    /// Lowercased category, falling back to the first tag label
+    pub fn primary_category(&self) -> String {
+        self.category
+            .clone()
+            .or_else(|| self.tags.iter().find_map(|t| t.label.clone().or_else(|| t.slug.clone())))
+            .map(|c| c.to_lowercase())
+            .unwrap_or_else(|| "uncategorized".to_string())
+    }
+}
+
+/// Parse a field that might be a JSON-encoded string or a plain array
+fn parse_list_field<T: for<'de> Deserialize<'de>>(value: &serde_json::Value) -> Vec<T> {
+    match value {
+        serde_json::Value::String(s) => serde_json::from_str(s).unwrap_or_default(),
+        serde_json::Value::Array(_) => serde_json::from_value(value.clone()).unwrap_or_default(),
+        _ => Vec::new(),
+    }
+}
+
+async fn fetch_market_batch(query: &str, env: &Env) -> Result<Vec<GammaMarket>> {
+    let url = format!("{}/markets?{}", env.gamma_api_url.trim_end_matches('/'), query);
+    let markets = fetch_data(&url, env)
+        .await?
+        .as_array()
+        .cloned()
+        .unwrap_or_default()
+        .into_iter()
+        .filter_map(|v| serde_json::from_value::<GammaMarket>(v).ok())
+        .collect();
+    Ok(markets)
+}
+
+/// Fetch markets for the given condition IDs (open and closed).
+/// IDs the API doesn't know about are simply absent from the result.
+pub async fn fetch_markets_by_condition_ids(
+    condition_ids: &[String],
+    env: &Env,
+) -> Result<Vec<GammaMarket>> {
+    let mut markets = Vec::new();
+
+    for batch in condition_ids.chunks(CONDITION_BATCH_SIZE) {
+        let query = batch
+            .iter()
+            .map(|id| format!("condition_ids={}", id))
+            .collect::<Vec<_>>()
+            .join("&");
+
+        let mut found = fetch_market_batch(&query, env).await?;
+
+        // Closed markets are filtered out by default - ask again for the missing ones
+        let missing: Vec<&String> = batch
+            .iter()
+            .filter(|id| !found.iter().any(|m| m.condition_id.eq_ignore_ascii_case(id)))
+            .collect();
+        if !missing.is_empty() {
+            let closed_query = missing
+                .iter()
+                .map(|id| format!("condition_ids={}", id))
+                .collect::<Vec<_>>()
+                .join("&");
+            found.extend(fetch_market_batch(&format!("{}&closed=true", closed_query), env).await?);
+        }
+
+        markets.extend(found);
+    }
+
+    Ok(markets)
+}
+
+/// Open markets ordered by 24h volume, most active first
+pub async fn fetch_active_markets(limit: usize, env: &Env) -> Result<Vec<GammaMarket>> {
+    fetch_market_batch(
+        &format!(
+            "active=true&closed=false&order=volume24hr&ascending=false&limit={}",
+            limit
+        ),
+        env,
+    )
+    .await
+}
+
+/// Single market with its tags, open or closed
+pub async fn fetch_market_with_tags(condition_id: &str, env: &Env) -> Result<Option<GammaMarket>> {
+    let query = format!("condition_ids={}&include_tag=true", condition_id);
+    let mut markets = fetch_market_batch(&query, env).await?;
+    if markets.is_empty() {
+        markets = fetch_market_batch(&format!("{}&closed=true", query), env).await?;
+    }
+    Ok(markets.into_iter().next())
+}
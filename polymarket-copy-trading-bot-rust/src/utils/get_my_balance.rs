@@ -1,10 +1,11 @@
 use anyhow::Result;
 use alloy::primitives::U256;
 use crate::config::Env;
+use crate::utils::http::{http_client, throttle};
 
 pub async fn get_my_balance(address: &str, env: &Env) -> Result<f64> {
     // Using RPC directly to call balanceOf on USDC contract
-    let client = reqwest::Client::new();
+    let client = http_client();
     
     // balanceOf(address) function selector: 0x70a08231
     // Pad address to 32 bytes (64 hex chars)
@@ -22,6 +23,7 @@ pub async fn get_my_balance(address: &str, env: &Env) -> Result<f64> {
         "id": 1
     });
     
+    throttle(&env.rpc_url, env).await;
     let response: serde_json::Value = client
         .post(&env.rpc_url)
         .json(&payload)
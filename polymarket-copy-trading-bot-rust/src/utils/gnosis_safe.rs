@@ -0,0 +1,373 @@
+use anyhow::Result;
+use alloy::primitives::{Address, Bytes, B256, U256};
+use alloy::providers::{Provider, ProviderBuilder};
+use alloy::signers::local::PrivateKeySigner;
+use alloy::signers::SignerSync;
+use alloy::sol;
+use alloy::sol_types::{Eip712Domain, SolCall, SolStruct};
+use std::str::FromStr;
+use crate::config::Env;
+use crate::utils::ctf_balance::CTF_CONTRACT;
+
+/// Safe MultiSendCallOnly v1.3.0 (same address on every chain)
+pub const MULTI_SEND_CALL_ONLY: &str = "0x40A2aCCbd92BCA938b02010E17A5b8929b49130D";
+/// Polymarket NegRiskAdapter, used to redeem neg-risk market positions
+pub const NEG_RISK_ADAPTER: &str = "0xd91E80cF2E7be2e162c6513ceD06f1dD0dA35296";
+
+sol! {
+    /// EIP-712 struct signed by Safe owners
+    struct SafeTx {
+        address to;
+        uint256 value;
+        bytes data;
+        uint8 operation;
+        uint256 safeTxGas;
+        uint256 baseGas;
+        uint256 gasPrice;
+        address gasToken;
+        address refundReceiver;
+        uint256 nonce;
+    }
+
+    #[sol(rpc)]
+    #[allow(clippy::too_many_arguments)]
+    interface IGnosisSafe {
+        function nonce() external view returns (uint256);
+        function getThreshold() external view returns (uint256);
+        function isOwner(address owner) external view returns (bool);
+        function getTransactionHash(
+            address to,
+            uint256 value,
+            bytes calldata data,
+            uint8 operation,
+            uint256 safeTxGas,
+            uint256 baseGas,
+            uint256 gasPrice,
+            address gasToken,
+            address refundReceiver,
+            uint256 _nonce
+        ) external view returns (bytes32);
+        function execTransaction(
+            address to,
+            uint256 value,
+            bytes calldata data,
+            uint8 operation,
+            uint256 safeTxGas,
+            uint256 baseGas,
+            uint256 gasPrice,
+            address gasToken,
+            address payable refundReceiver,
+            bytes memory signatures
+        ) external payable returns (bool success);
+    }
+
+    interface IMultiSend {
+        function multiSend(bytes memory transactions) external payable;
+    }
+
+    interface IERC20 {
+        function transfer(address to, uint256 amount) external returns (bool);
+        function approve(address spender, uint256 amount) external returns (bool);
+    }
+
+    interface IConditionalTokens {
+        function setApprovalForAll(address operator, bool approved) external;
+        function redeemPositions(address collateralToken, bytes32 parentCollectionId, bytes32 conditionId, uint256[] indexSets) external;
+        function mergePositions(address collateralToken, bytes32 parentCollectionId, bytes32 conditionId, uint256[] partition, uint256 amount) external;
+        function splitPosition(address collateralToken, bytes32 parentCollectionId, bytes32 conditionId, uint256[] partition, uint256 amount) external;
+    }
+
+    interface INegRiskAdapter {
+        function redeemPositions(bytes32 conditionId, uint256[] amounts) external;
+        function mergePositions(bytes32 conditionId, uint256 amount) external;
+        function splitPosition(bytes32 conditionId, uint256 amount) external;
+        function convertPositions(bytes32 marketId, uint256 indexSet, uint256 amount) external;
+    }
+}
+
+/// Safe operation type: plain call or delegatecall (used for MultiSend batches)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Operation {
+    Call = 0,
+    DelegateCall = 1,
+}
+
+/// One call to be executed by the Safe
+#[derive(Debug, Clone)]
+pub struct SafeCall {
+    pub to: Address,
+    pub value: U256,
+    pub data: Bytes,
+}
+
+impl SafeCall {
+    pub fn new(to: Address, data: impl Into<Bytes>) -> Self {
+        Self { to, value: U256::ZERO, data: data.into() }
+    }
+
+    pub fn with_value(mut self, value: U256) -> Self {
+        self.value = value;
+        self
+    }
+}
+
+fn parse_address(address: &str) -> Result<Address> {
+    Address::from_str(address.trim()).map_err(|e| anyhow::anyhow!("Invalid address {}: {}", address, e))
+}
+
+/// USDC / outcome token amount (6 decimals) in base units, rounded down
+pub fn to_base_units(amount: f64) -> U256 {
+    U256::from((amount * 1_000_000.0).floor().max(0.0) as u128)
+}
+
+pub fn erc20_transfer(token: &str, to: Address, amount: U256) -> Result<SafeCall> {
+    Ok(SafeCall::new(parse_address(token)?, IERC20::transferCall { to, amount }.abi_encode()))
+}
+
+pub fn erc20_approve(token: &str, spender: Address, amount: U256) -> Result<SafeCall> {
+    Ok(SafeCall::new(parse_address(token)?, IERC20::approveCall { spender, amount }.abi_encode()))
+}
+
+pub fn ctf_set_approval_for_all(operator: Address, approved: bool) -> Result<SafeCall> {
+    Ok(SafeCall::new(
+        parse_address(CTF_CONTRACT)?,
+        IConditionalTokens::setApprovalForAllCall { operator, approved }.abi_encode(),
+    ))
+}
+
+/// Redeem both outcomes of a binary market for `collateral`
+pub fn ctf_redeem_positions(collateral: &str, condition_id: B256) -> Result<SafeCall> {
+    Ok(SafeCall::new(
+        parse_address(CTF_CONTRACT)?,
+        IConditionalTokens::redeemPositionsCall {
+            collateralToken: parse_address(collateral)?,
+            parentCollectionId: B256::ZERO,
+            conditionId: condition_id,
+            indexSets: vec![U256::from(1), U256::from(2)],
+        }
+        .abi_encode(),
+    ))
+}
+
+/// Redeem a neg-risk market position; `amounts` is indexed by outcome (base units)
+pub fn neg_risk_redeem_positions(condition_id: B256, amounts: Vec<U256>) -> Result<SafeCall> {
+    Ok(SafeCall::new(
+        parse_address(NEG_RISK_ADAPTER)?,
+        INegRiskAdapter::redeemPositionsCall { conditionId: condition_id, amounts }.abi_encode(),
+    ))
+}
+
+/// Merge `amount` full sets (YES + NO) of a binary market back into collateral
+pub fn ctf_merge_positions(collateral: &str, condition_id: B256, amount: U256) -> Result<SafeCall> {
+    Ok(SafeCall::new(
+        parse_address(CTF_CONTRACT)?,
+        IConditionalTokens::mergePositionsCall {
+            collateralToken: parse_address(collateral)?,
+            parentCollectionId: B256::ZERO,
+            conditionId: condition_id,
+            partition: vec![U256::from(1), U256::from(2)],
+            amount,
+        }
+        .abi_encode(),
+    ))
+}
+
+/// Split `amount` collateral into `amount` full sets (YES + NO) of a binary market
+pub fn ctf_split_position(collateral: &str, condition_id: B256, amount: U256) -> Result<SafeCall> {
+    Ok(SafeCall::new(
+        parse_address(CTF_CONTRACT)?,
+        IConditionalTokens::splitPositionCall {
+            collateralToken: parse_address(collateral)?,
+            parentCollectionId: B256::ZERO,
+            conditionId: condition_id,
+            partition: vec![U256::from(1), U256::from(2)],
+            amount,
+        }
+        .abi_encode(),
+    ))
+}
+
+/// Merge full sets of a neg-risk market (collateral comes back as USDC.e)
+pub fn neg_risk_merge_positions(condition_id: B256, amount: U256) -> Result<SafeCall> {
+    Ok(SafeCall::new(
+        parse_address(NEG_RISK_ADAPTER)?,
+        INegRiskAdapter::mergePositionsCall { conditionId: condition_id, amount }.abi_encode(),
+    ))
+}
+
+/// Split USDC.e into full sets of a neg-risk market
+pub fn neg_risk_split_position(condition_id: B256, amount: U256) -> Result<SafeCall> {
+    Ok(SafeCall::new(
+        parse_address(NEG_RISK_ADAPTER)?,
+        INegRiskAdapter::splitPositionCall { conditionId: condition_id, amount }.abi_encode(),
+    ))
+}
+
+/// Convert NO positions of the questions in `index_set` into YES of the
+/// other questions of the neg-risk market (plus collateral)
+pub fn neg_risk_convert_positions(market_id: B256, index_set: U256, amount: U256) -> Result<SafeCall> {
+    Ok(SafeCall::new(
+        parse_address(NEG_RISK_ADAPTER)?,
+        INegRiskAdapter::convertPositionsCall { marketId: market_id, indexSet: index_set, amount }.abi_encode(),
+    ))
+}
+
+/// Packed MultiSend payload: operation (1) | to (20) | value (32) | data length (32) | data
+pub fn encode_multisend(calls: &[SafeCall]) -> Bytes {
+    let mut packed = Vec::new();
+    for call in calls {
+        packed.push(Operation::Call as u8);
+        packed.extend_from_slice(call.to.as_slice());
+        packed.extend_from_slice(&call.value.to_be_bytes::<32>());
+        packed.extend_from_slice(&U256::from(call.data.len()).to_be_bytes::<32>());
+        packed.extend_from_slice(&call.data);
+    }
+    IMultiSend::multiSendCall { transactions: packed.into() }.abi_encode().into()
+}
+
+/// Build the SafeTx for `calls`: a direct call for one, a MultiSend delegatecall for several.
+/// Gas refund fields are zero, so the executing owner pays gas.
+pub fn build_safe_tx(calls: &[SafeCall], nonce: U256) -> Result<SafeTx> {
+    let (to, value, data, operation) = match calls {
+        [] => anyhow::bail!("No calls to execute"),
+        [call] => (call.to, call.value, call.data.clone(), Operation::Call),
+        _ => (
+            parse_address(MULTI_SEND_CALL_ONLY)?,
+            U256::ZERO,
+            encode_multisend(calls),
+            Operation::DelegateCall,
+        ),
+    };
+    Ok(SafeTx {
+        to,
+        value,
+        data,
+        operation: operation as u8,
+        safeTxGas: U256::ZERO,
+        baseGas: U256::ZERO,
+        gasPrice: U256::ZERO,
+        gasToken: Address::ZERO,
+        refundReceiver: Address::ZERO,
+        nonce,
+    })
+}
+
+/// EIP-712 hash the owners sign (Safe >= 1.3 domain: chainId + verifyingContract)
+pub fn safe_tx_hash(tx: &SafeTx, safe: Address, chain_id: u64) -> B256 {
+    let domain = Eip712Domain::new(None, None, Some(U256::from(chain_id)), Some(safe), None);
+    tx.eip712_signing_hash(&domain)
+}
+
+/// Executes transactions through a Safe owned (threshold 1) by the configured signer
+pub struct SafeExecutor {
+    safe: Address,
+    signer: PrivateKeySigner,
+    rpc_url: url::Url,
+}
+
+impl SafeExecutor {
+    pub fn new(safe: Address, signer: PrivateKeySigner, rpc_url: &str) -> Result<Self> {
+        Ok(Self {
+            safe,
+            signer,
+            rpc_url: rpc_url.parse().map_err(|e| anyhow::anyhow!("Invalid RPC URL: {}", e))?,
+        })
+    }
+
+    /// Executor for the configured proxy wallet and private key
+    pub fn from_env(env: &Env) -> Result<Self> {
+        let private_key = if env.private_key.starts_with("0x") {
+            env.private_key.clone()
+        } else {
+            format!("0x{}", env.private_key)
+        };
+        let signer = PrivateKeySigner::from_str(&private_key)
+            .map_err(|e| anyhow::anyhow!("Invalid private key: {}", e))?;
+        Self::new(parse_address(&env.proxy_wallet)?, signer, &env.rpc_url)
+    }
+
+    pub fn safe(&self) -> Address {
+        self.safe
+    }
+
+    pub fn owner(&self) -> Address {
+        self.signer.address()
+    }
+
+    fn provider(&self) -> impl Provider + Clone {
+        ProviderBuilder::new()
+            .wallet(self.signer.clone())
+            .connect_http(self.rpc_url.clone())
+    }
+
+    /// Whether the proxy is a Safe the signer can execute on alone
+    pub async fn check_can_execute(&self) -> Result<()> {
+        let provider = self.provider();
+        if provider.get_code_at(self.safe).await?.is_empty() {
+            anyhow::bail!("{} is not a contract wallet", self.safe);
+        }
+        let safe = IGnosisSafe::new(self.safe, &provider);
+        let is_owner = safe
+            .isOwner(self.owner())
+            .call()
+            .await
+            .map_err(|e| anyhow::anyhow!("{} does not look like a Gnosis Safe: {}", self.safe, e))?;
+        if !is_owner {
+            anyhow::bail!("Signer {} is not an owner of Safe {}", self.owner(), self.safe);
+        }
+        let threshold = safe.getThreshold().call().await?;
+        if threshold > U256::from(1) {
+            anyhow::bail!(
+                "Safe {} needs {} signatures; only single-owner Safes can be executed by the bot",
+                self.safe,
+                threshold
+            );
+        }
+        Ok(())
+    }
+
+    pub async fn nonce(&self) -> Result<U256> {
+        let provider = self.provider();
+        Ok(IGnosisSafe::new(self.safe, &provider).nonce().call().await?)
+    }
+
+    /// Build and sign the SafeTx for `calls` at the current nonce
+    pub async fn prepare(&self, calls: &[SafeCall]) -> Result<(SafeTx, B256, Bytes)> {
+        let chain_id = self.provider().get_chain_id().await?;
+        let tx = build_safe_tx(calls, self.nonce().await?)?;
+        let hash = safe_tx_hash(&tx, self.safe, chain_id);
+        // Owner ECDSA signature over the SafeTx hash: r | s | v (27/28)
+        let signature = self.signer.sign_hash_sync(&hash)?;
+        Ok((tx, hash, Bytes::from(signature.as_bytes().to_vec())))
+    }
+
+    /// Sign and send `execTransaction`, paid for by the signer. Returns the transaction hash.
+    pub async fn execute(&self, calls: &[SafeCall]) -> Result<B256> {
+        self.check_can_execute().await?;
+        let (tx, _, signatures) = self.prepare(calls).await?;
+
+        let provider = self.provider();
+        let receipt = IGnosisSafe::new(self.safe, &provider)
+            .execTransaction(
+                tx.to,
+                tx.value,
+                tx.data,
+                tx.operation,
+                tx.safeTxGas,
+                tx.baseGas,
+                tx.gasPrice,
+                tx.gasToken,
+                tx.refundReceiver,
+                signatures,
+            )
+            .send()
+            .await?
+            .get_receipt()
+            .await?;
+
+        if !receipt.status() {
+            anyhow::bail!("Safe transaction 0x{:x} reverted", receipt.transaction_hash);
+        }
+        Ok(receipt.transaction_hash)
+    }
+}
@@ -1,8 +1,23 @@
 use anyhow::Result;
+use alloy::primitives::U256;
+use alloy::providers::{Provider, ProviderBuilder};
 use serde::{Deserialize, Serialize};
+use std::future::Future;
+use std::sync::atomic::{AtomicBool, AtomicI64, Ordering};
+use std::time::Duration;
 use crate::config::Env;
-use crate::utils::{get_my_balance, fetch_data, logger::Logger};
+use crate::utils::allowances::{check_allowances, signer_from_env};
+use crate::utils::gnosis_safe::SafeExecutor;
+use crate::utils::http::http_client;
+use futures_util::future::join_all;
+use crate::utils::{create_clob_client, get_my_balance, logger::Logger, DataApiClient, PositionsQuery};
 use mongodb::Database;
+use polymarket_client_sdk::auth::state::Authenticated;
+use polymarket_client_sdk::auth::Normal;
+use polymarket_client_sdk::clob::Client as ClobClient;
+
+/// Limit for each of the on-chain and API checks, so a periodic run can't hang
+const CHECK_TIMEOUT: Duration = Duration::from_secs(10);
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct HealthCheckResult {
@@ -17,14 +32,40 @@ pub struct HealthChecks {
     pub rpc: CheckResult,
     pub balance: BalanceCheckResult,
     pub polymarket_api: CheckResult,
+    /// MATIC held by the signer for gas
+    pub gas: BalanceCheckResult,
+    /// USDC allowances and CTF approvals for the Polymarket exchanges
+    pub allowances: CheckResult,
+    pub clob_api_key: CheckResult,
+    /// Local clock vs. CLOB server time
+    pub clock_skew: CheckResult,
+    /// Proxy wallet is the signer's EOA or a Safe the signer controls
+    pub wallet: CheckResult,
+    /// Only present when a trade monitor runs in this process
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rtds: Option<CheckResult>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CheckResult {
-    pub status: String, // "ok" | "error"
+    pub status: String, // "ok" | "error" | "warning"
     pub message: String,
 }
 
+impl CheckResult {
+    pub fn ok(message: impl Into<String>) -> Self {
+        Self { status: "ok".to_string(), message: message.into() }
+    }
+
+    pub fn warning(message: impl Into<String>) -> Self {
+        Self { status: "warning".to_string(), message: message.into() }
+    }
+
+    pub fn error(message: impl Into<String>) -> Self {
+        Self { status: "error".to_string(), message: message.into() }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct BalanceCheckResult {
     pub status: String, // "ok" | "error" | "warning"
@@ -33,7 +74,225 @@ pub struct BalanceCheckResult {
     pub balance: Option<f64>,
 }
 
-pub async fn perform_health_check(db: &Database, env: &Env) -> Result<HealthCheckResult> {
+// RTDS stream state, recorded by the trade monitor (unix ms, 0 = never)
+static RTDS_MONITORED: AtomicBool = AtomicBool::new(false);
+static RTDS_CONNECTED_AT: AtomicI64 = AtomicI64::new(0);
+static RTDS_LAST_MESSAGE_AT: AtomicI64 = AtomicI64::new(0);
+
+/// RTDS connection as seen by the health checks
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RtdsState {
+    pub connected_at: Option<i64>,
+    pub last_message_at: Option<i64>,
+}
+
+fn non_zero(value: i64) -> Option<i64> {
+    (value != 0).then_some(value)
+}
+
+/// The trade monitor started: RTDS becomes part of the health checks
+pub fn record_rtds_monitor_started() {
+    RTDS_MONITORED.store(true, Ordering::Relaxed);
+}
+
+pub fn record_rtds_connected() {
+    RTDS_CONNECTED_AT.store(chrono::Utc::now().timestamp_millis(), Ordering::Relaxed);
+    RTDS_LAST_MESSAGE_AT.store(0, Ordering::Relaxed);
+}
+
+pub fn record_rtds_disconnected() {
+    RTDS_CONNECTED_AT.store(0, Ordering::Relaxed);
+}
+
+pub fn record_rtds_message() {
+    RTDS_LAST_MESSAGE_AT.store(chrono::Utc::now().timestamp_millis(), Ordering::Relaxed);
+}
+
+/// Current RTDS state, or `None` when no trade monitor runs in this process
+pub fn rtds_state() -> Option<RtdsState> {
+    RTDS_MONITORED.load(Ordering::Relaxed).then(|| RtdsState {
+        connected_at: non_zero(RTDS_CONNECTED_AT.load(Ordering::Relaxed)),
+        last_message_at: non_zero(RTDS_LAST_MESSAGE_AT.load(Ordering::Relaxed)),
+    })
+}
+
+/// Connected, and heard from within `max_silence_seconds`
+pub fn check_rtds(state: &RtdsState, now_ms: i64, max_silence_seconds: u64) -> CheckResult {
+    let Some(connected_at) = state.connected_at else {
+        return CheckResult::error("Not connected");
+    };
+    let connected_for = (now_ms - connected_at).max(0) / 1000;
+    let silent_for = (now_ms - state.last_message_at.unwrap_or(connected_at)).max(0) / 1000;
+    if silent_for as u64 > max_silence_seconds {
+        return CheckResult::error(format!(
+            "No message for {}s (connected {}s ago)",
+            silent_for, connected_for
+        ));
+    }
+    match state.last_message_at {
+        Some(_) => CheckResult::ok(format!("Connected for {}s, last message {}s ago", connected_for, silent_for)),
+        None => CheckResult::ok(format!("Connected for {}s, no message yet", connected_for)),
+    }
+}
+
+/// Local clock within `max_skew_seconds` of the server's
+pub fn check_clock_skew(server_time: i64, local_time: i64, max_skew_seconds: u64) -> CheckResult {
+    let skew = local_time - server_time;
+    if skew.unsigned_abs() > max_skew_seconds {
+        CheckResult::error(format!(
+            "Local clock is {}s {} CLOB server time (max {}s) - sync the system clock",
+            skew.abs(),
+            if skew > 0 { "ahead of" } else { "behind" },
+            max_skew_seconds
+        ))
+    } else {
+        CheckResult::ok(format!("{}s from CLOB server time", skew))
+    }
+}
+
+fn status_rank(status: &str) -> u8 {
+    match status {
+        "ok" => 0,
+        "warning" => 1,
+        _ => 2,
+    }
+}
+
+/// One check result for all execution accounts: the worst status, and with
+/// several accounts each message labelled with its account name
+pub fn merge_account_checks(results: Vec<(String, CheckResult)>) -> CheckResult {
+    if results.len() == 1 {
+        return results.into_iter().next().map(|(_, check)| check).unwrap();
+    }
+    let status = results
+        .iter()
+        .map(|(_, check)| check.status.as_str())
+        .max_by_key(|status| status_rank(status))
+        .unwrap_or("error")
+        .to_string();
+    let message = results
+        .iter()
+        .map(|(name, check)| format!("{}: {}", name, check.message))
+        .collect::<Vec<_>>()
+        .join("; ");
+    CheckResult { status, message }
+}
+
+async fn within<T>(check: impl Future<Output = Result<T>>) -> Result<T> {
+    tokio::time::timeout(CHECK_TIMEOUT, check)
+        .await
+        .map_err(|_| anyhow::anyhow!("timed out after {}s", CHECK_TIMEOUT.as_secs()))?
+}
+
+async fn check_gas(env: &Env) -> BalanceCheckResult {
+    let balance = within(async {
+        let signer = signer_from_env(env)?.address();
+        let provider = ProviderBuilder::new().connect_http(env.rpc_url.parse()?);
+        Ok(provider.get_balance(signer).await?)
+    })
+    .await;
+    match balance {
+        Ok(wei) => {
+            let balance = (wei / U256::from(1_000_000_000_000u64)).saturating_to::<u64>() as f64 / 1_000_000.0;
+            BalanceCheckResult {
+                // Orders are gasless; gas is only needed for approvals, redemptions and merges
+                status: if balance < env.health.min_gas_balance { "warning" } else { "ok" }.to_string(),
+                message: if balance < env.health.min_gas_balance {
+                    format!("Low gas: {:.4} MATIC (below {})", balance, env.health.min_gas_balance)
+                } else {
+                    format!("{:.4} MATIC", balance)
+                },
+                balance: Some(balance),
+            }
+        }
+        Err(e) => BalanceCheckResult {
+            status: "error".to_string(),
+            message: format!("Gas balance check failed: {}", e),
+            balance: None,
+        },
+    }
+}
+
+async fn check_allowance_status(env: &Env) -> CheckResult {
+    let report = match within(check_allowances(env)).await {
+        Ok(report) => report,
+        Err(e) => return CheckResult::error(format!("Allowance check failed: {}", e)),
+    };
+    if report.is_complete() {
+        return CheckResult::ok("USDC and CTF approved for all Polymarket contracts");
+    }
+    let missing: Vec<String> = match report.missing_calls(env) {
+        Ok(calls) => calls.into_iter().map(|(label, _)| label).collect(),
+        Err(e) => return CheckResult::error(format!("Allowance check failed: {}", e)),
+    };
+    let message = format!("Missing {} (set with: polycopy wallet approve)", missing.join(", "));
+    // Nothing is spent in preview mode
+    if env.preview_mode {
+        CheckResult::warning(message)
+    } else {
+        CheckResult::error(message)
+    }
+}
+
+async fn check_clob_api_key(env: &Env, clob_client: Option<&ClobClient<Authenticated<Normal>>>) -> CheckResult {
+    let result = within(async {
+        match clob_client {
+            Some(client) => {
+                client.api_keys().await?;
+                Ok(())
+            }
+            // Authenticating derives the key, which validates it
+            None => create_clob_client(env).await.map(|_| ()),
+        }
+    })
+    .await;
+    match result {
+        Ok(()) => CheckResult::ok("API key accepted"),
+        Err(e) => CheckResult::error(format!("API key rejected: {}", e)),
+    }
+}
+
+async fn check_server_clock(env: &Env) -> CheckResult {
+    let server_time = within(async {
+        let url = format!("{}/time", env.clob_http_url.trim_end_matches('/'));
+        let value: serde_json::Value = http_client().get(&url).timeout(CHECK_TIMEOUT).send().await?.json().await?;
+        value
+            .as_i64()
+            .or_else(|| value.as_str().and_then(|s| s.parse().ok()))
+            .ok_or_else(|| anyhow::anyhow!("unexpected /time response: {}", value))
+    })
+    .await;
+    match server_time {
+        Ok(server_time) => check_clock_skew(server_time, chrono::Utc::now().timestamp(), env.health.max_clock_skew_seconds),
+        Err(e) => CheckResult::error(format!("Server time check failed: {}", e)),
+    }
+}
+
+async fn check_wallet(env: &Env) -> CheckResult {
+    let signer = match signer_from_env(env) {
+        Ok(signer) => signer.address(),
+        Err(e) => return CheckResult::error(e.to_string()),
+    };
+    if env.proxy_wallet.trim().eq_ignore_ascii_case(&signer.to_string()) {
+        return CheckResult::ok(format!("Proxy wallet is the signer EOA {}", signer));
+    }
+    let safe = match SafeExecutor::from_env(env) {
+        Ok(safe) => safe,
+        Err(e) => return CheckResult::error(e.to_string()),
+    };
+    match within(safe.check_can_execute()).await {
+        Ok(()) => CheckResult::ok(format!("Proxy wallet is a Safe owned by signer {}", signer)),
+        Err(e) => CheckResult::error(format!("Proxy wallet doesn't match the private key: {}", e)),
+    }
+}
+
+/// Run every check. `clob_client` is the bot's authenticated client; without
+/// one (e.g. `polycopy health check`) the API key is checked by authenticating.
+pub async fn perform_health_check(
+    db: &Database,
+    env: &Env,
+    clob_client: Option<&ClobClient<Authenticated<Normal>>>,
+) -> Result<HealthCheckResult> {
     let mut checks = HealthChecks {
         database: CheckResult {
             status: "error".to_string(),
@@ -52,6 +311,16 @@ pub async fn perform_health_check(db: &Database, env: &Env) -> Result<HealthChec
             status: "error".to_string(),
             message: "Not checked".to_string(),
         },
+        gas: BalanceCheckResult {
+            status: "error".to_string(),
+            message: "Not checked".to_string(),
+            balance: None,
+        },
+        allowances: CheckResult::error("Not checked"),
+        clob_api_key: CheckResult::error("Not checked"),
+        clock_skew: CheckResult::error("Not checked"),
+        wallet: CheckResult::error("Not checked"),
+        rtds: None,
     };
 
     // Check MongoDB connection
@@ -71,7 +340,7 @@ pub async fn perform_health_check(db: &Database, env: &Env) -> Result<HealthChec
     }
 
     // Check RPC endpoint
-    let client = reqwest::Client::new();
+    let client = crate::utils::http::http_client();
     let rpc_payload = serde_json::json!({
         "jsonrpc": "2.0",
         "method": "eth_blockNumber",
@@ -161,8 +430,8 @@ pub async fn perform_health_check(db: &Database, env: &Env) -> Result<HealthChec
     }
 
     // Check Polymarket API
-    let test_url = "https://data-api.polymarket.com/positions?user=0x0000000000000000000000000000000000000000";
-    match fetch_data(test_url, env).await {
+    let test_query = PositionsQuery::new("0x0000000000000000000000000000000000000000").limit(1);
+    match DataApiClient::new(env).positions(&test_query).await {
         Ok(_) => {
             checks.polymarket_api = CheckResult {
                 status: "ok".to_string(),
@@ -177,11 +446,53 @@ pub async fn perform_health_check(db: &Database, env: &Env) -> Result<HealthChec
         }
     }
 
+    // Wallet, key and clock checks are independent of each other. Gas,
+    // allowances and the wallet are checked for every execution account.
+    let accounts: Vec<(String, Env)> = env
+        .execution_accounts()
+        .iter()
+        .map(|account| (account.name.clone(), env.for_account(account)))
+        .collect();
+    let (gas, allowances, clob_api_key, clock_skew, wallets) = tokio::join!(
+        join_all(accounts.iter().map(|(_, env)| check_gas(env))),
+        join_all(accounts.iter().map(|(_, env)| check_allowance_status(env))),
+        check_clob_api_key(env, clob_client),
+        check_server_clock(env),
+        join_all(accounts.iter().map(|(_, env)| check_wallet(env))),
+    );
+    let names = || accounts.iter().map(|(name, _)| name.clone());
+    // The lowest gas balance is the one that runs out first
+    let lowest_gas = gas.iter().map(|g| g.balance).reduce(|a, b| Some(a?.min(b?))).flatten();
+    let merged_gas = merge_account_checks(
+        names()
+            .zip(gas.into_iter().map(|g| CheckResult { status: g.status, message: g.message }))
+            .collect(),
+    );
+    checks.gas = BalanceCheckResult {
+        status: merged_gas.status,
+        message: merged_gas.message,
+        balance: lowest_gas,
+    };
+    checks.allowances = merge_account_checks(names().zip(allowances).collect());
+    checks.clob_api_key = clob_api_key;
+    checks.clock_skew = clock_skew;
+    checks.wallet = merge_account_checks(names().zip(wallets).collect());
+
+    // Check RTDS stream (bot process only)
+    checks.rtds = rtds_state().map(|state| {
+        check_rtds(&state, chrono::Utc::now().timestamp_millis(), env.health.rtds_max_silence_seconds)
+    });
+
     // Determine overall health
     let healthy = checks.database.status == "ok"
         && checks.rpc.status == "ok"
         && checks.balance.status != "error"
-        && checks.polymarket_api.status == "ok";
+        && checks.polymarket_api.status == "ok"
+        && [&checks.allowances, &checks.clob_api_key, &checks.clock_skew, &checks.wallet]
+            .into_iter()
+            .chain(checks.rtds.as_ref())
+            .all(|check| check.status != "error")
+        && checks.gas.status != "error";
 
     Ok(HealthCheckResult {
         healthy,
@@ -230,6 +541,26 @@ pub fn log_health_check(result: &HealthCheckResult) {
         },
         result.checks.polymarket_api.message
     ));
+    let checks = &result.checks;
+    Logger::info(&format!("Gas: {} {}", status_icon(&checks.gas.status), checks.gas.message));
+    for (name, check) in [
+        ("Allowances", &checks.allowances),
+        ("CLOB API key", &checks.clob_api_key),
+        ("Clock", &checks.clock_skew),
+        ("Wallet", &checks.wallet),
+    ]
+    .into_iter()
+    .chain(checks.rtds.as_ref().map(|rtds| ("RTDS", rtds)))
+    {
+        Logger::info(&format!("{}: {} {}", name, status_icon(&check.status), check.message));
+    }
     Logger::separator();
 }
 
+fn status_icon(status: &str) -> &'static str {
+    match status {
+        "ok" => "✅",
+        "warning" => "⚠️",
+        _ => "❌",
+    }
+}
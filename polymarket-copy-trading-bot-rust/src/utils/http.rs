@@ -0,0 +1,158 @@
+//! Shared outbound HTTP layer: one pooled `reqwest::Client`, a token bucket
+//! per host (HTTP_RATE_LIMIT_PER_SECOND / HTTP_RATE_LIMIT_BURST) and a
+//! short-TTL response cache (HTTP_CACHE_TTL_MS) that lets concurrent callers
+//! share one in-flight request for the same URL.
+
+use anyhow::Result;
+use reqwest::header::{HeaderMap, RETRY_AFTER};
+use reqwest::Client;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, OnceLock};
+use std::time::{Duration, Instant};
+use tokio::sync::OnceCell;
+use crate::config::Env;
+
+/// Token bucket: `burst` requests at once, refilled at `rate` per second
+#[derive(Debug, Clone)]
+pub struct TokenBucket {
+    rate: f64,
+    burst: f64,
+    tokens: f64,
+    updated: Instant,
+    paused_until: Option<Instant>,
+}
+
+impl TokenBucket {
+    pub fn new(rate: f64, burst: u32, now: Instant) -> Self {
+        let burst = burst.max(1) as f64;
+        Self { rate, burst, tokens: burst, updated: now, paused_until: None }
+    }
+
+    fn refill(&mut self, now: Instant) {
+        let elapsed = now.saturating_duration_since(self.updated).as_secs_f64();
+        self.tokens = (self.tokens + elapsed * self.rate).min(self.burst);
+        self.updated = now;
+    }
+
+    /// Take a token, or return how long to wait before one is available
+    pub fn try_acquire(&mut self, now: Instant) -> Result<(), Duration> {
+        if let Some(until) = self.paused_until {
+            if now < until {
+                return Err(until - now);
+            }
+            self.paused_until = None;
+        }
+        self.refill(now);
+        if self.tokens >= 1.0 {
+            self.tokens -= 1.0;
+            Ok(())
+        } else {
+            Err(Duration::from_secs_f64((1.0 - self.tokens) / self.rate))
+        }
+    }
+
+    /// Hold every request to this host until `until` (after a 429)
+    pub fn pause_until(&mut self, until: Instant) {
+        self.paused_until = Some(self.paused_until.map_or(until, |current| current.max(until)));
+        self.tokens = 0.0;
+    }
+}
+
+struct CacheEntry {
+    created: Instant,
+    value: Arc<OnceCell<Value>>,
+}
+
+fn buckets() -> &'static Mutex<HashMap<String, TokenBucket>> {
+    static BUCKETS: OnceLock<Mutex<HashMap<String, TokenBucket>>> = OnceLock::new();
+    BUCKETS.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+fn cache() -> &'static Mutex<HashMap<String, CacheEntry>> {
+    static CACHE: OnceLock<Mutex<HashMap<String, CacheEntry>>> = OnceLock::new();
+    CACHE.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+/// Process-wide client so connections are pooled and reused
+pub fn http_client() -> &'static Client {
+    static CLIENT: OnceLock<Client> = OnceLock::new();
+    CLIENT.get_or_init(|| {
+        Client::builder()
+            .pool_idle_timeout(Duration::from_secs(90))
+            .build()
+            .expect("Failed to build HTTP client")
+    })
+}
+
+fn host_of(url: &str) -> String {
+    url::Url::parse(url)
+        .ok()
+        .and_then(|u| u.host_str().map(|h| h.to_string()))
+        .unwrap_or_default()
+}
+
+/// Wait for a token from the bucket of `url`'s host
+pub async fn throttle(url: &str, env: &Env) {
+    let host = host_of(url);
+    loop {
+        let wait = {
+            let mut buckets = buckets().lock().unwrap();
+            let bucket = buckets
+                .entry(host.clone())
+                .or_insert_with(|| TokenBucket::new(env.http_rate_limit_per_second, env.http_rate_limit_burst, Instant::now()));
+            match bucket.try_acquire(Instant::now()) {
+                Ok(()) => return,
+                Err(wait) => wait,
+            }
+        };
+        tokio::time::sleep(wait).await;
+    }
+}
+
+/// Hold all requests to `url`'s host for `delay` (the server sent 429)
+pub fn back_off_host(url: &str, delay: Duration, env: &Env) {
+    let now = Instant::now();
+    buckets()
+        .lock()
+        .unwrap()
+        .entry(host_of(url))
+        .or_insert_with(|| TokenBucket::new(env.http_rate_limit_per_second, env.http_rate_limit_burst, now))
+        .pause_until(now + delay);
+}
+
+/// Delay requested by a `Retry-After` header (seconds form only)
+pub fn retry_after(headers: &HeaderMap) -> Option<Duration> {
+    headers
+        .get(RETRY_AFTER)?
+        .to_str()
+        .ok()?
+        .trim()
+        .parse::<f64>()
+        .ok()
+        .filter(|s| s.is_finite() && *s >= 0.0)
+        .map(Duration::from_secs_f64)
+}
+
+/// `fetch` through the response cache: callers asking for the same URL within
+/// HTTP_CACHE_TTL_MS share one request and its result. Failures aren't cached.
+pub async fn cached<F, Fut>(url: &str, env: &Env, fetch: F) -> Result<Value>
+where
+    F: FnOnce() -> Fut,
+    Fut: std::future::Future<Output = Result<Value>>,
+{
+    if env.http_cache_ttl_ms == 0 {
+        return fetch().await;
+    }
+    let ttl = Duration::from_millis(env.http_cache_ttl_ms);
+    let cell = {
+        let mut cache = cache().lock().unwrap();
+        cache.retain(|_, entry| entry.created.elapsed() < ttl);
+        cache
+            .entry(url.to_string())
+            .or_insert_with(|| CacheEntry { created: Instant::now(), value: Arc::new(OnceCell::new()) })
+            .value
+            .clone()
+    };
+    Ok(cell.get_or_try_init(fetch).await?.clone())
+}
@@ -6,7 +6,21 @@ use chrono::Local;
 
 pub struct Logger;
 
+tokio::task_local! {
+    /// Execution account the current task works for, shown on its log lines
+    static ACCOUNT: String;
+}
+
 impl Logger {
+    /// Run `future` with its log lines tagged with the account name
+    pub async fn account_scope<F: std::future::Future>(account: &str, future: F) -> F::Output {
+        ACCOUNT.scope(account.to_string(), future).await
+    }
+
+    fn account_tag() -> String {
+        ACCOUNT.try_with(|account| format!("[{}] ", account)).unwrap_or_default()
+    }
+
     fn get_logs_dir() -> PathBuf {
         std::env::current_dir()
             .unwrap_or_else(|_| PathBuf::from("."))
@@ -74,6 +88,7 @@ impl Logger {
     }
 
     pub fn header(title: &str) {
+        let title = &format!("{}{}", Self::account_tag(), title);
         println!("\n{}", "━".repeat(70).cyan());
         println!("{}", format!("  {}", title).cyan().bold());
         println!("{}\n", "━".repeat(70).cyan());
@@ -81,21 +96,25 @@ impl Logger {
     }
 
     pub fn info(message: &str) {
+        let message = &format!("{}{}", Self::account_tag(), message);
         println!("{} {}", "ℹ".blue(), message);
         Self::write_to_file(&format!("INFO: {}", message));
     }
 
     pub fn success(message: &str) {
+        let message = &format!("{}{}", Self::account_tag(), message);
         println!("{} {}", "✓".green(), message);
         Self::write_to_file(&format!("SUCCESS: {}", message));
     }
 
     pub fn warning(message: &str) {
+        let message = &format!("{}{}", Self::account_tag(), message);
         println!("{} {}", "⚠".yellow(), message);
         Self::write_to_file(&format!("WARNING: {}", message));
     }
 
     pub fn error(message: &str) {
+        let message = &format!("{}{}", Self::account_tag(), message);
         println!("{} {}", "✗".red(), message);
         Self::write_to_file(&format!("ERROR: {}", message));
     }
@@ -177,6 +196,7 @@ impl Logger {
     }
 
     pub fn order_result(success: bool, message: &str) {
+        let message = &format!("{}{}", Self::account_tag(), message);
         if success {
             println!("{} {}", "✓".green(), format!("Order executed: {}", message).green().bold());
             Self::write_to_file(&format!("ORDER SUCCESS: {}", message));
@@ -503,25 +523,15 @@ impl PositionDisplay {
         }
     }
 
-    /// Create from JSON value (API response)
-    pub fn from_json_value(value: &serde_json::Value) -> Self {
+    /// Create from a Data API position
+    pub fn from_position(pos: &crate::utils::data_api::Position) -> Self {
         Self {
-            outcome: value.get("outcome").and_then(|v| v.as_str()).map(|s| s.to_string()),
-            title: value
-                .get("title")
-                .and_then(|v| v.as_str())
-                .map(|s| s.to_string())
-                .unwrap_or_default(),
-            current_value: value
-                .get("currentValue")
-                .and_then(|v| v.as_f64())
-                .unwrap_or(0.0),
-            percent_pnl: value
-                .get("percentPnl")
-                .and_then(|v| v.as_f64())
-                .unwrap_or(0.0),
-            avg_price: value.get("avgPrice").and_then(|v| v.as_f64()),
-            cur_price: value.get("curPrice").and_then(|v| v.as_f64()),
+            outcome: pos.outcome.clone(),
+            title: pos.title.clone().unwrap_or_default(),
+            current_value: pos.current_value,
+            percent_pnl: pos.percent_pnl,
+            avg_price: Some(pos.avg_price),
+            cur_price: Some(pos.cur_price),
         }
     }
 }
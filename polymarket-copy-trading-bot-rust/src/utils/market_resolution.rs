@@ -0,0 +1,182 @@
+//! Market resolution lookups for simulations.
+//! Resolution outcomes come from the Gamma API and are cached on disk so
+//! repeated simulations over the same history don't refetch settled markets.
+//! Unresolved positions are marked with the CLOB price history instead.
+
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use crate::config::Env;
+use crate::utils::fetch_data;
+use crate::utils::gamma_api::{fetch_markets_by_condition_ids, GammaMarket};
+
+const RESOLUTION_CACHE_FILE: &str = "trader_data_cache/market_resolutions.json";
+const RESOLVED_THRESHOLD: f64 = 0.99;
+const UNRESOLVED_TTL_SECS: i64 = 60 * 60; // Re-check open markets hourly
+const PRICE_HISTORY_LOOKBACK_SECS: i64 = 7 * 24 * 60 * 60;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MarketResolution {
+    pub condition_id: String,
+    pub question: String,
+    pub closed: bool,
+    pub resolved: bool,
+    pub outcomes: Vec<String>,
+    pub outcome_prices: Vec<f64>,
+    pub token_ids: Vec<String>,
+    pub end_date: Option<String>,
+    pub fetched_at: i64,
+}
+
+impl MarketResolution {
+    pub fn from_gamma(market: &GammaMarket) -> Self {
+        let outcome_prices = market.outcome_price_values();
+        // A market is settled once it's closed and one outcome pays out in full
+        let resolved = market.closed && outcome_prices.iter().any(|&p| p >= RESOLVED_THRESHOLD);
+
+        Self {
+            condition_id: market.condition_id.to_lowercase(),
+            question: market.question.clone(),
+            closed: market.closed,
+            resolved,
+            outcomes: market.outcome_names(),
+            outcome_prices,
+            token_ids: market.token_ids(),
+            end_date: market.end_date.clone(),
+            fetched_at: chrono::Utc::now().timestamp(),
+        }
+    }
+
+    /// Payout per share (1.0 or 0.0) for a resolved market.
+    /// The outcome is matched by token ID first, then by outcome name.
+    pub fn settlement_price(&self, asset: &str, outcome: Option<&str>) -> Option<f64> {
+        if !self.resolved {
+            return None;
+        }
+
+        let index = self
+            .token_ids
+            .iter()
+            .position(|t| t == asset)
+            .or_else(|| {
+                let outcome = outcome?;
+                self.outcomes.iter().position(|o| o.eq_ignore_ascii_case(outcome))
+            })?;
+
+        let price = *self.outcome_prices.get(index)?;
+        Some(if price >= RESOLVED_THRESHOLD { 1.0 } else { 0.0 })
+    }
+
+    fn is_stale(&self, now: i64) -> bool {
+        !self.resolved && now - self.fetched_at > UNRESOLVED_TTL_SECS
+    }
+}
+
+/// On-disk cache of market resolutions keyed by condition ID
+pub struct ResolutionCache {
+    path: PathBuf,
+    entries: HashMap<String, MarketResolution>,
+}
+
+impl ResolutionCache {
+    pub fn load() -> Self {
+        Self::load_from(Path::new(RESOLUTION_CACHE_FILE))
+    }
+
+    pub fn load_from(path: &Path) -> Self {
+        let entries = fs::read_to_string(path)
+            .ok()
+            .and_then(|content| serde_json::from_str::<Vec<MarketResolution>>(&content).ok())
+            .unwrap_or_default()
+            .into_iter()
+            .map(|r| (r.condition_id.clone(), r))
+            .collect();
+
+        Self {
+            path: path.to_path_buf(),
+            entries,
+        }
+    }
+
+    pub fn get(&self, condition_id: &str) -> Option<&MarketResolution> {
+        self.entries.get(&condition_id.to_lowercase())
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Fetch resolutions for any condition IDs that are missing or stale.
+    /// Returns the number of markets fetched from the API.
+    pub async fn refresh(&mut self, condition_ids: &[String], env: &Env) -> Result<usize> {
+        let now = chrono::Utc::now().timestamp();
+        let mut to_fetch: Vec<String> = condition_ids
+            .iter()
+            .map(|id| id.to_lowercase())
+            .filter(|id| !id.is_empty())
+            .filter(|id| self.entries.get(id).is_none_or(|r| r.is_stale(now)))
+            .collect();
+        to_fetch.sort();
+        to_fetch.dedup();
+
+        if to_fetch.is_empty() {
+            return Ok(0);
+        }
+
+        let markets = fetch_markets_by_condition_ids(&to_fetch, env).await?;
+        for market in &markets {
+            let resolution = MarketResolution::from_gamma(market);
+            self.entries.insert(resolution.condition_id.clone(), resolution);
+        }
+
+        Ok(markets.len())
+    }
+
+    pub fn save(&self) -> Result<()> {
+        if let Some(parent) = self.path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let mut entries: Vec<&MarketResolution> = self.entries.values().collect();
+        entries.sort_by(|a, b| a.condition_id.cmp(&b.condition_id));
+        fs::write(&self.path, serde_json::to_string_pretty(&entries)?)?;
+        Ok(())
+    }
+}
+
+/// Last traded price of a token at or before `timestamp` (unix seconds),
+/// from the CLOB `/prices-history` endpoint.
+pub async fn fetch_historical_price(asset: &str, timestamp: i64, env: &Env) -> Result<Option<f64>> {
+    let url = format!(
+        "{}/prices-history?market={}&startTs={}&endTs={}&fidelity=60",
+        env.clob_http_url.trim_end_matches('/'),
+        asset,
+        timestamp - PRICE_HISTORY_LOOKBACK_SECS,
+        timestamp
+    );
+    let json = fetch_data(&url, env).await?;
+
+    let price = json
+        .get("history")
+        .and_then(|h| h.as_array())
+        .and_then(|history| {
+            history
+                .iter()
+                .filter_map(|point| {
+                    let t = point.get("t").and_then(|v| v.as_i64())?;
+                    let p = point.get("p").and_then(|v| v.as_f64())?;
+                    Some((t, p))
+                })
+                .filter(|(t, _)| *t <= timestamp)
+                .max_by_key(|(t, _)| *t)
+                .map(|(_, p)| p)
+        });
+
+    Ok(price)
+}
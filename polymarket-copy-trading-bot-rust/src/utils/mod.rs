@@ -1,14 +1,37 @@
 pub mod logger;
+pub mod http;
 pub mod fetch_data;
+#[allow(dead_code)] // Holders, trades and leaderboard are used by trader discovery commands
+pub mod data_api;
 pub mod get_my_balance;
 pub mod health_check;
+pub mod rtds_recording;
 pub mod create_clob_client;
 pub mod post_order;
+pub mod ctf_balance;
+pub mod allowances;
 pub mod spinner;
+#[allow(dead_code)] // Used by market filters and simulation binaries
+pub mod gamma_api;
+#[allow(dead_code)] // Used by simulation binaries
+pub mod market_resolution;
+#[allow(dead_code)] // Used by simulation binaries
+pub mod risk_metrics;
+#[allow(dead_code)] // Used by simulation binaries
+pub mod copy_simulator;
+#[allow(dead_code)] // Used by simulation binaries
+pub mod execution_model;
+#[allow(dead_code)] // Used by simulation binaries
+pub mod strategy_sweep;
+#[allow(dead_code)] // Used by trader discovery binaries
+pub mod trader_scoring;
+#[allow(dead_code)] // Used by wallet binaries and the activity mirror
+pub mod gnosis_safe;
 
 // Re-export commonly used items
 pub use logger::Logger;
 pub use fetch_data::fetch_data;
+pub use data_api::{DataApiClient, PositionsQuery, ActivityQuery};
 pub use get_my_balance::get_my_balance;
 pub use health_check::{perform_health_check, log_health_check};
 pub use create_clob_client::create_clob_client;
@@ -8,7 +8,14 @@ use polymarket_client_sdk::auth::Normal;
 use alloy::signers::local::PrivateKeySigner;
 
 use crate::interfaces::{UserActivity, UserPosition};
-use crate::config::{CopyStrategyConfig, calculate_order_size, get_trade_multiplier};
+use crate::config::{
+    CopyStrategyConfig, calculate_order_size, get_buy_limit_price, get_sell_limit_price, get_trade_multiplier,
+};
+use crate::services::circuit_breaker::buying_halted;
+use crate::services::exposure_limits::cap_by_exposure_limits;
+use crate::services::limit_orders::place_limit_order;
+use crate::services::paper_trading::execute_paper_order;
+use crate::services::pnl_ledger::{record_ledger_entry, LedgerEntry, KIND_BUY, KIND_SELL};
 use crate::utils::{logger::Logger, fetch_data};
 use crate::config::Env;
 use mongodb::Database;
@@ -67,7 +74,7 @@ fn is_insufficient_balance_or_allowance_error(message: Option<&str>) -> bool {
 }
 
 /// Get order book from CLOB API
-async fn get_order_book(env: &Env, asset: &str) -> Result<serde_json::Value> {
+pub async fn get_order_book(env: &Env, asset: &str) -> Result<serde_json::Value> {
     let book_url = format!(
         "{}/book?token_id={}",
         env.clob_http_url.trim_end_matches('/'),
@@ -76,6 +83,24 @@ async fn get_order_book(env: &Env, asset: &str) -> Result<serde_json::Value> {
     fetch_data(&book_url, env).await
 }
 
+/// (price, size) levels from one side ("bids" / "asks") of an order book
+pub fn parse_book_levels(order_book: &serde_json::Value, side: &str) -> Vec<(f64, f64)> {
+    order_book
+        .get(side)
+        .and_then(|levels| levels.as_array())
+        .map(|levels| {
+            levels
+                .iter()
+                .filter_map(|l| {
+                    let price: f64 = l.get("price").and_then(|p| p.as_str()).and_then(|s| s.parse().ok())?;
+                    let size: f64 = l.get("size").and_then(|s| s.as_str()).and_then(|s| s.parse().ok())?;
+                    Some((price, size))
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
 /// Post order execution
 pub async fn post_order(
     _clob_client: &ClobClient<Authenticated<Normal>>,
@@ -92,11 +117,15 @@ pub async fn post_order(
     _signer: &PrivateKeySigner,
 ) -> Result<()> {
     // Preview mode: simulate execution without actually placing orders
-    Logger::info("🔍 PREVIEW MODE: Simulating order execution (no actual trades will be placed)");
+    if env.paper_trading.enabled {
+        Logger::info("📝 PAPER TRADING: Filling against live order book depth with the virtual balance");
+    } else {
+        Logger::info("🔍 PREVIEW MODE: Simulating order execution (no actual trades will be placed)");
+    }
 
     match condition {
         "merge" => {
-            execute_merge_strategy(_clob_client, trade, my_position, user_address, env, _signer).await?;
+            execute_merge_strategy(_clob_client, trade, my_position, user_address, env, _db, _signer).await?;
         }
         "buy" => {
             execute_buy_strategy(
@@ -108,6 +137,7 @@ pub async fn post_order(
                 user_address,
                 config,
                 env,
+                _db,
                 _signer,
             )
             .await?;
@@ -121,6 +151,7 @@ pub async fn post_order(
                 user_address,
                 config,
                 env,
+                _db,
                 _signer,
             )
             .await?;
@@ -137,8 +168,9 @@ async fn execute_merge_strategy(
     _clob_client: &ClobClient<Authenticated<Normal>>,
     trade: &UserActivity,
     my_position: Option<&UserPosition>,
-    _user_address: &str,
+    user_address: &str,
     env: &Env,
+    db: &Database,
     _signer: &PrivateKeySigner,
 ) -> Result<()> {
     Logger::info("Executing MERGE strategy...");
@@ -168,6 +200,10 @@ async fn execute_merge_strategy(
         return Ok(());
     }
 
+    if env.paper_trading.enabled {
+        return execute_paper_order(trade, KIND_SELL, remaining, None, user_address, env, db).await;
+    }
+
     let mut retry = 0u32;
 
     while remaining > 0.0 && retry < env.retry_limit {
@@ -214,6 +250,8 @@ async fn execute_merge_strategy(
             &format!("[PREVIEW] Would sell {:.2} tokens at ${} (${:.2} total)", 
                 sell_amount, best_price, sell_amount * best_price),
         );
+        let entry = LedgerEntry::copy_fill(trade, KIND_SELL, user_address, best_price, sell_amount, env.preview_mode);
+        record_ledger_entry(db, &entry).await;
         remaining -= sell_amount;
         retry = 0;
         continue;
@@ -229,13 +267,20 @@ async fn execute_buy_strategy(
     my_position: Option<&UserPosition>,
     my_balance: f64,
     _user_balance: f64,
-    _user_address: &str,
+    user_address: &str,
     config: &CopyStrategyConfig,
     env: &Env,
+    db: &Database,
     _signer: &PrivateKeySigner,
 ) -> Result<()> {
     Logger::info("Executing BUY strategy...");
 
+    if let Some(reason) = buying_halted(db).await? {
+        Logger::warning(&format!("⛔ Circuit breaker tripped ({}) - skipping buy", reason));
+        Logger::warning("Resume with: polycopy health breaker resume");
+        return Ok(());
+    }
+
     Logger::info(&format!("Your balance: ${:.2}", my_balance));
     Logger::info(&format!("Trader bought: ${:.2}", trade.usdc_size));
 
@@ -263,7 +308,54 @@ async fn execute_buy_strategy(
         return Ok(());
     }
 
-    let mut remaining = order_calc.final_amount;
+    let mut final_amount = order_calc.final_amount;
+
+    // Portfolio-level caps (total / event / category / trader / open positions)
+    if env.exposure_limits.is_enabled() {
+        final_amount = cap_by_exposure_limits(trade, final_amount, user_address, env, db).await?;
+        if final_amount < config.min_order_size_usd {
+            Logger::warning("❌ Skipping: exposure limits leave no room for this buy");
+            return Ok(());
+        }
+    }
+
+    if env.limit_orders.is_enabled() {
+        let limit = get_buy_limit_price(config, trade.price);
+        return place_limit_order(trade, "BUY", final_amount, limit, user_address, env, db).await;
+    }
+
+    let mut remaining = final_amount;
+
+    // Slippage guard: only take asks within the limit relative to the trader's price
+    let limit_price = get_buy_limit_price(config, trade.price);
+    if let Some(limit) = limit_price {
+        let order_book = get_order_book(env, &trade.asset).await?;
+        let in_range_usd: f64 = parse_book_levels(&order_book, "asks")
+            .iter()
+            .filter(|(price, _)| *price <= limit)
+            .map(|(price, size)| price * size)
+            .sum();
+
+        if in_range_usd < config.min_order_size_usd {
+            Logger::warning(&format!(
+                "❌ Skipping: no ask depth at or below ${:.4} (trader paid ${:.4}, max slippage reached)",
+                limit, trade.price
+            ));
+            return Ok(());
+        }
+        if in_range_usd < remaining {
+            Logger::warning(&format!(
+                "⚠️  Only ${:.2} of asks within slippage limit ${:.4} - partially filling (${:.2} requested)",
+                in_range_usd, limit, remaining
+            ));
+            remaining = in_range_usd;
+        }
+    }
+
+    if env.paper_trading.enabled {
+        return execute_paper_order(trade, KIND_BUY, remaining, limit_price, user_address, env, db).await;
+    }
+
     let mut available_balance = my_balance;
     let mut retry = 0u32;
     let mut total_bought_tokens = 0.0;
@@ -300,6 +392,14 @@ async fn execute_buy_strategy(
 
         Logger::info(&format!("Best ask: {} @ ${}", best_size, best_price));
 
+        if let Some(limit) = limit_price.filter(|&limit| best_price > limit) {
+            Logger::warning(&format!(
+                "Best ask ${} above slippage limit ${:.4} (trader paid ${:.4}) - stopping",
+                best_price, limit, trade.price
+            ));
+            break;
+        }
+
         // Check if remaining amount is below minimum
         if remaining < config.min_order_size_usd {
             Logger::info(&format!(
@@ -337,6 +437,8 @@ async fn execute_buy_strategy(
                 order_size, best_price, tokens_bought
             ),
         );
+        let entry = LedgerEntry::copy_fill(trade, KIND_BUY, user_address, best_price, tokens_bought, env.preview_mode);
+        record_ledger_entry(db, &entry).await;
         remaining -= order_size;
         available_balance -= order_size;
         retry = 0;
@@ -359,9 +461,10 @@ async fn execute_sell_strategy(
     trade: &UserActivity,
     my_position: Option<&UserPosition>,
     user_position: Option<&UserPosition>,
-    _user_address: &str,
+    user_address: &str,
     config: &CopyStrategyConfig,
     env: &Env,
+    db: &Database,
     _signer: &PrivateKeySigner,
 ) -> Result<()> {
     Logger::info("Executing SELL strategy...");
@@ -438,6 +541,41 @@ async fn execute_sell_strategy(
         remaining
     };
 
+    if env.limit_orders.is_enabled() {
+        let limit = get_sell_limit_price(config, trade.price);
+        return place_limit_order(trade, "SELL", remaining, limit, user_address, env, db).await;
+    }
+
+    // Slippage guard: only hit bids within the limit relative to the trader's sell price
+    let limit_price = get_sell_limit_price(config, trade.price);
+    if let Some(limit) = limit_price {
+        let order_book = get_order_book(env, &trade.asset).await?;
+        let in_range_tokens: f64 = parse_book_levels(&order_book, "bids")
+            .iter()
+            .filter(|(price, _)| *price >= limit)
+            .map(|(_, size)| size)
+            .sum();
+
+        if in_range_tokens < MIN_ORDER_SIZE_TOKENS {
+            Logger::warning(&format!(
+                "❌ Skipping: no bid depth at or above ${:.4} (trader sold at ${:.4}, max slippage reached)",
+                limit, trade.price
+            ));
+            return Ok(());
+        }
+        if in_range_tokens < remaining {
+            Logger::warning(&format!(
+                "⚠️  Only {:.2} tokens of bids within slippage limit ${:.4} - partially selling ({:.2} requested)",
+                in_range_tokens, limit, remaining
+            ));
+            remaining = in_range_tokens;
+        }
+    }
+
+    if env.paper_trading.enabled {
+        return execute_paper_order(trade, KIND_SELL, remaining, limit_price, user_address, env, db).await;
+    }
+
     let mut retry = 0u32;
 
     while remaining > 0.0 && retry < env.retry_limit {
@@ -472,6 +610,14 @@ async fn execute_sell_strategy(
 
         Logger::info(&format!("Best bid: {} @ ${}", best_size, best_price));
 
+        if let Some(limit) = limit_price.filter(|&limit| best_price < limit) {
+            Logger::warning(&format!(
+                "Best bid ${} below slippage limit ${:.4} (trader sold at ${:.4}) - stopping",
+                best_price, limit, trade.price
+            ));
+            break;
+        }
+
         // Check if remaining amount is below minimum
         if remaining < MIN_ORDER_SIZE_TOKENS {
             Logger::info("Remaining amount below minimum - completing trade");
@@ -494,6 +640,8 @@ async fn execute_sell_strategy(
                 sell_amount, best_price, sell_amount * best_price
             ),
         );
+        let entry = LedgerEntry::copy_fill(trade, KIND_SELL, user_address, best_price, sell_amount, env.preview_mode);
+        record_ledger_entry(db, &entry).await;
         remaining -= sell_amount;
         retry = 0;
         continue;
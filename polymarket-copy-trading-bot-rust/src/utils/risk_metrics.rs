@@ -0,0 +1,78 @@
+//! Return/risk statistics shared by simulations and trader analysis.
+
+use std::collections::BTreeMap;
+
+const DAY_SECS: i64 = 24 * 60 * 60;
+const TRADING_DAYS_PER_YEAR: f64 = 365.0; // Prediction markets trade every day
+
+/// Largest peak-to-trough decline of an equity series, in percent of the peak
+pub fn max_drawdown_percent(equity: &[f64]) -> f64 {
+    let mut peak = f64::MIN;
+    let mut max_drawdown: f64 = 0.0;
+
+    for &value in equity {
+        if value > peak {
+            peak = value;
+        }
+        if peak > 0.0 {
+            max_drawdown = max_drawdown.max((peak - value) / peak * 100.0);
+        }
+    }
+
+    max_drawdown
+}
+
+/// Collapse (timestamp, equity) points into the closing equity of each UTC day
+pub fn daily_closes(points: &[(i64, f64)]) -> Vec<f64> {
+    let mut by_day: BTreeMap<i64, f64> = BTreeMap::new();
+    for &(timestamp, value) in points {
+        // Accept both seconds and milliseconds
+        let secs = if timestamp > 1_000_000_000_000 { timestamp / 1000 } else { timestamp };
+        by_day.insert(secs.div_euclid(DAY_SECS), value);
+    }
+    by_day.into_values().collect()
+}
+
+/// Day-over-day simple returns of a series of daily closes
+pub fn daily_returns(closes: &[f64]) -> Vec<f64> {
+    closes
+        .windows(2)
+        .filter(|w| w[0] > 0.0)
+        .map(|w| (w[1] - w[0]) / w[0])
+        .collect()
+}
+
+fn mean(values: &[f64]) -> f64 {
+    if values.is_empty() {
+        return 0.0;
+    }
+    values.iter().sum::<f64>() / values.len() as f64
+}
+
+/// Annualized Sharpe ratio of daily returns (risk-free rate assumed zero)
+pub fn sharpe_ratio(returns: &[f64]) -> f64 {
+    if returns.len() < 2 {
+        return 0.0;
+    }
+    let avg = mean(returns);
+    let variance = returns.iter().map(|r| (r - avg).powi(2)).sum::<f64>() / (returns.len() - 1) as f64;
+    let std_dev = variance.sqrt();
+    if std_dev == 0.0 {
+        return 0.0;
+    }
+    avg / std_dev * TRADING_DAYS_PER_YEAR.sqrt()
+}
+
+/// Annualized Sortino ratio of daily returns (only downside deviation penalized)
+pub fn sortino_ratio(returns: &[f64]) -> f64 {
+    if returns.len() < 2 {
+        return 0.0;
+    }
+    let avg = mean(returns);
+    let downside = returns.iter().map(|r| r.min(0.0).powi(2)).sum::<f64>() / returns.len() as f64;
+    let downside_dev = downside.sqrt();
+    if downside_dev == 0.0 {
+        return 0.0;
+    }
+    avg / downside_dev * TRADING_DAYS_PER_YEAR.sqrt()
+}
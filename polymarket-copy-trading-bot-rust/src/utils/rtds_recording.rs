@@ -0,0 +1,106 @@
+//! RTDS session recordings: one JSON object per line holding the receive time
+//! and the raw text frame, so a session can be replayed through the trade monitor.
+
+use anyhow::{anyhow, Context, Result};
+use serde::{Deserialize, Serialize};
+use std::fs::{self, File, OpenOptions};
+use std::io::{BufWriter, Write};
+use std::path::Path;
+use std::sync::mpsc;
+use std::thread::JoinHandle;
+use std::time::Duration;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RecordedFrame {
+    /// Milliseconds since the Unix epoch when the frame arrived
+    pub received_at: i64,
+    /// Text frame exactly as received
+    pub frame: String,
+}
+
+/// Appends frames to a recording. Frames are queued to a writer thread, so
+/// recording never blocks the RTDS loop on disk; the writer flushes whenever
+/// the queue is empty. Dropping the recorder writes out what is still queued.
+pub struct RtdsRecorder {
+    frames: Option<mpsc::Sender<RecordedFrame>>,
+    writer: Option<JoinHandle<Result<()>>>,
+}
+
+impl RtdsRecorder {
+    pub fn open(path: &Path) -> Result<Self> {
+        if let Some(dir) = path.parent().filter(|d| !d.as_os_str().is_empty()) {
+            fs::create_dir_all(dir).with_context(|| format!("creating {}", dir.display()))?;
+        }
+        let file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)
+            .with_context(|| format!("opening RTDS recording {}", path.display()))?;
+        let (frames, queue) = mpsc::channel();
+        let writer = std::thread::Builder::new()
+            .name("rtds-recorder".to_string())
+            .spawn(move || write_frames(file, queue))?;
+        Ok(Self { frames: Some(frames), writer: Some(writer) })
+    }
+
+    /// Queue a frame; fails once the writer has stopped on a write error
+    pub fn record(&mut self, frame: &str, received_at: i64) -> Result<()> {
+        let frame = RecordedFrame { received_at, frame: frame.to_string() };
+        if self.frames.as_ref().is_some_and(|frames| frames.send(frame).is_ok()) {
+            return Ok(());
+        }
+        Err(self.finish().err().unwrap_or_else(|| anyhow!("RTDS recording writer stopped")))
+    }
+
+    /// Write out the queued frames and close the file
+    pub fn finish(&mut self) -> Result<()> {
+        self.frames = None;
+        match self.writer.take() {
+            Some(writer) => writer.join().map_err(|_| anyhow!("RTDS recording writer panicked"))?,
+            None => Ok(()),
+        }
+    }
+}
+
+impl Drop for RtdsRecorder {
+    fn drop(&mut self) {
+        let _ = self.finish();
+    }
+}
+
+fn write_frames(file: File, queue: mpsc::Receiver<RecordedFrame>) -> Result<()> {
+    let mut out = BufWriter::new(file);
+    while let Ok(first) = queue.recv() {
+        for frame in std::iter::once(first).chain(queue.try_iter()) {
+            writeln!(out, "{}", serde_json::to_string(&frame)?)?;
+        }
+        out.flush()?;
+    }
+    Ok(())
+}
+
+/// Frames of a recording in file order (blank lines are skipped)
+pub fn read_recording(path: &Path) -> Result<Vec<RecordedFrame>> {
+    let content = fs::read_to_string(path).with_context(|| format!("reading RTDS recording {}", path.display()))?;
+    content
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(index, line)| {
+            serde_json::from_str(line).with_context(|| format!("{} line {}: not a recorded frame", path.display(), index + 1))
+        })
+        .collect()
+}
+
+/// Wait before replaying `next`: the recorded gap since `previous` divided by
+/// `speed` (no wait for the first frame, at speed 0 or when time went backwards)
+pub fn replay_delay(previous: Option<&RecordedFrame>, next: &RecordedFrame, speed: f64) -> Duration {
+    let Some(previous) = previous else {
+        return Duration::ZERO;
+    };
+    let gap_ms = (next.received_at - previous.received_at).max(0) as f64;
+    if speed <= 0.0 {
+        return Duration::ZERO;
+    }
+    Duration::from_secs_f64(gap_ms / speed / 1000.0)
+}
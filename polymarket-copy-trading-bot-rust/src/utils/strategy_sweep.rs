@@ -0,0 +1,316 @@
+//! Parameter sweep over copy strategy configurations.
+//! Generates configurations by grid or random search, simulates each one
+//! against every trader's cached history in parallel and ranks the results.
+
+use anyhow::Result;
+use futures_util::stream::{self, StreamExt};
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::SeedableRng;
+use serde::Serialize;
+use std::sync::Arc;
+use crate::config::{CopyStrategy, CopyStrategyConfig};
+use crate::utils::copy_simulator::{simulate, SimOutcome, SimParams, SimTrade};
+use crate::utils::execution_model::{ExecutionModel, OrderBookCache};
+use crate::utils::market_resolution::ResolutionCache;
+
+/// Values to explore for each parameter
+#[derive(Debug, Clone)]
+pub struct SweepSpace {
+    pub strategies: Vec<CopyStrategy>,
+    pub copy_sizes: Vec<f64>,
+    pub multipliers: Vec<f64>,
+    pub min_order_sizes: Vec<f64>,
+    pub max_order_sizes: Vec<f64>,
+    pub take_profits: Vec<Option<f64>>,
+    pub stop_losses: Vec<Option<f64>>,
+}
+
+impl Default for SweepSpace {
+    fn default() -> Self {
+        Self {
+            strategies: vec![CopyStrategy::Percentage, CopyStrategy::Fixed, CopyStrategy::Adaptive],
+            copy_sizes: vec![5.0, 10.0, 20.0],
+            multipliers: vec![0.5, 1.0, 2.0],
+            min_order_sizes: vec![1.0, 5.0],
+            max_order_sizes: vec![50.0, 100.0],
+            take_profits: vec![None, Some(25.0), Some(50.0)],
+            stop_losses: vec![None, Some(20.0), Some(40.0)],
+        }
+    }
+}
+
+impl SweepSpace {
+    pub fn grid_size(&self) -> usize {
+        self.strategies.len()
+            * self.copy_sizes.len()
+            * self.multipliers.len()
+            * self.min_order_sizes.len()
+            * self.max_order_sizes.len()
+            * self.take_profits.len()
+            * self.stop_losses.len()
+    }
+
+    /// Every combination, skipping ones where min order size exceeds max
+    pub fn grid(&self) -> Vec<SweepConfig> {
+        let mut configs = Vec::new();
+        for &strategy in &self.strategies {
+            for &copy_size in &self.copy_sizes {
+                for &multiplier in &self.multipliers {
+                    for &min_order_size in &self.min_order_sizes {
+                        for &max_order_size in &self.max_order_sizes {
+                            if min_order_size > max_order_size {
+                                continue;
+                            }
+                            for &take_profit in &self.take_profits {
+                                for &stop_loss in &self.stop_losses {
+                                    configs.push(SweepConfig {
+                                        strategy,
+                                        copy_size,
+                                        multiplier,
+                                        min_order_size,
+                                        max_order_size,
+                                        take_profit,
+                                        stop_loss,
+                                    });
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+        configs
+    }
+
+    /// `samples` distinct random combinations (fewer if the grid is smaller)
+    pub fn random(&self, samples: usize, seed: u64) -> Vec<SweepConfig> {
+        let mut grid = self.grid();
+        let mut rng = StdRng::seed_from_u64(seed);
+        grid.shuffle(&mut rng);
+        grid.truncate(samples);
+        grid
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SweepConfig {
+    pub strategy: CopyStrategy,
+    pub copy_size: f64,
+    pub multiplier: f64,
+    pub min_order_size: f64,
+    pub max_order_size: f64,
+    pub take_profit: Option<f64>,
+    pub stop_loss: Option<f64>,
+}
+
+impl SweepConfig {
+    /// Short stable identifier, e.g. `PCT10_m2_min1_max100_tp25_slnone`
+    pub fn id(&self) -> String {
+        let strategy = match self.strategy {
+            CopyStrategy::Percentage => "PCT",
+            CopyStrategy::Fixed => "FIX",
+            CopyStrategy::Adaptive => "ADP",
+        };
+        let opt = |v: Option<f64>| v.map_or("none".to_string(), |v| v.to_string());
+        format!(
+            "{}{}_m{}_min{}_max{}_tp{}_sl{}",
+            strategy,
+            self.copy_size,
+            self.multiplier,
+            self.min_order_size,
+            self.max_order_size,
+            opt(self.take_profit),
+            opt(self.stop_loss)
+        )
+        .replace('.', "p")
+    }
+
+    pub fn to_params(&self, starting_capital: f64, execution: Option<ExecutionModel>) -> SimParams {
+        let mut strategy = CopyStrategyConfig {
+            strategy: self.strategy,
+            copy_size: self.copy_size,
+            max_order_size_usd: self.max_order_size,
+            min_order_size_usd: self.min_order_size,
+            ..Default::default()
+        };
+        if self.multiplier != 1.0 {
+            strategy.trade_multiplier = Some(self.multiplier);
+        }
+        if self.strategy == CopyStrategy::Adaptive {
+            // Scale between half and double the base percentage
+            strategy.adaptive_min_percent = Some(self.copy_size / 2.0);
+            strategy.adaptive_max_percent = Some(self.copy_size * 2.0);
+            strategy.adaptive_threshold = Some(500.0);
+        }
+
+        SimParams {
+            strategy,
+            take_profit_percent: self.take_profit,
+            stop_loss_percent: self.stop_loss,
+            starting_capital,
+            execution,
+        }
+    }
+}
+
+/// Metric used to order configurations
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RankBy {
+    Roi,
+    Drawdown,
+    Sharpe,
+}
+
+impl RankBy {
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.to_lowercase().as_str() {
+            "roi" => Some(RankBy::Roi),
+            "drawdown" | "dd" => Some(RankBy::Drawdown),
+            "sharpe" => Some(RankBy::Sharpe),
+            _ => None,
+        }
+    }
+}
+
+/// One configuration simulated against one trader
+#[derive(Debug, Clone)]
+pub struct TraderRun {
+    pub trader_address: String,
+    pub outcome: SimOutcome,
+}
+
+/// One configuration aggregated across all traders
+#[derive(Debug, Clone)]
+pub struct RankedConfig {
+    pub config: SweepConfig,
+    pub runs: Vec<TraderRun>,
+    pub avg_roi: f64,
+    pub avg_max_drawdown: f64,
+    pub avg_sharpe: f64,
+    pub profitable_traders: usize,
+}
+
+impl RankedConfig {
+    fn from_runs(config: SweepConfig, runs: Vec<TraderRun>) -> Self {
+        let n = runs.len().max(1) as f64;
+        Self {
+            avg_roi: runs.iter().map(|r| r.outcome.roi).sum::<f64>() / n,
+            avg_max_drawdown: runs.iter().map(|r| r.outcome.max_drawdown_percent).sum::<f64>() / n,
+            avg_sharpe: runs.iter().map(|r| r.outcome.sharpe).sum::<f64>() / n,
+            profitable_traders: runs.iter().filter(|r| r.outcome.roi > 0.0).count(),
+            config,
+            runs,
+        }
+    }
+}
+
+pub fn rank_configs(configs: &mut [RankedConfig], rank_by: RankBy) {
+    configs.sort_by(|a, b| {
+        let ordering = match rank_by {
+            RankBy::Roi => b.avg_roi.partial_cmp(&a.avg_roi),
+            // Smaller drawdown is better
+            RankBy::Drawdown => a.avg_max_drawdown.partial_cmp(&b.avg_max_drawdown),
+            RankBy::Sharpe => b.avg_sharpe.partial_cmp(&a.avg_sharpe),
+        };
+        ordering.unwrap_or(std::cmp::Ordering::Equal)
+    });
+}
+
+/// Market data shared by every simulation in a sweep
+pub struct SweepMarketData {
+    pub resolutions: ResolutionCache,
+    pub execution: Option<ExecutionModel>,
+    pub books: Option<OrderBookCache>,
+}
+
+/// Simulate every configuration against every trader, `parallelism`
+/// configurations at a time on the blocking thread pool
+pub async fn run_sweep(
+    configs: Vec<SweepConfig>,
+    traders: Vec<(String, Arc<Vec<SimTrade>>)>,
+    market: Arc<SweepMarketData>,
+    starting_capital: f64,
+    parallelism: usize,
+) -> Result<Vec<RankedConfig>> {
+    let traders = Arc::new(traders);
+
+    let results: Vec<Result<RankedConfig>> = stream::iter(configs)
+        .map(|config| {
+            let traders = traders.clone();
+            let market = market.clone();
+            async move {
+                let ranked = tokio::task::spawn_blocking(move || {
+                    let params = config.to_params(starting_capital, market.execution.clone());
+                    let runs = traders
+                        .iter()
+                        .map(|(address, trades)| TraderRun {
+                            trader_address: address.clone(),
+                            outcome: simulate(trades, &params, Some(&market.resolutions), market.books.as_ref()),
+                        })
+                        .collect();
+                    RankedConfig::from_runs(config, runs)
+                })
+                .await?;
+                Ok(ranked)
+            }
+        })
+        .buffer_unordered(parallelism.max(1))
+        .collect()
+        .await;
+
+    results.into_iter().collect()
+}
+
+/// Sweep summary in the `strategy_factory_results/` format: each entry
+/// carries a `config` and `traders` list like a trader scan, so
+/// `aggregate_results` can fold it into its strategy table
+pub fn sweep_report(
+    configs: &[RankedConfig],
+    mode: &str,
+    history_days: i32,
+    starting_capital: f64,
+    rank_by: RankBy,
+) -> serde_json::Value {
+    serde_json::json!({
+        "sweepDate": chrono::Utc::now().to_rfc3339(),
+        "mode": mode,
+        "rankBy": format!("{:?}", rank_by).to_lowercase(),
+        "historyDays": history_days,
+        "startingCapital": starting_capital,
+        "totalConfigurations": configs.len(),
+        "configurations": configs.iter().enumerate().map(|(i, c)| serde_json::json!({
+            "rank": i + 1,
+            "config": {
+                "strategyId": c.config.id(),
+                "historyDays": history_days,
+                "multiplier": c.config.multiplier,
+                "minOrderSize": c.config.min_order_size,
+                "startingCapital": starting_capital,
+                "strategy": c.config.strategy,
+                "copySize": c.config.copy_size,
+                "maxOrderSize": c.config.max_order_size,
+                "takeProfitPercent": c.config.take_profit,
+                "stopLossPercent": c.config.stop_loss,
+            },
+            "summary": {
+                "totalAnalyzed": c.runs.len(),
+                "profitable": c.profitable_traders,
+                "avgROI": c.avg_roi,
+                "avgMaxDrawdown": c.avg_max_drawdown,
+                "avgSharpe": c.avg_sharpe,
+            },
+            "traders": c.runs.iter().map(|r| serde_json::json!({
+                "address": r.trader_address,
+                "roi": r.outcome.roi,
+                "totalPnl": r.outcome.total_pnl,
+                "winRate": r.outcome.win_rate,
+                "copiedTrades": r.outcome.copied_trades,
+                "maxDrawdownPercent": r.outcome.max_drawdown_percent,
+                "sharpe": r.outcome.sharpe,
+                "status": if r.outcome.roi > 0.0 { "profitable" } else { "unprofitable" },
+            })).collect::<Vec<_>>(),
+        })).collect::<Vec<_>>(),
+    })
+}
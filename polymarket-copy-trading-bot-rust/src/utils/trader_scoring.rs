@@ -0,0 +1,339 @@
+//! Risk-adjusted trader scoring for trader discovery.
+//! Rebuilds a trader's own book from their activity to get a daily PnL
+//! series, derives drawdown / Sharpe / Sortino, holding time, market
+//! concentration and copyability, then combines them into a 0-100 score
+//! with configurable weights.
+
+use anyhow::Result;
+use futures_util::stream::{self, StreamExt};
+use serde::Serialize;
+use std::collections::HashMap;
+use std::env;
+use crate::config::{calculate_order_size, CopyStrategyConfig, Env};
+use crate::utils::copy_simulator::{load_or_fetch_trades, SimTrade};
+use crate::utils::market_resolution::ResolutionCache;
+use crate::utils::risk_metrics::{daily_closes, max_drawdown_percent, sharpe_ratio, sortino_ratio};
+
+const DUST_SHARES: f64 = 0.01;
+const MAX_PARALLEL_FETCHES: usize = 5;
+
+/// Relative importance of each metric in the final score
+#[derive(Debug, Clone)]
+pub struct ScoringWeights {
+    pub roi: f64,
+    pub sharpe: f64,
+    pub sortino: f64,
+    pub drawdown: f64,
+    pub win_rate: f64,
+    pub diversification: f64,
+    pub copyability: f64,
+}
+
+impl ScoringWeights {
+    /// Performance first, risk as a tie-breaker
+    pub fn balanced() -> Self {
+        Self {
+            roi: 3.0,
+            sharpe: 2.0,
+            sortino: 1.0,
+            drawdown: 1.0,
+            win_rate: 1.0,
+            diversification: 0.5,
+            copyability: 1.0,
+        }
+    }
+
+    /// Downside protection first
+    pub fn low_risk() -> Self {
+        Self {
+            roi: 1.0,
+            sharpe: 1.0,
+            sortino: 2.0,
+            drawdown: 3.0,
+            win_rate: 2.0,
+            diversification: 1.5,
+            copyability: 1.0,
+        }
+    }
+
+    /// Override individual weights with `SCORE_WEIGHT_<METRIC>` env vars
+    pub fn with_env_overrides(mut self) -> Self {
+        let read = |key: &str, current: f64| {
+            env::var(key)
+                .ok()
+                .and_then(|s| s.parse::<f64>().ok())
+                .filter(|&v| v >= 0.0)
+                .unwrap_or(current)
+        };
+        self.roi = read("SCORE_WEIGHT_ROI", self.roi);
+        self.sharpe = read("SCORE_WEIGHT_SHARPE", self.sharpe);
+        self.sortino = read("SCORE_WEIGHT_SORTINO", self.sortino);
+        self.drawdown = read("SCORE_WEIGHT_DRAWDOWN", self.drawdown);
+        self.win_rate = read("SCORE_WEIGHT_WIN_RATE", self.win_rate);
+        self.diversification = read("SCORE_WEIGHT_DIVERSIFICATION", self.diversification);
+        self.copyability = read("SCORE_WEIGHT_COPYABILITY", self.copyability);
+        self
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TraderScore {
+    pub address: String,
+    pub score: f64,
+    pub trades: usize,
+    pub total_pnl: f64,
+    /// PnL relative to peak capital deployed
+    pub roi: f64,
+    pub win_rate: f64,
+    pub max_drawdown_percent: f64,
+    pub sharpe: f64,
+    pub sortino: f64,
+    pub avg_hold_hours: f64,
+    /// Herfindahl index of volume by market (1.0 = a single market)
+    pub market_concentration: f64,
+    pub top_market_share: f64,
+    pub median_order_size: f64,
+    /// Share of buys that clear the minimum order size at the copy settings
+    pub copyability: f64,
+}
+
+#[derive(Default)]
+struct Holding {
+    shares: f64,
+    cost: f64,
+    avg_entry_ts: f64,
+    last_price: f64,
+    condition_id: Option<String>,
+    outcome: Option<String>,
+    realized: f64,
+}
+
+impl Holding {
+    fn value(&self) -> f64 {
+        self.shares * self.last_price
+    }
+}
+
+fn clamp01(value: f64) -> f64 {
+    value.clamp(0.0, 1.0)
+}
+
+/// Score `trades` (sorted by timestamp). `copy_config` is the strategy the
+/// trader would be copied with; `resolutions` settles finished markets.
+pub fn score_trader(
+    address: &str,
+    trades: &[SimTrade],
+    copy_config: &CopyStrategyConfig,
+    weights: &ScoringWeights,
+    resolutions: Option<&ResolutionCache>,
+) -> TraderScore {
+    let mut holdings: HashMap<String, Holding> = HashMap::new();
+    let mut cash_flow = 0.0; // Sells minus buys
+    let mut deployed: f64 = 0.0;
+    let mut peak_deployed: f64 = 0.0;
+    let mut pnl_points: Vec<(i64, f64)> = Vec::new();
+    let mut hold_secs_weighted = 0.0;
+    let mut shares_closed = 0.0;
+    let mut volume_by_market: HashMap<String, f64> = HashMap::new();
+
+    for trade in trades.iter().filter(|t| t.price > 0.0) {
+        let market = trade.condition_id.clone().unwrap_or_else(|| trade.asset.clone());
+        *volume_by_market.entry(market).or_insert(0.0) += trade.usdc_size;
+
+        let holding = holdings.entry(trade.asset.clone()).or_default();
+        holding.last_price = trade.price;
+        holding.condition_id = trade.condition_id.clone();
+        holding.outcome = trade.outcome.clone();
+
+        if trade.side == "BUY" {
+            let total = holding.shares + trade.size;
+            if total > 0.0 {
+                holding.avg_entry_ts =
+                    (holding.avg_entry_ts * holding.shares + trade.timestamp as f64 * trade.size) / total;
+            }
+            holding.shares = total;
+            holding.cost += trade.usdc_size;
+            cash_flow -= trade.usdc_size;
+            deployed += trade.usdc_size;
+        } else if holding.shares > 0.0 {
+            let sold = trade.size.min(holding.shares);
+            let cost_released = holding.cost * sold / holding.shares;
+            holding.realized += trade.usdc_size - cost_released;
+            holding.shares -= sold;
+            holding.cost -= cost_released;
+            if holding.shares < DUST_SHARES {
+                holding.shares = 0.0;
+                holding.cost = 0.0;
+            }
+            hold_secs_weighted += (trade.timestamp as f64 - holding.avg_entry_ts) * sold;
+            shares_closed += sold;
+            cash_flow += trade.usdc_size;
+            deployed = (deployed - cost_released).max(0.0);
+        }
+        peak_deployed = peak_deployed.max(deployed);
+
+        let open_value: f64 = holdings.values().map(|h| h.value()).sum();
+        pnl_points.push((trade.timestamp, cash_flow + open_value));
+    }
+
+    // Settle resolved markets for the final mark
+    if let Some(cache) = resolutions {
+        for (asset, holding) in holdings.iter_mut().filter(|(_, h)| h.shares > 0.0) {
+            let settlement = holding
+                .condition_id
+                .as_deref()
+                .and_then(|id| cache.get(id))
+                .and_then(|r| r.settlement_price(asset, holding.outcome.as_deref()));
+            if let Some(price) = settlement {
+                holding.last_price = price;
+            }
+        }
+    }
+    let total_pnl = cash_flow + holdings.values().map(|h| h.value()).sum::<f64>();
+    if let Some(&(last_ts, _)) = pnl_points.last() {
+        pnl_points.push((last_ts, total_pnl));
+    }
+
+    // Daily PnL changes; Sharpe/Sortino are scale-free so raw dollars work
+    let closes = daily_closes(&pnl_points);
+    let daily_pnl: Vec<f64> = std::iter::once(0.0)
+        .chain(closes.iter().copied())
+        .collect::<Vec<_>>()
+        .windows(2)
+        .map(|w| w[1] - w[0])
+        .collect();
+
+    // Drawdown on equity = capital base + cumulative PnL
+    let capital_base = peak_deployed.max(1.0);
+    let equity: Vec<f64> = pnl_points.iter().map(|&(_, pnl)| capital_base + pnl).collect();
+
+    let settled: Vec<&Holding> = holdings
+        .values()
+        .filter(|h| h.shares == 0.0 || h.last_price == 0.0 || h.last_price == 1.0)
+        .filter(|h| h.cost > 0.0 || h.realized != 0.0) // Skip sells of untracked buys
+        .collect();
+    let win_rate = if settled.is_empty() {
+        0.0
+    } else {
+        settled
+            .iter()
+            .filter(|h| h.realized + h.value() - h.cost > 0.0)
+            .count() as f64
+            / settled.len() as f64
+            * 100.0
+    };
+
+    let total_volume: f64 = volume_by_market.values().sum();
+    let (market_concentration, top_market_share) = if total_volume > 0.0 {
+        let shares: Vec<f64> = volume_by_market.values().map(|v| v / total_volume).collect();
+        (
+            shares.iter().map(|s| s * s).sum(),
+            shares.iter().cloned().fold(0.0, f64::max),
+        )
+    } else {
+        (1.0, 1.0)
+    };
+
+    let mut order_sizes: Vec<f64> = trades.iter().map(|t| t.usdc_size).collect();
+    order_sizes.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
+    let median_order_size = order_sizes.get(order_sizes.len() / 2).copied().unwrap_or(0.0);
+
+    let buys: Vec<&SimTrade> = trades.iter().filter(|t| t.side == "BUY").collect();
+    let copyability = if buys.is_empty() {
+        0.0
+    } else {
+        buys.iter()
+            .filter(|t| !calculate_order_size(copy_config, t.usdc_size, f64::MAX, 0.0).below_minimum)
+            .count() as f64
+            / buys.len() as f64
+    };
+
+    let mut score = TraderScore {
+        address: address.to_string(),
+        score: 0.0,
+        trades: trades.len(),
+        total_pnl,
+        roi: total_pnl / capital_base * 100.0,
+        win_rate,
+        max_drawdown_percent: max_drawdown_percent(&equity),
+        sharpe: sharpe_ratio(&daily_pnl),
+        sortino: sortino_ratio(&daily_pnl),
+        avg_hold_hours: if shares_closed > 0.0 {
+            hold_secs_weighted / shares_closed / 3600.0
+        } else {
+            0.0
+        },
+        market_concentration,
+        top_market_share,
+        median_order_size,
+        copyability,
+    };
+    score.score = weighted_score(&score, weights);
+    score
+}
+
+/// Map each metric onto 0..1 and take the weighted average (0-100)
+fn weighted_score(s: &TraderScore, w: &ScoringWeights) -> f64 {
+    let parts = [
+        (w.roi, clamp01((s.roi.clamp(-100.0, 100.0) + 100.0) / 200.0)),
+        (w.sharpe, clamp01((s.sharpe.clamp(-3.0, 3.0) + 3.0) / 6.0)),
+        (w.sortino, clamp01((s.sortino.clamp(-3.0, 5.0) + 3.0) / 8.0)),
+        (w.drawdown, clamp01(1.0 - s.max_drawdown_percent / 100.0)),
+        (w.win_rate, clamp01(s.win_rate / 100.0)),
+        (w.diversification, clamp01(1.0 - s.market_concentration)),
+        (w.copyability, clamp01(s.copyability)),
+    ];
+    let total_weight: f64 = parts.iter().map(|(w, _)| w).sum();
+    if total_weight <= 0.0 {
+        return 0.0;
+    }
+    parts.iter().map(|(w, v)| w * v).sum::<f64>() / total_weight * 100.0
+}
+
+pub fn rank_by_score(scores: &mut [TraderScore]) {
+    scores.sort_by(|a, b| b.score.partial_cmp(&a.score).unwrap_or(std::cmp::Ordering::Equal));
+}
+
+/// Load (or fetch and cache) each trader's history, settle resolved markets
+/// and score them. Traders with fewer than `min_trades` trades are dropped.
+pub async fn score_traders(
+    addresses: &[String],
+    history_days: i32,
+    min_trades: usize,
+    copy_config: &CopyStrategyConfig,
+    weights: &ScoringWeights,
+    env: &Env,
+) -> Result<Vec<TraderScore>> {
+    let histories: Vec<(String, Vec<SimTrade>)> = stream::iter(addresses.iter().cloned())
+        .map(|address| async move {
+            let trades = load_or_fetch_trades(&address, history_days, 5000, env).await;
+            (address, trades)
+        })
+        .buffer_unordered(MAX_PARALLEL_FETCHES)
+        .filter_map(|(address, trades)| async move {
+            trades
+                .ok()
+                .filter(|t| t.len() >= min_trades)
+                .map(|t| (address, t))
+        })
+        .collect()
+        .await;
+
+    let mut resolutions = ResolutionCache::load();
+    let condition_ids: Vec<String> = histories
+        .iter()
+        .flat_map(|(_, trades)| trades.iter().filter_map(|t| t.condition_id.clone()))
+        .collect();
+    // Scores still work without resolutions (open positions marked at last price)
+    if resolutions.refresh(&condition_ids, env).await.is_ok() {
+        let _ = resolutions.save();
+    }
+
+    let mut scores: Vec<TraderScore> = histories
+        .iter()
+        .map(|(address, trades)| score_trader(address, trades, copy_config, weights, Some(&resolutions)))
+        .collect();
+    rank_by_score(&mut scores);
+    Ok(scores)
+}
@@ -1,10 +1,99 @@
 //! Common test utilities and helpers
+#![allow(dead_code)]
 
+use polymarket_copy_trading_bot_rust::config::{build_env, flatten_config, CopyStrategy, CopyStrategyConfig, Env};
+use polymarket_copy_trading_bot_rust::interfaces::{RtdsActivity, UserActivity};
+use std::collections::HashMap;
 use std::env;
 use std::fs;
-use std::path::Path;
 use tempfile::TempDir;
 
+/// Minimal valid config file: one tracked trader, a wallet and the network
+/// endpoints (nothing is contacted unless a test points these at localhost)
+pub const CONFIG_TOML: &str = r#"
+[traders]
+addresses = ["0x1111111111111111111111111111111111111111"]
+
+[wallet]
+proxy_wallet = "0x3333333333333333333333333333333333333333"
+private_key = "0x1234567890abcdef1234567890abcdef1234567890abcdef1234567890abcdef"
+usdc_contract_address = "0x2791Bca1f2de4661ED88A30C99A7a9449Aa84174"
+
+[network]
+clob_http_url = "https://clob.polymarket.com"
+clob_ws_url = "wss://ws-subscriptions-clob.polymarket.com/ws"
+rpc_url = "https://polygon-rpc.com"
+mongo_uri = "mongodb://localhost:27017/test"
+"#;
+
+/// `CONFIG_TOML` with the sections in `extra` merged over it key by key
+/// (so `extra` may add sections or override single settings)
+pub fn config_table(extra: &str) -> toml::Table {
+    let mut config = toml::from_str::<toml::Table>(CONFIG_TOML).unwrap();
+    for (section, entries) in toml::from_str::<toml::Table>(extra).unwrap() {
+        match (config.get_mut(&section), entries) {
+            (Some(toml::Value::Table(base)), toml::Value::Table(entries)) => base.extend(entries),
+            (_, entries) => {
+                config.insert(section, entries);
+            }
+        }
+    }
+    config
+}
+
+/// Flattened values of `config_table(extra)`
+pub fn config_values(extra: &str) -> (HashMap<String, String>, Vec<String>) {
+    let file = flatten_config(&serde_json::to_value(config_table(extra)).unwrap());
+    (file.values, file.errors)
+}
+
+/// Build the bot's settings from `CONFIG_TOML` plus `extra`
+pub fn try_test_env(extra: &str) -> anyhow::Result<Env> {
+    let (values, errors) = config_values(extra);
+    build_env(values, errors, None)
+}
+
+pub fn test_env(extra: &str) -> Env {
+    try_test_env(extra).unwrap()
+}
+
+/// A tracked trader's $50 buy at 0.50, with `fields` (Data API names) overriding the defaults
+pub fn activity(fields: serde_json::Value) -> UserActivity {
+    let mut record = serde_json::json!({
+        "proxyWallet": "0x1111111111111111111111111111111111111111",
+        "timestamp": 1_760_000_000,
+        "conditionId": "0xc0",
+        "type": "TRADE",
+        "side": "BUY",
+        "size": 100.0,
+        "usdcSize": 50.0,
+        "price": 0.5,
+        "asset": "123",
+        "transactionHash": "0xtx",
+    });
+    record.as_object_mut().unwrap().extend(fields.as_object().unwrap().clone());
+    UserActivity::from(&serde_json::from_value::<RtdsActivity>(record).unwrap())
+}
+
+/// Copy strategy with a $1 minimum order and no other limits
+pub fn strategy_config(strategy: CopyStrategy, copy_size: f64) -> CopyStrategyConfig {
+    CopyStrategyConfig {
+        strategy,
+        copy_size,
+        adaptive_min_percent: None,
+        adaptive_max_percent: None,
+        adaptive_threshold: None,
+        tiered_multipliers: None,
+        trade_multiplier: None,
+        max_order_size_usd: 100.0,
+        min_order_size_usd: 1.0,
+        max_position_size_usd: None,
+        max_daily_volume_usd: None,
+        max_slippage_percent: None,
+        max_slippage_abs: None,
+    }
+}
+
 /// Create a temporary .env file for testing
 pub fn create_test_env_file() -> TempDir {
     let temp_dir = TempDir::new().expect("Failed to create temp directory");
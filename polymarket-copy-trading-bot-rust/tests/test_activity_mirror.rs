@@ -0,0 +1,89 @@
+//! Proportional mirroring of merges, splits and conversions (no database or network).
+
+use polymarket_copy_trading_bot_rust::interfaces::{RtdsActivity, UserActivity, UserPosition};
+use polymarket_copy_trading_bot_rust::services::activity_mirror::{
+    complete_sets, event_fraction, is_position_activity, question_index, unit_leg_prices,
+};
+use polymarket_copy_trading_bot_rust::utils::data_api::Position;
+
+fn assert_close(actual: f64, expected: f64) {
+    assert!((actual - expected).abs() < 1e-9, "expected {}, got {}", expected, actual);
+}
+
+#[test]
+fn test_event_fraction_of_holding_before_event() {
+    // Merged 30 sets and kept 90: a quarter of what they had
+    assert_close(event_fraction(30.0, 90.0), 0.25);
+    // Merged everything
+    assert_close(event_fraction(50.0, 0.0), 1.0);
+    assert_eq!(event_fraction(0.0, 100.0), 0.0);
+}
+
+#[test]
+fn test_complete_sets_needs_every_outcome() {
+    assert_close(complete_sets(&[120.0, 45.5]), 45.5);
+    assert_eq!(complete_sets(&[120.0]), 0.0);
+    assert_eq!(complete_sets(&[]), 0.0);
+
+    // Our side of a trader merge of 30 out of 120 sets
+    let ours = complete_sets(&[80.0, 60.0]) * event_fraction(30.0, 90.0);
+    assert_close(ours, 15.0);
+}
+
+#[test]
+fn test_unit_leg_prices_sum_to_one_dollar() {
+    let prices = unit_leg_prices(&[0.30, 0.50]);
+    assert_close(prices[0], 0.375);
+    assert_close(prices.iter().sum(), 1.0);
+    assert_eq!(unit_leg_prices(&[0.0, 0.0]), vec![0.5, 0.5]);
+}
+
+#[test]
+fn test_question_index_from_question_id() {
+    let market_id = "0x1cbd9c2b0e3c1f7b8f6f4a6e0a0d0d6b5c9e0b1a2c3d4e5f60718293a4b5c600";
+    assert_eq!(question_index(market_id), Some(0));
+    assert_eq!(question_index(&format!("{}0a", &market_id[..64])), Some(10));
+    assert_eq!(question_index("0x1234"), None);
+}
+
+#[test]
+fn test_position_activity_types() {
+    for activity_type in ["MERGE", "split", "REDEEM", "CONVERSION"] {
+        assert!(is_position_activity(activity_type));
+    }
+    assert!(!is_position_activity("TRADE"));
+    assert!(!is_position_activity(""));
+}
+
+#[test]
+fn test_data_api_shapes() {
+    let activity: RtdsActivity = serde_json::from_value(serde_json::json!({
+        "proxyWallet": "0xabc",
+        "timestamp": 1_760_000_000,
+        "conditionId": "0xc0",
+        "type": "MERGE",
+        "size": 25.0,
+        "usdcSize": 25.0,
+        "price": 0.0,
+        "transactionHash": "0xtx"
+    }))
+    .unwrap();
+    let activity = UserActivity::from(&activity);
+    assert_eq!(activity.r#type, "MERGE");
+    assert_close(activity.usdc_size, 25.0);
+
+    let position: Position = serde_json::from_value(serde_json::json!({
+        "asset": "123",
+        "conditionId": "0xc0",
+        "size": 40.0,
+        "avgPrice": 0.42,
+        "outcome": "No",
+        "outcomeIndex": 1,
+        "negativeRisk": true
+    }))
+    .unwrap();
+    let position = UserPosition::from(position);
+    assert_close(position.size, 40.0);
+    assert_eq!(position.outcome_index, 1);
+    assert!(position.negative_risk);
+}
@@ -0,0 +1,102 @@
+//! Circuit breaker equity tracking: drawdown from the rolling high-water
+//! mark, daily loss limits and breaker settings (no database).
+
+mod common;
+
+use chrono::{DateTime, TimeZone, Utc};
+use common::{test_env, try_test_env};
+use polymarket_copy_trading_bot_rust::config::CircuitBreakerConfig;
+use polymarket_copy_trading_bot_rust::services::circuit_breaker::BreakerState;
+
+fn breaker(section: &str) -> CircuitBreakerConfig {
+    test_env(&format!("[circuit_breaker]\n{}", section)).circuit_breaker
+}
+
+fn at(day: u32, hour: u32) -> DateTime<Utc> {
+    Utc.with_ymd_and_hms(2026, 3, day, hour, 0, 0).unwrap()
+}
+
+#[test]
+fn test_drawdown_from_high_water_mark() {
+    let config = breaker("max_drawdown_percent = 20\n");
+    let mut state = BreakerState::default();
+
+    assert_eq!(state.update(1000.0, at(1, 9), &config), None);
+    assert_eq!(state.update(1100.0, at(1, 10), &config), None);
+    assert_eq!(state.high_water_mark(), Some(1100.0));
+    assert_eq!(state.update(900.0, at(1, 11), &config), None);
+    assert!((state.drawdown_percent(900.0) - 100.0 * 200.0 / 1100.0).abs() < 1e-9);
+
+    let reason = state.update(880.0, at(1, 12), &config).unwrap();
+    assert_eq!(reason, "drawdown 20.0% from high-water mark $1100.00 (limit 20%)");
+    assert_eq!(state.last_equity, Some(880.0));
+    assert_eq!(state.updated_at, at(1, 12).timestamp());
+}
+
+#[test]
+fn test_high_water_mark_only_covers_the_window() {
+    let config = breaker("max_drawdown_percent = 20\nhwm_window_days = 2\n");
+    let mut state = BreakerState::default();
+
+    assert_eq!(state.update(2000.0, at(1, 12), &config), None);
+    assert_eq!(state.update(1900.0, at(2, 12), &config), None);
+    // Day 1 has left the two-day window: the mark is day 2's 1900, not 2000
+    assert_eq!(state.update(1550.0, at(3, 12), &config), None);
+    assert_eq!(state.high_water_mark(), Some(1900.0));
+    assert_eq!(state.daily_highs.len(), 2);
+    assert!(state.update(1500.0, at(3, 13), &config).unwrap().starts_with("drawdown 21.1%"));
+}
+
+#[test]
+fn test_daily_loss_resets_each_utc_day() {
+    let config = breaker("max_daily_loss_usd = 100\n");
+    let mut state = BreakerState::default();
+
+    assert_eq!(state.update(1000.0, at(1, 0), &config), None);
+    assert_eq!(state.update(920.0, at(1, 23), &config), None);
+    assert_eq!(state.daily_loss(920.0), 80.0);
+    // New day: the baseline is the first sample of the day
+    assert_eq!(state.update(910.0, at(2, 0), &config), None);
+    assert_eq!(state.day_start_equity, Some(910.0));
+    assert_eq!(state.daily_loss(910.0), 0.0);
+    assert_eq!(state.update(810.0, at(2, 6), &config).unwrap(), "daily loss $100.00 (limit $100.00)");
+
+    let config = breaker("max_daily_loss_percent = 5\n");
+    let mut state = BreakerState::default();
+    assert_eq!(state.update(1000.0, at(1, 0), &config), None);
+    // Gains don't count as losses
+    assert_eq!(state.update(1200.0, at(1, 1), &config), None);
+    assert_eq!(state.daily_loss(1200.0), 0.0);
+    assert_eq!(state.update(950.0, at(1, 2), &config).unwrap(), "daily loss 5.0% of $1000.00 (limit 5%)");
+}
+
+#[test]
+fn test_no_limits_never_trip() {
+    let config = breaker("");
+    assert!(!config.is_enabled());
+    let mut state = BreakerState::default();
+    assert_eq!(state.update(1000.0, at(1, 0), &config), None);
+    assert_eq!(state.update(1.0, at(1, 1), &config), None);
+}
+
+#[test]
+fn test_breaker_settings_are_validated() {
+    let config = breaker("max_drawdown_percent = 15\n");
+    assert_eq!(config.hwm_window_days, 30);
+    assert_eq!(config.check_interval_ms, 60_000);
+    assert!(!config.flatten_on_trip);
+
+    let message = try_test_env(
+        "[circuit_breaker]\nmax_drawdown_percent = 100\nmax_daily_loss_usd = -5\nhwm_window_days = 0\ncheck_interval_ms = 10\n",
+    )
+    .unwrap_err()
+    .to_string();
+    for key in [
+        "CIRCUIT_BREAKER_MAX_DRAWDOWN_PERCENT",
+        "CIRCUIT_BREAKER_MAX_DAILY_LOSS_USD",
+        "CIRCUIT_BREAKER_HWM_WINDOW_DAYS",
+        "CIRCUIT_BREAKER_CHECK_INTERVAL_MS",
+    ] {
+        assert!(message.contains(key), "{} missing from {}", key, message);
+    }
+}
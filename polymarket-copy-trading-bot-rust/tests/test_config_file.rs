@@ -0,0 +1,159 @@
+//! Config file parsing, validation and hot-reload tests.
+//!
+//! Required settings come from the file; tests only set environment variables
+//! that no other test reads, since the process environment is shared.
+
+use polymarket_copy_trading_bot_rust::config::{
+    build_env, flatten_config, read_config_file, restart_required_changes, CopyStrategy,
+};
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+const VALID_TOML: &str = r#"
+[traders]
+addresses = ["0x1111111111111111111111111111111111111111", "0x2222222222222222222222222222222222222222"]
+
+[wallet]
+proxy_wallet = "0x3333333333333333333333333333333333333333"
+private_key = "0x1234567890abcdef1234567890abcdef1234567890abcdef1234567890abcdef"
+usdc_contract_address = "0x2791Bca1f2de4661ED88A30C99A7a9449Aa84174"
+
+[network]
+clob_http_url = "https://clob.polymarket.com"
+clob_ws_url = "wss://ws-subscriptions-clob.polymarket.com/ws"
+rpc_url = "https://polygon-rpc.com"
+mongo_uri = "mongodb://localhost:27017/test"
+
+[strategy]
+copy_strategy = "FIXED"
+copy_size = 25
+max_order_size_usd = 100.0
+
+[filters]
+exclude_tags = ["Sports"]
+min_volume_usd = 5000
+
+[exposure]
+max_open_positions = 12
+"#;
+
+fn write_temp(name: &str, contents: &str) -> PathBuf {
+    let path = std::env::temp_dir().join(format!("polycopy-test-{}-{}", std::process::id(), name));
+    std::fs::write(&path, contents).expect("Failed to write temp config file");
+    path
+}
+
+fn valid_values() -> HashMap<String, String> {
+    let document = serde_json::to_value(toml::from_str::<toml::Table>(VALID_TOML).unwrap()).unwrap();
+    let file = flatten_config(&document);
+    assert!(file.errors.is_empty(), "{:?}", file.errors);
+    file.values
+}
+
+#[test]
+fn test_toml_file_builds_env() {
+    let path = write_temp("valid.toml", VALID_TOML);
+    let file = read_config_file(&path).unwrap();
+    assert!(file.errors.is_empty(), "{:?}", file.errors);
+
+    let env = build_env(file.values, file.errors, Some(path.clone())).unwrap();
+    assert_eq!(env.user_addresses.len(), 2);
+    assert_eq!(env.copy_strategy_config.strategy, CopyStrategy::Fixed);
+    assert_eq!(env.copy_strategy_config.copy_size, 25.0);
+    assert_eq!(env.market_filter.exclude_tags, vec!["sports".to_string()]);
+    assert_eq!(env.exposure_limits.max_open_positions, Some(12));
+    assert_eq!(env.config_file, Some(path.clone()));
+    std::fs::remove_file(path).ok();
+}
+
+#[test]
+fn test_yaml_file_matches_toml() {
+    let yaml = r#"
+traders:
+  addresses:
+    - "0x1111111111111111111111111111111111111111"
+strategy:
+  copy_size: 25
+  tiered_multipliers: "1-10:2.0,10+:1.0"
+bot:
+  preview_mode: true
+"#;
+    let path = write_temp("valid.yaml", yaml);
+    let file = read_config_file(&path).unwrap();
+    assert!(file.errors.is_empty(), "{:?}", file.errors);
+    assert_eq!(file.values["USER_ADDRESSES"], "0x1111111111111111111111111111111111111111");
+    assert_eq!(file.values["COPY_SIZE"], "25");
+    assert_eq!(file.values["TIERED_MULTIPLIERS"], "1-10:2.0,10+:1.0");
+    assert_eq!(file.values["PREVIEW_MODE"], "true");
+    std::fs::remove_file(path).ok();
+}
+
+#[test]
+fn test_schema_errors_are_collected() {
+    let document = serde_json::json!({
+        "wallets": { "proxy_wallet": "0x3333333333333333333333333333333333333333" },
+        "strategy": { "copy_sise": 10, "copy_size": "ten" },
+        "bot": { "preview_mode": "yes" }
+    });
+    let file = flatten_config(&document);
+    assert_eq!(file.errors.len(), 4, "{:?}", file.errors);
+    assert!(file.errors.iter().any(|e| e.contains("Unknown section [wallets]")));
+    assert!(file.errors.iter().any(|e| e.contains("Unknown setting strategy.copy_sise")));
+    assert!(file.errors.iter().any(|e| e.contains("strategy.copy_size")));
+    assert!(file.errors.iter().any(|e| e.contains("bot.preview_mode")));
+}
+
+#[test]
+fn test_invalid_values_reported_together() {
+    let mut values = valid_values();
+    values.insert("PROXY_WALLET".into(), "0x1234".into());
+    values.insert("CLOB_WS_URL".into(), "https://not-a-websocket".into());
+    values.insert("MAX_ORDER_SIZE_USD".into(), "lots".into());
+    values.remove("RPC_URL");
+
+    let error = build_env(values, vec!["Unknown setting bot.colour".into()], None)
+        .unwrap_err()
+        .to_string();
+    assert!(error.contains("5 errors"), "{}", error);
+    assert!(error.contains("Unknown setting bot.colour"), "{}", error);
+    assert!(error.contains("wallet.proxy_wallet (PROXY_WALLET)"), "{}", error);
+    assert!(error.contains("network.clob_ws_url (CLOB_WS_URL)"), "{}", error);
+    assert!(error.contains("strategy.max_order_size_usd (MAX_ORDER_SIZE_USD)"), "{}", error);
+    assert!(error.contains("Missing required setting RPC_URL"), "{}", error);
+}
+
+#[test]
+fn test_environment_overrides_file() {
+    std::env::set_var("FILTER_MIN_VOLUME_USD", "750");
+    let env = build_env(valid_values(), Vec::new(), None);
+    std::env::remove_var("FILTER_MIN_VOLUME_USD");
+    assert_eq!(env.unwrap().market_filter.min_volume_usd, Some(750.0));
+}
+
+#[test]
+fn test_hot_reload_applies_only_hot_fields() {
+    let current = build_env(valid_values(), Vec::new(), None).unwrap();
+
+    let mut values = valid_values();
+    values.insert("USER_ADDRESSES".into(), "0x4444444444444444444444444444444444444444".into());
+    values.insert("COPY_SIZE".into(), "40".into());
+    values.insert("FILTER_MIN_PRICE".into(), "0.05".into());
+    values.insert("RPC_URL".into(), "https://polygon.example.org".into());
+    let reloaded = build_env(values.clone(), Vec::new(), None).unwrap();
+
+    let applied = current.with_hot_reload(&reloaded);
+    assert_eq!(applied.user_addresses, vec!["0x4444444444444444444444444444444444444444".to_string()]);
+    assert_eq!(applied.copy_strategy_config.copy_size, 40.0);
+    assert_eq!(applied.market_filter.min_price, Some(0.05));
+    assert_eq!(applied.rpc_url, current.rpc_url);
+
+    let restart = restart_required_changes(&valid_values(), &values);
+    assert_eq!(restart, vec!["network.rpc_url".to_string()]);
+}
+
+#[test]
+fn test_example_config_matches_schema() {
+    let path = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("polycopy.example.toml");
+    let file = read_config_file(&path).unwrap();
+    assert!(file.errors.is_empty(), "{:?}", file.errors);
+}
@@ -0,0 +1,105 @@
+//! Market resolution settlement in the copy simulator (no network).
+
+mod common;
+
+use common::strategy_config;
+use polymarket_copy_trading_bot_rust::config::CopyStrategy;
+use polymarket_copy_trading_bot_rust::utils::copy_simulator::{simulate, SimParams, SimTrade};
+use polymarket_copy_trading_bot_rust::utils::market_resolution::{MarketResolution, ResolutionCache};
+
+const CONDITION: &str = "0xabc";
+const OPEN_CONDITION: &str = "0xdef";
+
+fn resolution(condition_id: &str, resolved: bool, outcome_prices: Vec<f64>) -> MarketResolution {
+    MarketResolution {
+        condition_id: condition_id.to_string(),
+        question: "Will it rain?".to_string(),
+        closed: resolved,
+        resolved,
+        outcomes: vec!["Yes".to_string(), "No".to_string()],
+        outcome_prices,
+        token_ids: vec!["yes-token".to_string(), "no-token".to_string()],
+        end_date: None,
+        fetched_at: 0,
+    }
+}
+
+fn trade(timestamp: i64, condition_id: &str, asset: &str, outcome: &str, side: &str, price: f64) -> SimTrade {
+    SimTrade {
+        timestamp,
+        condition_id: Some(condition_id.to_string()),
+        asset: asset.to_string(),
+        side: side.to_string(),
+        price,
+        usdc_size: 100.0,
+        size: 100.0 / price,
+        outcome: Some(outcome.to_string()),
+        title: Some("Will it rain?".to_string()),
+    }
+}
+
+fn fixed_ten_dollars() -> SimParams {
+    SimParams {
+        strategy: strategy_config(CopyStrategy::Fixed, 10.0),
+        take_profit_percent: None,
+        stop_loss_percent: None,
+        starting_capital: 1000.0,
+        execution: None,
+    }
+}
+
+#[test]
+fn test_settlement_price_by_token_then_outcome() {
+    let market = resolution(CONDITION, true, vec![1.0, 0.0]);
+    assert_eq!(market.settlement_price("yes-token", None), Some(1.0));
+    assert_eq!(market.settlement_price("no-token", Some("Yes")), Some(0.0), "token ID wins over the name");
+    assert_eq!(market.settlement_price("unknown", Some("no")), Some(0.0));
+    assert_eq!(market.settlement_price("unknown", Some("Yes")), Some(1.0));
+    assert_eq!(market.settlement_price("unknown", None), None);
+
+    // Near-1 prices still pay out in full
+    assert_eq!(resolution(CONDITION, true, vec![0.995, 0.005]).settlement_price("yes-token", None), Some(1.0));
+}
+
+#[test]
+fn test_unresolved_market_does_not_settle() {
+    let market = resolution(CONDITION, false, vec![0.7, 0.3]);
+    assert_eq!(market.settlement_price("yes-token", Some("Yes")), None);
+}
+
+#[test]
+fn test_simulation_settles_resolved_markets_and_marks_the_rest() {
+    let path = std::env::temp_dir().join(format!("polycopy-test-{}-resolutions.json", std::process::id()));
+    let resolutions = vec![resolution(CONDITION, true, vec![1.0, 0.0]), resolution(OPEN_CONDITION, false, vec![0.6, 0.4])];
+    std::fs::write(&path, serde_json::to_string(&resolutions).unwrap()).unwrap();
+    let cache = ResolutionCache::load_from(&path);
+    assert_eq!(cache.len(), 2);
+
+    let trades = vec![
+        trade(1_000, CONDITION, "yes-token", "Yes", "BUY", 0.5),
+        trade(2_000, CONDITION, "no-token", "No", "BUY", 0.25),
+        trade(3_000, OPEN_CONDITION, "open-token", "Yes", "BUY", 0.4),
+        // Later trade moves the open market's last price
+        trade(4_000, OPEN_CONDITION, "open-token", "Yes", "BUY", 0.5),
+    ];
+    let outcome = simulate(&trades, &fixed_ten_dollars(), Some(&cache), None);
+    assert_eq!(outcome.copied_trades, 4);
+
+    let position = |asset: &str| outcome.positions.iter().find(|p| p.asset == asset).unwrap();
+    let winner = position("yes-token");
+    assert!(winner.resolved);
+    assert!((winner.current_value - 20.0).abs() < 1e-9, "{:?}", winner);
+    let loser = position("no-token");
+    assert!(loser.resolved);
+    assert_eq!(loser.current_value, 0.0);
+    let open = position("open-token");
+    assert!(!open.resolved);
+    assert_eq!(open.last_price, 0.5);
+
+    // Winner +$10, loser -$10; the open position (25 + 20 shares at 0.5 for $20) gains $2.50
+    assert!(outcome.resolution_pnl.abs() < 1e-9, "{}", outcome.resolution_pnl);
+    assert!((outcome.unrealized_pnl - 2.5).abs() < 1e-9, "{}", outcome.unrealized_pnl);
+    assert!((outcome.final_equity - 1002.5).abs() < 1e-9, "{}", outcome.final_equity);
+
+    let _ = std::fs::remove_file(&path);
+}
@@ -1,118 +1,166 @@
-//! Integration tests for core utility scripts
+//! Integration tests for the polycopy CLI
 
-use std::process::Command;
-use std::env;
+mod common;
 
-#[test]
-fn test_help_binary() {
-    let output = Command::new("cargo")
-        .args(&["run", "--bin", "help", "--quiet"])
+use serde_json::json;
+use std::fs;
+use std::process::{Command, Output};
+
+fn polycopy(args: &[&str]) -> Output {
+    Command::new("cargo")
+        .args(["run", "--bin", "polycopy", "--quiet", "--"])
+        .args(args)
         .output()
-        .expect("Failed to execute help binary");
-    
-    assert!(output.status.success() || output.status.code() == Some(0));
-    let stdout = String::from_utf8_lossy(&output.stdout);
-    assert!(stdout.contains("POLYMARKET COPY TRADING BOT") || stdout.contains("COMMANDS"));
+        .expect("Failed to execute polycopy")
 }
 
-#[test]
-fn test_health_check_binary_exists() {
-    // Test that binary compiles and can be invoked (even if it fails due to missing .env)
-    let output = Command::new("cargo")
-        .args(&["run", "--bin", "health_check", "--quiet", "--", "--help"])
-        .output();
-    
-    // Binary should exist and be invokable
-    assert!(output.is_ok());
+fn stdout(output: &Output) -> String {
+    String::from_utf8_lossy(&output.stdout).to_string()
 }
 
 #[test]
-fn test_check_allowance_binary_exists() {
-    let output = Command::new("cargo")
-        .args(&["run", "--bin", "check_allowance", "--quiet", "--", "--help"])
-        .output();
-    
-    assert!(output.is_ok());
+fn test_help_lists_command_groups() {
+    let output = polycopy(&["--help"]);
+
+    assert!(output.status.success());
+    let help = stdout(&output);
+    for group in ["wallet", "positions", "sell", "redeem", "traders", "sim", "health"] {
+        assert!(help.contains(group), "missing group {} in:\n{}", group, help);
+    }
 }
 
 #[test]
-fn test_verify_allowance_binary_exists() {
-    let output = Command::new("cargo")
-        .args(&["run", "--bin", "verify_allowance", "--quiet", "--", "--help"])
-        .output();
-    
-    assert!(output.is_ok());
+fn test_health_check_help() {
+    let output = polycopy(&["health", "check", "--help"]);
+
+    assert!(output.status.success());
+    assert!(stdout(&output).contains("--json"));
 }
 
 #[test]
-fn test_set_token_allowance_binary_exists() {
-    let output = Command::new("cargo")
-        .args(&["run", "--bin", "set_token_allowance", "--quiet", "--", "--help"])
-        .output();
-    
-    assert!(output.is_ok());
+fn test_wallet_subcommands_help() {
+    let output = polycopy(&["wallet", "--help"]);
+
+    assert!(output.status.success());
+    let help = stdout(&output);
+    for command in ["allowance", "verify-allowance", "approve", "transfer", "swap", "eoa"] {
+        assert!(help.contains(command), "missing {} in:\n{}", command, help);
+    }
 }
 
 #[test]
-fn test_check_my_stats_binary_exists() {
-    let output = Command::new("cargo")
-        .args(&["run", "--bin", "check_my_stats", "--quiet", "--", "--help"])
-        .output();
-    
-    assert!(output.is_ok());
+fn test_positions_subcommands_help() {
+    let output = polycopy(&["positions", "--help"]);
+
+    assert!(output.status.success());
+    let help = stdout(&output);
+    for command in ["stats", "list", "activity", "pnl", "reconcile"] {
+        assert!(help.contains(command), "missing {} in:\n{}", command, help);
+    }
 }
 
 #[test]
-fn test_check_positions_detailed_binary_exists() {
-    let output = Command::new("cargo")
-        .args(&["run", "--bin", "check_positions_detailed", "--quiet", "--", "--help"])
-        .output();
-    
-    assert!(output.is_ok());
+fn test_swap_flags() {
+    let output = polycopy(&["wallet", "swap", "--help"]);
+
+    assert!(output.status.success());
+    let help = stdout(&output);
+    assert!(help.contains("--slippage"));
+    assert!(help.contains("--dry-run"));
 }
 
 #[test]
-fn test_check_recent_activity_binary_exists() {
-    let output = Command::new("cargo")
-        .args(&["run", "--bin", "check_recent_activity", "--quiet", "--", "--help"])
-        .output();
-    
-    assert!(output.is_ok());
+fn test_legacy_binary_names_are_aliases() {
+    let legacy = polycopy(&["check_allowance", "--help"]);
+    let grouped = polycopy(&["wallet", "allowance", "--help"]);
+
+    assert!(legacy.status.success());
+    assert_eq!(stdout(&legacy), stdout(&grouped));
+
+    let legacy = polycopy(&["pnl_report", "--help"]);
+    assert!(legacy.status.success());
+    assert!(stdout(&legacy).contains("--format"));
 }
 
 #[test]
-fn test_check_proxy_wallet_binary_exists() {
-    let output = Command::new("cargo")
-        .args(&["run", "--bin", "check_proxy_wallet", "--quiet", "--", "--help"])
-        .output();
-    
-    assert!(output.is_ok());
+fn test_json_rejected_for_text_only_commands() {
+    let output = polycopy(&["--json", "sell", "manual"]);
+
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("does not support --json"));
 }
 
 #[test]
-fn test_check_pnl_discrepancy_binary_exists() {
-    let output = Command::new("cargo")
-        .args(&["run", "--bin", "check_pnl_discrepancy", "--quiet", "--", "--help"])
-        .output();
-    
-    assert!(output.is_ok());
+fn test_unknown_command_fails() {
+    let output = polycopy(&["not-a-command"]);
+
+    assert!(!output.status.success());
 }
 
 #[test]
-fn test_find_my_eoa_binary_exists() {
-    let output = Command::new("cargo")
-        .args(&["run", "--bin", "find_my_eoa", "--quiet", "--", "--help"])
-        .output();
-    
-    assert!(output.is_ok());
+fn test_sim_batch_runs_simulations_in_process() {
+    // Cached history and resolutions in the working directory, so the run
+    // needs no network (the endpoints point at a closed port)
+    let dir = tempfile::TempDir::new().unwrap();
+    let trader = "0x7c3db723f1d4d8cb9c550095203b686cb11e5c6b";
+    let today = chrono::Utc::now().format("%Y-%m-%d");
+    let config = common::config_table("[network]\ndata_api_url = \"http://127.0.0.1:9/\"\ngamma_api_url = \"http://127.0.0.1:9/\"\n");
+    fs::write(dir.path().join("polycopy.toml"), toml::to_string(&config).unwrap()).unwrap();
+    fs::create_dir_all(dir.path().join("trader_data_cache")).unwrap();
+    let trade = json!({
+        "id": "0xtx", "timestamp": chrono::Utc::now().timestamp() - 3600, "conditionId": "0xabc",
+        "title": "Will it rain?", "asset": "yes-token", "side": "BUY", "price": 0.5,
+        "usdcSize": 100.0, "size": 200.0, "outcome": "Yes",
+    });
+    fs::write(
+        dir.path().join(format!("trader_data_cache/{}_7d_{}.json", trader, today)),
+        json!({ "trades": [trade] }).to_string(),
+    )
+    .unwrap();
+    let resolution = json!({
+        "conditionId": "0xabc", "question": "Will it rain?", "closed": true, "resolved": true,
+        "outcomes": ["Yes", "No"], "outcomePrices": [1.0, 0.0], "tokenIds": ["yes-token", "no-token"],
+        "endDate": null, "fetchedAt": 0,
+    });
+    fs::write(dir.path().join("trader_data_cache/market_resolutions.json"), json!([resolution]).to_string()).unwrap();
+
+    let output = Command::new(env!("CARGO_BIN_EXE_polycopy"))
+        .args(["sim", "batch", "custom", trader, "7", "2"])
+        .current_dir(dir.path())
+        .env_remove("CONFIG_FILE")
+        .env_remove("COPY_PERCENTAGE")
+        .env_remove("SIM_MARK_TIMESTAMP")
+        .output()
+        .expect("Failed to execute polycopy");
+    assert!(output.status.success(), "{}\n{}", stdout(&output), String::from_utf8_lossy(&output.stderr));
+    assert!(stdout(&output).contains("Simulation completed"));
+
+    let path = dir.path().join(format!("simulation_results/fixed_logic_{}_7d_copy10pct_custom_{}.json", trader, today));
+    let result: serde_json::Value = serde_json::from_str(&fs::read_to_string(&path).unwrap()).unwrap();
+    // 10% of $100 at 2x buys 40 tokens for $20, which settle at $1
+    assert_eq!(result["copiedTrades"], 1);
+    assert!((result["totalPnl"].as_f64().unwrap() - 20.0).abs() < 1e-9, "{}", result);
 }
 
 #[test]
-fn test_check_both_wallets_binary_exists() {
-    let output = Command::new("cargo")
-        .args(&["run", "--bin", "check_both_wallets", "--quiet", "--", "--help"])
-        .output();
-    
-    assert!(output.is_ok());
-}
+fn test_account_flags() {
+    let output = polycopy(&["health", "breaker", "--help"]);
+    assert!(output.status.success());
+    assert!(stdout(&output).contains("--all-accounts"));
+
+    let output = polycopy(&["positions", "pnl", "--account", "hedge", "--all-accounts"]);
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("cannot be used with"));
 
+    // Unknown names fail before any database connection
+    let dir = tempfile::TempDir::new().unwrap();
+    fs::write(dir.path().join("polycopy.toml"), common::CONFIG_TOML).unwrap();
+    let output = Command::new(env!("CARGO_BIN_EXE_polycopy"))
+        .args(["sim", "paper", "--account", "hedge"])
+        .current_dir(dir.path())
+        .env_remove("CONFIG_FILE")
+        .output()
+        .expect("Failed to execute polycopy");
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("Unknown account \"hedge\" (configured: primary)"));
+}
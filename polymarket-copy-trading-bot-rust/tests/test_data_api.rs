@@ -0,0 +1,174 @@
+//! Data API client: query building, decoding and pagination against a local
+//! stub server (no network).
+
+mod common;
+
+use common::test_env;
+use polymarket_copy_trading_bot_rust::interfaces::{UserActivity, UserPosition};
+use polymarket_copy_trading_bot_rust::utils::data_api::{
+    decode_list, Activity, ActivityQuery, DataApiClient, LeaderboardQuery, Position, PositionsQuery, TradesQuery,
+    MAX_PAGE_SIZE,
+};
+use std::collections::HashMap;
+use std::io::{BufRead, BufReader, Write};
+use std::net::TcpListener;
+use std::sync::{Arc, Mutex};
+
+const DATA_API_TOML: &str = "[network]\ndata_api_url = \"http://data-api.test/\"\n";
+
+fn query(url: &str) -> HashMap<String, String> {
+    url::Url::parse(url).unwrap().query_pairs().into_owned().collect()
+}
+
+/// Serve `total` activity records, honouring `limit`/`offset`, and record each request path
+fn serve_activity(total: usize) -> (String, Arc<Mutex<Vec<String>>>) {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let base_url = format!("http://{}", listener.local_addr().unwrap());
+    let requests = Arc::new(Mutex::new(Vec::new()));
+    let seen = requests.clone();
+
+    std::thread::spawn(move || {
+        for stream in listener.incoming() {
+            let mut stream = stream.unwrap();
+            let mut request_line = String::new();
+            BufReader::new(&stream).read_line(&mut request_line).unwrap();
+            let path = request_line.split_whitespace().nth(1).unwrap_or("/").to_string();
+            seen.lock().unwrap().push(path.clone());
+
+            let params = query(&format!("http://stub{}", path));
+            let offset: usize = params.get("offset").and_then(|v| v.parse().ok()).unwrap_or(0);
+            let limit: usize = params.get("limit").and_then(|v| v.parse().ok()).unwrap_or(100);
+            let page: Vec<serde_json::Value> = (offset..total.min(offset + limit))
+                .map(|i| serde_json::json!({ "type": "TRADE", "timestamp": i, "transactionHash": format!("0x{}", i), "title": null }))
+                .collect();
+            let body = serde_json::to_string(&page).unwrap();
+            let response = format!(
+                "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                body.len(),
+                body
+            );
+            let _ = stream.write_all(response.as_bytes());
+        }
+    });
+
+    (base_url, requests)
+}
+
+#[test]
+fn test_query_builders() {
+    let positions = PositionsQuery::new("0xabc").market("0xc1").market("0xc2").redeemable(true);
+    assert_eq!(
+        positions.params(),
+        vec![
+            ("user", "0xabc".to_string()),
+            ("market", "0xc1,0xc2".to_string()),
+            ("redeemable", "true".to_string()),
+        ]
+    );
+
+    let activity = ActivityQuery::new("0xabc").kind("MERGE").kind("SPLIT").start(100).ascending();
+    assert_eq!(
+        activity.params(),
+        vec![
+            ("user", "0xabc".to_string()),
+            ("type", "MERGE,SPLIT".to_string()),
+            ("start", "100".to_string()),
+            ("sortBy", "TIMESTAMP".to_string()),
+            ("sortDirection", "ASC".to_string()),
+        ]
+    );
+
+    let trades = TradesQuery::new().market("0xc1").taker_only();
+    assert_eq!(
+        trades.params(),
+        vec![("market", "0xc1".to_string()), ("takerOnly", "true".to_string())]
+    );
+
+    let leaderboard = LeaderboardQuery::new().time_period("WEEK").limit(10);
+    assert_eq!(leaderboard.params()[1], ("timePeriod", "WEEK".to_string()));
+    assert_eq!(leaderboard.params()[3], ("limit", "10".to_string()));
+}
+
+#[test]
+fn test_base_url_comes_from_config_and_can_be_overridden() {
+    let env = test_env(DATA_API_TOML);
+    let params = PositionsQuery::new("0xabc").market("0xc1,0xc2").params();
+
+    let url = DataApiClient::new(&env).url("/positions", &params);
+    assert!(url.starts_with("http://data-api.test/positions?"), "{}", url);
+    assert_eq!(query(&url)["market"], "0xc1,0xc2");
+
+    let url = DataApiClient::new(&env).with_base_url("http://localhost:8080/").url("/value", &[]);
+    assert_eq!(url, "http://localhost:8080/value");
+}
+
+#[test]
+fn test_decoding_tolerates_nulls_and_skips_bad_records() {
+    let json = serde_json::json!([
+        {
+            "proxyWallet": "0xabc",
+            "asset": "123",
+            "conditionId": "0xc1",
+            "size": 40.0,
+            "curPrice": 0.995,
+            "redeemable": true,
+            "title": null,
+            "outcome": "Yes",
+            "outcomeIndex": 0,
+            "endDate": null
+        },
+        { "asset": 42 }
+    ]);
+    let positions: Vec<Position> = decode_list(json);
+    assert_eq!(positions.len(), 1);
+    assert!(positions[0].redeemable);
+    assert_eq!(positions[0].title(), "Unknown");
+
+    let position = UserPosition::from(positions[0].clone());
+    assert_eq!(position.condition_id, "0xc1");
+    assert_eq!(position.end_date, "");
+
+    let activities: Vec<Activity> = decode_list(serde_json::json!([{
+        "type": "REDEEM",
+        "conditionId": "0xc1",
+        "size": 10.0,
+        "usdcSize": 10.0,
+        "side": null,
+        "timestamp": 1700000000
+    }]));
+    let activity = UserActivity::from(&activities[0]);
+    assert_eq!(activity.r#type, "REDEEM");
+    assert_eq!(activity.usdc_size, 10.0);
+    assert_eq!(activity.side, "");
+}
+
+#[tokio::test]
+async fn test_activity_walks_pages_until_limit_or_end() {
+    let env = test_env(DATA_API_TOML);
+    let (base_url, requests) = serve_activity(1200);
+    let client = DataApiClient::new(&env).with_base_url(&base_url);
+
+    let all = client.activity(&ActivityQuery::new("0xabc").kind("TRADE")).await.unwrap();
+    assert_eq!(all.len(), 1200);
+    assert_eq!(all[1199].timestamp, 1199);
+    let offsets: Vec<String> = requests.lock().unwrap().iter().map(|p| query(&format!("http://stub{}", p))["offset"].clone()).collect();
+    assert_eq!(offsets, vec!["0", "500", "1000"]);
+
+    requests.lock().unwrap().clear();
+    let some = client.activity(&ActivityQuery::new("0xabc").limit(520)).await.unwrap();
+    assert_eq!(some.len(), 520);
+    let limits: Vec<String> = requests.lock().unwrap().iter().map(|p| query(&format!("http://stub{}", p))["limit"].clone()).collect();
+    assert_eq!(limits, vec![MAX_PAGE_SIZE.to_string(), "20".to_string()]);
+}
+
+#[tokio::test]
+async fn test_activity_stops_at_the_offset_cap() {
+    let env = test_env(DATA_API_TOML);
+    let (base_url, requests) = serve_activity(12_000);
+    let client = DataApiClient::new(&env).with_base_url(&base_url);
+
+    // The last page the API serves starts at offset 10 000; the rest is cut off (with a warning)
+    let all = client.activity(&ActivityQuery::new("0xabc")).await.unwrap();
+    assert_eq!(all.len(), 10_500);
+    assert_eq!(requests.lock().unwrap().len(), 21);
+}
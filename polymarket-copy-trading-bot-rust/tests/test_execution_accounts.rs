@@ -0,0 +1,96 @@
+//! Execution account (multi-wallet fan-out) config tests.
+//!
+//! Everything comes from config file values, since the process environment is shared.
+
+mod common;
+
+use common::{config_values, test_env, try_test_env};
+use polymarket_copy_trading_bot_rust::config::{build_env, restart_required_changes, CopyStrategy, PRIMARY_ACCOUNT};
+
+const STRATEGY_TOML: &str = r#"
+[strategy]
+copy_strategy = "PERCENTAGE"
+copy_size = 10
+max_order_size_usd = 100.0
+"#;
+
+const ACCOUNTS_TOML: &str = r#"
+[accounts.hedge]
+proxy_wallet = "0x4444444444444444444444444444444444444444"
+private_key = "0xabcdef1234567890abcdef1234567890abcdef1234567890abcdef1234567890"
+copy_strategy = "FIXED"
+copy_size = 5
+max_order_size_usd = 20.0
+
+[accounts.small]
+proxy_wallet = "0x5555555555555555555555555555555555555555"
+private_key = "0x1111111111111111111111111111111111111111111111111111111111111111"
+max_order_size_usd = 10.0
+"#;
+
+#[test]
+fn test_accounts_inherit_and_override_strategy() {
+    let env = test_env(&format!("{}{}", STRATEGY_TOML, ACCOUNTS_TOML));
+    let accounts = env.execution_accounts();
+    let names: Vec<&str> = accounts.iter().map(|a| a.name.as_str()).collect();
+    assert_eq!(names, vec![PRIMARY_ACCOUNT, "hedge", "small"]);
+
+    let hedge = &accounts[1];
+    assert_eq!(hedge.copy_strategy_config.strategy, CopyStrategy::Fixed);
+    assert_eq!(hedge.copy_strategy_config.copy_size, 5.0);
+    assert_eq!(hedge.copy_strategy_config.max_order_size_usd, 20.0);
+
+    // Only the order cap is overridden
+    let small = &accounts[2];
+    assert_eq!(small.copy_strategy_config.strategy, CopyStrategy::Percentage);
+    assert_eq!(small.copy_strategy_config.copy_size, 10.0);
+    assert_eq!(small.copy_strategy_config.max_order_size_usd, 10.0);
+
+    let hedge_env = env.for_account(hedge);
+    assert_eq!(hedge_env.proxy_wallet, "0x4444444444444444444444444444444444444444");
+    assert_eq!(hedge_env.copy_strategy_config.copy_size, 5.0);
+    assert!(hedge_env.accounts.is_empty());
+    assert_eq!(hedge_env.user_addresses, env.user_addresses);
+}
+
+#[test]
+fn test_single_wallet_setup_has_only_primary() {
+    let env = test_env(STRATEGY_TOML);
+    assert!(env.accounts.is_empty());
+    let accounts = env.execution_accounts();
+    assert_eq!(accounts.len(), 1);
+    assert_eq!(accounts[0].proxy_wallet, env.proxy_wallet);
+}
+
+#[test]
+fn test_invalid_accounts_reported_together() {
+    let invalid = r#"
+[accounts.copycat]
+proxy_wallet = "0x3333333333333333333333333333333333333333"
+copy_strategy = "YOLO"
+leverage = 10
+"#;
+    let message = try_test_env(&format!("{}{}", STRATEGY_TOML, invalid)).unwrap_err().to_string();
+    assert!(message.contains("Unknown setting accounts.copycat.leverage"), "{}", message);
+    assert!(message.contains("ACCOUNT_COPYCAT_PROXY_WALLET"), "{}", message);
+    assert!(message.contains("already used by another account"), "{}", message);
+    assert!(message.contains("Missing required setting ACCOUNT_COPYCAT_PRIVATE_KEY"), "{}", message);
+    assert!(message.contains("YOLO"), "{}", message);
+}
+
+#[test]
+fn test_account_strategy_hot_reloads_but_wallets_need_restart() {
+    let (old_values, _) = config_values(&format!("{}{}", STRATEGY_TOML, ACCOUNTS_TOML));
+    let current = build_env(old_values.clone(), Vec::new(), None).unwrap();
+
+    let resized = ACCOUNTS_TOML.replace("copy_size = 5", "copy_size = 7");
+    let (new_values, _) = config_values(&format!("{}{}", STRATEGY_TOML, resized));
+    let reloaded = build_env(new_values.clone(), Vec::new(), None).unwrap();
+    let env = current.with_hot_reload(&reloaded);
+    assert_eq!(env.accounts[0].copy_strategy_config.copy_size, 7.0);
+    assert!(restart_required_changes(&old_values, &new_values).is_empty());
+
+    let rekeyed = ACCOUNTS_TOML.replace("0x5555555555555555555555555555555555555555", "0x6666666666666666666666666666666666666666");
+    let (new_values, _) = config_values(&format!("{}{}", STRATEGY_TOML, rekeyed));
+    assert_eq!(restart_required_changes(&old_values, &new_values), vec!["accounts".to_string()]);
+}
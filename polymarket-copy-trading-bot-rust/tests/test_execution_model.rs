@@ -0,0 +1,102 @@
+//! Simulated fills: depth walking, latency slippage, fees and the slippage cap.
+
+use polymarket_copy_trading_bot_rust::utils::execution_model::{
+    BookLevel, BookSnapshot, DepthSource, ExecutionModel, Fill, FillOutcome,
+};
+
+fn model() -> ExecutionModel {
+    ExecutionModel {
+        latency_secs: 0,
+        depth_source: DepthSource::Synthetic,
+        level_usd: 10.0,
+        tick: 0.01,
+        levels: 10,
+        taker_fee_bps: 0.0,
+        max_slippage_percent: 5.0,
+    }
+}
+
+fn filled(outcome: FillOutcome) -> Fill {
+    match outcome {
+        FillOutcome::Filled(fill) => fill,
+        FillOutcome::Rejected(reason) => panic!("expected a fill, rejected: {}", reason),
+    }
+}
+
+fn rejection(outcome: FillOutcome) -> String {
+    match outcome {
+        FillOutcome::Filled(fill) => panic!("expected a rejection, filled: {:?}", fill),
+        FillOutcome::Rejected(reason) => reason,
+    }
+}
+
+fn book(bids: &[(f64, f64)], asks: &[(f64, f64)]) -> BookSnapshot {
+    let levels = |levels: &[(f64, f64)]| levels.iter().map(|&(price, size)| BookLevel { price, size }).collect();
+    BookSnapshot { asset: "1".to_string(), bids: levels(bids), asks: levels(asks), fetched_at: 0 }
+}
+
+#[test]
+fn test_buy_walks_synthetic_levels() {
+    // $10 at 0.50, $10 at 0.51, the last $5 at 0.52
+    let fill = filled(model().fill_buy(0.5, 0.5, 25.0, None));
+    let shares = 20.0 + 10.0 / 0.51 + 5.0 / 0.52;
+    assert!((fill.shares - shares).abs() < 1e-9, "{:?}", fill);
+    assert!((fill.amount - 25.0).abs() < 1e-9, "{:?}", fill);
+    assert!((fill.price - 25.0 / shares).abs() < 1e-12, "{:?}", fill);
+    assert!((fill.slippage_percent - (fill.price - 0.5) / 0.5 * 100.0).abs() < 1e-9, "{:?}", fill);
+    assert_eq!(fill.fee, 0.0);
+}
+
+#[test]
+fn test_latency_move_beyond_max_slippage_is_rejected() {
+    // The price moved from the trader's 0.50 to 0.53 before our copy arrived
+    let reason = rejection(model().fill_buy(0.5, 0.53, 5.0, None));
+    assert!(reason.starts_with("slippage 6.00%"), "{}", reason);
+
+    // A sell is hurt by the price falling instead
+    let reason = rejection(model().fill_sell(0.5, 0.47, 10.0, None));
+    assert!(reason.starts_with("slippage 6.00%"), "{}", reason);
+    let fill = filled(model().fill_sell(0.5, 0.53, 10.0, None));
+    assert!((fill.slippage_percent + 6.0).abs() < 1e-9, "price improvement is negative slippage: {:?}", fill);
+}
+
+#[test]
+fn test_sell_walks_recorded_book_shape() {
+    let recorded = ExecutionModel { depth_source: DepthSource::Recorded, ..model() };
+    // Recorded spacing (0.48 -> 0.46) is anchored at the 0.50 start price
+    let snapshot = book(&[(0.48, 100.0), (0.46, 50.0)], &[]);
+    let fill = filled(recorded.fill_sell(0.5, 0.5, 120.0, Some(&snapshot)));
+    assert_eq!(fill.shares, 120.0);
+    assert!((fill.amount - (100.0 * 0.5 + 20.0 * 0.48)).abs() < 1e-9, "{:?}", fill);
+
+    // Not enough depth: only what the book holds is sold
+    let fill = filled(recorded.fill_sell(0.5, 0.5, 500.0, Some(&snapshot)));
+    assert_eq!(fill.shares, 150.0);
+
+    // Synthetic depth ignores the recorded book
+    let fill = filled(model().fill_sell(0.5, 0.5, 20.0, Some(&snapshot)));
+    assert_eq!(fill.shares, 20.0);
+    assert!((fill.amount - 10.0).abs() < 1e-9, "{:?}", fill);
+}
+
+#[test]
+fn test_taker_fee_is_charged_on_both_sides() {
+    let with_fee = ExecutionModel { taker_fee_bps: 100.0, ..model() };
+
+    // Buy: the fee (1% * min(p, 1 - p) * shares) comes out of the shares received
+    let fill = filled(with_fee.fill_buy(0.4, 0.4, 4.0, None));
+    assert!((fill.fee - 0.01 * 0.4 * 10.0).abs() < 1e-12, "{:?}", fill);
+    assert!((fill.shares - (10.0 - fill.fee / 0.4)).abs() < 1e-9, "{:?}", fill);
+    assert_eq!(fill.amount, 4.0);
+
+    // Sell at 0.7: the fee uses 1 - p and comes out of the proceeds
+    let fill = filled(with_fee.fill_sell(0.7, 0.7, 10.0, None));
+    assert!((fill.fee - 0.01 * 0.3 * 10.0).abs() < 1e-12, "{:?}", fill);
+    assert!((fill.amount - (7.0 - fill.fee)).abs() < 1e-9, "{:?}", fill);
+}
+
+#[test]
+fn test_no_liquidity_outside_price_range() {
+    assert_eq!(rejection(model().fill_buy(0.999, 1.0, 5.0, None)), "no liquidity");
+    assert_eq!(rejection(model().fill_sell(0.5, 0.5, 0.0, None)), "no liquidity");
+}
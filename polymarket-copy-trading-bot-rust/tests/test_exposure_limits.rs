@@ -0,0 +1,142 @@
+//! Portfolio exposure caps and the trader attribution behind the per-trader
+//! cap (no database or network).
+
+mod common;
+
+use common::{activity, test_env, try_test_env};
+use polymarket_copy_trading_bot_rust::config::ExposureLimitsConfig;
+use polymarket_copy_trading_bot_rust::services::exposure_limits::{exposure_headroom, lot_shares, Exposure, ExposureItem};
+use polymarket_copy_trading_bot_rust::services::pnl_ledger::{open_lots_by_trader, LedgerEntry, KIND_BUY, KIND_REDEEM, KIND_SELL};
+use serde_json::json;
+
+const ALICE: &str = "0xaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa";
+const BOB: &str = "0xbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbb";
+
+fn item(asset: &str, event_slug: &str, value: f64, traders: &[(&str, f64)]) -> ExposureItem {
+    ExposureItem {
+        asset: asset.to_string(),
+        condition_id: format!("0xc{}", asset),
+        event_slug: event_slug.to_string(),
+        value,
+        traders: traders.iter().map(|&(t, share)| (t.to_string(), share)).collect(),
+    }
+}
+
+fn limits(section: &str) -> ExposureLimitsConfig {
+    test_env(&format!("[exposure]\n{}", section)).exposure_limits
+}
+
+fn entry(kind: &str, trader: Option<&str>, asset: &str, size: f64) -> LedgerEntry {
+    LedgerEntry {
+        kind: kind.to_string(),
+        trader_address: trader.map(str::to_string),
+        asset: asset.to_string(),
+        condition_id: "0xc1".to_string(),
+        title: String::new(),
+        slug: String::new(),
+        outcome: String::new(),
+        price: 0.5,
+        size,
+        usdc: size * 0.5,
+        timestamp: 0,
+        transaction_hash: String::new(),
+        simulated: false,
+    }
+}
+
+fn portfolio() -> Exposure {
+    let mut exposure = Exposure::default();
+    exposure.add(&item("1", "election", 120.0, &[(ALICE, 1.0)]), Some("politics".to_string()));
+    exposure.add(&item("2", "election", 60.0, &[(ALICE, 0.5), (BOB, 0.5)]), Some("politics".to_string()));
+    exposure.add(&item("3", "derby", 40.0, &[(BOB, 1.0)]), Some("sports".to_string()));
+    exposure
+}
+
+#[test]
+fn test_exposure_buckets() {
+    let exposure = portfolio();
+    assert_eq!(exposure.total, 220.0);
+    assert_eq!(exposure.by_event["election"], 180.0);
+    assert_eq!(exposure.by_category["politics"], 180.0);
+    assert_eq!(exposure.by_trader[ALICE], 150.0);
+    assert_eq!(exposure.by_trader[BOB], 70.0);
+    assert_eq!(exposure.open_assets.len(), 3);
+}
+
+#[test]
+fn test_buys_shrink_to_the_tightest_cap() {
+    let exposure = portfolio();
+    let trade = activity(json!({ "asset": "4", "eventSlug": "election" }));
+
+    let no_caps = ExposureLimitsConfig::default();
+    assert_eq!(exposure_headroom(&no_caps, &exposure, &trade, 50.0, ALICE, None), (50.0, None));
+
+    let (allowed, binding) = exposure_headroom(&limits("max_total_usd = 250\n"), &exposure, &trade, 50.0, ALICE, None);
+    assert_eq!(allowed, 30.0);
+    assert_eq!(binding.unwrap(), "total exposure $220.00 of $250.00");
+
+    // Event ($20 left) binds before total ($80 left)
+    let caps = limits("max_total_usd = 300\nmax_event_usd = 200\n");
+    let (allowed, binding) = exposure_headroom(&caps, &exposure, &trade, 50.0, ALICE, None);
+    assert_eq!(allowed, 20.0);
+    assert!(binding.unwrap().starts_with("event \"election\""));
+
+    let caps = limits("max_category_usd = 190\n");
+    assert_eq!(exposure_headroom(&caps, &exposure, &trade, 50.0, ALICE, Some("politics")).0, 10.0);
+    assert_eq!(exposure_headroom(&caps, &exposure, &trade, 50.0, ALICE, Some("weather")).0, 50.0);
+
+    // Per trader: addresses are compared lowercased
+    let caps = limits("max_trader_usd = 160\n");
+    assert_eq!(exposure_headroom(&caps, &exposure, &trade, 50.0, &ALICE.to_uppercase().replace("0X", "0x"), None).0, 10.0);
+    assert_eq!(exposure_headroom(&caps, &exposure, &trade, 50.0, BOB, None).0, 50.0);
+
+    // Exhausted headroom never goes negative
+    let caps = limits("max_total_usd = 100\n");
+    assert_eq!(exposure_headroom(&caps, &exposure, &trade, 50.0, ALICE, None).0, 0.0);
+}
+
+#[test]
+fn test_open_position_count_only_blocks_new_assets() {
+    let exposure = portfolio();
+    let caps = limits("max_open_positions = 3\n");
+
+    let (allowed, binding) = exposure_headroom(&caps, &exposure, &activity(json!({ "asset": "4" })), 50.0, ALICE, None);
+    assert_eq!(allowed, 0.0);
+    assert_eq!(binding.unwrap(), "3 of 3 open positions");
+    // Adding to a position we already hold is fine
+    assert_eq!(exposure_headroom(&caps, &exposure, &activity(json!({ "asset": "2" })), 50.0, ALICE, None).0, 50.0);
+}
+
+#[test]
+fn test_positions_stay_attributed_to_the_trader_who_opened_them() {
+    // Alice opened 100 tokens, Bob added 50; a 120 token sell closes Alice's lot first
+    let ledger = vec![
+        entry(KIND_BUY, Some(ALICE), "1", 100.0),
+        entry(KIND_BUY, Some(BOB), "1", 50.0),
+        entry(KIND_SELL, Some(BOB), "1", 120.0),
+        entry(KIND_BUY, Some(ALICE), "2", 40.0),
+    ];
+    let lots = open_lots_by_trader(&ledger);
+    assert_eq!(lots["1"].len(), 1);
+    assert!((lots["1"][BOB] - 30.0).abs() < 1e-9);
+    // Nothing in the ledger says Alice exited asset 2, so it is still hers
+    // whatever she holds herself now
+    assert_eq!(lot_shares(&lots["2"]), vec![(ALICE.to_string(), 1.0)]);
+
+    let redeemed = [ledger.clone(), vec![entry(KIND_REDEEM, None, "", 70.0)]].concat();
+    assert!(open_lots_by_trader(&redeemed).is_empty());
+
+    let mut split = lots["1"].clone();
+    split.insert(ALICE.to_string(), 90.0);
+    let mut shares = lot_shares(&split);
+    shares.sort_by(|a, b| a.0.cmp(&b.0));
+    assert_eq!(shares, vec![(ALICE.to_string(), 0.75), (BOB.to_string(), 0.25)]);
+}
+
+#[test]
+fn test_exposure_settings_are_validated() {
+    assert!(!limits("").is_enabled());
+    let message = try_test_env("[exposure]\nmax_total_usd = 0\nmax_open_positions = 0\n").unwrap_err().to_string();
+    assert!(message.contains("MAX_TOTAL_EXPOSURE_USD"), "{}", message);
+    assert!(message.contains("MAX_OPEN_POSITIONS"), "{}", message);
+}
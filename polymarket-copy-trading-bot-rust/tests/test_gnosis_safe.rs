@@ -0,0 +1,171 @@
+//! Safe transaction builder tests against a local anvil fork of Polygon.
+//!
+//! A fresh 1-of-1 Safe is deployed through the canonical v1.3.0 proxy factory.
+//! The fork test is ignored by default: it needs `anvil` on PATH and a Polygon
+//! RPC in SAFE_TEST_FORK_URL, and runs with `cargo test --test test_gnosis_safe -- --ignored`.
+
+use alloy::primitives::{address, Address, Bytes, U256};
+use alloy::providers::{Provider, ProviderBuilder};
+use alloy::signers::local::PrivateKeySigner;
+use alloy::sol;
+use alloy::sol_types::SolCall;
+use polymarket_copy_trading_bot_rust::utils::gnosis_safe::{
+    build_safe_tx, encode_multisend, safe_tx_hash, IGnosisSafe, SafeCall, SafeExecutor,
+};
+use std::process::{Child, Command, Stdio};
+use std::time::Duration;
+
+const ANVIL_PORT: u16 = 8547;
+// anvil's first default account
+const OWNER_KEY: &str = "0xac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80";
+const SAFE_PROXY_FACTORY: Address = address!("a6B71E26C5e0845f74c812102Ca7114b6a896AB2");
+const SAFE_SINGLETON_L2: Address = address!("3E5c63644E683549055b9Be8653de26E0B4CD36E");
+
+sol! {
+    #[sol(rpc)]
+    interface ISafeProxyFactory {
+        function createProxyWithNonce(address _singleton, bytes memory initializer, uint256 saltNonce) external returns (address proxy);
+    }
+
+    interface ISafeSetup {
+        function setup(
+            address[] calldata _owners,
+            uint256 _threshold,
+            address to,
+            bytes calldata data,
+            address fallbackHandler,
+            address paymentToken,
+            uint256 payment,
+            address paymentReceiver
+        ) external;
+    }
+}
+
+struct AnvilGuard(Child);
+
+impl Drop for AnvilGuard {
+    fn drop(&mut self) {
+        let _ = self.0.kill();
+    }
+}
+
+fn start_anvil() -> AnvilGuard {
+    let fork_url = std::env::var("SAFE_TEST_FORK_URL").expect("SAFE_TEST_FORK_URL must point at a Polygon RPC");
+    let child = Command::new("anvil")
+        .args(["--fork-url", &fork_url, "--port", &ANVIL_PORT.to_string()])
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()
+        .expect("failed to spawn anvil - is it on PATH?");
+    AnvilGuard(child)
+}
+
+#[test]
+fn test_multisend_encoding() {
+    let to = address!("1111111111111111111111111111111111111111");
+    let calls = vec![
+        SafeCall::new(to, vec![0xab, 0xcd]).with_value(U256::from(5)),
+        SafeCall::new(to, Vec::new()),
+    ];
+    let encoded = encode_multisend(&calls);
+
+    // multiSend(bytes) selector, offset, length, then the packed transactions
+    assert_eq!(&encoded[..4], &[0x8d, 0x80, 0xff, 0x0a]);
+    let packed_len = U256::from_be_slice(&encoded[36..68]).to::<usize>();
+    assert_eq!(packed_len, (1 + 20 + 32 + 32) * 2 + 2);
+    let packed = &encoded[68..68 + packed_len];
+    assert_eq!(packed[0], 0);
+    assert_eq!(&packed[1..21], to.as_slice());
+    assert_eq!(U256::from_be_slice(&packed[21..53]), U256::from(5));
+    assert_eq!(U256::from_be_slice(&packed[53..85]), U256::from(2));
+    assert_eq!(&packed[85..87], &[0xab, 0xcd]);
+
+    let single = build_safe_tx(&calls[..1], U256::ZERO).unwrap();
+    assert_eq!(single.operation, 0);
+    assert_eq!(single.to, to);
+    let batch = build_safe_tx(&calls, U256::ZERO).unwrap();
+    assert_eq!(batch.operation, 1);
+    assert!(build_safe_tx(&[], U256::ZERO).is_err());
+}
+
+#[tokio::test]
+#[ignore = "needs anvil and SAFE_TEST_FORK_URL"]
+async fn test_safe_exec_on_anvil_fork() {
+    let _anvil = start_anvil();
+    let rpc_url = format!("http://127.0.0.1:{}", ANVIL_PORT);
+    let signer: PrivateKeySigner = OWNER_KEY.parse().unwrap();
+    let owner = signer.address();
+    let provider = ProviderBuilder::new()
+        .wallet(signer.clone())
+        .connect_http(rpc_url.parse().unwrap());
+
+    let mut ready = false;
+    for _ in 0..60 {
+        if provider.get_block_number().await.is_ok() {
+            ready = true;
+            break;
+        }
+        tokio::time::sleep(Duration::from_millis(500)).await;
+    }
+    assert!(ready, "anvil did not start");
+    let chain_id = provider.get_chain_id().await.unwrap();
+
+    // Deploy a 1-of-1 Safe owned by the signer
+    let initializer = ISafeSetup::setupCall {
+        _owners: vec![owner],
+        _threshold: U256::from(1),
+        to: Address::ZERO,
+        data: Bytes::new(),
+        fallbackHandler: Address::ZERO,
+        paymentToken: Address::ZERO,
+        payment: U256::ZERO,
+        paymentReceiver: Address::ZERO,
+    }
+    .abi_encode();
+    let factory = ISafeProxyFactory::new(SAFE_PROXY_FACTORY, &provider);
+    let create = factory.createProxyWithNonce(SAFE_SINGLETON_L2, initializer.into(), U256::from(chrono::Utc::now().timestamp()));
+    let safe = create.call().await.unwrap();
+    assert!(create.send().await.unwrap().get_receipt().await.unwrap().status());
+
+    // Fund the Safe with 1 native token
+    let fund = alloy::rpc::types::TransactionRequest::default()
+        .to(safe)
+        .value(U256::from(10u128.pow(18)));
+    provider.send_transaction(fund).await.unwrap().get_receipt().await.unwrap();
+
+    let executor = SafeExecutor::new(safe, signer, &rpc_url).unwrap();
+    executor.check_can_execute().await.unwrap();
+    assert_eq!(executor.nonce().await.unwrap(), U256::ZERO);
+
+    // Our EIP-712 hash must match the Safe's own
+    let recipient = address!("00000000000000000000000000000000000bEEF1");
+    let call = SafeCall::new(recipient, Vec::new()).with_value(U256::from(1000));
+    let tx = build_safe_tx(std::slice::from_ref(&call), U256::ZERO).unwrap();
+    let on_chain_hash = IGnosisSafe::new(safe, &provider)
+        .getTransactionHash(
+            tx.to, tx.value, tx.data.clone(), tx.operation, tx.safeTxGas, tx.baseGas,
+            tx.gasPrice, tx.gasToken, tx.refundReceiver, tx.nonce,
+        )
+        .call()
+        .await
+        .unwrap();
+    assert_eq!(safe_tx_hash(&tx, safe, chain_id), on_chain_hash);
+
+    // Single call
+    executor.execute(&[call]).await.unwrap();
+    assert_eq!(provider.get_balance(recipient).await.unwrap(), U256::from(1000));
+    assert_eq!(executor.nonce().await.unwrap(), U256::from(1));
+
+    // MultiSend batch
+    let other = address!("00000000000000000000000000000000000bEEF2");
+    executor
+        .execute(&[
+            SafeCall::new(recipient, Vec::new()).with_value(U256::from(1)),
+            SafeCall::new(other, Vec::new()).with_value(U256::from(2)),
+        ])
+        .await
+        .unwrap();
+    assert_eq!(provider.get_balance(recipient).await.unwrap(), U256::from(1001));
+    assert_eq!(provider.get_balance(other).await.unwrap(), U256::from(2));
+    assert_eq!(executor.nonce().await.unwrap(), U256::from(2));
+}
@@ -0,0 +1,174 @@
+//! Extended health checks: RTDS liveness, clock skew, the `/healthz` and
+//! `/readyz` responses and health settings (no network besides localhost).
+
+mod common;
+
+use common::{test_env, try_test_env};
+use polymarket_copy_trading_bot_rust::services::health_monitor::{health_response, serve_health_endpoints};
+use polymarket_copy_trading_bot_rust::utils::health_check::{
+    check_clock_skew, check_rtds, merge_account_checks, BalanceCheckResult, CheckResult, HealthCheckResult, HealthChecks, RtdsState,
+};
+use std::io::{Read, Write};
+use std::sync::{Arc, RwLock};
+
+fn result(healthy: bool, timestamp: i64, rtds: Option<CheckResult>) -> HealthCheckResult {
+    let balance = BalanceCheckResult { status: "ok".to_string(), message: "Balance: $100.00".to_string(), balance: Some(100.0) };
+    HealthCheckResult {
+        healthy,
+        checks: HealthChecks {
+            database: CheckResult::ok("Connected"),
+            rpc: CheckResult::ok("RPC endpoint responding"),
+            balance: balance.clone(),
+            polymarket_api: CheckResult::ok("API responding"),
+            gas: balance,
+            allowances: if healthy { CheckResult::ok("approved") } else { CheckResult::error("Missing USDC approve") },
+            clob_api_key: CheckResult::ok("API key accepted"),
+            clock_skew: CheckResult::ok("0s from CLOB server time"),
+            wallet: CheckResult::ok("Proxy wallet is the signer EOA"),
+            rtds,
+        },
+        timestamp,
+    }
+}
+
+/// Plain blocking GET, retried until the server is listening
+fn get(port: u16, path: &str) -> String {
+    for _ in 0..50 {
+        if let Ok(mut stream) = std::net::TcpStream::connect(("127.0.0.1", port)) {
+            write!(stream, "GET {} HTTP/1.1\r\nHost: localhost\r\n\r\n", path).unwrap();
+            let mut response = String::new();
+            stream.read_to_string(&mut response).unwrap();
+            return response;
+        }
+        std::thread::sleep(std::time::Duration::from_millis(20));
+    }
+    panic!("health endpoint never came up");
+}
+
+#[test]
+fn test_rtds_check_tracks_connection_and_silence() {
+    let now = 1_700_000_000_000;
+    let disconnected = RtdsState { connected_at: None, last_message_at: None };
+    assert_eq!(check_rtds(&disconnected, now, 120).status, "error");
+
+    let fresh = RtdsState { connected_at: Some(now - 10_000), last_message_at: None };
+    let check = check_rtds(&fresh, now, 120);
+    assert_eq!(check.status, "ok");
+    assert!(check.message.contains("no message yet"), "{}", check.message);
+
+    let chatty = RtdsState { connected_at: Some(now - 3_600_000), last_message_at: Some(now - 2_000) };
+    let check = check_rtds(&chatty, now, 120);
+    assert_eq!(check.status, "ok");
+    assert!(check.message.contains("Connected for 3600s, last message 2s ago"), "{}", check.message);
+
+    let silent = RtdsState { connected_at: Some(now - 3_600_000), last_message_at: Some(now - 300_000) };
+    let check = check_rtds(&silent, now, 120);
+    assert_eq!(check.status, "error");
+    assert!(check.message.contains("No message for 300s"), "{}", check.message);
+}
+
+#[test]
+fn test_clock_skew_check() {
+    assert_eq!(check_clock_skew(1_700_000_000, 1_700_000_003, 5).status, "ok");
+    assert_eq!(check_clock_skew(1_700_000_000, 1_699_999_996, 5).status, "ok");
+
+    let ahead = check_clock_skew(1_700_000_000, 1_700_000_030, 5);
+    assert_eq!(ahead.status, "error");
+    assert!(ahead.message.contains("30s ahead of"), "{}", ahead.message);
+
+    let behind = check_clock_skew(1_700_000_000, 1_699_999_990, 5);
+    assert!(behind.message.contains("10s behind"), "{}", behind.message);
+}
+
+#[test]
+fn test_account_checks_merge_to_the_worst_status() {
+    let single = merge_account_checks(vec![("primary".to_string(), CheckResult::ok("0.5000 MATIC"))]);
+    assert_eq!((single.status.as_str(), single.message.as_str()), ("ok", "0.5000 MATIC"));
+
+    let merged = merge_account_checks(vec![
+        ("primary".to_string(), CheckResult::ok("approved")),
+        ("hedge".to_string(), CheckResult::error("Missing USDC approve")),
+        ("alt".to_string(), CheckResult::warning("Low gas")),
+    ]);
+    assert_eq!(merged.status, "error");
+    assert_eq!(merged.message, "primary: approved; hedge: Missing USDC approve; alt: Low gas");
+
+    let warned = merge_account_checks(vec![
+        ("primary".to_string(), CheckResult::ok("approved")),
+        ("hedge".to_string(), CheckResult::warning("Missing USDC approve")),
+    ]);
+    assert_eq!(warned.status, "warning");
+}
+
+#[test]
+fn test_readyz_follows_latest_result() {
+    let now = 1_700_000_000;
+    let (status, body) = health_response("/readyz", &result(true, now, None), now, Some(240));
+    assert_eq!(status, 200);
+    // Only the status: wallet addresses and balances stay out of the probe
+    assert_eq!(body, r#"{"status":"ok"}"#);
+
+    let (status, body) = health_response("/readyz", &result(false, now, None), now, Some(240));
+    assert_eq!(status, 503);
+    assert_eq!(body, r#"{"status":"error"}"#);
+    assert_eq!(health_response("/metrics", &result(true, now, None), now, None).0, 404);
+}
+
+#[test]
+fn test_healthz_fails_on_stale_checks_or_dead_rtds() {
+    let now = 1_700_000_000;
+    // Unhealthy (e.g. missing allowances) is not a reason to restart
+    assert_eq!(health_response("/healthz", &result(false, now - 30, None), now, Some(240)).0, 200);
+    assert_eq!(health_response("/healthz?verbose=1", &result(true, now, Some(CheckResult::ok("Connected"))), now, Some(240)).0, 200);
+
+    let (status, body) = health_response("/healthz", &result(true, now - 600, None), now, Some(240));
+    assert_eq!(status, 503);
+    assert!(body.contains("last health check was 600s ago"), "{}", body);
+    // Without periodic checks the startup result never goes stale
+    assert_eq!(health_response("/healthz", &result(true, now - 600, None), now, None).0, 200);
+
+    let dead = result(true, now, Some(CheckResult::error("No message for 300s (connected 3600s ago)")));
+    let (status, body) = health_response("/healthz", &dead, now, Some(240));
+    assert_eq!(status, 503);
+    assert!(body.contains("RTDS: No message for 300s"), "{}", body);
+}
+
+#[tokio::test]
+async fn test_endpoints_are_served_over_http() {
+    let port = std::net::TcpListener::bind("127.0.0.1:0").unwrap().local_addr().unwrap().port();
+    let now = chrono::Utc::now().timestamp();
+    let health = Arc::new(RwLock::new(result(false, now, None)));
+    let address = std::net::SocketAddr::from(([127, 0, 0, 1], port));
+    tokio::spawn(serve_health_endpoints(address, health.clone(), Some(240)));
+
+    let healthz = tokio::task::spawn_blocking(move || get(port, "/healthz")).await.unwrap();
+    assert!(healthz.starts_with("HTTP/1.1 200 OK"), "{}", healthz);
+    let readyz = tokio::task::spawn_blocking(move || get(port, "/readyz")).await.unwrap();
+    assert!(readyz.starts_with("HTTP/1.1 503 Service Unavailable"), "{}", readyz);
+
+    *health.write().unwrap() = result(true, now, None);
+    let readyz = tokio::task::spawn_blocking(move || get(port, "/readyz")).await.unwrap();
+    assert!(readyz.starts_with("HTTP/1.1 200 OK"), "{}", readyz);
+}
+
+#[test]
+fn test_health_settings() {
+    let env = test_env("");
+    assert_eq!(env.health.check_interval_seconds, 60);
+    assert_eq!(env.health.port, None);
+    assert_eq!(env.health.bind_address.to_string(), "127.0.0.1");
+    assert_eq!(env.health.max_clock_skew_seconds, 5);
+
+    let env = test_env("\n[health]\nport = 8080\nbind_address = \"0.0.0.0\"\ncheck_interval_seconds = 0\n");
+    assert_eq!(env.health.port, Some(8080));
+    assert_eq!(env.health.bind_address.to_string(), "0.0.0.0");
+    assert_eq!(env.health.check_interval_seconds, 0);
+
+    let message = try_test_env("\n[health]\nport = 0\nbind_address = \"localhost:80\"\ncheck_interval_seconds = 3\nmin_gas_balance = -1\n")
+        .unwrap_err()
+        .to_string();
+    assert!(message.contains("HEALTH_PORT"), "{}", message);
+    assert!(message.contains("HEALTH_BIND_ADDRESS"), "{}", message);
+    assert!(message.contains("HEALTH_CHECK_INTERVAL_SECONDS"), "{}", message);
+    assert!(message.contains("HEALTH_MIN_GAS_BALANCE"), "{}", message);
+}
@@ -0,0 +1,163 @@
+//! Shared HTTP layer: token bucket, Retry-After handling and the response
+//! cache, against a local stub server (no network).
+
+mod common;
+
+use common::{test_env, try_test_env};
+use polymarket_copy_trading_bot_rust::utils::fetch_data::fetch_data;
+use polymarket_copy_trading_bot_rust::utils::http::{cached, retry_after, TokenBucket};
+use reqwest::header::{HeaderMap, HeaderValue, RETRY_AFTER};
+use std::io::{BufRead, BufReader, Write};
+use std::net::TcpListener;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+/// Answer the first `rate_limited` requests with 429 + `Retry-After: 1`, then 200
+fn serve_rate_limited(rate_limited: usize) -> (String, Arc<AtomicUsize>) {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let url = format!("http://{}/positions", listener.local_addr().unwrap());
+    let hits = Arc::new(AtomicUsize::new(0));
+    let seen = hits.clone();
+
+    std::thread::spawn(move || {
+        for stream in listener.incoming() {
+            let mut stream = stream.unwrap();
+            let mut request_line = String::new();
+            BufReader::new(&stream).read_line(&mut request_line).unwrap();
+            let response = if seen.fetch_add(1, Ordering::SeqCst) < rate_limited {
+                "HTTP/1.1 429 Too Many Requests\r\nRetry-After: 1\r\nContent-Length: 0\r\nConnection: close\r\n\r\n".to_string()
+            } else {
+                let body = r#"[{"asset":"1"}]"#;
+                format!(
+                    "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                    body.len(),
+                    body
+                )
+            };
+            let _ = stream.write_all(response.as_bytes());
+        }
+    });
+
+    (url, hits)
+}
+
+#[test]
+fn test_token_bucket_bursts_then_refills() {
+    let start = Instant::now();
+    let mut bucket = TokenBucket::new(2.0, 3, start);
+
+    for _ in 0..3 {
+        assert!(bucket.try_acquire(start).is_ok());
+    }
+    let wait = bucket.try_acquire(start).unwrap_err();
+    assert_eq!(wait, Duration::from_millis(500));
+
+    // Half a second at 2/sec buys one more request
+    let later = start + Duration::from_millis(500);
+    assert!(bucket.try_acquire(later).is_ok());
+    assert!(bucket.try_acquire(later).is_err());
+
+    // Refill never exceeds the burst
+    let much_later = start + Duration::from_secs(60);
+    for _ in 0..3 {
+        assert!(bucket.try_acquire(much_later).is_ok());
+    }
+    assert!(bucket.try_acquire(much_later).is_err());
+}
+
+#[test]
+fn test_token_bucket_pause_holds_requests() {
+    let start = Instant::now();
+    let mut bucket = TokenBucket::new(10.0, 5, start);
+    bucket.pause_until(start + Duration::from_secs(2));
+
+    assert_eq!(bucket.try_acquire(start).unwrap_err(), Duration::from_secs(2));
+    // A shorter pause doesn't cut an existing one short
+    bucket.pause_until(start + Duration::from_secs(1));
+    assert_eq!(bucket.try_acquire(start + Duration::from_secs(1)).unwrap_err(), Duration::from_secs(1));
+    assert!(bucket.try_acquire(start + Duration::from_secs(2)).is_ok());
+}
+
+#[test]
+fn test_retry_after_parsing() {
+    let mut headers = HeaderMap::new();
+    assert_eq!(retry_after(&headers), None);
+
+    headers.insert(RETRY_AFTER, HeaderValue::from_static("3"));
+    assert_eq!(retry_after(&headers), Some(Duration::from_secs(3)));
+
+    headers.insert(RETRY_AFTER, HeaderValue::from_static("0.5"));
+    assert_eq!(retry_after(&headers), Some(Duration::from_millis(500)));
+
+    // HTTP-date form falls back to exponential backoff
+    headers.insert(RETRY_AFTER, HeaderValue::from_static("Wed, 21 Oct 2015 07:28:00 GMT"));
+    assert_eq!(retry_after(&headers), None);
+}
+
+#[tokio::test]
+async fn test_concurrent_callers_share_one_fetch() {
+    let env = test_env("");
+    let calls = Arc::new(AtomicUsize::new(0));
+    let url = "http://cache.test/positions?user=0xabc";
+
+    let fetch = || {
+        let calls = calls.clone();
+        async move {
+            calls.fetch_add(1, Ordering::SeqCst);
+            tokio::time::sleep(Duration::from_millis(50)).await;
+            Ok(serde_json::json!([{ "asset": "1" }]))
+        }
+    };
+    let (a, b, c) = tokio::join!(cached(url, &env, fetch), cached(url, &env, fetch), cached(url, &env, fetch));
+    assert_eq!(a.unwrap(), b.unwrap());
+    assert_eq!(c.unwrap()[0]["asset"], "1");
+    assert_eq!(calls.load(Ordering::SeqCst), 1);
+
+    // A different URL is a different entry
+    cached("http://cache.test/positions?user=0xdef", &env, fetch).await.unwrap();
+    assert_eq!(calls.load(Ordering::SeqCst), 2);
+}
+
+#[tokio::test]
+async fn test_failures_are_not_cached_and_ttl_zero_disables() {
+    let env = test_env("");
+    let url = "http://cache.test/activity?user=0xfail";
+    let failed = cached(url, &env, || async { Err(anyhow::anyhow!("boom")) }).await;
+    assert!(failed.is_err());
+    let value = cached(url, &env, || async { Ok(serde_json::json!(1)) }).await.unwrap();
+    assert_eq!(value, 1);
+
+    let uncached = test_env("[network]\ncache_ttl_ms = 0\n");
+    let calls = AtomicUsize::new(0);
+    for _ in 0..2 {
+        cached("http://cache.test/value?user=0xabc", &uncached, || async {
+            calls.fetch_add(1, Ordering::SeqCst);
+            Ok(serde_json::json!(0))
+        })
+        .await
+        .unwrap();
+    }
+    assert_eq!(calls.load(Ordering::SeqCst), 2);
+}
+
+#[tokio::test]
+async fn test_fetch_data_waits_out_retry_after() {
+    let env = test_env("");
+    let (url, hits) = serve_rate_limited(1);
+
+    let started = Instant::now();
+    let value = fetch_data(&url, &env).await.unwrap();
+    assert_eq!(value[0]["asset"], "1");
+    assert_eq!(hits.load(Ordering::SeqCst), 2);
+    assert!(started.elapsed() >= Duration::from_millis(900), "{:?}", started.elapsed());
+}
+
+#[test]
+fn test_rate_limit_settings_are_validated() {
+    let message = try_test_env("[network]\nrate_limit_per_second = 0\nrate_limit_burst = 0\n")
+        .unwrap_err()
+        .to_string();
+    assert!(message.contains("HTTP_RATE_LIMIT_PER_SECOND"), "{}", message);
+    assert!(message.contains("HTTP_RATE_LIMIT_BURST"), "{}", message);
+}
@@ -0,0 +1,73 @@
+//! Simulated limit order fills against resting book depth (no database or network).
+
+use polymarket_copy_trading_bot_rust::services::limit_orders::{LimitOrder, STATUS_OPEN};
+use serde_json::json;
+
+fn buy_order(price: f64, size: f64) -> LimitOrder {
+    LimitOrder {
+        id: None,
+        trader_address: "0xaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa".to_string(),
+        source_transaction_hash: "0xtx".to_string(),
+        asset: "111".to_string(),
+        condition_id: "0xc111".to_string(),
+        event_slug: "event".to_string(),
+        title: "Market".to_string(),
+        slug: "market".to_string(),
+        outcome: "Yes".to_string(),
+        side: "BUY".to_string(),
+        order_type: "GTC".to_string(),
+        trader_price: price,
+        price,
+        price_cap: price,
+        size,
+        filled_size: 0.0,
+        fills: Vec::new(),
+        consumed_depth: Vec::new(),
+        status: STATUS_OPEN.to_string(),
+        reprice_count: 0,
+        placed_at: 0,
+        priced_at: 0,
+        expires_at: None,
+        closed_at: None,
+    }
+}
+
+fn book(asks: &[(&str, &str)]) -> serde_json::Value {
+    json!({
+        "asks": asks.iter().map(|(price, size)| json!({ "price": price, "size": size })).collect::<Vec<_>>(),
+        "bids": [],
+    })
+}
+
+#[test]
+fn test_resting_depth_is_only_filled_against_once() {
+    let mut order = buy_order(0.50, 100.0);
+    let snapshot = book(&[("0.49", "10"), ("0.50", "20"), ("0.51", "50")]);
+    assert!((order.fresh_depth(&snapshot) - 30.0).abs() < 1e-9);
+
+    // The same book on the next poll has nothing new to offer
+    order.consume_depth(&snapshot, 30.0);
+    assert!(order.fresh_depth(&snapshot).abs() < 1e-9);
+
+    // Depth added at a crossing level is fresh
+    let grown = book(&[("0.49", "10"), ("0.50", "25"), ("0.51", "50")]);
+    assert!((order.fresh_depth(&grown) - 5.0).abs() < 1e-9);
+}
+
+#[test]
+fn test_partial_fills_consume_the_best_levels_first() {
+    let mut order = buy_order(0.50, 100.0);
+    let snapshot = book(&[("0.50", "20"), ("0.49", "10")]);
+
+    // Paper cash only covered 15 tokens: 10 @ 0.49, then 5 @ 0.50
+    order.consume_depth(&snapshot, 15.0);
+    assert!((order.fresh_depth(&snapshot) - 15.0).abs() < 1e-9);
+
+    // A level that shrank below what we used offers nothing, and a level
+    // that left the book is forgotten
+    let thinned = book(&[("0.50", "3")]);
+    assert!(order.fresh_depth(&thinned).abs() < 1e-9);
+    order.consume_depth(&thinned, 0.0);
+    let refilled = book(&[("0.49", "10"), ("0.50", "3")]);
+    assert!((order.fresh_depth(&refilled) - 10.0).abs() < 1e-9);
+}
@@ -0,0 +1,109 @@
+//! Market filters on copied buys: price, keyword, tag, liquidity / volume and
+//! time-to-resolution checks (no network).
+
+mod common;
+
+use chrono::{TimeZone, Utc};
+use common::{activity, test_env, try_test_env};
+use polymarket_copy_trading_bot_rust::config::MarketFilterConfig;
+use polymarket_copy_trading_bot_rust::interfaces::UserActivity;
+use polymarket_copy_trading_bot_rust::services::market_filter::check_market_filters;
+use polymarket_copy_trading_bot_rust::utils::gamma_api::GammaMarket;
+use serde_json::json;
+
+fn buy(title: &str, price: f64) -> UserActivity {
+    activity(json!({
+        "price": price,
+        "title": title,
+        "slug": title.to_lowercase().replace(' ', "-"),
+        "eventSlug": "weather",
+    }))
+}
+
+fn market(tags: &[&str], liquidity: f64, volume: f64, end_date: Option<&str>) -> GammaMarket {
+    serde_json::from_value(json!({
+        "conditionId": "0xc0",
+        "question": "Will it rain in London tomorrow?",
+        "endDate": end_date,
+        "liquidityNum": liquidity,
+        "volumeNum": volume,
+        "tags": tags.iter().map(|t| json!({ "label": t, "slug": t.to_lowercase() })).collect::<Vec<_>>(),
+    }))
+    .unwrap()
+}
+
+fn filters(section: &str) -> MarketFilterConfig {
+    test_env(&format!("[filters]\n{}", section)).market_filter
+}
+
+fn rejected_by(config: &MarketFilterConfig, trade: &UserActivity, market: Option<&GammaMarket>) -> Option<&'static str> {
+    let now = Utc.with_ymd_and_hms(2026, 1, 1, 0, 0, 0).unwrap();
+    check_market_filters(config, trade, market, now).err().map(|r| r.filter)
+}
+
+#[test]
+fn test_price_and_keyword_filters_need_no_market_data() {
+    let config = filters("min_price = 0.1\nmax_price = 0.9\nexclude_keywords = [\"Bitcoin\"]\n");
+    assert!(config.is_enabled());
+    assert!(!config.needs_market_data());
+
+    assert_eq!(rejected_by(&config, &buy("Will it rain?", 0.5), None), None);
+    assert_eq!(rejected_by(&config, &buy("Will it rain?", 0.05), None), Some("min_price"));
+    assert_eq!(rejected_by(&config, &buy("Will it rain?", 0.95), None), Some("max_price"));
+    // Keywords are matched case-insensitively against title and slugs
+    assert_eq!(rejected_by(&config, &buy("BITCOIN above 100k?", 0.5), None), Some("exclude_keywords"));
+
+    let config = filters("include_keywords = [\"rain\", \"snow\"]\n");
+    assert_eq!(rejected_by(&config, &buy("Will it snow?", 0.5), None), None);
+    assert_eq!(rejected_by(&config, &buy("Who wins the derby?", 0.5), None), Some("include_keywords"));
+}
+
+#[test]
+fn test_tag_filters() {
+    let trade = buy("Will it rain?", 0.5);
+    let weather = market(&["Weather"], 10_000.0, 10_000.0, None);
+    let sports = market(&["Sports", "Soccer"], 10_000.0, 10_000.0, None);
+
+    let config = filters("exclude_tags = [\"sports\"]\n");
+    assert!(config.needs_market_data());
+    assert_eq!(rejected_by(&config, &trade, Some(&weather)), None);
+    assert_eq!(rejected_by(&config, &trade, Some(&sports)), Some("exclude_tags"));
+    assert_eq!(rejected_by(&config, &trade, None), Some("market_data"));
+
+    let config = filters("include_tags = [\"Weather\", \"Politics\"]\n");
+    assert_eq!(rejected_by(&config, &trade, Some(&weather)), None);
+    assert_eq!(rejected_by(&config, &trade, Some(&sports)), Some("include_tags"));
+}
+
+#[test]
+fn test_liquidity_and_volume_floors() {
+    let trade = buy("Will it rain?", 0.5);
+    let config = filters("min_liquidity_usd = 5000\nmin_volume_usd = 20000\n");
+
+    assert_eq!(rejected_by(&config, &trade, Some(&market(&[], 5_000.0, 20_000.0, None))), None);
+    assert_eq!(rejected_by(&config, &trade, Some(&market(&[], 4_999.0, 50_000.0, None))), Some("min_liquidity"));
+    assert_eq!(rejected_by(&config, &trade, Some(&market(&[], 9_000.0, 19_999.0, None))), Some("min_volume"));
+}
+
+#[test]
+fn test_time_to_resolution_window() {
+    let trade = buy("Will it rain?", 0.5);
+    let config = filters("min_hours_to_end = 6\nmax_hours_to_end = 48\n");
+
+    let ends = |end: &str| market(&[], 10_000.0, 10_000.0, Some(end));
+    assert_eq!(rejected_by(&config, &trade, Some(&ends("2026-01-02T00:00:00Z"))), None);
+    assert_eq!(rejected_by(&config, &trade, Some(&ends("2026-01-01T05:00:00Z"))), Some("min_hours_to_end"));
+    assert_eq!(rejected_by(&config, &trade, Some(&ends("2026-01-04T00:00:00Z"))), Some("max_hours_to_end"));
+    assert_eq!(rejected_by(&config, &trade, Some(&market(&[], 10_000.0, 10_000.0, None))), Some("end_date"));
+}
+
+#[test]
+fn test_filter_settings_are_validated() {
+    assert!(!filters("").is_enabled());
+
+    let message = try_test_env("[filters]\nmin_price = 0.8\nmax_price = 0.2\nmin_hours_to_end = 10\nmax_hours_to_end = 5\n")
+        .unwrap_err()
+        .to_string();
+    assert!(message.contains("FILTER_MIN_PRICE must be below FILTER_MAX_PRICE"), "{}", message);
+    assert!(message.contains("FILTER_MIN_HOURS_TO_END must be below FILTER_MAX_HOURS_TO_END"), "{}", message);
+}
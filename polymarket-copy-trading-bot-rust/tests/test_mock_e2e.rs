@@ -0,0 +1,240 @@
+//! End-to-end copy pipeline against the local mock Polymarket stack, with
+//! every hop going over localhost (no network): the RTDS parsing, Data API,
+//! Gamma, RPC and book reads the executor depends on, and the trade monitor
+//! and executor themselves copying into a paper account. The executor keeps
+//! its paper account and ledger in MongoDB, so that test needs a database in
+//! TEST_MONGO_URI and is skipped otherwise.
+
+mod common;
+
+use common::test_env;
+use futures_util::{SinkExt, StreamExt};
+use mongodb::Database;
+use polymarket_copy_trading_bot_rust::config::{calculate_order_size, Env};
+use polymarket_copy_trading_bot_rust::services::accounts::connect_execution_accounts;
+use polymarket_copy_trading_bot_rust::services::paper_trading::{fill_against_levels, load_paper_account, load_paper_position};
+use polymarket_copy_trading_bot_rust::services::pnl_ledger::{load_ledger, KIND_BUY};
+use polymarket_copy_trading_bot_rust::services::trade_monitor::{
+    parse_rtds_frame, rtds_subscribe_message, start_trade_monitor, RtdsFrame,
+};
+use polymarket_copy_trading_bot_rust::utils::data_api::{DataApiClient, PositionsQuery};
+use polymarket_copy_trading_bot_rust::utils::gamma_api::fetch_markets_by_condition_ids;
+use polymarket_copy_trading_bot_rust::utils::get_my_balance::get_my_balance;
+use polymarket_copy_trading_bot_rust::utils::post_order::{get_order_book, parse_book_levels};
+use polymarket_mock_server::{Event, MockServer, Scenario};
+use serde_json::json;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::watch;
+use tokio_tungstenite::connect_async;
+use tokio_tungstenite::tungstenite::Message;
+
+const TRADER: &str = "0x1111111111111111111111111111111111111111";
+const OTHER_WALLET: &str = "0x2222222222222222222222222222222222222222";
+const PROXY: &str = "0x3333333333333333333333333333333333333333";
+const CONDITION: &str = "0x5f65177b394277fd294cd75650044e32ba009a95022d88a0c1d565897d72f8f1";
+const TOKEN: &str = "71321045679252212594626385532706912750332728571942532289631379312455583992563";
+
+fn trade(wallet: &str, side: &str, size: f64, price: f64) -> serde_json::Value {
+    json!({
+        "proxyWallet": wallet,
+        "conditionId": CONDITION,
+        "asset": TOKEN,
+        "type": "TRADE",
+        "side": side,
+        "size": size,
+        "price": price,
+        "outcome": "Yes",
+        "outcomeIndex": 0,
+        "timestamp": chrono::Utc::now().timestamp(),
+        "transactionHash": format!("0x{:064x}", size as u64),
+    })
+}
+
+fn scenario() -> Scenario {
+    serde_json::from_value(json!({
+        "books": {
+            TOKEN: { "market": CONDITION, "bids": [[0.48, 200]], "asks": [[0.5, 30], [0.51, 40], [0.6, 1000]] }
+        },
+        "markets": [{
+            "conditionId": CONDITION,
+            "question": "Will it rain in London tomorrow?",
+            "slug": "will-it-rain-in-london-tomorrow",
+            "active": true,
+            "closed": false,
+            "outcomes": "[\"Yes\", \"No\"]",
+            "clobTokenIds": format!("[\"{}\", \"2\"]", TOKEN),
+        }],
+        "positions": {
+            TRADER: [{ "proxyWallet": TRADER, "asset": TOKEN, "conditionId": CONDITION, "size": 2000, "avgPrice": 0.45, "currentValue": 980 }]
+        },
+        "balances": { PROXY: 250.0 },
+        "steps": [
+            { "event": "rtds_trade", "trade": trade(OTHER_WALLET, "BUY", 5000.0, 0.5) },
+            { "after_ms": 50, "event": "rtds_trade", "trade": trade(TRADER, "BUY", 400.0, 0.5) },
+        ],
+    }))
+    .unwrap()
+}
+
+/// Settings pointing every endpoint at `server`, plus the sections in `extra`
+fn mock_env(server: &MockServer, extra: &str) -> Env {
+    test_env(&format!(
+        r#"
+[network]
+clob_http_url = "{}"
+clob_ws_url = "{}"
+rpc_url = "{}"
+data_api_url = "{}"
+gamma_api_url = "{}"
+rtds_url = "{}"
+
+[strategy]
+copy_strategy = "PERCENTAGE"
+copy_size = 10.0
+{}"#,
+        server.clob_url(),
+        server.market_ws_url(),
+        server.rpc_url(),
+        server.data_api_url(),
+        server.gamma_url(),
+        server.rtds_url(),
+        extra,
+    ))
+}
+
+/// A fresh database on TEST_MONGO_URI, or `None` when it isn't set
+async fn test_database() -> Option<Database> {
+    let uri = std::env::var("TEST_MONGO_URI").ok()?;
+    let client = mongodb::Client::with_uri_str(&uri).await.unwrap();
+    Some(client.database(&format!("polycopy_e2e_{}", chrono::Utc::now().timestamp_millis())))
+}
+
+#[tokio::test]
+async fn test_rtds_trade_flows_through_copy_pipeline() {
+    let server = MockServer::start(scenario()).await.unwrap();
+    let env = mock_env(&server, "");
+
+    // RTDS: subscribe like the trade monitor and keep the first tracked trade
+    let (mut socket, _) = connect_async(env.rtds_url.as_str()).await.unwrap();
+    socket.send(Message::Text(rtds_subscribe_message(env.user_addresses.len()).to_string())).await.unwrap();
+    server.wait_for_rtds_subscribers(1).await;
+    server.play().await;
+
+    let mut ignored = 0;
+    let (trader, activity) = loop {
+        let message = tokio::time::timeout(Duration::from_secs(5), socket.next()).await.unwrap().unwrap().unwrap();
+        let Message::Text(text) = message else { continue };
+        match parse_rtds_frame(&text, &env.user_addresses) {
+            RtdsFrame::TraderActivity { trader, activity } => break (trader, activity),
+            RtdsFrame::Ignored => ignored += 1,
+            RtdsFrame::Subscribed => {}
+        }
+    };
+    assert_eq!(ignored, 1, "the untracked wallet's trade is skipped");
+    assert_eq!(trader, TRADER);
+    assert_eq!(activity.side, "BUY");
+    assert_eq!(activity.usdc_size, 200.0);
+
+    // Context the executor gathers before sizing
+    let positions = DataApiClient::new(&env).positions(&PositionsQuery::new(TRADER)).await.unwrap();
+    assert_eq!(positions.len(), 1);
+    assert_eq!(positions[0].size, 2000.0);
+    let markets = fetch_markets_by_condition_ids(&[CONDITION.to_string()], &env).await.unwrap();
+    assert_eq!(markets[0].token_ids()[0], TOKEN);
+    let balance = get_my_balance(&env.proxy_wallet, &env).await.unwrap();
+    assert_eq!(balance, 250.0);
+
+    // 10% of the trader's $200 buy, filled against the mock book
+    let sizing = calculate_order_size(&env.copy_strategy_config, activity.usdc_size, balance, 0.0);
+    assert_eq!(sizing.final_amount, 20.0);
+    let book = get_order_book(&env, &activity.asset).await.unwrap();
+    let asks = parse_book_levels(&book, "asks");
+    assert_eq!(asks.len(), 3);
+    let fill = fill_against_levels(&asks, true, sizing.final_amount, Some(0.55));
+    assert_eq!(fill.levels, 2);
+    assert!((fill.size - (30.0 + 5.0 / 0.51)).abs() < 1e-9, "{:?}", fill);
+    assert!((fill.usdc - 20.0).abs() < 1e-9, "{:?}", fill);
+
+    let paths: Vec<String> = server.requests().into_iter().map(|r| r.path).collect();
+    assert!(paths.iter().any(|p| p.starts_with("/data-api/positions?user=")), "{:?}", paths);
+    assert!(paths.iter().any(|p| p.starts_with("/gamma/markets?condition_ids=")), "{:?}", paths);
+    assert!(paths.iter().any(|p| p == &format!("/book?token_id={}", TOKEN)), "{:?}", paths);
+    assert!(paths.iter().any(|p| p == "/rpc"), "{:?}", paths);
+}
+
+#[tokio::test]
+async fn test_trade_monitor_copies_into_the_paper_account() {
+    let Some(db) = test_database().await else {
+        eprintln!("Skipping: set TEST_MONGO_URI to run the executor against the mock");
+        return;
+    };
+    let db = Arc::new(db);
+    let server = MockServer::start(scenario()).await.unwrap();
+    let env = Arc::new(mock_env(&server, "\n[paper_trading]\nenabled = true\n"));
+
+    // The bot's own startup: CLOB sessions (mock API keys), then the RTDS monitor
+    let accounts = Arc::new(connect_execution_accounts(&env, &db).await.unwrap());
+    let (_env_tx, env_rx) = watch::channel(env.clone());
+    let monitor = tokio::spawn(start_trade_monitor(env_rx, db.clone(), accounts));
+    server.wait_for_rtds_subscribers(1).await;
+    server.play().await;
+
+    // The ledger entry is written last
+    let ledger = tokio::time::timeout(Duration::from_secs(10), async {
+        loop {
+            let ledger = load_ledger(&db).await.unwrap();
+            if !ledger.is_empty() {
+                break ledger;
+            }
+            tokio::time::sleep(Duration::from_millis(50)).await;
+        }
+    })
+    .await
+    .expect("the tracked trade was never copied");
+    monitor.abort();
+
+    // Only the tracked trader's buy: 10% of $200 across the 0.50 and 0.51 asks
+    let tokens = 30.0 + 5.0 / 0.51;
+    assert_eq!(ledger.len(), 1);
+    assert_eq!(ledger[0].kind, KIND_BUY);
+    assert_eq!(ledger[0].trader_address.as_deref(), Some(TRADER));
+    assert!(ledger[0].simulated);
+    assert!((ledger[0].size - tokens).abs() < 1e-9, "{:?}", ledger[0]);
+    assert!((ledger[0].usdc - 20.0).abs() < 1e-9, "{:?}", ledger[0]);
+
+    let position = load_paper_position(&db, TOKEN).await.unwrap().unwrap();
+    assert!((position.size - tokens).abs() < 1e-9, "{:?}", position);
+    assert_eq!(position.traders, vec![TRADER.to_string()]);
+    let account = load_paper_account(&db, &env.paper_trading).await.unwrap();
+    assert!((account.cash - (account.starting_balance - 20.0)).abs() < 1e-9, "{:?}", account);
+
+    // Paper orders never reach the CLOB
+    assert!(server.orders().is_empty());
+    Database::drop(&db, None).await.unwrap();
+}
+
+#[tokio::test]
+async fn test_scripted_rate_limit_is_retried() {
+    let server = MockServer::start(scenario()).await.unwrap();
+    let env = mock_env(&server, "");
+    server.apply(&Event::Fail { path: "/data-api/positions".to_string(), status: 429, times: 1, retry_after: Some(1) });
+
+    let positions = DataApiClient::new(&env).positions(&PositionsQuery::new(TRADER)).await.unwrap();
+    assert_eq!(positions.len(), 1);
+    let attempts = server.requests().iter().filter(|r| r.path.starts_with("/data-api/positions")).count();
+    assert_eq!(attempts, 2);
+}
+
+#[tokio::test]
+async fn test_book_moves_are_seen_by_the_next_fill() {
+    let server = MockServer::start(scenario()).await.unwrap();
+    let env = mock_env(&server, "");
+    server.apply(&Event::Book { token_id: TOKEN.to_string(), bids: vec![(0.58, 100.0)], asks: vec![(0.62, 100.0)] });
+
+    // The trader's 0.50 price plus a 0.05 slippage limit no longer finds liquidity
+    let book = get_order_book(&env, TOKEN).await.unwrap();
+    let fill = fill_against_levels(&parse_book_levels(&book, "asks"), true, 20.0, Some(0.55));
+    assert_eq!(fill.levels, 0);
+    assert_eq!(fill.size, 0.0);
+}
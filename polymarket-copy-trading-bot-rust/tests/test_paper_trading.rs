@@ -0,0 +1,77 @@
+//! Paper trading fill and position accounting tests (no database or network).
+
+use polymarket_copy_trading_bot_rust::services::paper_trading::{
+    fill_against_levels, PaperAccount, PaperPosition,
+};
+
+const ASKS: [(f64, f64); 3] = [(0.52, 100.0), (0.50, 40.0), (0.55, 200.0)];
+const BIDS: [(f64, f64); 3] = [(0.47, 50.0), (0.48, 30.0), (0.40, 500.0)];
+
+fn assert_close(actual: f64, expected: f64) {
+    assert!((actual - expected).abs() < 1e-6, "expected {}, got {}", expected, actual);
+}
+
+#[test]
+fn test_buy_walks_asks_cheapest_first() {
+    // $20 at 0.50 (40 tokens) + $52 at 0.52 (100 tokens) + $8.25 at 0.55 (15 tokens)
+    let fill = fill_against_levels(&ASKS, true, 80.25, None);
+    assert_eq!(fill.levels, 3);
+    assert_close(fill.usdc, 80.25);
+    assert_close(fill.size, 155.0);
+    assert_close(fill.avg_price(), 80.25 / 155.0);
+}
+
+#[test]
+fn test_buy_stops_at_limit_price() {
+    let fill = fill_against_levels(&ASKS, true, 500.0, Some(0.52));
+    assert_eq!(fill.levels, 2);
+    assert_close(fill.size, 140.0);
+    assert_close(fill.usdc, 72.0);
+}
+
+#[test]
+fn test_sell_walks_bids_highest_first() {
+    let fill = fill_against_levels(&BIDS, false, 60.0, None);
+    assert_eq!(fill.levels, 2);
+    assert_close(fill.size, 60.0);
+    assert_close(fill.usdc, 30.0 * 0.48 + 30.0 * 0.47);
+
+    let limited = fill_against_levels(&BIDS, false, 1000.0, Some(0.45));
+    assert_close(limited.size, 80.0);
+}
+
+#[test]
+fn test_empty_book_fills_nothing() {
+    let fill = fill_against_levels(&[], true, 100.0, None);
+    assert_eq!(fill.size, 0.0);
+    assert_eq!(fill.avg_price(), 0.0);
+}
+
+#[test]
+fn test_position_average_cost_and_realized_pnl() {
+    let mut position = PaperPosition::default();
+    position.apply_buy(100.0, 0.40, "0xAbC");
+    position.apply_buy(100.0, 0.60, "0xabc");
+    assert_close(position.avg_price, 0.50);
+    assert_eq!(position.traders, vec!["0xabc".to_string()]);
+
+    let (sold, realized) = position.apply_sell(50.0, 0.70);
+    assert_close(sold, 50.0);
+    assert_close(realized, 10.0);
+    assert_close(position.size, 150.0);
+    assert_close(position.unrealized_pnl(), 150.0 * 0.20);
+
+    // Selling more than held closes the position
+    let (sold, _) = position.apply_sell(1000.0, 0.70);
+    assert_close(sold, 150.0);
+    assert_eq!(position.size, 0.0);
+}
+
+#[test]
+fn test_account_equity_and_return() {
+    let mut account = PaperAccount::new(1000.0);
+    account.cash = 700.0;
+    account.positions_value = 450.0;
+    assert_close(account.equity(), 1150.0);
+    assert_close(account.return_percent(), 15.0);
+}
@@ -0,0 +1,190 @@
+//! FIFO PnL over the ledger: partial sells, redemptions at each token's
+//! settlement price and attribution to copied traders (no database).
+
+use polymarket_copy_trading_bot_rust::services::pnl_ledger::{
+    compute_pnl, open_assets, redemption_entries, LedgerEntry, PnlBucket, PnlReport, KIND_BUY, KIND_SELL,
+};
+use polymarket_copy_trading_bot_rust::utils::data_api::Activity;
+use polymarket_copy_trading_bot_rust::utils::market_resolution::MarketResolution;
+use std::collections::HashMap;
+
+const ALICE: &str = "0xaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa";
+const BOB: &str = "0xbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbb";
+const DAY: i64 = 24 * 60 * 60;
+const START: i64 = 1_700_006_400; // Midnight UTC
+
+fn fill(kind: &str, trader: &str, asset: &str, condition_id: &str, price: f64, size: f64, day: i64) -> LedgerEntry {
+    LedgerEntry {
+        kind: kind.to_string(),
+        trader_address: Some(trader.to_string()),
+        asset: asset.to_string(),
+        condition_id: condition_id.to_string(),
+        title: format!("Market {}", condition_id),
+        slug: String::new(),
+        outcome: String::new(),
+        price,
+        size,
+        usdc: price * size,
+        timestamp: START + day * DAY,
+        transaction_hash: String::new(),
+        simulated: false,
+    }
+}
+
+fn redeem(asset: &str, condition_id: &str, price: f64, size: f64, day: i64) -> LedgerEntry {
+    LedgerEntry {
+        timestamp: START + day * DAY,
+        ..LedgerEntry::redemption(asset, condition_id, "", price, size, "0xredeem", false)
+    }
+}
+
+fn bucket<'a>(buckets: &'a [PnlBucket], key: &str) -> &'a PnlBucket {
+    buckets.iter().find(|b| b.key == key).unwrap()
+}
+
+fn assert_close(actual: f64, expected: f64) {
+    assert!((actual - expected).abs() < 1e-9, "expected {}, got {}", expected, actual);
+}
+
+fn pnl(entries: &[LedgerEntry], prices: &[(&str, f64)]) -> PnlReport {
+    let prices: HashMap<String, f64> = prices.iter().map(|&(a, p)| (a.to_string(), p)).collect();
+    compute_pnl(entries, &prices, None)
+}
+
+#[test]
+fn test_partial_sells_close_the_oldest_lots_first() {
+    let entries = vec![
+        fill(KIND_BUY, ALICE, "a", "c1", 0.40, 100.0, 0),
+        fill(KIND_BUY, BOB, "a", "c1", 0.60, 100.0, 1),
+        // Closes all of Alice's lot and half of Bob's
+        fill(KIND_SELL, BOB, "a", "c1", 0.70, 150.0, 2),
+    ];
+    let report = pnl(&entries, &[("a", 0.50)]);
+
+    assert_close(report.total.bought_usd, 100.0);
+    assert_close(report.total.proceeds_usd, 105.0);
+    assert_close(report.total.realized_pnl, 35.0);
+    assert_close(report.total.unrealized_pnl, -5.0);
+    assert_close(report.total.open_cost_usd, 30.0);
+
+    let alice = bucket(&report.by_trader, ALICE);
+    assert_close(alice.realized_pnl, 30.0);
+    assert_close(alice.open_cost_usd, 0.0);
+    let bob = bucket(&report.by_trader, BOB);
+    assert_close(bob.realized_pnl, 5.0);
+    assert_close(bob.unrealized_pnl, -5.0);
+    // Best total first
+    assert_eq!(report.by_trader[0].key, ALICE);
+
+    let days: Vec<&str> = report.by_day.iter().map(|b| b.key.as_str()).collect();
+    assert_eq!(days, ["2023-11-15", "2023-11-16", "2023-11-17"]);
+    assert_close(report.by_day[2].realized_pnl, 35.0);
+}
+
+#[test]
+fn test_unpriced_tokens_are_marked_at_the_last_fill() {
+    let entries = vec![
+        fill(KIND_BUY, ALICE, "a", "c1", 0.40, 100.0, 0),
+        fill(KIND_SELL, ALICE, "a", "c1", 0.55, 40.0, 1),
+        // Sells of tokens bought before the ledger existed are ignored
+        fill(KIND_SELL, ALICE, "old", "c0", 0.90, 10.0, 1),
+    ];
+    let report = pnl(&entries, &[]);
+    assert_close(report.total.realized_pnl, 6.0);
+    assert_close(report.total.unrealized_pnl, 9.0);
+    assert_eq!(open_assets(&entries), vec!["a".to_string()]);
+}
+
+#[test]
+fn test_redemptions_pay_each_token_its_settlement_price() {
+    let entries = vec![
+        fill(KIND_BUY, ALICE, "yes", "c2", 0.30, 100.0, 0),
+        fill(KIND_BUY, BOB, "no", "c2", 0.60, 50.0, 0),
+        redeem("yes", "c2", 1.0, 100.0, 3),
+        redeem("no", "c2", 0.0, 50.0, 3),
+    ];
+    let report = pnl(&entries, &[("yes", 0.99), ("no", 0.01)]);
+
+    // The winning lot makes $70 and the losing lot loses its $30 cost
+    assert_close(bucket(&report.by_trader, ALICE).realized_pnl, 70.0);
+    assert_close(bucket(&report.by_trader, BOB).realized_pnl, -30.0);
+    assert_close(report.total.proceeds_usd, 100.0);
+    assert_close(report.total.unrealized_pnl, 0.0);
+    assert_close(report.total.open_cost_usd, 0.0);
+    assert!(open_assets(&entries).is_empty());
+
+    // Redeeming only the winner leaves the loser open
+    let partial = &entries[..3];
+    assert_eq!(open_assets(partial), vec!["no".to_string()]);
+    assert_close(pnl(partial, &[]).total.open_cost_usd, 30.0);
+}
+
+#[test]
+fn test_redemption_without_a_token_covers_the_condition_pro_rata() {
+    let mut legacy = redeem("", "c2", 0.0, 150.0, 3);
+    legacy.usdc = 100.0;
+    let entries = vec![
+        fill(KIND_BUY, ALICE, "yes", "c2", 0.30, 100.0, 0),
+        fill(KIND_BUY, BOB, "no", "c2", 0.60, 50.0, 0),
+        legacy,
+    ];
+    let report = pnl(&entries, &[]);
+    assert_close(report.total.realized_pnl, 40.0);
+    assert_close(bucket(&report.by_trader, ALICE).realized_pnl, (100.0 / 150.0 - 0.30) * 100.0);
+    assert!(open_assets(&entries).is_empty());
+}
+
+#[test]
+fn test_since_limits_fills_but_keeps_older_cost_basis() {
+    let entries = vec![
+        fill(KIND_BUY, ALICE, "a", "c1", 0.40, 100.0, 0),
+        fill(KIND_SELL, ALICE, "a", "c1", 0.50, 100.0, 5),
+    ];
+    let prices = HashMap::new();
+    let report = compute_pnl(&entries, &prices, Some(START + 2 * DAY));
+    assert_close(report.total.bought_usd, 0.0);
+    assert_close(report.total.realized_pnl, 10.0);
+    assert_eq!(report.total.fills, 1);
+    assert_eq!(report.by_market[0].label, "Market c1");
+}
+
+#[test]
+fn test_imported_redemptions_only_cover_live_fills() {
+    let mut simulated = fill(KIND_BUY, BOB, "yes", "c2", 0.30, 400.0, 0);
+    simulated.simulated = true;
+    let ledger = vec![fill(KIND_BUY, ALICE, "yes", "c2", 0.30, 100.0, 0), simulated];
+    let activity = Activity {
+        condition_id: "c2".to_string(),
+        size: 100.0,
+        usdc_size: 100.0,
+        transaction_hash: "0xredeem".to_string(),
+        timestamp: START + 3 * DAY,
+        ..Default::default()
+    };
+    let resolution = MarketResolution {
+        condition_id: "c2".to_string(),
+        question: String::new(),
+        closed: true,
+        resolved: true,
+        outcomes: vec!["Yes".to_string(), "No".to_string()],
+        outcome_prices: vec![1.0, 0.0],
+        token_ids: vec!["yes".to_string(), "no".to_string()],
+        end_date: None,
+        fetched_at: 0,
+    };
+
+    // The 400 simulated tokens never existed on-chain
+    let entries = redemption_entries(&activity, &ledger, Some(&resolution));
+    assert_eq!(entries.len(), 1);
+    assert_eq!(entries[0].asset, "yes");
+    assert_close(entries[0].size, 100.0);
+    assert_close(entries[0].usdc, 100.0);
+    assert!(!entries[0].simulated);
+    assert_eq!(entries[0].timestamp, START + 3 * DAY);
+
+    // Only simulated holdings: the payout is recorded for the condition as reported
+    let entries = redemption_entries(&activity, &ledger[1..], Some(&resolution));
+    assert_eq!(entries.len(), 1);
+    assert_eq!(entries[0].asset, "");
+    assert_close(entries[0].usdc, 100.0);
+}
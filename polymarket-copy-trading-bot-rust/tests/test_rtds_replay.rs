@@ -0,0 +1,171 @@
+//! RTDS recordings: the file format, replay pacing, recording a session from
+//! the mock RTDS stream and replaying it through the frame parser, and the
+//! record / replay settings (no network besides localhost).
+
+mod common;
+
+use common::{activity, test_env, try_test_env};
+use futures_util::{SinkExt, StreamExt};
+use polymarket_copy_trading_bot_rust::services::trade_monitor::{
+    is_too_old, parse_rtds_frame, rtds_subscribe_message, RtdsFrame,
+};
+use polymarket_copy_trading_bot_rust::utils::rtds_recording::{read_recording, replay_delay, RecordedFrame, RtdsRecorder};
+use polymarket_mock_server::{MockServer, Scenario};
+use serde_json::json;
+use std::path::PathBuf;
+use std::time::Duration;
+use tokio_tungstenite::connect_async;
+use tokio_tungstenite::tungstenite::Message;
+
+const TRADER: &str = "0x1111111111111111111111111111111111111111";
+
+fn temp_path(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("polycopy-test-{}-{}", std::process::id(), name));
+    let _ = std::fs::remove_dir_all(&dir);
+    dir.join("recordings").join("session.jsonl")
+}
+
+fn frame(received_at: i64) -> RecordedFrame {
+    RecordedFrame { received_at, frame: String::new() }
+}
+
+fn trade(wallet: &str, side: &str) -> serde_json::Value {
+    json!({
+        "proxyWallet": wallet,
+        "conditionId": "0xabc",
+        "asset": "123",
+        "type": "TRADE",
+        "side": side,
+        "size": 100,
+        "price": 0.5,
+        "timestamp": 1_700_000_000,
+    })
+}
+
+#[test]
+fn test_recording_round_trips_and_appends() {
+    let path = temp_path("round-trip");
+    let frames = [
+        r#"{"topic":"activity","type":"trades","payload":{"side":"BUY"}}"#,
+        "not json, \"quoted\"\nacross lines",
+    ];
+
+    let mut recorder = RtdsRecorder::open(&path).unwrap();
+    recorder.record(frames[0], 1_700_000_000_000).unwrap();
+    drop(recorder);
+    // Reopening (e.g. after a restart) appends to the same session file
+    let mut recorder = RtdsRecorder::open(&path).unwrap();
+    recorder.record(frames[1], 1_700_000_000_250).unwrap();
+    for i in 0..500 {
+        recorder.record("{}", 1_700_000_001_000 + i).unwrap();
+    }
+    // Everything queued is on disk once the writer finishes
+    recorder.finish().unwrap();
+
+    let recorded = read_recording(&path).unwrap();
+    assert_eq!(recorded.len(), 502);
+    assert_eq!(recorded[0].frame, frames[0]);
+    assert_eq!(recorded[1], RecordedFrame { received_at: 1_700_000_000_250, frame: frames[1].to_string() });
+    assert!(recorded[2..].windows(2).all(|pair| pair[0].received_at + 1 == pair[1].received_at));
+
+    std::fs::write(&path, "\n{\"received_at\":1,\"frame\":\"x\"}\n{broken\n").unwrap();
+    let message = read_recording(&path).unwrap_err().to_string();
+    assert!(message.contains("line 3"), "{}", message);
+}
+
+#[test]
+fn test_replay_delay_scales_recorded_gaps() {
+    let (first, second) = (frame(1_000), frame(3_000));
+    assert_eq!(replay_delay(None, &first, 1.0), Duration::ZERO);
+    assert_eq!(replay_delay(Some(&first), &second, 1.0), Duration::from_secs(2));
+    assert_eq!(replay_delay(Some(&first), &second, 10.0), Duration::from_millis(200));
+    assert_eq!(replay_delay(Some(&first), &second, 0.5), Duration::from_secs(4));
+    assert_eq!(replay_delay(Some(&first), &second, 0.0), Duration::ZERO);
+    // Clock steps backwards don't stall the replay
+    assert_eq!(replay_delay(Some(&second), &first, 1.0), Duration::ZERO);
+}
+
+#[test]
+fn test_age_is_judged_at_the_receive_time() {
+    const HOUR_MS: i64 = 60 * 60 * 1000;
+    // Seconds or milliseconds, as the Data API and RTDS send them
+    let seconds = activity(serde_json::json!({ "timestamp": 1_700_000_000 }));
+    let millis = activity(serde_json::json!({ "timestamp": 1_700_000_000_000_i64 }));
+    for trade in [&seconds, &millis] {
+        assert!(!is_too_old(trade, 1_700_000_000_000 + 2 * HOUR_MS, 24));
+        assert!(is_too_old(trade, 1_700_000_000_000 + 25 * HOUR_MS, 24));
+    }
+    // A replay long after the recording judges the event as the live run did
+    assert!(!is_too_old(&seconds, 1_700_000_000_000 + 1000, 1));
+}
+
+#[tokio::test]
+async fn test_recorded_session_replays_like_the_live_stream() {
+    let scenario: Scenario = serde_json::from_value(json!({
+        "steps": [
+            { "event": "rtds_trade", "trade": trade("0x2222222222222222222222222222222222222222", "BUY") },
+            { "after_ms": 20, "event": "rtds", "message": { "topic": "comments", "type": "comment_created" } },
+            { "after_ms": 20, "event": "rtds_trade", "trade": trade(TRADER, "SELL") },
+        ],
+    }))
+    .unwrap();
+    let server = MockServer::start(scenario).await.unwrap();
+    let traders = vec![TRADER.to_string()];
+
+    // Live: classify frames as they arrive and record them
+    let path = temp_path("session");
+    let mut recorder = RtdsRecorder::open(&path).unwrap();
+    let (mut socket, _) = connect_async(server.rtds_url()).await.unwrap();
+    socket.send(Message::Text(rtds_subscribe_message(traders.len()).to_string())).await.unwrap();
+    server.wait_for_rtds_subscribers(1).await;
+    server.play().await;
+
+    let mut live = Vec::new();
+    while live.len() < 3 {
+        let message = tokio::time::timeout(Duration::from_secs(5), socket.next()).await.unwrap().unwrap().unwrap();
+        let Message::Text(text) = message else { continue };
+        recorder.record(&text, chrono::Utc::now().timestamp_millis()).unwrap();
+        live.push(format!("{:?}", parse_rtds_frame(&text, &traders)));
+    }
+
+    // Replay: the same frames, in order and paced as received, classify identically
+    drop(recorder);
+    let recorded = read_recording(&path).unwrap();
+    assert_eq!(recorded.len(), 3);
+    assert!(recorded.windows(2).all(|pair| pair[0].received_at <= pair[1].received_at));
+    let replayed: Vec<String> = recorded.iter().map(|f| format!("{:?}", parse_rtds_frame(&f.frame, &traders))).collect();
+    assert_eq!(replayed, live);
+
+    match parse_rtds_frame(&recorded[2].frame, &traders) {
+        RtdsFrame::TraderActivity { trader, activity } => {
+            assert_eq!(trader, TRADER);
+            assert_eq!(activity.side, "SELL");
+            assert_eq!(activity.timestamp, 1_700_000_000);
+        }
+        other => panic!("expected the tracked trader's trade, got {:?}", other),
+    }
+    assert!(matches!(parse_rtds_frame(&recorded[0].frame, &traders), RtdsFrame::Ignored));
+}
+
+#[test]
+fn test_record_and_replay_settings() {
+    let env = test_env("");
+    assert!(env.rtds.record_file.is_none());
+    assert!(env.rtds.replay_file.is_none());
+    assert_eq!(env.rtds.replay_speed, 1.0);
+
+    let env = test_env("\n[rtds]\nreplay_file = \"sessions/monday.jsonl\"\nreplay_speed = 0\n");
+    assert_eq!(env.rtds.replay_file, Some(PathBuf::from("sessions/monday.jsonl")));
+    assert_eq!(env.rtds.replay_speed, 0.0);
+
+    let message = try_test_env("\n[rtds]\nrecord_file = \"a.jsonl\"\nreplay_file = \"a.jsonl\"\nreplay_speed = -2\n")
+        .unwrap_err()
+        .to_string();
+    assert!(message.contains("RTDS_REPLAY_SPEED"), "{}", message);
+    assert!(message.contains("must differ from RTDS_REPLAY_FILE"), "{}", message);
+
+    let message = try_test_env("\n[rtds]\nreplay_file = \"a.jsonl\"\n\n[bot]\npreview_mode = false\n")
+        .unwrap_err()
+        .to_string();
+    assert!(message.contains("replay only runs in preview mode"), "{}", message);
+}
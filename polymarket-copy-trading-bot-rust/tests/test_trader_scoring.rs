@@ -0,0 +1,128 @@
+//! Risk-adjusted trader scoring and the risk metrics behind it (no network).
+
+mod common;
+
+use common::strategy_config;
+use polymarket_copy_trading_bot_rust::config::{CopyStrategy, CopyStrategyConfig};
+use polymarket_copy_trading_bot_rust::utils::copy_simulator::SimTrade;
+use polymarket_copy_trading_bot_rust::utils::market_resolution::{MarketResolution, ResolutionCache};
+use polymarket_copy_trading_bot_rust::utils::risk_metrics::{daily_closes, daily_returns, max_drawdown_percent, sharpe_ratio};
+use polymarket_copy_trading_bot_rust::utils::trader_scoring::{rank_by_score, score_trader, ScoringWeights};
+
+const DAY: i64 = 24 * 60 * 60;
+const START: i64 = 1_700_006_400; // Midnight UTC
+
+fn trade(day: i64, market: &str, side: &str, size: f64, price: f64) -> SimTrade {
+    SimTrade {
+        timestamp: START + day * DAY,
+        condition_id: Some(market.to_string()),
+        asset: format!("{}-yes", market),
+        side: side.to_string(),
+        price,
+        usdc_size: size * price,
+        size,
+        outcome: Some("Yes".to_string()),
+        title: None,
+    }
+}
+
+/// Two profitable round trips in different markets
+fn winner() -> Vec<SimTrade> {
+    vec![
+        trade(0, "m1", "BUY", 100.0, 0.5),
+        trade(2, "m1", "SELL", 100.0, 0.6),
+        trade(2, "m2", "BUY", 50.0, 0.4),
+        trade(3, "m2", "SELL", 50.0, 0.5),
+    ]
+}
+
+/// One losing round trip
+fn loser() -> Vec<SimTrade> {
+    vec![trade(0, "m1", "BUY", 100.0, 0.5), trade(1, "m1", "SELL", 100.0, 0.3)]
+}
+
+#[test]
+fn test_score_metrics_from_round_trips() {
+    let config = strategy_config(CopyStrategy::Percentage, 10.0);
+    let score = score_trader("0xwinner", &winner(), &config, &ScoringWeights::balanced(), None);
+
+    assert_eq!(score.trades, 4);
+    assert!((score.total_pnl - 15.0).abs() < 1e-9, "{:?}", score);
+    // $15 on a peak of $50 deployed
+    assert!((score.roi - 30.0).abs() < 1e-9, "{:?}", score);
+    assert_eq!(score.win_rate, 100.0);
+    // 100 shares held 48h and 50 shares held 24h
+    assert!((score.avg_hold_hours - 40.0).abs() < 1e-9, "{:?}", score);
+    let (m1, m2) = (110.0 / 155.0, 45.0 / 155.0);
+    assert!((score.market_concentration - (m1 * m1 + m2 * m2)).abs() < 1e-9, "{:?}", score);
+    assert!((score.top_market_share - m1).abs() < 1e-9, "{:?}", score);
+    assert_eq!(score.copyability, 1.0);
+    assert!(score.score > 0.0 && score.score <= 100.0, "{:?}", score);
+
+    // At 10% the $20 buy copies as $2, below a $3 minimum
+    let strict = CopyStrategyConfig { min_order_size_usd: 3.0, ..config };
+    let score = score_trader("0xwinner", &winner(), &strict, &ScoringWeights::balanced(), None);
+    assert_eq!(score.copyability, 0.5);
+}
+
+#[test]
+fn test_losses_and_drawdown_rank_lower() {
+    let config = strategy_config(CopyStrategy::Percentage, 10.0);
+    for weights in [ScoringWeights::balanced(), ScoringWeights::low_risk()] {
+        let losing = score_trader("0xloser", &loser(), &config, &weights, None);
+        assert!((losing.total_pnl + 20.0).abs() < 1e-9, "{:?}", losing);
+        assert_eq!(losing.win_rate, 0.0);
+        // Equity on a $50 base falls from 50 to 30
+        assert!((losing.max_drawdown_percent - 40.0).abs() < 1e-9, "{:?}", losing);
+
+        let mut scores = vec![losing, score_trader("0xwinner", &winner(), &config, &weights, None)];
+        rank_by_score(&mut scores);
+        assert_eq!(scores[0].address, "0xwinner");
+    }
+}
+
+#[test]
+fn test_open_positions_settle_at_resolution() {
+    let path = std::env::temp_dir().join(format!("polycopy-test-{}-scoring-resolutions.json", std::process::id()));
+    let resolution = MarketResolution {
+        condition_id: "m1".to_string(),
+        question: String::new(),
+        closed: true,
+        resolved: true,
+        outcomes: vec!["Yes".to_string(), "No".to_string()],
+        outcome_prices: vec![1.0, 0.0],
+        token_ids: vec!["m1-yes".to_string(), "m1-no".to_string()],
+        end_date: None,
+        fetched_at: 0,
+    };
+    std::fs::write(&path, serde_json::to_string(&vec![resolution]).unwrap()).unwrap();
+    let cache = ResolutionCache::load_from(&path);
+
+    let trades = vec![trade(0, "m1", "BUY", 100.0, 0.4)];
+    let config = strategy_config(CopyStrategy::Percentage, 10.0);
+    let open = score_trader("0xholder", &trades, &config, &ScoringWeights::balanced(), None);
+    assert!(open.total_pnl.abs() < 1e-9, "marked at the last trade price: {:?}", open);
+    assert_eq!(open.win_rate, 0.0, "nothing settled yet");
+
+    let settled = score_trader("0xholder", &trades, &config, &ScoringWeights::balanced(), Some(&cache));
+    assert!((settled.total_pnl - 60.0).abs() < 1e-9, "{:?}", settled);
+    assert_eq!(settled.win_rate, 100.0);
+
+    let _ = std::fs::remove_file(&path);
+}
+
+#[test]
+fn test_risk_metrics() {
+    assert_eq!(max_drawdown_percent(&[100.0, 120.0, 90.0, 130.0, 117.0]), 25.0);
+    assert_eq!(max_drawdown_percent(&[]), 0.0);
+
+    // Last point of each UTC day, seconds or milliseconds
+    let points = [(START, 100.0), (START + 60, 101.0), ((START + DAY) * 1000, 105.0)];
+    assert_eq!(daily_closes(&points), vec![101.0, 105.0]);
+    assert_eq!(daily_returns(&[100.0, 110.0, 99.0]), vec![0.1, -0.1]);
+
+    assert_eq!(sharpe_ratio(&[0.01]), 0.0);
+    assert_eq!(sharpe_ratio(&[0.01, 0.01, 0.01]), 0.0, "no variance");
+    assert!(sharpe_ratio(&[0.02, 0.01, 0.03]) > 0.0);
+    assert!(sharpe_ratio(&[-0.02, -0.01, -0.03]) < 0.0);
+}
@@ -1,12 +1,10 @@
-use crate::args::Args;
 use crate::clob_api::ClobApi;
-use crate::constants::MAX_DECIMALS;
 use crate::contracts::Contracts;
 use crate::gas::{GasStation, GasStrategy};
 use crate::lifecycle::Lifecycle;
 use crate::market::Market;
 use std::str::FromStr;
-use crate::metrics::{register_metrics, KEEPER_BALANCE_AMOUNT};
+use crate::metrics::register_metrics;
 use crate::order::{Order, Side};
 use crate::orderbook::OrderBookManager;
 use crate::price_feed::{PriceFeed, PriceFeedClob};
@@ -17,16 +15,18 @@ use ethers::prelude::*;
 use prometheus::Registry;
 use std::collections::HashMap;
 use std::sync::Arc;
+use tokio::runtime::Handle;
 use tokio::time::sleep;
 
 // Main app struct - holds all the pieces together
+#[allow(dead_code)] // Chain & CLOB handles are for the TODO balance/approval calls
 pub struct App {
     sync_interval: u64, // How often we sync (in secs)
     clob_api: ClobApi, // API client for CLOB
     market: Market, // Market we're trading on
     price_feed: Arc<PriceFeedClob>, // Price feed for getting midpoints
     order_book_manager: OrderBookManager, // Manages orderbook state
-    strategy_manager: StrategyManager, // Strategy logic (AMM/Bands)
+    strategy_manager: Arc<StrategyManager>, // Strategy logic (AMM/Bands)
     contracts: Contracts, // Ethereum contract interactions
     address: Address, // Our wallet address
 }
@@ -73,82 +73,18 @@ impl App {
         )?; // Create strategy manager
 
         let mut order_book_manager = OrderBookManager::new(args.refresh_frequency); // Create orderbook manager
-        let clob_api_for_orders = clob_api.clone();
-        let market_for_orders = market.clone();
-        order_book_manager.get_orders_with(move || {
-            // Fetch orders from CLOB API - gotta convert JSON to Order structs
-            let rt = tokio::runtime::Runtime::new().unwrap();
-            let orders = rt.block_on(
-                clob_api_for_orders.get_orders(&market_for_orders.condition_id)
-            ).unwrap_or_default();
-            orders
-                .into_iter()
-                .map(|order_dict| {
-                    // Calculate remaining size (original - matched)
-                    let size = order_dict.get("original_size")
-                        .and_then(|v| v.as_f64())
-                        .unwrap_or(0.0) - 
-                        order_dict.get("size_matched")
-                        .and_then(|v| v.as_f64())
-                        .unwrap_or(0.0);
-                    Order::new(
-                        size,
-                        order_dict.get("price").and_then(|v| v.as_f64()).unwrap_or(0.0),
-                        Side::from_str(order_dict.get("side").and_then(|v| v.as_str()).unwrap_or("BUY")).unwrap_or(Side::Buy),
-                        market_for_orders.token(order_dict.get("asset_id").and_then(|v| v.as_u64()).unwrap_or(0)).unwrap_or(Token::A),
-                        order_dict.get("id").and_then(|v| v.as_str()).map(|s| s.to_string()),
-                    )
-                })
-                .collect()
-        });
+        connect_order_book(&mut order_book_manager, clob_api.clone(), market.clone());
 
-        let contracts_for_balances = contracts.clone();
-        let address_for_balances = address;
-        let clob_api_for_balances = clob_api.clone();
-        let market_for_balances = market.clone();
         order_book_manager.get_balances_with(move || {
             // Fetch balances from chain - tbd: implement actual contract calls
-            let rt = tokio::runtime::Runtime::new().unwrap();
             let mut balances = HashMap::new();
-            // TODO: Gotta fetch real balances from contracts_for_balances
+            // TODO: Gotta fetch real balances from contracts
             balances.insert(COLLATERAL.to_string(), 0.0);
             balances.insert("TokenA".to_string(), 0.0);
             balances.insert("TokenB".to_string(), 0.0);
             balances
         });
 
-        let clob_api_for_place = clob_api.clone();
-        let market_for_place = market.clone();
-        order_book_manager.place_orders_with(move |order: Order| {
-            // Place order via CLOB API - returns order ID if successful
-            let rt = tokio::runtime::Runtime::new().unwrap();
-            let order_id = rt.block_on(
-                clob_api_for_place.place_order(
-                    order.price,
-                    order.size,
-                    order.side.value(),
-                    market_for_place.token_id(order.token),
-                )
-            );
-            order_id.map(|id| Order::new(order.size, order.price, order.side, order.token, Some(id))) // Add ID to order
-        });
-
-        let clob_api_for_cancel = clob_api.clone();
-        order_book_manager.cancel_orders_with(move |order: &Order| {
-            if let Some(ref id) = order.id {
-                let rt = tokio::runtime::Runtime::new().unwrap();
-                rt.block_on(clob_api_for_cancel.cancel_order(id))
-            } else {
-                true
-            }
-        });
-
-        let clob_api_for_cancel_all = clob_api.clone();
-        order_book_manager.cancel_all_orders_with(move || {
-            let rt = tokio::runtime::Runtime::new().unwrap();
-            rt.block_on(clob_api_for_cancel_all.cancel_all_orders())
-        });
-
         order_book_manager.start();
 
         Ok(Self {
@@ -157,7 +93,7 @@ impl App {
             market,
             price_feed,
             order_book_manager,
-            strategy_manager,
+            strategy_manager: Arc::new(strategy_manager),
             contracts,
             address,
         })
@@ -175,38 +111,21 @@ impl App {
             })
         });
 
-        let order_book_manager = &self.order_book_manager;
-        let strategy_manager = &self.strategy_manager;
+        let order_book_manager = self.order_book_manager.clone();
+        let strategy_manager = Arc::clone(&self.strategy_manager);
         lifecycle.every(self.sync_interval, move || {
             // Main sync loop - runs every sync_interval seconds
-            let order_book_manager = order_book_manager;
-            let strategy_manager = strategy_manager;
+            let order_book_manager = order_book_manager.clone();
+            let strategy_manager = Arc::clone(&strategy_manager);
             Box::pin(async move {
-                log::debug!("Synchronizing orderbook...");
-                if let Ok(orderbook) = order_book_manager.get_order_book().await {
-                    // Get current orderbook state
-                    if let Ok((orders_to_cancel, orders_to_place)) =
-                        strategy_manager.synchronize(&orderbook).await
-                    {
-                        // Strategy decides what to do
-                        if !orders_to_cancel.is_empty() {
-                            log::info!("About to cancel {} existing orders!", orders_to_cancel.len());
-                            order_book_manager.cancel_orders(orders_to_cancel).await; // Cancel stale orders
-                        }
-                        if !orders_to_place.is_empty() {
-                            log::info!("About to place {} new orders!", orders_to_place.len());
-                            order_book_manager.place_orders(orders_to_place).await; // Place new ones
-                        }
-                    }
-                }
-                log::debug!("Synchronized orderbook!");
+                synchronize(&order_book_manager, &strategy_manager).await;
             })
         });
 
-        let order_book_manager_for_shutdown = &self.order_book_manager;
-        lifecycle.on_shutdown(|| {
+        let order_book_manager_for_shutdown = self.order_book_manager.clone();
+        lifecycle.on_shutdown(move || {
             // Cleanup on shutdown - gotta cancel all orders before exit
-            let order_book_manager = order_book_manager_for_shutdown;
+            let order_book_manager = order_book_manager_for_shutdown.clone();
             Box::pin(async move {
                 log::info!("Keeper shutting down...");
                 order_book_manager.cancel_all_orders().await; // Cancel everything
@@ -219,3 +138,93 @@ impl App {
     }
 }
 
+// Hook the orderbook manager up to the CLOB - open orders, placing & cancelling.
+// The manager calls these off the async workers, so they block on the runtime handle
+pub fn connect_order_book(order_book_manager: &mut OrderBookManager, clob_api: ClobApi, market: Market) {
+    let handle = Handle::current();
+
+    let clob_api_for_orders = clob_api.clone();
+    let market_for_orders = market.clone();
+    let handle_for_orders = handle.clone();
+    order_book_manager.get_orders_with(move || {
+        // Fetch orders from CLOB API - gotta convert JSON to Order structs
+        let orders = handle_for_orders.block_on(
+            clob_api_for_orders.get_orders(&market_for_orders.condition_id)
+        );
+        orders
+            .into_iter()
+            .map(|order_dict| {
+                // Calculate remaining size (original - matched)
+                let size = json_number(order_dict.get("original_size")) - json_number(order_dict.get("size_matched"));
+                let token_id = order_dict
+                    .get("asset_id")
+                    .and_then(|v| v.as_str().and_then(|s| s.parse().ok()).or_else(|| v.as_u64()))
+                    .unwrap_or(0);
+                Order::new(
+                    size,
+                    json_number(order_dict.get("price")),
+                    Side::from_str(order_dict.get("side").and_then(|v| v.as_str()).unwrap_or("BUY")).unwrap_or(Side::Buy),
+                    market_for_orders.token(token_id).unwrap_or(Token::A),
+                    order_dict.get("id").and_then(|v| v.as_str()).map(|s| s.to_string()),
+                )
+            })
+            .collect()
+    });
+
+    let clob_api_for_place = clob_api.clone();
+    let handle_for_place = handle.clone();
+    order_book_manager.place_orders_with(move |order: Order| {
+        // Place order via CLOB API - returns order ID if successful
+        let order_id = handle_for_place.block_on(
+            clob_api_for_place.place_order(
+                order.price,
+                order.size,
+                order.side.value(),
+                market.token_id(order.token),
+            )
+        );
+        order_id.map(|id| Order::new(order.size, order.price, order.side, order.token, Some(id))) // Add ID to order
+    });
+
+    let clob_api_for_cancel = clob_api.clone();
+    let handle_for_cancel = handle.clone();
+    order_book_manager.cancel_orders_with(move |order: &Order| {
+        if let Some(ref id) = order.id {
+            handle_for_cancel.block_on(clob_api_for_cancel.cancel_order(id))
+        } else {
+            true
+        }
+    });
+
+    order_book_manager.cancel_all_orders_with(move || {
+        handle.block_on(clob_api.cancel_all_orders())
+    });
+}
+
+// One pass of the sync loop - strategy diffs the book, then we cancel & place
+pub async fn synchronize(order_book_manager: &OrderBookManager, strategy_manager: &StrategyManager) {
+    log::debug!("Synchronizing orderbook...");
+    let orderbook = order_book_manager.get_order_book().await; // Get current orderbook state
+    match strategy_manager.synchronize(&orderbook).await {
+        Ok((orders_to_cancel, orders_to_place)) => {
+            // Strategy decides what to do
+            if !orders_to_cancel.is_empty() {
+                log::info!("About to cancel {} existing orders!", orders_to_cancel.len());
+                order_book_manager.cancel_orders(orders_to_cancel).await; // Cancel stale orders
+            }
+            if !orders_to_place.is_empty() {
+                log::info!("About to place {} new orders!", orders_to_place.len());
+                order_book_manager.place_orders(orders_to_place).await; // Place new ones
+            }
+        }
+        Err(e) => log::warn!("Skipping sync: {}", e),
+    }
+    log::debug!("Synchronized orderbook!");
+}
+
+// The CLOB sends numbers as strings - accept either
+fn json_number(value: Option<&serde_json::Value>) -> f64 {
+    value
+        .and_then(|v| v.as_f64().or_else(|| v.as_str().and_then(|s| s.parse().ok())))
+        .unwrap_or(0.0)
+}
@@ -1,4 +1,3 @@
-use crate::constants::OK;
 use crate::metrics::CLOB_REQUESTS_LATENCY;
 use crate::utils::randomize_default_price;
 use std::time::Instant;
@@ -6,6 +5,7 @@ use std::time::Instant;
 const DEFAULT_PRICE: f64 = 0.5;
 
 #[derive(Clone)]
+#[allow(dead_code)] // Chain ID & keys are for signed orders (tbd)
 pub struct ClobApi {
     host: String,
     chain_id: u64,
@@ -59,7 +59,9 @@ impl ClobApi {
         match reqwest::get(&url).await {
             Ok(resp) => {
                 if let Ok(json) = resp.json::<serde_json::Value>().await {
-                    if let Some(mid) = json.get("mid").and_then(|v| v.as_f64()) {
+                    // The CLOB sends the midpoint as a string
+                    let mid = json.get("mid").and_then(|v| v.as_f64().or_else(|| v.as_str()?.parse().ok()));
+                    if let Some(mid) = mid {
                         let duration = start_time.elapsed().as_secs_f64();
                         CLOB_REQUESTS_LATENCY.observe(duration); // Track latency
                         return mid; // Got it!
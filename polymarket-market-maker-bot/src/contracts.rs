@@ -1,17 +1,9 @@
 use crate::gas::GasStation;
 use crate::metrics::CHAIN_REQUESTS_COUNTER;
 use ethers::prelude::*;
-use std::str::FromStr;
 
 #[derive(Clone)]
-pub struct Contracts {
-    provider: Provider<Http>,
-    gas_station: GasStation,
-    address: Address,
-}
-
-const DECIMALS: u64 = 1_000_000;
-
+#[allow(dead_code)] // Gas station & address are for the tbd balance/approval calls
 pub struct Contracts {
     provider: Provider<Http>,
     gas_station: GasStation,
@@ -33,14 +25,13 @@ impl Contracts {
         address: Address,
         token_id: Option<u64>,
     ) -> f64 {
-        if token_id.is_none() {
-            self.balance_of_erc20(token, address).await
-        } else {
-            self.balance_of_erc1155(token, address, token_id.unwrap()).await
+        match token_id {
+            None => self.balance_of_erc20(token, address).await,
+            Some(token_id) => self.balance_of_erc1155(token, address, token_id).await,
         }
     }
 
-    async fn balance_of_erc20(&self, token: Address, address: Address) -> f64 {
+    async fn balance_of_erc20(&self, _token: Address, _address: Address) -> f64 {
         // ERC20 balanceOf implementation would go here
         // For now, return 0.0
         0.0
@@ -14,7 +14,7 @@ impl CTHelpers {
 
     pub fn get_collection_id(condition_id: &str, index_set: u64) -> String {
         let x1 = Self::get_x1(condition_id, index_set);
-        let odd = (x1 >> 255) == 1;
+        let odd = (&x1 >> 255u32) == num_bigint::BigUint::from(1u64);
         let p = num_bigint::BigUint::from_str(Self::P).unwrap();
         let mut a = x1 % &p;
 
@@ -58,8 +58,7 @@ impl CTHelpers {
         input.extend_from_slice(&collateral_bytes);
         input.extend_from_slice(&collection_bytes);
 
-        let hash = Keccak256::digest(&input);
-        let hash_bytes = hash.as_slice();
+        let hash_bytes: [u8; 32] = Keccak256::digest(&input).into();
         u64::from_be_bytes([
             hash_bytes[24], hash_bytes[25], hash_bytes[26], hash_bytes[27],
             hash_bytes[28], hash_bytes[29], hash_bytes[30], hash_bytes[31],
@@ -16,6 +16,7 @@ pub enum GasStrategy {
 }
 
 impl GasStrategy {
+    #[allow(clippy::should_implement_trait)]
     pub fn from_str(s: &str) -> Option<Self> {
         match s.to_lowercase().as_str() {
             "fixed" => Some(GasStrategy::Fixed),
@@ -34,12 +35,6 @@ impl GasStrategy {
     }
 }
 
-pub struct GasStation {
-    strategy: GasStrategy,
-    fixed_gas_price: u64,
-    gas_station_url: Option<String>,
-}
-
 impl GasStation {
     const DEFAULT_FIXED_GAS_PRICE: u64 = 100_000_000_000;
 
@@ -0,0 +1,18 @@
+// Library target - lets the integration tests drive the keeper's pieces
+pub mod app;
+pub mod args;
+pub mod clob_api;
+pub mod constants;
+pub mod contracts;
+pub mod ct_helpers;
+pub mod gas;
+pub mod lifecycle;
+pub mod market;
+pub mod metrics;
+pub mod order;
+pub mod orderbook;
+pub mod price_feed;
+pub mod strategy;
+pub mod strategies;
+pub mod token;
+pub mod utils;
@@ -2,11 +2,19 @@ use tokio::signal;
 use tokio::time::{sleep, Duration};
 use std::sync::Arc;
 
+type Callback = Arc<dyn Fn() -> std::pin::Pin<Box<dyn std::future::Future<Output = ()> + Send>> + Send + Sync>;
+
 pub struct Lifecycle {
     sync_interval: u64,
-    startup_callback: Option<Arc<dyn Fn() -> std::pin::Pin<Box<dyn std::future::Future<Output = ()> + Send>> + Send + Sync>>,
-    sync_callback: Option<Arc<dyn Fn() -> std::pin::Pin<Box<dyn std::future::Future<Output = ()> + Send>> + Send + Sync>>,
-    shutdown_callback: Option<Arc<dyn Fn() -> std::pin::Pin<Box<dyn std::future::Future<Output = ()> + Send>> + Send + Sync>>,
+    startup_callback: Option<Callback>,
+    sync_callback: Option<Callback>,
+    shutdown_callback: Option<Callback>,
+}
+
+impl Default for Lifecycle {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl Lifecycle {
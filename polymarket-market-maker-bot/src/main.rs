@@ -1,24 +1,6 @@
 // Main entry point - sets up the market maker & runs it
-mod app;
-mod args;
-mod clob_api;
-mod constants;
-mod contracts;
-mod ct_helpers;
-mod gas;
-mod lifecycle;
-mod market;
-mod metrics;
-mod order;
-mod orderbook;
-mod price_feed;
-mod strategy;
-mod strategies;
-mod token;
-mod utils;
-
 use anyhow::Result;
-use app::App;
+use poly_market_maker_rust::app::App;
 
 #[tokio::main]
 async fn main() -> Result<()> {
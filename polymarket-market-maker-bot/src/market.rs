@@ -1,6 +1,5 @@
 use crate::ct_helpers::CTHelpers;
 use crate::token::Token;
-use std::collections::HashMap;
 use std::fmt;
 
 #[derive(Clone)]
@@ -9,11 +8,6 @@ pub struct Market {
     pub token_ids: std::collections::HashMap<Token, u64>,
 }
 
-pub struct Market {
-    pub condition_id: String,
-    pub token_ids: std::collections::HashMap<Token, u64>,
-}
-
 impl Market {
     pub fn new(condition_id: String, collateral_address: String) -> Self {
         let mut token_ids = std::collections::HashMap::new();
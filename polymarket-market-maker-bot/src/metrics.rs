@@ -1,5 +1,4 @@
 use prometheus::{Counter, Gauge, Histogram, Registry, HistogramOpts, Opts};
-use std::sync::Arc;
 
 lazy_static::lazy_static! {
     pub static ref CHAIN_REQUESTS_COUNTER: Counter = Counter::with_opts(
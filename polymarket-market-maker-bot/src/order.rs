@@ -18,6 +18,7 @@ impl Side {
         }
     }
 
+    #[allow(clippy::should_implement_trait)]
     pub fn from_str(s: &str) -> Option<Self> {
         // Parse from string - case insensitive
         match s.to_uppercase().as_str() {
@@ -35,7 +36,7 @@ impl fmt::Display for Side {
 }
 
 // Order struct - represents a single order on the book
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Order {
     pub size: f64, // Order size
     pub price: f64, // Order price
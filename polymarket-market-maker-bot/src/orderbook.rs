@@ -4,13 +4,16 @@ use std::sync::{Arc, Mutex};
 use tokio::sync::RwLock;
 use tokio::time::{sleep, Duration};
 
+type CancelOrderFunction = Arc<dyn Fn(&Order) -> bool + Send + Sync>;
+
+// Manages orderbook state w/ background refresh - keeps it fresh w/o constant polling
 #[derive(Clone)]
 pub struct OrderBookManager {
     refresh_frequency: u64,
     get_orders_function: Option<Arc<dyn Fn() -> Vec<Order> + Send + Sync>>,
     get_balances_function: Option<Arc<dyn Fn() -> HashMap<String, f64> + Send + Sync>>,
     place_order_function: Option<Arc<dyn Fn(Order) -> Option<Order> + Send + Sync>>,
-    cancel_order_function: Option<Arc<dyn Fn(&Order) -> bool + Send + Sync>>,
+    cancel_order_function: Option<CancelOrderFunction>,
     cancel_all_orders_function: Option<Arc<dyn Fn() -> bool + Send + Sync>>,
     state: Arc<RwLock<Option<OrderBookState>>>,
     refresh_count: Arc<Mutex<u64>>,
@@ -28,21 +31,6 @@ pub struct OrderBook {
     pub orders_being_cancelled: bool,
 }
 
-// Manages orderbook state w/ background refresh - keeps it fresh w/o constant polling
-pub struct OrderBookManager {
-    refresh_frequency: u64,
-    get_orders_function: Option<Arc<dyn Fn() -> Vec<Order> + Send + Sync>>,
-    get_balances_function: Option<Arc<dyn Fn() -> HashMap<String, f64> + Send + Sync>>,
-    place_order_function: Option<Arc<dyn Fn(Order) -> Option<Order> + Send + Sync>>,
-    cancel_order_function: Option<Arc<dyn Fn(&Order) -> bool + Send + Sync>>,
-    cancel_all_orders_function: Option<Arc<dyn Fn() -> bool + Send + Sync>>,
-    state: Arc<RwLock<Option<OrderBookState>>>,
-    refresh_count: Arc<Mutex<u64>>,
-    currently_placing_orders: Arc<Mutex<u64>>,
-    orders_placed: Arc<Mutex<Vec<Order>>>,
-    order_ids_cancelling: Arc<Mutex<std::collections::HashSet<String>>>,
-    order_ids_cancelled: Arc<Mutex<std::collections::HashSet<String>>>,
-}
 
 struct OrderBookState {
     orders: Vec<Order>,
@@ -109,15 +97,16 @@ impl OrderBookManager {
         let get_balances = self.get_balances_function.as_ref().map(Arc::clone);
         let refresh_frequency = self.refresh_frequency;
         let refresh_count = Arc::clone(&self.refresh_count);
-        let orders_placed = Arc::clone(&self.orders_placed);
-        let order_ids_cancelled = Arc::clone(&self.order_ids_cancelled);
-        let order_ids_cancelling = Arc::clone(&self.order_ids_cancelling);
 
         tokio::spawn(async move {
             loop {
-                // Fetch fresh data from API
-                let orders = get_orders();
-                let balances = get_balances.as_ref().map(|f| f());
+                // Fetch fresh data from API (BTW: the fetchers block, so keep them off the async workers)
+                let get_orders = Arc::clone(&get_orders);
+                let orders = tokio::task::spawn_blocking(move || get_orders()).await.unwrap_or_default();
+                let balances = match get_balances.as_ref().map(Arc::clone) {
+                    Some(get_balances) => tokio::task::spawn_blocking(move || get_balances()).await.ok(),
+                    None => None,
+                };
 
                 let mut state_guard = state.write().await;
                 *state_guard = Some(OrderBookState {
@@ -163,7 +152,7 @@ impl OrderBookManager {
 
         // Remove orders we're cancelling or already cancelled
         orders.retain(|order| {
-            order.id.as_ref().map_or(true, |id| {
+            order.id.as_ref().is_none_or(|id| {
                 !order_ids_cancelling.contains(id) && !order_ids_cancelled.contains(id)
             })
         });
@@ -201,7 +190,7 @@ impl OrderBookManager {
 
             tokio::spawn(async move {
                 // Place each order async
-                if let Some(new_order) = place_fn(order) {
+                if let Ok(Some(new_order)) = tokio::task::spawn_blocking(move || place_fn(order)).await {
                     orders_placed.lock().unwrap().push(new_order); // Track successful placements
                 }
                 let mut count = currently_placing.lock().unwrap();
@@ -230,7 +219,8 @@ impl OrderBookManager {
             let order_id = order.id.clone();
 
             tokio::spawn(async move {
-                if cancel_fn(&order) {
+                let cancelled = tokio::task::spawn_blocking(move || cancel_fn(&order)).await.unwrap_or(false);
+                if cancelled {
                     if let Some(ref id) = order_id {
                         order_ids_cancelled.lock().unwrap().insert(id.clone());
                         order_ids_cancelling.lock().unwrap().remove(id);
@@ -265,8 +255,8 @@ impl OrderBookManager {
                 }
             }
 
-            if let Some(ref cancel_all_fn) = self.cancel_all_orders_function {
-                if cancel_all_fn() {
+            if let Some(cancel_all_fn) = self.cancel_all_orders_function.as_ref().map(Arc::clone) {
+                if tokio::task::spawn_blocking(move || cancel_all_fn()).await.unwrap_or(false) {
                     // Successfully cancelled
                     let mut cancelled = self.order_ids_cancelled.lock().unwrap();
                     let mut cancelling = self.order_ids_cancelling.lock().unwrap();
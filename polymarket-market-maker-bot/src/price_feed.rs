@@ -14,11 +14,6 @@ pub trait PriceFeed: Send + Sync {
     async fn get_price(&self, token: Token) -> f64;
 }
 
-pub struct PriceFeedClob {
-    market: Market,
-    clob_api: ClobApi,
-}
-
 impl PriceFeedClob {
     pub fn new(market: Market, clob_api: ClobApi) -> Self {
         Self { market, clob_api }
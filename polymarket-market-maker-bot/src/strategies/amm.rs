@@ -3,6 +3,7 @@ use crate::order::{Order, Side};
 use crate::token::{Token, COLLATERAL};
 use crate::utils::math_round_down;
 
+#[derive(Clone)]
 pub struct AMMConfig {
     pub p_min: f64,
     pub p_max: f64,
@@ -19,7 +20,6 @@ pub struct AMM {
     delta: f64,
     spread: f64,
     depth: f64,
-    max_collateral: f64,
     p_i: Option<f64>,
     p_u: Option<f64>,
     p_l: Option<f64>,
@@ -40,7 +40,6 @@ impl AMM {
             delta: config.delta,
             spread: config.spread,
             depth: config.depth,
-            max_collateral: config.max_collateral,
             p_i: None,
             p_u: None,
             p_l: None,
@@ -6,14 +6,26 @@ use crate::strategies::base_strategy::BaseStrategy;
 use crate::token::Token;
 use async_trait::async_trait;
 use std::collections::{HashMap, HashSet};
+use std::hash::{Hash, Hasher};
 
-#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, PartialEq)]
 struct OrderType {
     price: f64,
     side: crate::order::Side,
     token: Token,
 }
 
+// Prices are rounded to the tick, so comparing their bits is safe
+impl Eq for OrderType {}
+
+impl Hash for OrderType {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.price.to_bits().hash(state);
+        self.side.hash(state);
+        self.token.hash(state);
+    }
+}
+
 impl OrderType {
     fn from_order(order: &Order) -> Self {
         Self {
@@ -25,7 +37,7 @@ impl OrderType {
 }
 
 pub struct AMMStrategy {
-    amm_manager: AMMManager,
+    config: AMMConfig,
 }
 
 impl AMMStrategy {
@@ -39,9 +51,7 @@ impl AMMStrategy {
             max_collateral: config_dict["max_collateral"].as_f64().unwrap(),
         };
 
-        Self {
-            amm_manager: AMMManager::new(config),
-        }
+        Self { config }
     }
 }
 
@@ -60,18 +70,11 @@ impl BaseStrategy for AMMStrategy {
         balances.insert("TokenA".to_string(), orderbook.balances.get("TokenA").copied().unwrap_or(0.0));
         balances.insert("TokenB".to_string(), orderbook.balances.get("TokenB").copied().unwrap_or(0.0));
 
-        let mut amm_manager = AMMManager::new(AMMConfig {
-            p_min: 0.05,
-            p_max: 0.95,
-            spread: 0.01,
-            delta: 0.01,
-            depth: 0.1,
-            max_collateral: 200.0,
-        });
+        let mut amm_manager = AMMManager::new(self.config.clone());
         let expected_orders = amm_manager.get_expected_orders(target_prices, &balances);
         let expected_order_types: HashSet<OrderType> = expected_orders
             .iter()
-            .map(|order| OrderType::from_order(order))
+            .map(OrderType::from_order)
             .collect();
 
         orders_to_cancel.extend(
@@ -96,7 +99,7 @@ impl BaseStrategy for AMMStrategy {
                 .sum();
 
             let new_size = if open_size > expected_size {
-                orders_to_cancel.extend(open_orders.iter().cloned());
+                orders_to_cancel.extend(open_orders.iter().map(|order| (*order).clone()));
                 expected_size
             } else {
                 (expected_size - open_size) * 100.0 / 100.0
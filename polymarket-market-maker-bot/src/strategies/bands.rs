@@ -1,4 +1,4 @@
-use crate::constants::{MAX_DECIMALS, MIN_SIZE, MIN_TICK};
+use crate::constants::{MAX_DECIMALS, MIN_SIZE};
 use crate::order::{Order, Side};
 use crate::token::Token;
 use crate::utils::math_round_down;
@@ -51,7 +51,6 @@ impl Band {
             .iter()
             .filter(|order| self.includes(order, target_price))
             .collect();
-        let orders_total_size: f64 = orders_in_band.iter().map(|order| order.size).sum();
 
         let mut orders_in_band = orders_in_band.clone();
         if is_first_band {
@@ -194,9 +193,10 @@ impl Bands {
         let sell_token = buy_token.complement();
         let mut new_orders = Vec::new();
         let mut free_collateral_balance = collateral_balance;
+        let mut free_token_balance = token_balance;
 
         for band in self.calculate_virtual_bands(target_price) {
-            let band_amount: f64 = orders
+            let mut band_amount: f64 = orders
                 .iter()
                 .filter(|order| band.includes(order, target_price))
                 .map(|order| order.size)
@@ -205,13 +205,15 @@ impl Bands {
             if band_amount < band.min_amount {
                 let sell_price = band.sell_price(target_price);
                 let sell_size = math_round_down(
-                    (band.avg_amount - band_amount).min(token_balance),
+                    (band.avg_amount - band_amount).min(free_token_balance),
                     MAX_DECIMALS,
                 );
 
                 if Self::new_order_is_valid(sell_price, sell_size) {
                     new_orders.push(Order::new(sell_size, sell_price, Side::Sell, sell_token, None));
-                    free_collateral_balance -= sell_size * sell_price;
+                    // Sells use tokens, not collateral - the buy only tops up what's left
+                    free_token_balance -= sell_size;
+                    band_amount += sell_size;
                 }
 
                 let buy_price = band.buy_price(target_price);
@@ -237,10 +239,8 @@ impl Bands {
     fn bands_overlap(bands: &[Band]) -> bool {
         for (i, band1) in bands.iter().enumerate() {
             for (j, band2) in bands.iter().enumerate() {
-                if i != j {
-                    if band1.min_margin < band2.max_margin && band2.min_margin < band1.max_margin {
-                        return true;
-                    }
+                if i != j && band1.min_margin < band2.max_margin && band2.min_margin < band1.max_margin {
+                    return true;
                 }
             }
         }
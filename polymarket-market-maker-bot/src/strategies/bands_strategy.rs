@@ -67,7 +67,7 @@ impl BaseStrategy for BandsStrategy {
             let complement_token = token.complement();
             let free_token_balance = orderbook
                 .balances
-                .get(&complement_token.value().to_string())
+                .get(complement_token.value())
                 .copied()
                 .unwrap_or(0.0)
                 - balance_locked_by_open_sells;
@@ -12,6 +12,7 @@ pub enum Strategy {
 }
 
 impl Strategy {
+    #[allow(clippy::should_implement_trait)]
     pub fn from_str(s: &str) -> Option<Self> {
         match s.to_lowercase().as_str() {
             "amm" => Some(Strategy::AMM),
@@ -0,0 +1,109 @@
+// Sync loop end to end against the local mock Polymarket stack (no network):
+// midpoint feed, bands strategy, and the orderbook manager placing &
+// cancelling through the CLOB API. Balances are fixed here since the keeper
+// doesn't read them from chain yet.
+
+use poly_market_maker_rust::app::{connect_order_book, synchronize};
+use poly_market_maker_rust::clob_api::ClobApi;
+use poly_market_maker_rust::market::Market;
+use poly_market_maker_rust::orderbook::OrderBookManager;
+use poly_market_maker_rust::price_feed::PriceFeedClob;
+use poly_market_maker_rust::strategy::{Strategy, StrategyManager};
+use poly_market_maker_rust::token::{Token, COLLATERAL};
+use polymarket_mock_server::{Event, MockOrder, MockServer, Scenario};
+use serde_json::json;
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+
+const CONDITION: &str = "0x5f65177b394277fd294cd75650044e32ba009a95022d88a0c1d565897d72f8f1";
+const USDC: &str = "0x2791Bca1f2de4661ED88A30C99A7a9449Aa84174";
+const PRIVATE_KEY: &str = "0xac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80";
+
+fn scenario(market: &Market) -> Scenario {
+    let token_a = market.token_id(Token::A).to_string();
+    let token_b = market.token_id(Token::B).to_string();
+    serde_json::from_value(json!({
+        "books": {
+            token_a: { "market": CONDITION, "bids": [[0.49, 500]], "asks": [[0.51, 500]] },
+            token_b: { "market": CONDITION, "bids": [[0.49, 500]], "asks": [[0.51, 500]] }
+        }
+    }))
+    .unwrap()
+}
+
+fn live_orders(server: &MockServer) -> Vec<MockOrder> {
+    server.orders().into_iter().filter(|o| o.status == "live").collect()
+}
+
+// Wait until the mock has the orders the last sync sent
+async fn settle(order_book_manager: &OrderBookManager) {
+    tokio::time::timeout(Duration::from_secs(10), order_book_manager.wait_for_stable_order_book())
+        .await
+        .expect("orders still being placed or cancelled");
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_sync_loop_places_and_reprices_bands() {
+    let market = Market::new(CONDITION.to_string(), USDC.to_string());
+    let token_a = market.token_id(Token::A).to_string();
+    let server = MockServer::start(scenario(&market)).await.unwrap();
+    let clob_api = ClobApi::new(server.clob_url(), 137, PRIVATE_KEY.to_string());
+
+    let mut order_book_manager = OrderBookManager::new(1);
+    connect_order_book(&mut order_book_manager, clob_api.clone(), market.clone());
+    order_book_manager.get_balances_with(|| {
+        HashMap::from([
+            (COLLATERAL.to_string(), 1000.0),
+            (Token::A.value().to_string(), 100.0),
+            (Token::B.value().to_string(), 100.0),
+        ])
+    });
+    order_book_manager.start();
+
+    let price_feed = Arc::new(PriceFeedClob::new(market.clone(), clob_api));
+    let strategy_manager = StrategyManager::new(
+        Strategy::BANDS,
+        concat!(env!("CARGO_MANIFEST_DIR"), "/config/bands.json"),
+        price_feed,
+    )
+    .unwrap();
+
+    // First pass quotes both tokens around the 0.50 midpoint
+    synchronize(&order_book_manager, &strategy_manager).await;
+    settle(&order_book_manager).await;
+    let placed = live_orders(&server);
+    assert!(!placed.is_empty(), "the first sync places the bands");
+    assert!(placed.iter().all(|o| o.market == CONDITION && o.order_type == "GTC"));
+    assert!(placed.iter().all(|o| o.price > 0.45 && o.price < 0.55), "bands sit within 5 cents of the midpoint");
+
+    // Nothing moved: the next pass leaves the book alone
+    tokio::time::sleep(Duration::from_millis(1500)).await;
+    synchronize(&order_book_manager, &strategy_manager).await;
+    settle(&order_book_manager).await;
+    assert_eq!(server.orders().len(), placed.len(), "no orders added or replaced");
+    assert_eq!(live_orders(&server).len(), placed.len());
+
+    // Token A rallies to 0.70: the old bands are cancelled and re-quoted around it
+    server.apply(&Event::Book {
+        token_id: token_a.clone(),
+        bids: vec![(0.69, 500.0)],
+        asks: vec![(0.71, 500.0)],
+    });
+    synchronize(&order_book_manager, &strategy_manager).await;
+    settle(&order_book_manager).await;
+    let orders = server.orders();
+    assert!(placed.iter().all(|old| orders.iter().any(|o| o.id == old.id && o.status == "canceled")));
+    let requoted = live_orders(&server);
+    assert!(!requoted.is_empty());
+    assert!(requoted
+        .iter()
+        .filter(|o| o.asset_id == token_a && o.side == "BUY")
+        .all(|o| o.price > 0.6));
+
+    // Shutdown cancels everything still resting
+    tokio::time::timeout(Duration::from_secs(15), order_book_manager.cancel_all_orders())
+        .await
+        .expect("cancel all timed out");
+    assert!(live_orders(&server).is_empty());
+}
@@ -0,0 +1,212 @@
+//! Local mock of the Polymarket stack for integration tests.
+//!
+//! One port serves the CLOB REST API at the root, Gamma under `/gamma`, the
+//! Data API under `/data-api`, Polygon JSON-RPC at `/rpc`, the CLOB market
+//! channel on WebSocket paths under `/ws` and RTDS on any other WebSocket path.
+//! State comes from a [`Scenario`]; its steps (or [`MockServer::apply`]) move
+//! books, push RTDS trades and script failures while a bot runs against it.
+
+mod http;
+mod routes;
+pub mod scenario;
+mod state;
+mod ws;
+
+pub use scenario::{BookSpec, Event, Scenario, Step};
+pub use state::{MockOrder, RecordedRequest};
+
+use anyhow::Result;
+use serde_json::{json, Value};
+use state::{now_ms, Data};
+use std::net::SocketAddr;
+use std::sync::{Arc, Mutex};
+use tokio::net::{TcpListener, TcpStream, ToSocketAddrs};
+use tokio::sync::{broadcast, watch};
+use tokio::task::JoinHandle;
+use tokio::time::{sleep, Duration};
+use ws::MarketMessage;
+
+/// Everything the connection tasks share
+pub(crate) struct Shared {
+    data: Mutex<Data>,
+    rtds: broadcast::Sender<String>,
+    market: broadcast::Sender<MarketMessage>,
+    rtds_subscribers: watch::Sender<usize>,
+    market_subscribers: watch::Sender<usize>,
+}
+
+impl Shared {
+    /// Market channel `book` messages for the known books among `assets`
+    fn book_messages<'a>(&self, assets: impl Iterator<Item = &'a str>) -> Vec<Value> {
+        let data = self.data.lock().unwrap();
+        assets
+            .filter_map(|asset| data.books.get(asset).map(|book| book.to_json(asset)))
+            .map(|mut book| {
+                book["event_type"] = json!("book");
+                book
+            })
+            .collect()
+    }
+}
+
+pub struct MockServer {
+    addr: SocketAddr,
+    shared: Arc<Shared>,
+    steps: Vec<Step>,
+    accept_loop: JoinHandle<()>,
+}
+
+impl MockServer {
+    /// Serve `scenario` on a free localhost port
+    pub async fn start(scenario: Scenario) -> Result<Self> {
+        Self::bind("127.0.0.1:0", scenario).await
+    }
+
+    pub async fn bind(addr: impl ToSocketAddrs, scenario: Scenario) -> Result<Self> {
+        let listener = TcpListener::bind(addr).await?;
+        let addr = listener.local_addr()?;
+        let shared = Arc::new(Shared {
+            data: Mutex::new(Data::from_scenario(&scenario)),
+            rtds: broadcast::channel(1024).0,
+            market: broadcast::channel(1024).0,
+            rtds_subscribers: watch::channel(0).0,
+            market_subscribers: watch::channel(0).0,
+        });
+
+        let accepting = shared.clone();
+        let accept_loop = tokio::spawn(async move {
+            while let Ok((stream, _)) = listener.accept().await {
+                tokio::spawn(handle_connection(stream, accepting.clone()));
+            }
+        });
+
+        Ok(Self { addr, shared, steps: scenario.steps, accept_loop })
+    }
+
+    pub fn addr(&self) -> SocketAddr {
+        self.addr
+    }
+
+    /// CLOB_HTTP_URL
+    pub fn clob_url(&self) -> String {
+        format!("http://{}", self.addr)
+    }
+
+    pub fn gamma_url(&self) -> String {
+        format!("http://{}/gamma", self.addr)
+    }
+
+    /// DATA_API_URL
+    pub fn data_api_url(&self) -> String {
+        format!("http://{}/data-api", self.addr)
+    }
+
+    /// RPC_URL
+    pub fn rpc_url(&self) -> String {
+        format!("http://{}/rpc", self.addr)
+    }
+
+    pub fn rtds_url(&self) -> String {
+        format!("ws://{}/rtds", self.addr)
+    }
+
+    /// CLOB_WS_URL (market channel)
+    pub fn market_ws_url(&self) -> String {
+        format!("ws://{}/ws/market", self.addr)
+    }
+
+    /// Apply one event now: update state and push WebSocket messages
+    pub fn apply(&self, event: &Event) {
+        self.shared.data.lock().unwrap().apply(event);
+
+        // Sends only fail when nobody is connected, which is fine for a broadcast
+        match event {
+            Event::RtdsTrade { trade } => {
+                let message = json!({
+                    "topic": "activity",
+                    "type": "trades",
+                    "timestamp": now_ms(),
+                    "payload": trade,
+                });
+                let _ = self.shared.rtds.send(message.to_string());
+            }
+            Event::Rtds { message } => {
+                let _ = self.shared.rtds.send(message.to_string());
+            }
+            Event::Book { token_id, .. } => {
+                if let Some(book) = self.shared.book_messages(std::iter::once(token_id.as_str())).pop() {
+                    let _ = self.shared.market.send(MarketMessage {
+                        asset_id: Some(token_id.clone()),
+                        text: book.to_string(),
+                    });
+                }
+            }
+            Event::MarketWs { message } => {
+                let _ = self.shared.market.send(MarketMessage { asset_id: None, text: message.to_string() });
+            }
+            _ => {}
+        }
+    }
+
+    /// Run the scenario's steps in order, waiting `after_ms` before each
+    pub async fn play(&self) {
+        for step in &self.steps {
+            sleep(Duration::from_millis(step.after_ms)).await;
+            self.apply(&step.event);
+        }
+    }
+
+    /// Wait until `count` RTDS clients have subscribed
+    pub async fn wait_for_rtds_subscribers(&self, count: usize) {
+        let mut subscribers = self.shared.rtds_subscribers.subscribe();
+        let _ = subscribers.wait_for(|n| *n >= count).await;
+    }
+
+    /// Wait until `count` market channel clients have subscribed to an asset
+    pub async fn wait_for_market_subscribers(&self, count: usize) {
+        let mut subscribers = self.shared.market_subscribers.subscribe();
+        let _ = subscribers.wait_for(|n| *n >= count).await;
+    }
+
+    /// Every request received so far, WebSocket upgrades included
+    pub fn requests(&self) -> Vec<RecordedRequest> {
+        self.shared.data.lock().unwrap().requests.clone()
+    }
+
+    /// Every accepted order with its current status (rejected orders are not kept)
+    pub fn orders(&self) -> Vec<MockOrder> {
+        self.shared.data.lock().unwrap().orders.clone()
+    }
+}
+
+impl Drop for MockServer {
+    fn drop(&mut self) {
+        self.accept_loop.abort();
+    }
+}
+
+async fn handle_connection(mut stream: TcpStream, shared: Arc<Shared>) {
+    let Ok(request) = http::read_request(&mut stream).await else {
+        return;
+    };
+
+    if request.is_websocket_upgrade() {
+        shared.data.lock().unwrap().requests.push(RecordedRequest {
+            method: request.method.clone(),
+            path: request.target.clone(),
+            body: String::new(),
+        });
+        let Ok(socket) = http::accept_websocket(stream, &request).await else {
+            return;
+        };
+        if request.target.starts_with("/ws") {
+            ws::market_session(socket, shared).await;
+        } else {
+            ws::rtds_session(socket, shared).await;
+        }
+        return;
+    }
+
+    let response = routes::handle(&mut shared.data.lock().unwrap(), &request.method, &request.target, &request.body);
+    let _ = http::write_response(&mut stream, response).await;
+}
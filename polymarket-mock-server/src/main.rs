@@ -0,0 +1,55 @@
+use anyhow::Result;
+use clap::Parser;
+use polymarket_mock_server::{MockServer, Scenario};
+
+/// Serve a mock Polymarket stack (CLOB, market channel, RTDS, Gamma, Data API, RPC)
+#[derive(Parser)]
+#[command(name = "polymarket-mock-server")]
+struct Args {
+    /// Address to listen on
+    #[arg(long, default_value = "127.0.0.1")]
+    host: String,
+
+    #[arg(long, default_value_t = 8080)]
+    port: u16,
+
+    /// Scenario JSON file (initial books, markets, positions and scripted steps)
+    #[arg(long)]
+    scenario: Option<String>,
+
+    /// Run the scenario's steps once the first RTDS or market channel client subscribes
+    #[arg(long)]
+    autoplay: bool,
+}
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    let args = Args::parse();
+    let scenario = match &args.scenario {
+        Some(path) => Scenario::from_file(path)?,
+        None => Scenario::default(),
+    };
+    let step_count = scenario.steps.len();
+
+    let server = MockServer::bind((args.host.as_str(), args.port), scenario).await?;
+    println!("Mock Polymarket stack listening on {}", server.addr());
+    println!("  CLOB_HTTP_URL = {}", server.clob_url());
+    println!("  CLOB_WS_URL   = {}", server.market_ws_url());
+    println!("  RTDS_URL      = {}", server.rtds_url());
+    println!("  GAMMA_API_URL = {}", server.gamma_url());
+    println!("  DATA_API_URL  = {}", server.data_api_url());
+    println!("  RPC_URL       = {}", server.rpc_url());
+
+    if args.autoplay && step_count > 0 {
+        println!("Waiting for a WebSocket subscriber to play {} step(s)...", step_count);
+        tokio::select! {
+            _ = server.wait_for_rtds_subscribers(1) => {}
+            _ = server.wait_for_market_subscribers(1) => {}
+        }
+        server.play().await;
+        println!("Scenario finished");
+    }
+
+    std::future::pending::<()>().await;
+    Ok(())
+}
@@ -0,0 +1,453 @@
+//! HTTP routes: CLOB REST at the root, Gamma under `/gamma`, the Data API
+//! under `/data-api` and Polygon JSON-RPC at `/rpc`.
+
+use crate::state::{now_ms, Data, MockOrder};
+use serde_json::{json, Value};
+
+pub(crate) struct Response {
+    pub status: u16,
+    pub headers: Vec<(String, String)>,
+    pub body: String,
+}
+
+impl Response {
+    fn json(status: u16, body: Value) -> Self {
+        Self { status, headers: Vec::new(), body: body.to_string() }
+    }
+
+    fn ok(body: Value) -> Self {
+        Self::json(200, body)
+    }
+
+    fn error(status: u16, message: &str) -> Self {
+        Self::json(status, json!({ "error": message }))
+    }
+}
+
+/// Parsed query string; repeated keys and comma-separated values both count
+struct Query(Vec<(String, String)>);
+
+impl Query {
+    fn parse(query: &str) -> Self {
+        Self(url::form_urlencoded::parse(query.as_bytes()).into_owned().collect())
+    }
+
+    fn get(&self, key: &str) -> Option<&str> {
+        self.0.iter().find(|(k, _)| k == key).map(|(_, v)| v.as_str())
+    }
+
+    fn all(&self, key: &str) -> Vec<String> {
+        self.0
+            .iter()
+            .filter(|(k, _)| k == key)
+            .flat_map(|(_, v)| v.split(','))
+            .filter(|v| !v.is_empty())
+            .map(|v| v.to_string())
+            .collect()
+    }
+
+    fn number(&self, key: &str, default: usize) -> usize {
+        self.get(key).and_then(|v| v.parse().ok()).unwrap_or(default)
+    }
+
+    /// `limit`/`offset` window over `records`
+    fn page(&self, records: Vec<Value>) -> Value {
+        let offset = self.number("offset", 0);
+        let limit = self.number("limit", 100);
+        Value::Array(records.into_iter().skip(offset).take(limit).collect())
+    }
+}
+
+fn field<'a>(record: &'a Value, key: &str) -> &'a str {
+    record.get(key).and_then(|v| v.as_str()).unwrap_or("")
+}
+
+pub(crate) fn handle(data: &mut Data, method: &str, target: &str, body: &str) -> Response {
+    data.requests.push(crate::state::RecordedRequest {
+        method: method.to_string(),
+        path: target.to_string(),
+        body: body.to_string(),
+    });
+
+    let (path, query) = target.split_once('?').unwrap_or((target, ""));
+    let query = Query::parse(query);
+
+    if let Some(failure) = data.take_failure(path) {
+        let mut response = Response::error(failure.status, "scripted failure");
+        if let Some(seconds) = failure.retry_after {
+            response.headers.push(("Retry-After".to_string(), seconds.to_string()));
+        }
+        return response;
+    }
+    for key in [format!("{} {}", method, target), format!("{} {}", method, path)] {
+        if let Some(fixed) = data.routes.get(&key) {
+            return Response::ok(fixed.clone());
+        }
+    }
+
+    if let Some(rest) = path.strip_prefix("/gamma") {
+        return gamma(data, rest, &query);
+    }
+    if let Some(rest) = path.strip_prefix("/data-api") {
+        return data_api(data, rest, &query);
+    }
+    if path == "/rpc" {
+        return rpc(data, body);
+    }
+    clob(data, method, path, &query, body)
+}
+
+fn clob(data: &mut Data, method: &str, path: &str, query: &Query, body: &str) -> Response {
+    let token = query.get("token_id").unwrap_or("").to_string();
+    let body: Value = serde_json::from_str(body).unwrap_or(Value::Null);
+
+    match (method, path) {
+        ("GET", "/") => Response::ok(json!("OK")),
+        ("GET", "/time") => Response::ok(json!(now_ms() / 1000)),
+        ("GET", "/book") => match data.books.get(&token) {
+            Some(book) => Response::ok(book.to_json(&token)),
+            None => Response::error(404, "No orderbook exists for the requested token id"),
+        },
+        ("POST", "/books") => {
+            let books = body
+                .as_array()
+                .into_iter()
+                .flatten()
+                .filter_map(|params| {
+                    let token = field(params, "token_id");
+                    data.books.get(token).map(|book| book.to_json(token))
+                })
+                .collect();
+            Response::ok(Value::Array(books))
+        }
+        ("GET", "/midpoint") => midpoint(data, &token),
+        ("GET", _) if path.starts_with("/midpoint/") => midpoint(data, &path["/midpoint/".len()..]),
+        ("GET", "/price") => {
+            let Some(book) = data.books.get(&token) else {
+                return Response::error(404, "No orderbook exists for the requested token id");
+            };
+            let price = if query.get("side") == Some("SELL") { book.best_bid() } else { book.best_ask() };
+            Response::ok(json!({ "price": price.unwrap_or(0.0).to_string() }))
+        }
+        ("GET", "/tick-size") => match data.books.get(&token) {
+            Some(book) => Response::ok(json!({ "minimum_tick_size": book.tick_size })),
+            None => Response::error(404, "market not found"),
+        },
+        ("GET", "/neg-risk") => {
+            let neg_risk = data.books.get(&token).map(|b| b.neg_risk).unwrap_or(false);
+            Response::ok(json!({ "neg_risk": neg_risk }))
+        }
+        ("GET", "/fee-rate") => Response::ok(json!({ "base_fee": 0 })),
+        ("GET", "/auth/derive-api-key") | ("POST", "/auth/api-key") => Response::ok(json!({
+            "apiKey": "00000000-0000-0000-0000-000000000000",
+            "secret": "bW9jay1zZWNyZXQtbW9jay1zZWNyZXQtbW9jay1zZWNyZXQ=",
+            "passphrase": "mock-passphrase",
+        })),
+        ("GET", "/auth/api-keys") => Response::ok(json!({ "apiKeys": ["00000000-0000-0000-0000-000000000000"] })),
+        ("POST", "/order") => post_order(data, body),
+        ("GET", "/data/orders") | ("GET", "/orders") => {
+            let market = query.get("market").unwrap_or("");
+            let asset = query.get("asset_id").unwrap_or("");
+            let orders: Vec<Value> = data
+                .orders
+                .iter()
+                .filter(|o| o.status == "live")
+                .filter(|o| market.is_empty() || o.market == market)
+                .filter(|o| asset.is_empty() || o.asset_id == asset)
+                .map(MockOrder::to_json)
+                .collect();
+            if path == "/orders" {
+                Response::ok(Value::Array(orders))
+            } else {
+                let count = orders.len();
+                Response::ok(json!({ "data": orders, "next_cursor": "LTE=", "limit": count, "count": count }))
+            }
+        }
+        ("GET", _) if path.starts_with("/data/order/") => {
+            let id = &path["/data/order/".len()..];
+            match data.orders.iter().find(|o| o.id == id) {
+                Some(order) => Response::ok(order.to_json()),
+                None => Response::error(404, "order not found"),
+            }
+        }
+        ("DELETE", "/order") => cancel(data, |o| o.id == field(&body, "orderID")),
+        ("DELETE", _) if path.starts_with("/order/") => {
+            let id = path["/order/".len()..].to_string();
+            cancel(data, |o| o.id == id)
+        }
+        ("DELETE", "/orders") => match body.as_array() {
+            Some(ids) => {
+                let ids: Vec<&str> = ids.iter().filter_map(|id| id.as_str()).collect();
+                cancel(data, |o| ids.contains(&o.id.as_str()))
+            }
+            None => cancel(data, |_| true),
+        },
+        ("DELETE", "/cancel-all") => cancel(data, |_| true),
+        _ => Response::error(404, "not found"),
+    }
+}
+
+fn midpoint(data: &Data, token: &str) -> Response {
+    match data.books.get(token).and_then(|b| Some((b.best_bid()?, b.best_ask()?))) {
+        Some((bid, ask)) => Response::ok(json!({ "mid": ((bid + ask) / 2.0).to_string() })),
+        None => Response::error(404, "No orderbook exists for the requested token id"),
+    }
+}
+
+fn number(value: Option<&Value>) -> Option<f64> {
+    let value = value?;
+    value.as_f64().or_else(|| value.as_str()?.parse().ok())
+}
+
+/// Token, side, price and size of a posted order: either a signed SDK order
+/// (`{"order": {tokenId, side, makerAmount, takerAmount, ...}, "orderType"}`)
+/// or plain `{token_id, side, price, size}` arguments
+fn order_terms(body: &Value) -> Option<(String, String, f64, f64)> {
+    if let Some(order) = body.get("order") {
+        let token = order.get("tokenId").map(|t| t.as_str().map(str::to_string).unwrap_or_else(|| t.to_string()))?;
+        let side = match order.get("side")? {
+            Value::Number(n) if n.as_u64() == Some(1) => "SELL".to_string(),
+            Value::Number(_) => "BUY".to_string(),
+            side => side.as_str()?.to_uppercase(),
+        };
+        let maker = number(order.get("makerAmount"))? / 1e6;
+        let taker = number(order.get("takerAmount"))? / 1e6;
+        if maker <= 0.0 || taker <= 0.0 {
+            return None;
+        }
+        // Buys give USDC for tokens, sells give tokens for USDC
+        let (price, size) = if side == "BUY" { (maker / taker, taker) } else { (taker / maker, maker) };
+        return Some((token, side, price, size));
+    }
+    let token = body.get("token_id").map(|t| t.as_str().map(str::to_string).unwrap_or_else(|| t.to_string()))?;
+    let side = body.get("side")?.as_str()?.to_uppercase();
+    Some((token, side, number(body.get("price"))?, number(body.get("size"))?))
+}
+
+fn post_order(data: &mut Data, body: Value) -> Response {
+    let reject = |message: &str| {
+        Response::json(400, json!({ "success": false, "errorMsg": message, "error": message, "orderID": "" }))
+    };
+    if let Some(error) = data.reject_orders.clone() {
+        return reject(&error);
+    }
+    let Some((asset_id, side, price, size)) = order_terms(&body) else {
+        return reject("invalid order payload");
+    };
+    let order_type = body.get("orderType").and_then(|t| t.as_str()).unwrap_or("GTC").to_uppercase();
+
+    // Market orders fill against the book at the order price or better; resting orders go live
+    let book = data.books.get(&asset_id);
+    let size_matched = match order_type.as_str() {
+        "FOK" | "FAK" => {
+            let available = book.map(|b| b.depth_at(&side, price)).unwrap_or(0.0);
+            if order_type == "FOK" && available + 1e-9 < size {
+                return reject("order couldn't be fully filled. FOK orders are fully filled or killed.");
+            }
+            if available <= 0.0 {
+                return reject("no orders found to match with FAK order. FAK orders are partially filled or killed if no match is found.");
+            }
+            size.min(available)
+        }
+        _ => 0.0,
+    };
+    let status = if size_matched > 0.0 { "matched" } else { "live" };
+    let id = format!("0x{:064x}", data.orders.len() + 1);
+    data.orders.push(MockOrder {
+        id: id.clone(),
+        market: book.map(|b| b.market.clone()).unwrap_or_default(),
+        asset_id,
+        side: side.clone(),
+        price,
+        original_size: size,
+        size_matched,
+        order_type,
+        status: status.to_string(),
+        body,
+    });
+
+    // Amounts as the CLOB reports them: what the maker gave and received
+    let (making, taking) = if side == "BUY" { (size_matched * price, size_matched) } else { (size_matched, size_matched * price) };
+    Response::ok(json!({
+        "success": true,
+        "errorMsg": "",
+        "orderID": id,
+        "status": status,
+        "makingAmount": making.to_string(),
+        "takingAmount": taking.to_string(),
+        "transactionsHashes": [],
+    }))
+}
+
+fn cancel(data: &mut Data, matches: impl Fn(&MockOrder) -> bool) -> Response {
+    let mut canceled = Vec::new();
+    for order in data.orders.iter_mut().filter(|o| o.status == "live") {
+        if matches(order) {
+            order.status = "canceled".to_string();
+            canceled.push(order.id.clone());
+        }
+    }
+    Response::ok(json!({ "canceled": canceled, "not_canceled": {} }))
+}
+
+fn gamma(data: &Data, path: &str, query: &Query) -> Response {
+    if let Some(slug) = path.strip_prefix("/markets/slug/") {
+        return match data.markets.iter().find(|m| field(m, "slug") == slug) {
+            Some(market) => Response::ok(market.clone()),
+            None => Response::error(404, "market not found"),
+        };
+    }
+    let records = match path {
+        "/markets" => &data.markets,
+        "/events" => &data.events,
+        _ => return Response::error(404, "not found"),
+    };
+
+    let condition_ids = query.all("condition_ids");
+    let slugs = query.all("slug");
+    let closed = query.get("closed").map(|c| c == "true");
+    let matching = records
+        .iter()
+        .filter(|r| condition_ids.is_empty() || condition_ids.iter().any(|id| id.eq_ignore_ascii_case(field(r, "conditionId"))))
+        .filter(|r| slugs.is_empty() || slugs.iter().any(|s| s == field(r, "slug")))
+        .filter(|r| closed.is_none() || r.get("closed").and_then(|c| c.as_bool()).unwrap_or(false) == closed.unwrap())
+        .cloned()
+        .collect();
+    Response::ok(query.page(matching))
+}
+
+fn data_api(data: &Data, path: &str, query: &Query) -> Response {
+    let user = query.get("user").unwrap_or("").to_lowercase();
+    let markets = query.all("market");
+    let in_market = |r: &&Value| markets.is_empty() || markets.iter().any(|m| m.eq_ignore_ascii_case(field(r, "conditionId")));
+
+    match path {
+        "/positions" => {
+            let positions = data.positions.get(&user).cloned().unwrap_or_default();
+            Response::ok(query.page(positions.iter().filter(in_market).cloned().collect()))
+        }
+        "/activity" => {
+            let types = query.all("type");
+            let activity = data.activity.get(&user).cloned().unwrap_or_default();
+            let matching = activity
+                .iter()
+                .filter(in_market)
+                .filter(|r| types.is_empty() || types.iter().any(|t| t.eq_ignore_ascii_case(field(r, "type"))))
+                .cloned()
+                .collect();
+            Response::ok(query.page(matching))
+        }
+        "/trades" => {
+            let matching = data
+                .trades
+                .iter()
+                .filter(|t| user.is_empty() || field(t, "proxyWallet").to_lowercase() == user)
+                .filter(in_market)
+                .cloned()
+                .collect();
+            Response::ok(query.page(matching))
+        }
+        "/value" => {
+            let value: f64 = data
+                .positions
+                .get(&user)
+                .into_iter()
+                .flatten()
+                .filter_map(|p| p.get("currentValue").and_then(|v| v.as_f64()))
+                .sum();
+            Response::ok(json!([{ "user": user, "value": value }]))
+        }
+        "/leaderboard" | "/v1/leaderboard" => Response::ok(query.page(data.leaderboard.clone())),
+        "/holders" => Response::ok(json!([])),
+        _ => Response::error(404, "not found"),
+    }
+}
+
+/// 32-byte ABI word for an unsigned integer
+fn word(value: u128) -> String {
+    format!("0x{:064x}", value)
+}
+
+/// Decimal string of a hex-encoded uint256 (token IDs don't fit in u128)
+fn hex_to_decimal(hex: &str) -> String {
+    let mut digits: Vec<u32> = vec![0]; // little-endian base 10^9
+    for c in hex.trim_start_matches("0x").chars() {
+        let mut carry = c.to_digit(16).unwrap_or(0) as u64;
+        for digit in digits.iter_mut() {
+            let value = *digit as u64 * 16 + carry;
+            *digit = (value % 1_000_000_000) as u32;
+            carry = value / 1_000_000_000;
+        }
+        if carry > 0 {
+            digits.push(carry as u32);
+        }
+    }
+    let mut out = digits.last().unwrap().to_string();
+    for digit in digits.iter().rev().skip(1) {
+        out.push_str(&format!("{:09}", digit));
+    }
+    out
+}
+
+fn rpc(data: &Data, body: &str) -> Response {
+    let request: Value = serde_json::from_str(body).unwrap_or(Value::Null);
+    match request {
+        Value::Array(calls) => Response::ok(Value::Array(calls.iter().map(|c| rpc_call(data, c)).collect())),
+        call => Response::ok(rpc_call(data, &call)),
+    }
+}
+
+fn rpc_call(data: &Data, call: &Value) -> Value {
+    let id = call.get("id").cloned().unwrap_or(json!(1));
+    let params = call.get("params").cloned().unwrap_or(json!([]));
+    let address = || params[0].as_str().unwrap_or("").to_lowercase();
+
+    let result = match field(call, "method") {
+        "eth_chainId" | "net_version" => json!("0x89"),
+        "eth_blockNumber" => json!(format!("0x{:x}", now_ms() / 2000)),
+        "eth_gasPrice" | "eth_maxPriorityFeePerGas" => json!("0x6fc23ac00"),
+        "eth_getTransactionCount" => json!("0x0"),
+        "eth_estimateGas" => json!("0x30d40"),
+        "eth_getBalance" => {
+            let matic = data.gas_balances.get(&address()).copied().unwrap_or(0.0);
+            json!(format!("0x{:x}", (matic * 1e18) as u128))
+        }
+        "eth_getCode" => json!(if data.contracts.contains(&address()) { "0x6080604052" } else { "0x" }),
+        "eth_call" => {
+            let input = params[0].get("input").or_else(|| params[0].get("data")).and_then(|d| d.as_str()).unwrap_or("");
+            let input = input.trim_start_matches("0x");
+            let arg = |index: usize| input.get(8 + index * 64..8 + (index + 1) * 64).unwrap_or("");
+            let holder = || format!("0x{}", &arg(0).get(24..).unwrap_or("")).to_lowercase();
+            json!(match input.get(..8).unwrap_or("") {
+                // balanceOf(address): USDC, 6 decimals
+                "70a08231" => word((data.balances.get(&holder()).copied().unwrap_or(0.0) * 1e6).round() as u128),
+                // allowance(address,address): everything is approved
+                "dd62ed3e" => format!("0x{}", "f".repeat(64)),
+                // isApprovedForAll(address,address)
+                "e985e9c5" => word(1),
+                // balanceOf(address,uint256): CTF tokens from the holder's positions
+                "00fdd58e" => {
+                    let token = hex_to_decimal(arg(1));
+                    let size: f64 = data
+                        .positions
+                        .get(&holder())
+                        .into_iter()
+                        .flatten()
+                        .filter(|p| field(p, "asset") == token)
+                        .filter_map(|p| p.get("size").and_then(|s| s.as_f64()))
+                        .sum();
+                    word((size * 1e6).round() as u128)
+                }
+                _ => word(0),
+            })
+        }
+        method => {
+            return json!({
+                "jsonrpc": "2.0",
+                "id": id,
+                "error": { "code": -32601, "message": format!("method {} not supported by the mock", method) },
+            })
+        }
+    };
+    json!({ "jsonrpc": "2.0", "id": id, "result": result })
+}
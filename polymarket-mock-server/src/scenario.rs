@@ -0,0 +1,117 @@
+//! Scenario files: the initial state of the mock and a script of timed events.
+
+use anyhow::{Context, Result};
+use serde::Deserialize;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::path::Path;
+
+/// Initial state plus the steps `MockServer::play` runs through
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct Scenario {
+    /// Order books by token ID
+    pub books: HashMap<String, BookSpec>,
+    /// Gamma `/markets` records, served as given
+    pub markets: Vec<Value>,
+    /// Gamma `/events` records, served as given
+    pub events: Vec<Value>,
+    /// Data API `/positions` by wallet address
+    pub positions: HashMap<String, Vec<Value>>,
+    /// Data API `/activity` by wallet address, newest first
+    pub activity: HashMap<String, Vec<Value>>,
+    /// Data API `/trades`, filtered by `proxyWallet` when a user is given
+    pub trades: Vec<Value>,
+    /// Data API `/leaderboard`
+    pub leaderboard: Vec<Value>,
+    /// USDC balance by wallet address (answers `balanceOf` on `/rpc`)
+    pub balances: HashMap<String, f64>,
+    /// Native (MATIC) balance by wallet address
+    pub gas_balances: HashMap<String, f64>,
+    /// Addresses `eth_getCode` reports as contracts (e.g. Gnosis Safes)
+    pub contracts: Vec<String>,
+    /// Fixed responses keyed by `"<METHOD> <path>"`, checked before the built-in routes
+    pub routes: HashMap<String, Value>,
+    pub steps: Vec<Step>,
+}
+
+impl Scenario {
+    pub fn from_file(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        let content = std::fs::read_to_string(path).with_context(|| format!("reading {}", path.display()))?;
+        serde_json::from_str(&content).with_context(|| format!("parsing {}", path.display()))
+    }
+}
+
+/// One side of a book is a list of `[price, size]` pairs
+#[derive(Debug, Clone, Deserialize)]
+pub struct BookSpec {
+    /// Condition ID the token belongs to
+    #[serde(default)]
+    pub market: String,
+    #[serde(default)]
+    pub bids: Vec<(f64, f64)>,
+    #[serde(default)]
+    pub asks: Vec<(f64, f64)>,
+    #[serde(default = "default_tick_size")]
+    pub tick_size: f64,
+    #[serde(default)]
+    pub neg_risk: bool,
+}
+
+fn default_tick_size() -> f64 {
+    0.01
+}
+
+/// A scripted event, run `after_ms` after the previous one
+#[derive(Debug, Clone, Deserialize)]
+pub struct Step {
+    #[serde(default)]
+    pub after_ms: u64,
+    #[serde(flatten)]
+    pub event: Event,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(tag = "event", rename_all = "snake_case")]
+pub enum Event {
+    /// Broadcast an `activity`/`trades` message with this payload on RTDS
+    RtdsTrade { trade: Value },
+    /// Broadcast a raw RTDS message
+    Rtds { message: Value },
+    /// Replace a book and push it to market channel subscribers of the token
+    Book {
+        token_id: String,
+        #[serde(default)]
+        bids: Vec<(f64, f64)>,
+        #[serde(default)]
+        asks: Vec<(f64, f64)>,
+    },
+    /// Broadcast a raw market channel message
+    MarketWs { message: Value },
+    /// Replace a wallet's Data API positions
+    Positions { user: String, positions: Vec<Value> },
+    /// Prepend records to a wallet's Data API activity
+    Activity { user: String, activity: Vec<Value> },
+    /// Set a wallet's USDC balance
+    Balance { user: String, usdc: f64 },
+    /// Answer the next `times` requests whose path starts with `path` with `status`
+    Fail {
+        path: String,
+        status: u16,
+        #[serde(default = "default_times")]
+        times: u32,
+        /// Seconds, sent as a `Retry-After` header
+        #[serde(default)]
+        retry_after: Option<u64>,
+    },
+    /// Reject new orders with this error message (`null` accepts them again)
+    RejectOrders {
+        #[serde(default)]
+        error: Option<String>,
+    },
+}
+
+fn default_times() -> u32 {
+    1
+}
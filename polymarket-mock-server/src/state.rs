@@ -0,0 +1,228 @@
+//! Mutable state of the mock: books, Data API records, orders and the request log.
+
+use crate::scenario::{BookSpec, Event, Scenario};
+use serde::Serialize;
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+pub(crate) fn now_ms() -> u64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_millis() as u64).unwrap_or(0)
+}
+
+#[derive(Debug, Clone)]
+pub(crate) struct Book {
+    pub market: String,
+    pub bids: Vec<(f64, f64)>,
+    pub asks: Vec<(f64, f64)>,
+    pub tick_size: f64,
+    pub neg_risk: bool,
+    pub timestamp: u64,
+}
+
+impl From<BookSpec> for Book {
+    fn from(spec: BookSpec) -> Self {
+        Self {
+            market: spec.market,
+            bids: spec.bids,
+            asks: spec.asks,
+            tick_size: spec.tick_size,
+            neg_risk: spec.neg_risk,
+            timestamp: now_ms(),
+        }
+    }
+}
+
+impl Book {
+    pub fn best_bid(&self) -> Option<f64> {
+        self.bids.iter().map(|l| l.0).fold(None, |best, p| Some(best.map_or(p, |b: f64| b.max(p))))
+    }
+
+    pub fn best_ask(&self) -> Option<f64> {
+        self.asks.iter().map(|l| l.0).fold(None, |best, p| Some(best.map_or(p, |b: f64| b.min(p))))
+    }
+
+    /// The CLOB's book shape: string prices and sizes, bids ascending and
+    /// asks descending (best level last), as `/book` and the market channel send it
+    pub fn to_json(&self, token_id: &str) -> Value {
+        let levels = |levels: &[(f64, f64)], ascending: bool| {
+            let mut levels = levels.to_vec();
+            levels.sort_by(|a, b| {
+                let order = a.0.partial_cmp(&b.0).unwrap_or(std::cmp::Ordering::Equal);
+                if ascending { order } else { order.reverse() }
+            });
+            levels
+                .iter()
+                .map(|(price, size)| json!({ "price": price.to_string(), "size": size.to_string() }))
+                .collect::<Vec<_>>()
+        };
+        json!({
+            "market": self.market,
+            "asset_id": token_id,
+            "timestamp": self.timestamp.to_string(),
+            "hash": format!("0x{:040x}", self.timestamp),
+            "bids": levels(&self.bids, true),
+            "asks": levels(&self.asks, false),
+            "min_order_size": "5",
+            "tick_size": self.tick_size.to_string(),
+            "neg_risk": self.neg_risk,
+        })
+    }
+
+    /// Size available at `price` or better for an order on `side`
+    pub fn depth_at(&self, side: &str, price: f64) -> f64 {
+        if side == "BUY" {
+            self.asks.iter().filter(|l| l.0 <= price + 1e-9).map(|l| l.1).sum()
+        } else {
+            self.bids.iter().filter(|l| l.0 >= price - 1e-9).map(|l| l.1).sum()
+        }
+    }
+}
+
+/// An order accepted by `POST /order`
+#[derive(Debug, Clone, Serialize)]
+pub struct MockOrder {
+    pub id: String,
+    pub asset_id: String,
+    /// Condition ID, from the token's book when known
+    pub market: String,
+    /// `BUY` or `SELL`
+    pub side: String,
+    pub price: f64,
+    pub original_size: f64,
+    pub size_matched: f64,
+    /// GTC, GTD, FOK or FAK
+    pub order_type: String,
+    /// `live`, `matched` or `canceled`
+    pub status: String,
+    /// Request body as posted
+    pub body: Value,
+}
+
+impl MockOrder {
+    pub(crate) fn to_json(&self) -> Value {
+        json!({
+            "id": self.id,
+            "status": self.status.to_uppercase(),
+            "market": self.market,
+            "asset_id": self.asset_id,
+            "side": self.side,
+            "price": self.price.to_string(),
+            "original_size": self.original_size.to_string(),
+            "size_matched": self.size_matched.to_string(),
+            "order_type": self.order_type,
+            "outcome": "",
+            "created_at": 0,
+        })
+    }
+}
+
+/// A request as the mock received it
+#[derive(Debug, Clone, Serialize)]
+pub struct RecordedRequest {
+    pub method: String,
+    /// Path including the query string
+    pub path: String,
+    pub body: String,
+}
+
+#[derive(Debug, Clone)]
+pub(crate) struct Failure {
+    pub path: String,
+    pub status: u16,
+    pub remaining: u32,
+    pub retry_after: Option<u64>,
+}
+
+#[derive(Debug, Default)]
+pub(crate) struct Data {
+    pub books: HashMap<String, Book>,
+    pub markets: Vec<Value>,
+    pub events: Vec<Value>,
+    pub positions: HashMap<String, Vec<Value>>,
+    pub activity: HashMap<String, Vec<Value>>,
+    pub trades: Vec<Value>,
+    pub leaderboard: Vec<Value>,
+    pub balances: HashMap<String, f64>,
+    pub gas_balances: HashMap<String, f64>,
+    pub contracts: Vec<String>,
+    pub routes: HashMap<String, Value>,
+    pub failures: Vec<Failure>,
+    pub reject_orders: Option<String>,
+    pub orders: Vec<MockOrder>,
+    pub requests: Vec<RecordedRequest>,
+}
+
+/// Wallet addresses are matched case-insensitively
+fn lowercase_keys<T>(map: HashMap<String, T>) -> HashMap<String, T> {
+    map.into_iter().map(|(k, v)| (k.to_lowercase(), v)).collect()
+}
+
+impl Data {
+    pub fn from_scenario(scenario: &Scenario) -> Self {
+        let scenario = scenario.clone();
+        Self {
+            books: scenario.books.into_iter().map(|(token, spec)| (token, spec.into())).collect(),
+            markets: scenario.markets,
+            events: scenario.events,
+            positions: lowercase_keys(scenario.positions),
+            activity: lowercase_keys(scenario.activity),
+            trades: scenario.trades,
+            leaderboard: scenario.leaderboard,
+            balances: lowercase_keys(scenario.balances),
+            gas_balances: lowercase_keys(scenario.gas_balances),
+            contracts: scenario.contracts.iter().map(|a| a.to_lowercase()).collect(),
+            routes: scenario.routes,
+            ..Self::default()
+        }
+    }
+
+    /// Apply the state part of an event; WebSocket broadcasts are left to the caller
+    pub fn apply(&mut self, event: &Event) {
+        match event {
+            Event::Book { token_id, bids, asks } => {
+                let book = self.books.entry(token_id.clone()).or_insert_with(|| Book {
+                    market: String::new(),
+                    bids: Vec::new(),
+                    asks: Vec::new(),
+                    tick_size: 0.01,
+                    neg_risk: false,
+                    timestamp: 0,
+                });
+                book.bids = bids.clone();
+                book.asks = asks.clone();
+                book.timestamp = now_ms();
+            }
+            Event::Positions { user, positions } => {
+                self.positions.insert(user.to_lowercase(), positions.clone());
+            }
+            Event::Activity { user, activity } => {
+                let records = self.activity.entry(user.to_lowercase()).or_default();
+                records.splice(0..0, activity.iter().cloned());
+            }
+            Event::Balance { user, usdc } => {
+                self.balances.insert(user.to_lowercase(), *usdc);
+            }
+            Event::Fail { path, status, times, retry_after } => self.failures.push(Failure {
+                path: path.clone(),
+                status: *status,
+                remaining: *times,
+                retry_after: *retry_after,
+            }),
+            Event::RejectOrders { error } => self.reject_orders = error.clone(),
+            Event::RtdsTrade { .. } | Event::Rtds { .. } | Event::MarketWs { .. } => {}
+        }
+    }
+
+    /// Consume one scripted failure matching `path`, if any
+    pub fn take_failure(&mut self, path: &str) -> Option<Failure> {
+        let index = self.failures.iter().position(|f| f.remaining > 0 && path.starts_with(&f.path))?;
+        let failure = &mut self.failures[index];
+        failure.remaining -= 1;
+        let taken = failure.clone();
+        if failure.remaining == 0 {
+            self.failures.remove(index);
+        }
+        Some(taken)
+    }
+}
@@ -0,0 +1,109 @@
+//! WebSocket sessions: RTDS (`activity`/`trades` stream) and the CLOB market channel.
+
+use crate::Shared;
+use futures_util::{SinkExt, StreamExt};
+use serde_json::Value;
+use std::collections::HashSet;
+use std::sync::Arc;
+use tokio::net::TcpStream;
+use tokio::sync::broadcast::error::RecvError;
+use tokio_tungstenite::tungstenite::Message;
+use tokio_tungstenite::WebSocketStream;
+
+/// A market channel message, delivered to subscribers of `asset_id` (or to everyone)
+#[derive(Debug, Clone)]
+pub(crate) struct MarketMessage {
+    pub asset_id: Option<String>,
+    pub text: String,
+}
+
+/// Forward RTDS broadcasts once the client has sent a `subscribe` action
+pub(crate) async fn rtds_session(ws: WebSocketStream<TcpStream>, shared: Arc<Shared>) {
+    let (mut sink, mut stream) = ws.split();
+    let mut broadcasts = shared.rtds.subscribe();
+    let mut subscribed = false;
+
+    loop {
+        tokio::select! {
+            incoming = stream.next() => match incoming {
+                Some(Ok(Message::Text(text))) => {
+                    let action = serde_json::from_str::<Value>(&text)
+                        .ok()
+                        .and_then(|m| m.get("action").and_then(|a| a.as_str()).map(str::to_string));
+                    if !subscribed && action.as_deref() == Some("subscribe") {
+                        subscribed = true;
+                        shared.rtds_subscribers.send_modify(|n| *n += 1);
+                    }
+                }
+                Some(Ok(Message::Ping(data))) => {
+                    let _ = sink.send(Message::Pong(data)).await;
+                }
+                Some(Ok(Message::Close(_))) | Some(Err(_)) | None => break,
+                Some(Ok(_)) => {}
+            },
+            outgoing = broadcasts.recv() => match outgoing {
+                Ok(text) if subscribed => {
+                    if sink.send(Message::Text(text)).await.is_err() {
+                        break;
+                    }
+                }
+                Ok(_) | Err(RecvError::Lagged(_)) => {}
+                Err(RecvError::Closed) => break,
+            },
+        }
+    }
+
+    if subscribed {
+        shared.rtds_subscribers.send_modify(|n| *n -= 1);
+    }
+}
+
+/// Send the current book of each subscribed asset, then its updates
+pub(crate) async fn market_session(ws: WebSocketStream<TcpStream>, shared: Arc<Shared>) {
+    let (mut sink, mut stream) = ws.split();
+    let mut broadcasts = shared.market.subscribe();
+    let mut assets: HashSet<String> = HashSet::new();
+
+    loop {
+        tokio::select! {
+            incoming = stream.next() => match incoming {
+                Some(Ok(Message::Text(text))) => {
+                    let Ok(message) = serde_json::from_str::<Value>(&text) else { continue };
+                    let Some(requested) = message.get("assets_ids").and_then(|a| a.as_array()) else { continue };
+                    let first_subscription = assets.is_empty();
+                    assets.extend(requested.iter().filter_map(|a| a.as_str()).map(str::to_string));
+
+                    let snapshot = shared.book_messages(requested.iter().filter_map(|a| a.as_str()));
+                    if sink.send(Message::Text(Value::Array(snapshot).to_string())).await.is_err() {
+                        break;
+                    }
+                    if first_subscription && !assets.is_empty() {
+                        shared.market_subscribers.send_modify(|n| *n += 1);
+                    }
+                }
+                Some(Ok(Message::Ping(data))) => {
+                    let _ = sink.send(Message::Pong(data)).await;
+                }
+                Some(Ok(Message::Close(_))) | Some(Err(_)) | None => break,
+                Some(Ok(_)) => {}
+            },
+            outgoing = broadcasts.recv() => match outgoing {
+                Ok(message) => {
+                    let wanted = match &message.asset_id {
+                        Some(asset) => assets.contains(asset),
+                        None => !assets.is_empty(),
+                    };
+                    if wanted && sink.send(Message::Text(message.text)).await.is_err() {
+                        break;
+                    }
+                }
+                Err(RecvError::Lagged(_)) => {}
+                Err(RecvError::Closed) => break,
+            },
+        }
+    }
+
+    if !assets.is_empty() {
+        shared.market_subscribers.send_modify(|n| *n -= 1);
+    }
+}
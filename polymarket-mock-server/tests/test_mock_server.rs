@@ -0,0 +1,200 @@
+//! The mock's REST routes, WebSocket channels and scripted events, exercised
+//! over real localhost connections.
+
+use futures_util::{SinkExt, StreamExt};
+use polymarket_mock_server::{Event, MockServer, Scenario};
+use serde_json::{json, Value};
+use std::time::Duration;
+use tokio_tungstenite::connect_async;
+use tokio_tungstenite::tungstenite::Message;
+
+const TOKEN: &str = "71321045679252212594626385532706912750332728571942532289631379312455583992563";
+const TRADER: &str = "0x1111111111111111111111111111111111111111";
+
+fn scenario() -> Scenario {
+    Scenario::from_file(concat!(env!("CARGO_MANIFEST_DIR"), "/scenarios/copy_trade.json")).unwrap()
+}
+
+async fn get(url: &str) -> (u16, Value) {
+    let response = reqwest::get(url).await.unwrap();
+    let status = response.status().as_u16();
+    (status, response.json().await.unwrap_or(Value::Null))
+}
+
+async fn next_text<S>(stream: &mut S) -> Value
+where
+    S: StreamExt<Item = Result<Message, tokio_tungstenite::tungstenite::Error>> + Unpin,
+{
+    loop {
+        let message = tokio::time::timeout(Duration::from_secs(5), stream.next()).await.unwrap().unwrap().unwrap();
+        if let Message::Text(text) = message {
+            return serde_json::from_str(&text).unwrap();
+        }
+    }
+}
+
+#[tokio::test]
+async fn test_book_and_prices_use_clob_format() {
+    let server = MockServer::start(scenario()).await.unwrap();
+
+    let (status, book) = get(&format!("{}/book?token_id={}", server.clob_url(), TOKEN)).await;
+    assert_eq!(status, 200);
+    assert_eq!(book["asset_id"], TOKEN);
+    // Best level last on both sides, prices and sizes as strings
+    assert_eq!(book["bids"], json!([{ "price": "0.47", "size": "500" }, { "price": "0.48", "size": "200" }]));
+    assert_eq!(book["asks"][2], json!({ "price": "0.5", "size": "150" }));
+
+    let (_, mid) = get(&format!("{}/midpoint?token_id={}", server.clob_url(), TOKEN)).await;
+    assert_eq!(mid["mid"], "0.49");
+    let (_, price) = get(&format!("{}/price?token_id={}&side=SELL", server.clob_url(), TOKEN)).await;
+    assert_eq!(price["price"], "0.48");
+    assert_eq!(get(&format!("{}/book?token_id=999", server.clob_url())).await.0, 404);
+}
+
+#[tokio::test]
+async fn test_orders_fill_rest_and_cancel() {
+    let server = MockServer::start(scenario()).await.unwrap();
+    let client = reqwest::Client::new();
+    let post = |body: Value| {
+        let request = client.post(format!("{}/order", server.clob_url())).json(&body);
+        async move {
+            let response = request.send().await.unwrap();
+            (response.status().as_u16(), response.json::<Value>().await.unwrap())
+        }
+    };
+
+    // Signed SDK order: 100 USDC for 200 tokens at 0.50, filled by the 0.50 ask
+    let signed = json!({
+        "order": { "tokenId": TOKEN, "side": "BUY", "makerAmount": "75000000", "takerAmount": "150000000" },
+        "orderType": "FOK",
+    });
+    let (status, filled) = post(signed).await;
+    assert_eq!(status, 200);
+    assert_eq!(filled["status"], "matched");
+    assert_eq!(filled["takingAmount"], "150");
+
+    // More than the book holds at 0.50 kills a FOK order
+    let too_big = json!({
+        "order": { "tokenId": TOKEN, "side": "BUY", "makerAmount": "100000000", "takerAmount": "200000000" },
+        "orderType": "FOK",
+    });
+    assert_eq!(post(too_big).await.0, 400);
+
+    // Plain market-maker arguments rest as a live order
+    let (_, resting) = post(json!({ "token_id": TOKEN, "side": "SELL", "price": 0.55, "size": 20 })).await;
+    assert_eq!(resting["status"], "live");
+    let market = "0x5f65177b394277fd294cd75650044e32ba009a95022d88a0c1d565897d72f8f1";
+    let (_, open) = get(&format!("{}/orders?market={}", server.clob_url(), market)).await;
+    assert_eq!(open.as_array().unwrap().len(), 1);
+    assert_eq!(open[0]["price"], "0.55");
+
+    let id = resting["orderID"].as_str().unwrap();
+    client.delete(format!("{}/order/{}", server.clob_url(), id)).send().await.unwrap();
+    let (_, open) = get(&format!("{}/data/orders", server.clob_url())).await;
+    assert_eq!(open["count"], 0);
+
+    server.apply(&Event::RejectOrders { error: Some("not enough balance / allowance".to_string()) });
+    let (status, rejected) = post(json!({ "token_id": TOKEN, "side": "BUY", "price": 0.4, "size": 10 })).await;
+    assert_eq!(status, 400);
+    assert_eq!(rejected["errorMsg"], "not enough balance / allowance");
+
+    let orders = server.orders();
+    assert_eq!(orders.len(), 2);
+    assert_eq!(orders[0].size_matched, 150.0);
+    assert_eq!(orders[1].status, "canceled");
+}
+
+#[tokio::test]
+async fn test_data_api_gamma_and_rpc() {
+    let server = MockServer::start(scenario()).await.unwrap();
+
+    let (_, positions) = get(&format!("{}/positions?user={}&limit=10&offset=0", server.data_api_url(), TRADER)).await;
+    assert_eq!(positions[0]["size"], 1000);
+    let (_, page) = get(&format!("{}/positions?user={}&offset=1", server.data_api_url(), TRADER)).await;
+    assert_eq!(page, json!([]));
+    let (_, value) = get(&format!("{}/value?user={}", server.data_api_url(), TRADER)).await;
+    assert_eq!(value[0]["value"], 490.0);
+
+    let condition = "0x5f65177b394277fd294cd75650044e32ba009a95022d88a0c1d565897d72f8f1";
+    let (_, markets) = get(&format!("{}/markets?condition_ids={}", server.gamma_url(), condition)).await;
+    assert_eq!(markets[0]["slug"], "will-it-rain-in-london-tomorrow");
+    let (status, _) = get(&format!("{}/markets/slug/unknown", server.gamma_url())).await;
+    assert_eq!(status, 404);
+
+    // balanceOf(0x3333...) on the USDC contract: 1000 USDC with 6 decimals
+    let call = json!({
+        "jsonrpc": "2.0",
+        "id": 7,
+        "method": "eth_call",
+        "params": [{ "to": "0x2791bca1f2de4661ed88a30c99a7a9449aa84174",
+                     "data": "0x70a082310000000000000000000000003333333333333333333333333333333333333333" }, "latest"],
+    });
+    let response: Value = reqwest::Client::new().post(server.rpc_url()).json(&call).send().await.unwrap().json().await.unwrap();
+    assert_eq!(response["id"], 7);
+    assert_eq!(u128::from_str_radix(response["result"].as_str().unwrap().trim_start_matches("0x"), 16).unwrap(), 1_000_000_000);
+}
+
+#[tokio::test]
+async fn test_scripted_failures_and_fixed_routes() {
+    let mut scenario = scenario();
+    scenario.routes.insert("GET /data-api/holders".to_string(), json!([{ "token": TOKEN, "holders": [] }]));
+    let server = MockServer::start(scenario).await.unwrap();
+
+    server.apply(&Event::Fail { path: "/data-api/activity".to_string(), status: 429, times: 1, retry_after: Some(2) });
+    let url = format!("{}/activity?user={}", server.data_api_url(), TRADER);
+    let response = reqwest::get(&url).await.unwrap();
+    assert_eq!(response.status().as_u16(), 429);
+    assert_eq!(response.headers()["retry-after"], "2");
+    assert_eq!(get(&url).await.0, 200);
+
+    let (_, holders) = get(&format!("{}/holders?market=0x5f", server.data_api_url())).await;
+    assert_eq!(holders[0]["token"], TOKEN);
+    assert_eq!(server.requests().iter().filter(|r| r.path.starts_with("/data-api/activity")).count(), 2);
+}
+
+#[tokio::test]
+async fn test_rtds_trades_reach_subscribers() {
+    let server = MockServer::start(scenario()).await.unwrap();
+    let (mut socket, _) = connect_async(server.rtds_url()).await.unwrap();
+    let subscribe = json!({ "action": "subscribe", "subscriptions": [{ "topic": "activity", "type": "trades" }] });
+    socket.send(Message::Text(subscribe.to_string())).await.unwrap();
+    server.wait_for_rtds_subscribers(1).await;
+
+    let play = server.play();
+    let receive = async {
+        let first = next_text(&mut socket).await;
+        let second = next_text(&mut socket).await;
+        (first, second)
+    };
+    let ((), (first, second)) = tokio::join!(play, receive);
+
+    assert_eq!(first["topic"], "activity");
+    assert_eq!(first["type"], "trades");
+    assert_eq!(first["payload"]["side"], "BUY");
+    assert_eq!(second["payload"]["side"], "SELL");
+    // The scripted book move also landed in REST state
+    let (_, book) = get(&format!("{}/book?token_id={}", server.clob_url(), TOKEN)).await;
+    assert_eq!(book["bids"], json!([{ "price": "0.52", "size": "300" }]));
+}
+
+#[tokio::test]
+async fn test_market_channel_snapshot_and_updates() {
+    let scenario = Scenario::from_file(concat!(env!("CARGO_MANIFEST_DIR"), "/scenarios/arbitrage.json")).unwrap();
+    let server = MockServer::start(scenario).await.unwrap();
+    let (mut socket, _) = connect_async(server.market_ws_url()).await.unwrap();
+    socket.send(Message::Text(json!({ "assets_ids": ["1001", "1002"], "type": "MARKET" }).to_string())).await.unwrap();
+
+    let snapshot = next_text(&mut socket).await;
+    let books = snapshot.as_array().unwrap();
+    assert_eq!(books.len(), 2);
+    assert!(books.iter().all(|b| b["event_type"] == "book"));
+    server.wait_for_market_subscribers(1).await;
+
+    // Unsubscribed assets are filtered out
+    server.apply(&Event::Book { token_id: "2001".to_string(), bids: vec![], asks: vec![(0.1, 1.0)] });
+    server.apply(&Event::Book { token_id: "1002".to_string(), bids: vec![(0.46, 100.0)], asks: vec![(0.48, 100.0)] });
+    let update = next_text(&mut socket).await;
+    assert_eq!(update["asset_id"], "1002");
+    assert_eq!(update["asks"], json!([{ "price": "0.48", "size": "100" }]));
+    assert_eq!(update["market"], "0xb7c1");
+}